@@ -11,8 +11,9 @@ use sjgfx_interface::{
 use sjvi::{IDisplay, IInstance};
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct ConstantBuffer {
-    pv: glm::Mat4x4,
+    pv: [[f32; 4]; 4],
 }
 
 fn main() {
@@ -93,7 +94,7 @@ fn run<TApi: IApi>() {
         let fov = std::f32::consts::PI / 4.0;
         let projection_matrix: glm::Mat4x4 = glm::perspective_fov(fov, 640.0, 480.0, 0.1, 100.0);
 
-        x.pv = projection_matrix * view_matrix;
+        x.pv = (projection_matrix * view_matrix).into();
     });
 
     // 頂点バッファ、インデクスバッファ
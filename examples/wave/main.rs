@@ -11,8 +11,9 @@ use sjgfx_interface::{
 use sjvi::{IDisplay, IInstance};
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct ConstantBuffer {
-    pv: glm::Mat4x4,
+    pv: [[f32; 4]; 4],
     time: f32,
     _padding0: f32,
     _padding1: f32,
@@ -79,7 +80,7 @@ fn run<TApi: IApi>() {
             .set_gpu_access_flags(GpuAccess::CONSTANT_BUFFER)
             .set_size(std::mem::size_of::<ConstantBuffer>()),
     );
-    constant_buffer.map_mut(|x: &mut ConstantBuffer| {
+    let mut constant_buffer_data = {
         let position = glm::vec3(1.6, 1.5, -2.0);
         let at = glm::vec3(0.0, 0.0, 0.0);
         let up = glm::vec3(0.0, 1.0, 0.0);
@@ -87,9 +88,14 @@ fn run<TApi: IApi>() {
         let fov = std::f32::consts::PI / 4.0;
         let projection_matrix: glm::Mat4x4 = glm::perspective_fov(fov, 640.0, 480.0, 0.1, 100.0);
 
-        x.pv = projection_matrix * view_matrix;
-        x.time = 0.0;
-    });
+        ConstantBuffer {
+            pv: (projection_matrix * view_matrix).into(),
+            time: 0.0,
+            _padding0: 0.0,
+            _padding1: 0.0,
+            _padding2: 0.0,
+        }
+    };
 
     // 頂点バッファ、インデクスバッファ
     let obj_data = sjgfx_examples::load_obj(
@@ -112,8 +118,10 @@ fn run<TApi: IApi>() {
     while instance.try_update() {
         let display = instance.try_get_display(&id).unwrap();
         if display.is_redraw_requested() {
+            // マップした内容は読めないので CPU 側の値を毎フレーム書き込む
+            constant_buffer_data.time += 0.05;
             constant_buffer.map_mut(|x: &mut ConstantBuffer| {
-                x.time += 0.05;
+                *x = constant_buffer_data;
             });
 
             // queue.sync_semaphore(&mut semaphore);
@@ -0,0 +1,189 @@
+use std::{ops::Range, sync::Arc};
+
+use bytemuck::Pod;
+use sjgfx_interface::{
+    get_whole_map_range, validate_map_range, BufferInfo, CpuAccessMode, IBuffer,
+};
+use uuid::Uuid;
+
+use crate::{detail::Buffer, util, DeviceAsh};
+
+pub struct BufferAsh {
+    buffer: Arc<Buffer>,
+    cpu_access_mode: CpuAccessMode,
+    id: Uuid,
+}
+
+impl BufferAsh {
+    // CPU からアクセスするバッファーはホストから見えるメモリーに置いてマップしたままにする
+    pub fn new(device: &DeviceAsh, info: &BufferInfo) -> Self {
+        let buffer = Buffer::new(
+            device.clone_device(),
+            info.get_size(),
+            util::convert_to_buffer_usage(info.get_gpu_access_flags()),
+            info.get_cpu_access_mode().is_mappable(),
+        );
+
+        Self {
+            buffer: Arc::new(buffer),
+            cpu_access_mode: info.get_cpu_access_mode(),
+            id: Uuid::new_v4(),
+        }
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn clone_buffer(&self) -> Arc<Buffer> {
+        self.buffer.clone()
+    }
+
+    pub fn view(&self) -> BufferView {
+        BufferView {
+            buffer: self.buffer.clone(),
+            id: self.id,
+            offset: 0,
+            size: self.buffer.get_size(),
+        }
+    }
+
+    pub fn view_range(&self, offset: usize, size: usize) -> BufferView {
+        assert!(
+            offset + size <= self.buffer.get_size(),
+            "buffer range {}..{} is out of buffer size {}",
+            offset,
+            offset + size,
+            self.buffer.get_size()
+        );
+        BufferView {
+            buffer: self.buffer.clone(),
+            id: self.id,
+            offset,
+            size,
+        }
+    }
+
+    pub fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map_range(0..std::mem::size_of::<T>(), |x: &[T]| func(&x[0]));
+    }
+
+    pub fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_range(get_whole_map_range::<T>(self.buffer.get_size()), func);
+    }
+
+    pub fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        let data = self.get_mapped_slice(&range);
+        match bytemuck::try_cast_slice(data) {
+            Ok(slice) => func(slice),
+            Err(_) => func(&Self::copy_to_vec::<T>(data)),
+        }
+    }
+
+    pub fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_range_mut(0..std::mem::size_of::<T>(), |x: &mut [T]| func(&mut x[0]));
+    }
+
+    pub fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_range_mut(get_whole_map_range::<T>(self.buffer.get_size()), func);
+    }
+
+    pub fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        let data = self.get_mapped_slice_mut(&range);
+        match bytemuck::try_cast_slice_mut(data) {
+            Ok(slice) => func(slice),
+            Err(_) => {
+                let mut temp = Self::copy_to_vec::<T>(data);
+                func(&mut temp);
+                data.copy_from_slice(bytemuck::cast_slice(&temp));
+            }
+        }
+    }
+
+    fn validate_map_range<T: Pod>(&self, range: &Range<usize>) {
+        assert!(
+            self.cpu_access_mode.is_mappable(),
+            "buffer created with CpuAccessMode::None can't be mapped"
+        );
+        validate_map_range::<T>(self.buffer.get_size(), range);
+    }
+
+    // コヒーレントなメモリーなので GPU の処理が終わっていれば同期なしで読み書きできる
+    fn get_mapped_slice(&self, range: &Range<usize>) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(
+                self.buffer.get_mapped_ptr().add(range.start),
+                range.end - range.start,
+            )
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn get_mapped_slice_mut(&self, range: &Range<usize>) -> &mut [u8] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.buffer.get_mapped_ptr().add(range.start),
+                range.end - range.start,
+            )
+        }
+    }
+
+    // マップしたメモリーが T のアライメントを満たしていなければコピーしてから渡す
+    fn copy_to_vec<T: Pod>(data: &[u8]) -> Vec<T> {
+        let mut result = vec![T::zeroed(); data.len() / std::mem::size_of::<T>()];
+        bytemuck::cast_slice_mut(&mut result).copy_from_slice(data);
+        result
+    }
+}
+
+#[derive(Clone)]
+pub struct BufferView {
+    pub buffer: Arc<Buffer>,
+    pub id: Uuid,
+    pub offset: usize,
+    pub size: usize,
+}
+
+impl BufferView {
+    pub fn is_same(&self, other: &BufferView) -> bool {
+        self.id == other.id && self.offset == other.offset && self.size == other.size
+    }
+}
+
+impl IBuffer for BufferAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map(func);
+    }
+
+    fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_as_slice(func);
+    }
+
+    fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.map_range(range, func);
+    }
+
+    fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_mut(func);
+    }
+
+    fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_as_slice_mut(func);
+    }
+
+    fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.map_range_mut(range, func);
+    }
+
+    fn flush_mapped_range(&self, _offset: isize, _size: usize) {}
+
+    fn invalidate_mapped_range(&self, _offset: isize, _size: usize) {}
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData, ImageFormat};
+
+use crate::{
+    detail::{Device, Image, ImageView},
+    util, DeviceAsh, TextureAsh,
+};
+
+#[derive(Clone)]
+pub struct ColorTargetViewAsh {
+    device: Arc<Device>,
+    image_view: Arc<ImageView>,
+}
+
+impl ColorTargetViewAsh {
+    pub fn new(device: &DeviceAsh, info: &ColorTargetViewInfo, texture: &TextureAsh) -> Self {
+        let format = util::convert_format(info.get_image_format());
+        let image_view = ImageView::new(texture.clone_image(), format, 1);
+        Self {
+            device: device.clone_device(),
+            image_view: Arc::new(image_view),
+        }
+    }
+
+    pub(crate) fn new_direct(device: Arc<Device>, image: Arc<Image>) -> Self {
+        let format = image.get_format();
+        Self {
+            device,
+            image_view: Arc::new(ImageView::new(image, format, 1)),
+        }
+    }
+
+    pub fn get_image_view(&self) -> &ImageView {
+        &self.image_view
+    }
+
+    pub fn clone_image_view(&self) -> Arc<ImageView> {
+        self.image_view.clone()
+    }
+
+    pub fn get_image(&self) -> &Image {
+        self.image_view.get_image()
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.get_image().get_width()
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.get_image().get_height()
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        // RGB は RGBA のイメージで扱っているので、イメージのフォーマットから読み戻す形式を決める
+        let format = self.get_image().get_format();
+        let image_format: ImageFormat = util::convert_from_format(format)
+            .unwrap_or_else(|| panic!("{:?} can't be read back", format));
+        TextureAsh::read_image_impl(&self.device, self.get_image(), image_format)
+    }
+}
+
+impl IColorTargetView for ColorTargetViewAsh {
+    type DeviceType = DeviceAsh;
+    type TextureType = TextureAsh;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &ColorTargetViewInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        Self::new(device, info, texture)
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
+}
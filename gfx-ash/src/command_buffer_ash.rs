@@ -0,0 +1,1129 @@
+use std::{
+    ffi::{CStr, CString},
+    sync::Arc,
+};
+
+use ash::vk;
+use sjgfx_interface::{
+    CommandBufferInfo, ICommandBuffer, IndexFormat, PrimitiveTopology, ScissorStateInfo,
+    TextureArrayRange, ViewportStateInfo,
+};
+use uuid::Uuid;
+
+use crate::{
+    buffer_ash::BufferView,
+    detail::{execute_immediately, record_full_barrier, Device, ImageView, RecordContext, Sampler},
+    shader_ash::ShaderView,
+    util,
+    vertex_state_ash::VertexStateView,
+    BufferAsh, ColorTargetViewAsh, DepthStencilViewAsh, DeviceAsh, SamplerAsh, ShaderAsh,
+    TextureAsh, TextureViewAsh, VertexStateAsh,
+};
+
+struct DrawInfo {
+    pub vertex_count: u32,
+    pub first_vertex: u32,
+    pub instance_count: u32,
+    pub base_instance: u32,
+}
+
+struct DrawIndexedInfo {
+    pub index_type: vk::IndexType,
+    pub index_buffer: BufferView,
+    pub index_count: u32,
+    pub base_vertex: i32,
+    pub instance_count: u32,
+    pub base_instance: u32,
+}
+
+enum DrawCommand {
+    Draw(DrawInfo),
+    DrawIndexed(DrawIndexedInfo),
+}
+
+enum DebugCommand {
+    PushGroup(String),
+    PopGroup,
+    InsertMarker(String),
+}
+
+// この組み合わせが変わったときだけグラフィックスパイプラインを作り直す
+#[derive(Clone, PartialEq)]
+struct GraphicsPipelineKey {
+    shader_id: Uuid,
+    vertex_state_id: Option<Uuid>,
+    color_formats: Vec<vk::Format>,
+    has_depth: bool,
+    primitive_topology: vk::PrimitiveTopology,
+}
+
+// 記録済みのコマンドが GPU で終わるまで破棄できないので Arc で共有する
+struct GraphicsPipeline {
+    device: Arc<Device>,
+    render_pass: vk::RenderPass,
+    pipeline: vk::Pipeline,
+}
+
+impl Drop for GraphicsPipeline {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.handle.destroy_pipeline(self.pipeline, None);
+            self.device
+                .handle
+                .destroy_render_pass(self.render_pass, None);
+        }
+    }
+}
+
+pub struct CommandBufferAsh {
+    device: Arc<Device>,
+
+    // レンダーターゲット
+    color_target_view: [Option<ColorTargetViewAsh>; 8],
+    depth_stencil_view: Option<Arc<ImageView>>,
+
+    // ビューポートシザー
+    viewport_state_info: Option<ViewportStateInfo>,
+    scissor_state_info: Option<ScissorStateInfo>,
+
+    shader: Option<ShaderView>,
+    constant_buffers: [Option<BufferView>; 8],
+    unordered_access_buffer: [Option<BufferView>; 8],
+    dispatch_count: Option<(u32, u32, u32)>,
+
+    // テクスチャ
+    textures: [Option<Arc<ImageView>>; 8],
+    samplers: [Option<Arc<Sampler>>; 8],
+    images: [Option<Arc<ImageView>>; 8],
+
+    // Draw
+    vertex_buffer: [Option<BufferView>; 8],
+    vertex_state: Option<VertexStateView>,
+    primitive_topology: vk::PrimitiveTopology,
+    draw_command: Option<DrawCommand>,
+
+    graphics_pipeline: Option<Arc<GraphicsPipeline>>,
+    graphics_pipeline_key: Option<GraphicsPipelineKey>,
+
+    // デバッグ情報。描画コマンドの前後で積まれたものを分けて保持する
+    debug_commands_before_draw: Vec<DebugCommand>,
+    debug_commands_after_draw: Vec<DebugCommand>,
+}
+
+impl CommandBufferAsh {
+    pub fn new(device: &DeviceAsh, _info: &CommandBufferInfo) -> Self {
+        Self {
+            device: device.clone_device(),
+
+            color_target_view: Default::default(),
+            depth_stencil_view: None,
+
+            viewport_state_info: None,
+            scissor_state_info: None,
+
+            shader: None,
+            constant_buffers: Default::default(),
+            unordered_access_buffer: Default::default(),
+            dispatch_count: None,
+
+            textures: Default::default(),
+            samplers: Default::default(),
+            images: Default::default(),
+
+            vertex_buffer: Default::default(),
+            vertex_state: None,
+            primitive_topology: vk::PrimitiveTopology::TRIANGLE_LIST,
+            draw_command: None,
+
+            graphics_pipeline: None,
+            graphics_pipeline_key: None,
+
+            debug_commands_before_draw: Vec::new(),
+            debug_commands_after_draw: Vec::new(),
+        }
+    }
+
+    pub fn begin(&mut self) {
+        self.debug_commands_before_draw.clear();
+        self.debug_commands_after_draw.clear();
+    }
+
+    pub fn end(&mut self) {
+        let shader = match &self.shader {
+            Some(shader) if !shader.is_compute() => shader.clone(),
+            _ => return,
+        };
+
+        let key = GraphicsPipelineKey {
+            shader_id: *shader.get_id(),
+            vertex_state_id: self.vertex_state.as_ref().map(|x| x.id),
+            color_formats: self
+                .color_target_view
+                .iter()
+                .flatten()
+                .map(|x| x.get_image().get_format())
+                .collect(),
+            has_depth: self.depth_stencil_view.is_some(),
+            primitive_topology: self.primitive_topology,
+        };
+        if self.graphics_pipeline_key.as_ref() == Some(&key) {
+            // レンダーパイプラインの更新が不要なのでなにもしない
+            return;
+        }
+
+        self.graphics_pipeline = Some(Arc::new(self.create_graphics_pipeline(&shader, &key)));
+        self.graphics_pipeline_key = Some(key);
+    }
+
+    pub fn push_debug_group(&mut self, label: &str) {
+        self.push_debug_command(DebugCommand::PushGroup(label.to_string()));
+    }
+
+    pub fn pop_debug_group(&mut self) {
+        self.push_debug_command(DebugCommand::PopGroup);
+    }
+
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        self.push_debug_command(DebugCommand::InsertMarker(label.to_string()));
+    }
+
+    // wgpu 実装と同じくキューを介さずにその場でクリアする
+    pub fn clear_color(
+        &mut self,
+        color_target_view: &mut ColorTargetViewAsh,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        let image = color_target_view.get_image();
+        let clear_color = vk::ClearColorValue {
+            float32: [red, green, blue, alpha],
+        };
+        let range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: texture_array_range.get_base_index() as u32,
+            layer_count: texture_array_range.get_length() as u32,
+        };
+        execute_immediately(&self.device, |device, command_buffer| unsafe {
+            device.cmd_clear_color_image(
+                command_buffer,
+                image.get_handle(),
+                vk::ImageLayout::GENERAL,
+                &clear_color,
+                &[range],
+            );
+        });
+    }
+
+    pub fn set_render_targets(
+        &mut self,
+        color_target_views: &[&ColorTargetViewAsh],
+        depth_stencil_view: Option<&DepthStencilViewAsh>,
+    ) {
+        if !color_target_views.is_empty() {
+            self.color_target_view = Default::default();
+            for (index, view) in color_target_views.iter().enumerate() {
+                self.color_target_view[index] = Some((*view).clone());
+            }
+        }
+
+        self.depth_stencil_view = depth_stencil_view.map(|x| x.clone_image_view());
+    }
+
+    pub fn set_viewport(&mut self, viewport_state_info: &ViewportStateInfo) {
+        self.viewport_state_info = Some(*viewport_state_info);
+    }
+
+    pub fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo) {
+        self.scissor_state_info = Some(*scissor_state_info);
+    }
+
+    pub fn set_shader(&mut self, shader: &ShaderAsh) {
+        if let Some(current_shader) = &self.shader {
+            if current_shader.get_id() == shader.get_id() {
+                // 更新しない
+                return;
+            }
+        }
+        self.shader = Some(shader.view());
+    }
+
+    pub fn set_constant_buffer(&mut self, index: i32, buffer: &BufferAsh) {
+        self.constant_buffers[index as usize] = Some(buffer.view());
+    }
+
+    pub fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &BufferAsh,
+        offset: usize,
+        size: usize,
+    ) {
+        self.constant_buffers[index as usize] = Some(buffer.view_range(offset, size));
+    }
+
+    pub fn set_unordered_access_buffer(&mut self, index: i32, buffer: &BufferAsh) {
+        self.unordered_access_buffer[index as usize] = Some(buffer.view());
+    }
+
+    pub fn set_image(&mut self, index: i32, texture: &TextureViewAsh) {
+        self.images[index as usize] = Some(texture.clone_storage_image_view());
+    }
+
+    pub fn set_sampler(&mut self, index: i32, sampler: &SamplerAsh) {
+        self.samplers[index as usize] = Some(sampler.clone_sampler());
+    }
+
+    pub fn set_texture(&mut self, index: i32, texture: &TextureViewAsh) {
+        self.textures[index as usize] = Some(texture.clone_image_view());
+    }
+
+    pub fn set_vertex_buffer(&mut self, index: i32, buffer: &BufferAsh) {
+        self.vertex_buffer[index as usize] = Some(buffer.view());
+    }
+
+    pub fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &BufferAsh,
+        offset: usize,
+        size: usize,
+    ) {
+        self.vertex_buffer[index as usize] = Some(buffer.view_range(offset, size));
+    }
+
+    pub fn set_vertex_state(&mut self, vertex_state: &VertexStateAsh) {
+        self.vertex_state = Some(vertex_state.view());
+    }
+
+    pub fn dispatch(
+        &mut self,
+        dispatch_count_x: i32,
+        dispatch_count_y: i32,
+        dispatch_count_z: i32,
+    ) {
+        self.dispatch_count = Some((
+            dispatch_count_x as u32,
+            dispatch_count_y as u32,
+            dispatch_count_z as u32,
+        ));
+    }
+
+    pub fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        self.draw_instanced(
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            1, /*instance_count*/
+            0, /*base_instance*/
+        );
+    }
+
+    pub fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.primitive_topology = util::convert_primitive_topology(&primitive_topology);
+        self.draw_command = Some(DrawCommand::Draw(DrawInfo {
+            vertex_count: vertex_count as u32,
+            first_vertex: vertex_offset as u32,
+            instance_count: instance_count as u32,
+            base_instance: base_instance as u32,
+        }));
+    }
+
+    pub fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &BufferAsh,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        self.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer,
+            index_count,
+            base_vertex,
+            1, /*instance_count*/
+            0, /*base_instance*/
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &BufferAsh,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        let index_type = match index_format {
+            IndexFormat::Uint32 => vk::IndexType::UINT32,
+        };
+
+        self.primitive_topology = util::convert_primitive_topology(&primitive_topology);
+        self.draw_command = Some(DrawCommand::DrawIndexed(DrawIndexedInfo {
+            index_type,
+            index_buffer: index_buffer.view(),
+            index_count: index_count as u32,
+            base_vertex,
+            instance_count: instance_count as u32,
+            base_instance: base_instance as u32,
+        }));
+    }
+
+    // シェーダーが設定されていなければなにも記録しない
+    // 記録に使ったオブジェクトは GPU で完了するまで context に保持させる
+    pub(crate) fn record(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        context: &mut RecordContext,
+    ) -> bool {
+        let shader = match &self.shader {
+            Some(shader) => shader,
+            None => return false,
+        };
+        let device = &self.device.handle;
+
+        self.record_debug_commands(command_buffer, &self.debug_commands_before_draw);
+        record_full_barrier(device, command_buffer);
+
+        let descriptor_set = self.create_descriptor_set(shader, context);
+        context.retain(shader.clone());
+        if shader.is_compute() {
+            let (dispatch_count_x, dispatch_count_y, dispatch_count_z) =
+                self.dispatch_count.unwrap();
+            unsafe {
+                device.cmd_bind_pipeline(
+                    command_buffer,
+                    vk::PipelineBindPoint::COMPUTE,
+                    shader.get_compute_pipeline(),
+                );
+                Self::bind_descriptor_set(
+                    device,
+                    command_buffer,
+                    vk::PipelineBindPoint::COMPUTE,
+                    shader,
+                    descriptor_set,
+                );
+                device.cmd_dispatch(
+                    command_buffer,
+                    dispatch_count_x,
+                    dispatch_count_y,
+                    dispatch_count_z,
+                );
+            }
+        } else {
+            self.record_graphics_command(command_buffer, shader, descriptor_set, context);
+        }
+
+        record_full_barrier(device, command_buffer);
+        self.record_debug_commands(command_buffer, &self.debug_commands_after_draw);
+        true
+    }
+
+    fn record_graphics_command(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        shader: &ShaderView,
+        descriptor_set: Option<vk::DescriptorSet>,
+        context: &mut RecordContext,
+    ) {
+        // レンダーパイプライン。CommandBufferAsh::end() で更新済み
+        let graphics_pipeline = self.graphics_pipeline.as_ref().unwrap();
+        context.retain(graphics_pipeline.clone());
+
+        let mut attachments = Vec::new();
+        for color_target_view in self.color_target_view.iter().flatten() {
+            attachments.push(color_target_view.get_image_view().get_handle());
+            context.retain(color_target_view.clone_image_view());
+        }
+        if let Some(depth_stencil_view) = &self.depth_stencil_view {
+            attachments.push(depth_stencil_view.get_handle());
+            context.retain(depth_stencil_view.clone());
+        }
+
+        let (width, height) = self.get_render_target_size();
+        let create_info = vk::FramebufferCreateInfo::builder()
+            .render_pass(graphics_pipeline.render_pass)
+            .attachments(&attachments)
+            .width(width)
+            .height(height)
+            .layers(1);
+        let device = &self.device.handle;
+        let framebuffer = unsafe { device.create_framebuffer(&create_info, None) }.unwrap();
+        context.push_framebuffer(framebuffer);
+
+        // 深度だけは描画のたびにクリアする
+        let mut clear_values = vec![vk::ClearValue::default(); attachments.len()];
+        if self.depth_stencil_view.is_some() {
+            *clear_values.last_mut().unwrap() = vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: 1.0,
+                    stencil: 0,
+                },
+            };
+        }
+        let render_area = vk::Rect2D {
+            offset: vk::Offset2D::default(),
+            extent: vk::Extent2D { width, height },
+        };
+        let begin_info = vk::RenderPassBeginInfo::builder()
+            .render_pass(graphics_pipeline.render_pass)
+            .framebuffer(framebuffer)
+            .render_area(render_area)
+            .clear_values(&clear_values);
+
+        unsafe {
+            device.cmd_begin_render_pass(command_buffer, &begin_info, vk::SubpassContents::INLINE);
+            device.cmd_bind_pipeline(
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                graphics_pipeline.pipeline,
+            );
+            Self::bind_descriptor_set(
+                device,
+                command_buffer,
+                vk::PipelineBindPoint::GRAPHICS,
+                shader,
+                descriptor_set,
+            );
+
+            // ビューポート
+            // wgpu とあわせるために上下を反転する
+            let (x, y, viewport_width, viewport_height) = match &self.viewport_state_info {
+                Some(info) => (
+                    info.get_origin_x(),
+                    info.get_origin_y(),
+                    info.get_width(),
+                    info.get_height(),
+                ),
+                None => (0.0, 0.0, width as f32, height as f32),
+            };
+            let viewport = vk::Viewport {
+                x,
+                y: y + viewport_height,
+                width: viewport_width,
+                height: -viewport_height,
+                min_depth: 0.0,
+                max_depth: 1.0,
+            };
+            device.cmd_set_viewport(command_buffer, 0, &[viewport]);
+
+            // シザリング
+            let scissor = match &self.scissor_state_info {
+                Some(info) => vk::Rect2D {
+                    offset: vk::Offset2D {
+                        x: info.get_origin_x(),
+                        y: info.get_origin_y(),
+                    },
+                    extent: vk::Extent2D {
+                        width: info.get_width() as u32,
+                        height: info.get_height() as u32,
+                    },
+                },
+                None => render_area,
+            };
+            device.cmd_set_scissor(command_buffer, 0, &[scissor]);
+
+            // 頂点バッファ
+            for (index, vertex_buffer) in self.vertex_buffer.iter().enumerate() {
+                if let Some(vertex_buffer) = vertex_buffer {
+                    device.cmd_bind_vertex_buffers(
+                        command_buffer,
+                        index as u32,
+                        &[vertex_buffer.buffer.get_handle()],
+                        &[vertex_buffer.offset as u64],
+                    );
+                    context.retain(vertex_buffer.buffer.clone());
+                }
+            }
+
+            // 描画
+            match &self.draw_command {
+                Some(DrawCommand::Draw(draw_info)) => {
+                    device.cmd_draw(
+                        command_buffer,
+                        draw_info.vertex_count,
+                        draw_info.instance_count,
+                        draw_info.first_vertex,
+                        draw_info.base_instance,
+                    );
+                }
+                Some(DrawCommand::DrawIndexed(draw_indexed_info)) => {
+                    let index_buffer = &draw_indexed_info.index_buffer;
+                    device.cmd_bind_index_buffer(
+                        command_buffer,
+                        index_buffer.buffer.get_handle(),
+                        index_buffer.offset as u64,
+                        draw_indexed_info.index_type,
+                    );
+                    context.retain(index_buffer.buffer.clone());
+                    device.cmd_draw_indexed(
+                        command_buffer,
+                        draw_indexed_info.index_count,
+                        draw_indexed_info.instance_count,
+                        0,
+                        draw_indexed_info.base_vertex,
+                        draw_indexed_info.base_instance,
+                    );
+                }
+                None => {}
+            }
+
+            device.cmd_end_render_pass(command_buffer);
+        }
+    }
+
+    unsafe fn bind_descriptor_set(
+        device: &ash::Device,
+        command_buffer: vk::CommandBuffer,
+        bind_point: vk::PipelineBindPoint,
+        shader: &ShaderView,
+        descriptor_set: Option<vk::DescriptorSet>,
+    ) {
+        if let Some(descriptor_set) = descriptor_set {
+            device.cmd_bind_descriptor_sets(
+                command_buffer,
+                bind_point,
+                shader.get_pipeline_layout(),
+                0,
+                &[descriptor_set],
+                &[],
+            );
+        }
+    }
+
+    fn get_render_target_size(&self) -> (u32, u32) {
+        if let Some(color_target_view) = self.color_target_view.iter().flatten().next() {
+            return (
+                color_target_view.get_width(),
+                color_target_view.get_height(),
+            );
+        }
+        let image = self.depth_stencil_view.as_ref().unwrap().get_image();
+        (image.get_width(), image.get_height())
+    }
+
+    // 記録のたびにデスクリプタプールを作り、シェーダーのバインディングの種類にあわせて埋める
+    fn create_descriptor_set(
+        &self,
+        shader: &ShaderView,
+        context: &mut RecordContext,
+    ) -> Option<vk::DescriptorSet> {
+        let bindings = shader.get_bindings();
+        if bindings.is_empty() {
+            return None;
+        }
+
+        let device = &self.device.handle;
+        let pool_sizes = bindings
+            .iter()
+            .map(|(_, descriptor_type)| vk::DescriptorPoolSize {
+                ty: *descriptor_type,
+                descriptor_count: 1,
+            })
+            .collect::<Vec<_>>();
+        let create_info = vk::DescriptorPoolCreateInfo::builder()
+            .max_sets(1)
+            .pool_sizes(&pool_sizes);
+        let descriptor_pool = unsafe { device.create_descriptor_pool(&create_info, None) }.unwrap();
+        context.push_descriptor_pool(descriptor_pool);
+
+        let set_layouts = [shader.get_descriptor_set_layout()];
+        let allocate_info = vk::DescriptorSetAllocateInfo::builder()
+            .descriptor_pool(descriptor_pool)
+            .set_layouts(&set_layouts);
+        let descriptor_set = unsafe { device.allocate_descriptor_sets(&allocate_info) }.unwrap()[0];
+
+        // 書き込みが参照する情報は update_descriptor_sets まで生かしておく
+        let mut buffer_infos = Vec::new();
+        let mut image_infos = Vec::new();
+        for (binding, descriptor_type) in bindings {
+            let index = *binding as usize;
+            match *descriptor_type {
+                vk::DescriptorType::UNIFORM_BUFFER | vk::DescriptorType::STORAGE_BUFFER => {
+                    let buffer_view = if *descriptor_type == vk::DescriptorType::UNIFORM_BUFFER {
+                        &self.constant_buffers[index]
+                    } else {
+                        &self.unordered_access_buffer[index]
+                    };
+                    if let Some(buffer_view) = buffer_view {
+                        buffer_infos.push((
+                            *binding,
+                            *descriptor_type,
+                            vk::DescriptorBufferInfo {
+                                buffer: buffer_view.buffer.get_handle(),
+                                offset: buffer_view.offset as u64,
+                                range: buffer_view.size.max(1) as u64,
+                            },
+                        ));
+                        context.retain(buffer_view.buffer.clone());
+                    }
+                }
+                vk::DescriptorType::SAMPLER => {
+                    if let Some(sampler) = &self.samplers[index] {
+                        image_infos.push((
+                            *binding,
+                            *descriptor_type,
+                            vk::DescriptorImageInfo {
+                                sampler: sampler.get_handle(),
+                                ..Default::default()
+                            },
+                        ));
+                        context.retain(sampler.clone());
+                    }
+                }
+                vk::DescriptorType::SAMPLED_IMAGE | vk::DescriptorType::STORAGE_IMAGE => {
+                    let image_view = if *descriptor_type == vk::DescriptorType::SAMPLED_IMAGE {
+                        &self.textures[index]
+                    } else {
+                        &self.images[index]
+                    };
+                    if let Some(image_view) = image_view {
+                        image_infos.push((
+                            *binding,
+                            *descriptor_type,
+                            vk::DescriptorImageInfo {
+                                sampler: vk::Sampler::null(),
+                                image_view: image_view.get_handle(),
+                                image_layout: vk::ImageLayout::GENERAL,
+                            },
+                        ));
+                        context.retain(image_view.clone());
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let writes = buffer_infos
+            .iter()
+            .map(|(binding, descriptor_type, buffer_info)| {
+                vk::WriteDescriptorSet::builder()
+                    .dst_set(descriptor_set)
+                    .dst_binding(*binding)
+                    .descriptor_type(*descriptor_type)
+                    .buffer_info(std::slice::from_ref(buffer_info))
+                    .build()
+            })
+            .chain(
+                image_infos
+                    .iter()
+                    .map(|(binding, descriptor_type, image_info)| {
+                        vk::WriteDescriptorSet::builder()
+                            .dst_set(descriptor_set)
+                            .dst_binding(*binding)
+                            .descriptor_type(*descriptor_type)
+                            .image_info(std::slice::from_ref(image_info))
+                            .build()
+                    }),
+            )
+            .collect::<Vec<_>>();
+        unsafe { device.update_descriptor_sets(&writes, &[]) };
+
+        Some(descriptor_set)
+    }
+
+    fn create_graphics_pipeline(
+        &self,
+        shader: &ShaderView,
+        key: &GraphicsPipelineKey,
+    ) -> GraphicsPipeline {
+        let device = &self.device.handle;
+        let render_pass = Self::create_render_pass(device, key);
+
+        // シェーダ
+        let entry_point = CStr::from_bytes_with_nul(b"main\0").unwrap();
+        let stages = [
+            vk::PipelineShaderStageCreateInfo::builder()
+                .stage(vk::ShaderStageFlags::VERTEX)
+                .module(shader.get_vertex_module())
+                .name(entry_point)
+                .build(),
+            vk::PipelineShaderStageCreateInfo::builder()
+                .stage(vk::ShaderStageFlags::FRAGMENT)
+                .module(shader.get_pixel_module())
+                .name(entry_point)
+                .build(),
+        ];
+
+        // 頂点ステート
+        let (bindings, attributes) = match &self.vertex_state {
+            Some(vertex_state) => (
+                vertex_state.bindings.as_slice(),
+                vertex_state.attributes.as_slice(),
+            ),
+            None => (&[][..], &[][..]),
+        };
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo::builder()
+            .vertex_binding_descriptions(bindings)
+            .vertex_attribute_descriptions(attributes);
+        let input_assembly_state =
+            vk::PipelineInputAssemblyStateCreateInfo::builder().topology(key.primitive_topology);
+
+        // ビューポートとシザーは記録するときに設定する
+        let viewport_state = vk::PipelineViewportStateCreateInfo::builder()
+            .viewport_count(1)
+            .scissor_count(1);
+        let dynamic_states = [vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR];
+        let dynamic_state =
+            vk::PipelineDynamicStateCreateInfo::builder().dynamic_states(&dynamic_states);
+
+        let rasterization_state = vk::PipelineRasterizationStateCreateInfo::builder()
+            .polygon_mode(vk::PolygonMode::FILL)
+            .cull_mode(vk::CullModeFlags::NONE)
+            .front_face(vk::FrontFace::COUNTER_CLOCKWISE)
+            .depth_bias_enable(key.has_depth)
+            .depth_bias_constant_factor(2.0)
+            .depth_bias_slope_factor(2.0)
+            .line_width(1.0);
+        let multisample_state = vk::PipelineMultisampleStateCreateInfo::builder()
+            .rasterization_samples(vk::SampleCountFlags::TYPE_1);
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo::builder()
+            .depth_test_enable(key.has_depth)
+            .depth_write_enable(key.has_depth)
+            .depth_compare_op(vk::CompareOp::LESS_OR_EQUAL);
+
+        // wgpu 実装と同じブレンド
+        let color_blend_attachments = key
+            .color_formats
+            .iter()
+            .map(|_| vk::PipelineColorBlendAttachmentState {
+                blend_enable: vk::TRUE,
+                src_color_blend_factor: vk::BlendFactor::ONE,
+                dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
+                color_blend_op: vk::BlendOp::ADD,
+                src_alpha_blend_factor: vk::BlendFactor::ONE_MINUS_DST_ALPHA,
+                dst_alpha_blend_factor: vk::BlendFactor::ONE,
+                alpha_blend_op: vk::BlendOp::ADD,
+                color_write_mask: vk::ColorComponentFlags::RGBA,
+            })
+            .collect::<Vec<_>>();
+        let color_blend_state =
+            vk::PipelineColorBlendStateCreateInfo::builder().attachments(&color_blend_attachments);
+
+        let create_info = vk::GraphicsPipelineCreateInfo::builder()
+            .stages(&stages)
+            .vertex_input_state(&vertex_input_state)
+            .input_assembly_state(&input_assembly_state)
+            .viewport_state(&viewport_state)
+            .rasterization_state(&rasterization_state)
+            .multisample_state(&multisample_state)
+            .depth_stencil_state(&depth_stencil_state)
+            .color_blend_state(&color_blend_state)
+            .dynamic_state(&dynamic_state)
+            .layout(shader.get_pipeline_layout())
+            .render_pass(render_pass)
+            .subpass(0)
+            .build();
+        let pipeline = unsafe {
+            device.create_graphics_pipelines(vk::PipelineCache::null(), &[create_info], None)
+        }
+        .unwrap()[0];
+
+        GraphicsPipeline {
+            device: self.device.clone(),
+            render_pass,
+            pipeline,
+        }
+    }
+
+    // イメージは常に GENERAL レイアウトなので、レンダーパスの前後でもレイアウトを変えない
+    fn create_render_pass(device: &ash::Device, key: &GraphicsPipelineKey) -> vk::RenderPass {
+        let mut attachments = key
+            .color_formats
+            .iter()
+            .map(|format| vk::AttachmentDescription {
+                format: *format,
+                samples: vk::SampleCountFlags::TYPE_1,
+                load_op: vk::AttachmentLoadOp::LOAD,
+                store_op: vk::AttachmentStoreOp::STORE,
+                stencil_load_op: vk::AttachmentLoadOp::DONT_CARE,
+                stencil_store_op: vk::AttachmentStoreOp::DONT_CARE,
+                initial_layout: vk::ImageLayout::GENERAL,
+                final_layout: vk::ImageLayout::GENERAL,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let color_references = (0..key.color_formats.len())
+            .map(|index| vk::AttachmentReference {
+                attachment: index as u32,
+                layout: vk::ImageLayout::GENERAL,
+            })
+            .collect::<Vec<_>>();
+        let depth_reference = vk::AttachmentReference {
+            attachment: attachments.len() as u32,
+            layout: vk::ImageLayout::GENERAL,
+        };
+        if key.has_depth {
+            attachments.push(vk::AttachmentDescription {
+                format: vk::Format::D32_SFLOAT,
+                samples: vk::SampleCountFlags::TYPE_1,
+                load_op: vk::AttachmentLoadOp::CLEAR,
+                store_op: vk::AttachmentStoreOp::STORE,
+                stencil_load_op: vk::AttachmentLoadOp::DONT_CARE,
+                stencil_store_op: vk::AttachmentStoreOp::DONT_CARE,
+                initial_layout: vk::ImageLayout::GENERAL,
+                final_layout: vk::ImageLayout::GENERAL,
+                ..Default::default()
+            });
+        }
+
+        let mut subpass = vk::SubpassDescription::builder()
+            .pipeline_bind_point(vk::PipelineBindPoint::GRAPHICS)
+            .color_attachments(&color_references);
+        if key.has_depth {
+            subpass = subpass.depth_stencil_attachment(&depth_reference);
+        }
+        let subpasses = [subpass.build()];
+        let create_info = vk::RenderPassCreateInfo::builder()
+            .attachments(&attachments)
+            .subpasses(&subpasses);
+        unsafe { device.create_render_pass(&create_info, None) }.unwrap()
+    }
+
+    fn push_debug_command(&mut self, debug_command: DebugCommand) {
+        // 描画コマンドはステートとして保持しているので、描画の前後どちらで積まれたかだけを記録する
+        if self.draw_command.is_some() || self.dispatch_count.is_some() {
+            self.debug_commands_after_draw.push(debug_command);
+        } else {
+            self.debug_commands_before_draw.push(debug_command);
+        }
+    }
+
+    // VK_EXT_debug_utils が使えないときはなにもしない
+    fn record_debug_commands(
+        &self,
+        command_buffer: vk::CommandBuffer,
+        debug_commands: &[DebugCommand],
+    ) {
+        let debug_utils = match self.device.get_instance().get_debug_utils() {
+            Some(debug_utils) => debug_utils,
+            None => return,
+        };
+
+        for debug_command in debug_commands {
+            unsafe {
+                match debug_command {
+                    DebugCommand::PushGroup(label) => {
+                        let label = CString::new(label.as_str()).unwrap();
+                        let label_info = vk::DebugUtilsLabelEXT::builder().label_name(&label);
+                        debug_utils.cmd_begin_debug_utils_label(command_buffer, &label_info);
+                    }
+                    DebugCommand::PopGroup => debug_utils.cmd_end_debug_utils_label(command_buffer),
+                    DebugCommand::InsertMarker(label) => {
+                        let label = CString::new(label.as_str()).unwrap();
+                        let label_info = vk::DebugUtilsLabelEXT::builder().label_name(&label);
+                        debug_utils.cmd_insert_debug_utils_label(command_buffer, &label_info);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ICommandBuffer for CommandBufferAsh {
+    type DeviceType = DeviceAsh;
+    type BufferType = BufferAsh;
+    type ColorTargetViewType = ColorTargetViewAsh;
+    type DepthStencilViewType = DepthStencilViewAsh;
+    type SamplerType = SamplerAsh;
+    type ShaderType = ShaderAsh;
+    type TextureType = TextureAsh;
+    type TextureViewType = TextureViewAsh;
+    type VertexStateType = VertexStateAsh;
+
+    fn new(device: &Self::DeviceType, info: &CommandBufferInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn begin(&mut self) {
+        CommandBufferAsh::begin(self);
+    }
+
+    fn end(&mut self) {
+        CommandBufferAsh::end(self);
+    }
+
+    fn push_debug_group(&mut self, label: &str) {
+        CommandBufferAsh::push_debug_group(self, label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        CommandBufferAsh::pop_debug_group(self);
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        CommandBufferAsh::insert_debug_marker(self, label);
+    }
+
+    fn clear_color(
+        &mut self,
+        color_target_view: &mut Self::ColorTargetViewType,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        self.clear_color(
+            color_target_view,
+            red,
+            green,
+            blue,
+            alpha,
+            texture_array_range,
+        );
+    }
+
+    fn set_render_targets(
+        &mut self,
+        color_target_views: &[&Self::ColorTargetViewType],
+        depth_stencil_view: Option<&Self::DepthStencilViewType>,
+    ) {
+        self.set_render_targets(color_target_views, depth_stencil_view)
+    }
+
+    fn set_shader(&mut self, shader: &Self::ShaderType) {
+        self.set_shader(shader);
+    }
+
+    fn set_sampler(&mut self, index: i32, sampler: &Self::SamplerType) {
+        self.set_sampler(index, sampler);
+    }
+
+    fn set_texture(&mut self, index: i32, texture_view: &Self::TextureViewType) {
+        self.set_texture(index, texture_view);
+    }
+
+    fn set_image(&mut self, index: i32, texture: &Self::TextureViewType) {
+        self.set_image(index, texture);
+    }
+
+    fn set_constant_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.set_constant_buffer(index, buffer);
+    }
+
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_constant_buffer_range(index, buffer, offset, size);
+    }
+
+    fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.set_unordered_access_buffer(index, buffer);
+    }
+
+    fn set_vertex_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.set_vertex_buffer(index, buffer);
+    }
+
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_vertex_buffer_range(index, buffer, offset, size);
+    }
+
+    fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
+        self.set_vertex_state(vertex_state);
+    }
+
+    fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo) {
+        self.set_scissor(scissor_state_info);
+    }
+
+    fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        self.dispatch(count_x, count_y, count_z);
+    }
+
+    fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        self.draw(primitive_topology, vertex_count, vertex_offset);
+    }
+
+    fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.draw_instanced(
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        );
+    }
+
+    fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        self.draw_indexed(
+            primitive_topology,
+            index_format,
+            index_buffer,
+            index_count,
+            base_vertex,
+        );
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer,
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use sjgfx_interface::{DepthStencilStateInfo, IDepthStencilView};
+
+use crate::{detail::ImageView, DeviceAsh, TextureAsh};
+
+pub struct DepthStencilViewAsh {
+    image_view: Arc<ImageView>,
+}
+
+impl DepthStencilViewAsh {
+    pub fn new(_device: &DeviceAsh, texture: &TextureAsh) -> Self {
+        let image = texture.clone_image();
+        let format = image.get_format();
+        Self {
+            image_view: Arc::new(ImageView::new(image, format, 1)),
+        }
+    }
+
+    pub fn get_image_view(&self) -> &ImageView {
+        &self.image_view
+    }
+
+    pub fn clone_image_view(&self) -> Arc<ImageView> {
+        self.image_view.clone()
+    }
+}
+
+impl IDepthStencilView for DepthStencilViewAsh {
+    type DeviceType = DeviceAsh;
+    type TextureType = TextureAsh;
+
+    fn new(
+        device: &Self::DeviceType,
+        _info: &DepthStencilStateInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        Self::new(device, texture)
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    ffi::c_char,
+    sync::{Arc, Mutex},
+};
+
+use super::Instance;
+
+pub struct Device {
+    pub handle: ash::Device,
+    pub physical_device: ash::vk::PhysicalDevice,
+    pub queue_family_index: u32,
+
+    // vkQueueSubmit は外部同期が必要なのでロックしてから使う
+    pub queue: Mutex<ash::vk::Queue>,
+
+    pub properties: ash::vk::PhysicalDeviceProperties,
+    pub memory_properties: ash::vk::PhysicalDeviceMemoryProperties,
+
+    // デバイスより先にインスタンスが破棄されないように保持しておく
+    instance: Arc<Instance>,
+}
+
+impl Device {
+    // キューファミリーからキューを 1 つだけ作る
+    // タイムラインセマフォは常に有効にする
+    pub fn new(
+        instance: Arc<Instance>,
+        physical_device: ash::vk::PhysicalDevice,
+        queue_family_index: u32,
+        extension_names: &[*const c_char],
+        features: &ash::vk::PhysicalDeviceFeatures,
+    ) -> Self {
+        let queue_priorities = [1.0];
+        let queue_create_infos = [ash::vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(queue_family_index)
+            .queue_priorities(&queue_priorities)
+            .build()];
+        let mut vulkan12_features =
+            ash::vk::PhysicalDeviceVulkan12Features::builder().timeline_semaphore(true);
+        let create_info = ash::vk::DeviceCreateInfo::builder()
+            .queue_create_infos(&queue_create_infos)
+            .enabled_extension_names(extension_names)
+            .enabled_features(features)
+            .push_next(&mut vulkan12_features);
+
+        let handle = unsafe {
+            instance
+                .handle
+                .create_device(physical_device, &create_info, None)
+                .expect("Device creation error")
+        };
+        let queue = unsafe { handle.get_device_queue(queue_family_index, 0) };
+        let properties = unsafe {
+            instance
+                .handle
+                .get_physical_device_properties(physical_device)
+        };
+        let memory_properties = unsafe {
+            instance
+                .handle
+                .get_physical_device_memory_properties(physical_device)
+        };
+
+        Self {
+            handle,
+            physical_device,
+            queue_family_index,
+            queue: Mutex::new(queue),
+            properties,
+            memory_properties,
+            instance,
+        }
+    }
+
+    pub fn get_instance(&self) -> &Instance {
+        &self.instance
+    }
+
+    pub fn clone_instance(&self) -> Arc<Instance> {
+        self.instance.clone()
+    }
+
+    pub fn find_memory_type_index(
+        &self,
+        memory_type_bits: u32,
+        flags: ash::vk::MemoryPropertyFlags,
+    ) -> Option<u32> {
+        self.memory_properties.memory_types[..self.memory_properties.memory_type_count as usize]
+            .iter()
+            .enumerate()
+            .find(|(index, memory_type)| {
+                (memory_type_bits & (1 << index)) != 0 && memory_type.property_flags.contains(flags)
+            })
+            .map(|(index, _)| index as u32)
+    }
+}
+
+impl Drop for Device {
+    fn drop(&mut self) {
+        unsafe {
+            self.handle.device_wait_idle().unwrap();
+            self.handle.destroy_device(None);
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use ash::vk;
+
+use super::Device;
+
+// 1 回だけのコマンドを記録してサブミットし、完了を待ってから戻る
+// テクスチャーの初期化や読み書きなど、キューを介さずに済ませたい処理で使う
+pub fn execute_immediately<F>(device: &Device, record: F)
+where
+    F: FnOnce(&ash::Device, vk::CommandBuffer),
+{
+    let handle = &device.handle;
+    unsafe {
+        let command_pool = handle
+            .create_command_pool(
+                &vk::CommandPoolCreateInfo::builder()
+                    .flags(vk::CommandPoolCreateFlags::TRANSIENT)
+                    .queue_family_index(device.queue_family_index),
+                None,
+            )
+            .unwrap();
+        let command_buffer = handle
+            .allocate_command_buffers(
+                &vk::CommandBufferAllocateInfo::builder()
+                    .command_pool(command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1),
+            )
+            .unwrap()[0];
+
+        handle
+            .begin_command_buffer(
+                command_buffer,
+                &vk::CommandBufferBeginInfo::builder()
+                    .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT),
+            )
+            .unwrap();
+        record_full_barrier(handle, command_buffer);
+        record(handle, command_buffer);
+        record_full_barrier(handle, command_buffer);
+        handle.end_command_buffer(command_buffer).unwrap();
+
+        let fence = handle
+            .create_fence(&vk::FenceCreateInfo::default(), None)
+            .unwrap();
+        let command_buffers = [command_buffer];
+        let submit_info = vk::SubmitInfo::builder()
+            .command_buffers(&command_buffers)
+            .build();
+        {
+            let queue = device.queue.lock().unwrap();
+            handle.queue_submit(*queue, &[submit_info], fence).unwrap();
+        }
+        handle.wait_for_fences(&[fence], true, u64::MAX).unwrap();
+
+        handle.destroy_fence(fence, None);
+        handle.destroy_command_pool(command_pool, None);
+    }
+}
+
+// 前後のコマンドの書き込みをすべて待つ
+// リソースごとの依存関係を追跡しないかわりに、記録するコマンドの前後に必ず積む
+// CPU からの読み戻しに備えてホストへの可視性もあわせて保証する
+pub fn record_full_barrier(device: &ash::Device, command_buffer: vk::CommandBuffer) {
+    let memory_barrier = vk::MemoryBarrier::builder()
+        .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+        .dst_access_mask(
+            vk::AccessFlags::MEMORY_READ
+                | vk::AccessFlags::MEMORY_WRITE
+                | vk::AccessFlags::HOST_READ,
+        )
+        .build();
+    unsafe {
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::ALL_COMMANDS | vk::PipelineStageFlags::HOST,
+            vk::DependencyFlags::empty(),
+            &[memory_barrier],
+            &[],
+            &[],
+        );
+    }
+}
+
+pub fn record_image_layout_transition(
+    device: &ash::Device,
+    command_buffer: vk::CommandBuffer,
+    image: vk::Image,
+    aspect_mask: vk::ImageAspectFlags,
+    old_layout: vk::ImageLayout,
+    new_layout: vk::ImageLayout,
+) {
+    let image_barrier = vk::ImageMemoryBarrier::builder()
+        .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+        .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)
+        .old_layout(old_layout)
+        .new_layout(new_layout)
+        .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+        .image(image)
+        .subresource_range(vk::ImageSubresourceRange {
+            aspect_mask,
+            base_mip_level: 0,
+            level_count: vk::REMAINING_MIP_LEVELS,
+            base_array_layer: 0,
+            layer_count: vk::REMAINING_ARRAY_LAYERS,
+        })
+        .build();
+    unsafe {
+        device.cmd_pipeline_barrier(
+            command_buffer,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::PipelineStageFlags::ALL_COMMANDS,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[image_barrier],
+        );
+    }
+}
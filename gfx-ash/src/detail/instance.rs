@@ -0,0 +1,195 @@
+use std::ffi::{c_char, CStr};
+
+use super::PhysicalDevice;
+
+struct DebugData {
+    debug_utils: ash::extensions::ext::DebugUtils,
+    debug_utils_messanger: ash::vk::DebugUtilsMessengerEXT,
+}
+
+pub struct Instance {
+    pub handle: ash::Instance,
+    debug_data: Option<DebugData>,
+
+    // ローダーを読み込んだ Entry はインスタンスより長生きさせる
+    pub entry: ash::Entry,
+}
+
+impl Instance {
+    pub fn new() -> Self {
+        Self::new_with_extensions(&[], false)
+    }
+
+    // サーフェスなどの拡張を有効にしてインスタンスを作る
+    // デバッグを有効にしても検証レイヤーがインストールされていなければ使わない
+    pub fn new_with_extensions(extension_names: &[*const c_char], is_debug_enabled: bool) -> Self {
+        let entry = unsafe { ash::Entry::load() }.expect("Vulkan loader is not found");
+        let app_name = CStr::from_bytes_with_nul(b"VulkanTriangle\0").unwrap();
+
+        let validation_layer_name =
+            CStr::from_bytes_with_nul(b"VK_LAYER_KHRONOS_validation\0").unwrap();
+        let is_validation_enabled = is_debug_enabled
+            && entry
+                .enumerate_instance_layer_properties()
+                .unwrap()
+                .iter()
+                .any(|property| unsafe {
+                    CStr::from_ptr(property.layer_name.as_ptr()) == validation_layer_name
+                });
+        let layers_names_raw: Vec<*const c_char> = if is_validation_enabled {
+            vec![validation_layer_name.as_ptr()]
+        } else {
+            Vec::new()
+        };
+
+        let is_debug_utils_enabled = is_debug_enabled
+            && entry
+                .enumerate_instance_extension_properties(None)
+                .unwrap()
+                .iter()
+                .any(|property| unsafe {
+                    CStr::from_ptr(property.extension_name.as_ptr())
+                        == ash::extensions::ext::DebugUtils::name()
+                });
+        let mut extension_names = extension_names.to_vec();
+        if is_debug_utils_enabled {
+            extension_names.push(ash::extensions::ext::DebugUtils::name().as_ptr());
+        }
+
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        {
+            // Enabling this extension is a requirement when using `VK_KHR_portability_subset`
+            extension_names.push(ash::vk::KhrGetPhysicalDeviceProperties2Fn::name().as_ptr());
+        }
+
+        // タイムラインセマフォを使うので 1.2 を要求する
+        let appinfo = ash::vk::ApplicationInfo::builder()
+            .application_name(app_name)
+            .application_version(0)
+            .engine_name(app_name)
+            .engine_version(0)
+            .api_version(ash::vk::make_api_version(0, 1, 2, 0));
+
+        let create_info = ash::vk::InstanceCreateInfo::builder()
+            .application_info(&appinfo)
+            .enabled_layer_names(&layers_names_raw)
+            .enabled_extension_names(&extension_names);
+
+        let instance: ash::Instance = unsafe {
+            entry
+                .create_instance(&create_info, None)
+                .expect("Instance creation error")
+        };
+
+        let debug_data = if is_debug_utils_enabled {
+            Some(Self::create_debug_data(&entry, &instance))
+        } else {
+            None
+        };
+        Self {
+            handle: instance,
+            debug_data,
+            entry,
+        }
+    }
+
+    // デバッグを有効にしてインスタンスを作ったときだけデバッグラベルを積める
+    pub fn get_debug_utils(&self) -> Option<&ash::extensions::ext::DebugUtils> {
+        self.debug_data
+            .as_ref()
+            .map(|debug_data| &debug_data.debug_utils)
+    }
+
+    pub fn enumerate_physical_devices(&self) -> Vec<PhysicalDevice> {
+        let physical_devices = unsafe {
+            self.handle
+                .enumerate_physical_devices()
+                .expect("Physical device error")
+        };
+        physical_devices
+            .iter()
+            .map(|handle| PhysicalDevice::new(*handle, self.handle.clone()))
+            .collect()
+    }
+
+    fn create_debug_data(entry: &ash::Entry, instance: &ash::Instance) -> DebugData {
+        let debug_utils = ash::extensions::ext::DebugUtils::new(entry, instance);
+        let debug_utils_messanger_create_info =
+            ash::vk::DebugUtilsMessengerCreateInfoEXT::builder()
+                .message_severity(
+                    ash::vk::DebugUtilsMessageSeverityFlagsEXT::ERROR
+                        | ash::vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
+                        | ash::vk::DebugUtilsMessageSeverityFlagsEXT::INFO,
+                )
+                .message_type(
+                    ash::vk::DebugUtilsMessageTypeFlagsEXT::GENERAL
+                        | ash::vk::DebugUtilsMessageTypeFlagsEXT::VALIDATION
+                        | ash::vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE,
+                )
+                .pfn_user_callback(Some(Self::call_debug_info));
+
+        let debug_utils_messanger = unsafe {
+            debug_utils
+                .create_debug_utils_messenger(&debug_utils_messanger_create_info, None)
+                .unwrap()
+        };
+
+        DebugData {
+            debug_utils,
+            debug_utils_messanger,
+        }
+    }
+
+    unsafe extern "system" fn call_debug_info(
+        message_severity: ash::vk::DebugUtilsMessageSeverityFlagsEXT,
+        message_type: ash::vk::DebugUtilsMessageTypeFlagsEXT,
+        p_callback_data: *const ash::vk::DebugUtilsMessengerCallbackDataEXT,
+        _user_data: *mut std::ffi::c_void,
+    ) -> u32 {
+        let callback_data = *p_callback_data;
+        let message_id_number: i32 = callback_data.message_id_number;
+
+        let message_id_name = if callback_data.p_message_id_name.is_null() {
+            std::borrow::Cow::from("")
+        } else {
+            CStr::from_ptr(callback_data.p_message_id_name).to_string_lossy()
+        };
+
+        let message = if callback_data.p_message.is_null() {
+            std::borrow::Cow::from("")
+        } else {
+            CStr::from_ptr(callback_data.p_message).to_string_lossy()
+        };
+
+        println!(
+            "{:?}:\n{:?} [{} ({})] : {}\n",
+            message_severity,
+            message_type,
+            message_id_name,
+            &message_id_number.to_string(),
+            message,
+        );
+
+        ash::vk::TRUE
+    }
+}
+
+impl Default for Instance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        if let Some(debug_data) = &self.debug_data {
+            unsafe {
+                debug_data
+                    .debug_utils
+                    .destroy_debug_utils_messenger(debug_data.debug_utils_messanger, None)
+            };
+        }
+
+        unsafe { self.handle.destroy_instance(None) };
+    }
+}
@@ -0,0 +1,19 @@
+mod device;
+mod immediate;
+mod instance;
+mod offscreen_scan_buffers;
+mod physical_device;
+mod record_context;
+mod resource;
+mod surface;
+mod swap_chain_blit;
+
+pub use device::Device;
+pub use immediate::{execute_immediately, record_full_barrier, record_image_layout_transition};
+pub use instance::Instance;
+pub use offscreen_scan_buffers::OffscreenScanBuffers;
+pub use physical_device::PhysicalDevice;
+pub use record_context::RecordContext;
+pub use resource::{Buffer, Image, ImageView, Sampler};
+pub use surface::Surface;
+pub use swap_chain_blit::SwapChainBlit;
@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use ash::vk;
+use sjgfx_interface::ImageData;
+
+use super::{Device, Image};
+use crate::ColorTargetViewAsh;
+
+// サーフェスがないときにスキャンバッファの代わりに使うイメージのリング
+// 表示されたフレームは次に取得されるまで読み戻せる
+pub struct OffscreenScanBuffers {
+    device: Arc<Device>,
+    format: vk::Format,
+    count: usize,
+    views: Vec<ColorTargetViewAsh>,
+    next_index: usize,
+    presented_index: Option<usize>,
+}
+
+impl OffscreenScanBuffers {
+    pub fn new(device: Arc<Device>, format: vk::Format, count: usize) -> Self {
+        assert!(count > 0);
+        Self {
+            device,
+            format,
+            count,
+            views: Vec::new(),
+            next_index: 0,
+            presented_index: None,
+        }
+    }
+
+    // 大きさが変わると中身は失われる
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.views = (0..self.count)
+            .map(|_| self.create_view(width, height))
+            .collect();
+        self.next_index = 0;
+        self.presented_index = None;
+    }
+
+    fn create_view(&self, width: u32, height: u32) -> ColorTargetViewAsh {
+        let image = Image::new(
+            self.device.clone(),
+            self.format,
+            width,
+            height,
+            1,
+            1,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT,
+        );
+        ColorTargetViewAsh::new_direct(self.device.clone(), Arc::new(image))
+    }
+
+    pub fn acquire(&mut self) -> usize {
+        assert!(
+            !self.views.is_empty(),
+            "offscreen scan buffers are not sized"
+        );
+        let index = self.next_index;
+        self.next_index = (self.next_index + 1) % self.views.len();
+        index
+    }
+
+    pub fn get_view(&self, index: usize) -> &ColorTargetViewAsh {
+        &self.views[index]
+    }
+
+    pub fn present(&mut self, index: usize) {
+        self.presented_index = Some(index);
+    }
+
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        let index = self.presented_index?;
+        Some(self.views[index].read_image())
+    }
+}
@@ -0,0 +1,39 @@
+use std::ffi::CStr;
+
+pub struct PhysicalDevice {
+    pub handle: ash::vk::PhysicalDevice,
+    instance: ash::Instance,
+}
+
+impl PhysicalDevice {
+    pub fn new(handle: ash::vk::PhysicalDevice, instance: ash::Instance) -> Self {
+        Self { handle, instance }
+    }
+
+    pub fn is_extension_supported(&self, extension_name: &CStr) -> bool {
+        unsafe {
+            self.instance
+                .enumerate_device_extension_properties(self.handle)
+        }
+        .unwrap()
+        .iter()
+        .any(|property| unsafe {
+            CStr::from_ptr(property.extension_name.as_ptr()) == extension_name
+        })
+    }
+
+    pub fn get_queue_family_properties(&self) -> Vec<ash::vk::QueueFamilyProperties> {
+        unsafe {
+            self.instance
+                .get_physical_device_queue_family_properties(self.handle)
+        }
+    }
+
+    pub fn get_properties(&self) -> ash::vk::PhysicalDeviceProperties {
+        unsafe { self.instance.get_physical_device_properties(self.handle) }
+    }
+
+    pub fn get_features(&self) -> ash::vk::PhysicalDeviceFeatures {
+        unsafe { self.instance.get_physical_device_features(self.handle) }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{any::Any, sync::Arc};
+
+use ash::vk;
+
+// 1 回の記録で作ったオブジェクトと、GPU が使い終わるまで破棄できないリソース
+// キューがサブミットの完了を確認してから破棄する
+#[derive(Default)]
+pub struct RecordContext {
+    descriptor_pools: Vec<vk::DescriptorPool>,
+    framebuffers: Vec<vk::Framebuffer>,
+    resources: Vec<Arc<dyn Any + Send + Sync>>,
+}
+
+impl RecordContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push_descriptor_pool(&mut self, descriptor_pool: vk::DescriptorPool) {
+        self.descriptor_pools.push(descriptor_pool);
+    }
+
+    pub fn push_framebuffer(&mut self, framebuffer: vk::Framebuffer) {
+        self.framebuffers.push(framebuffer);
+    }
+
+    pub fn retain<T: Any + Send + Sync>(&mut self, resource: Arc<T>) {
+        self.resources.push(resource);
+    }
+
+    pub fn append(&mut self, other: RecordContext) {
+        self.descriptor_pools.extend(other.descriptor_pools);
+        self.framebuffers.extend(other.framebuffers);
+        self.resources.extend(other.resources);
+    }
+
+    pub fn destroy(self, device: &ash::Device) {
+        unsafe {
+            for framebuffer in self.framebuffers {
+                device.destroy_framebuffer(framebuffer, None);
+            }
+            for descriptor_pool in self.descriptor_pools {
+                device.destroy_descriptor_pool(descriptor_pool, None);
+            }
+        }
+    }
+}
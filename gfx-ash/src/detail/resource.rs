@@ -0,0 +1,325 @@
+use std::sync::Arc;
+
+use ash::vk;
+
+use super::{execute_immediately, record_image_layout_transition, Device};
+use crate::util;
+
+// メモリーはリソースごとに確保する
+fn allocate_memory(
+    device: &Device,
+    requirements: &vk::MemoryRequirements,
+    flags: vk::MemoryPropertyFlags,
+) -> vk::DeviceMemory {
+    let memory_type_index = device
+        .find_memory_type_index(requirements.memory_type_bits, flags)
+        .or_else(|| {
+            device.find_memory_type_index(
+                requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::empty(),
+            )
+        })
+        .unwrap();
+    let allocate_info = vk::MemoryAllocateInfo::builder()
+        .allocation_size(requirements.size)
+        .memory_type_index(memory_type_index);
+    unsafe { device.handle.allocate_memory(&allocate_info, None) }.unwrap()
+}
+
+pub struct Image {
+    device: Arc<Device>,
+    handle: vk::Image,
+
+    // スワップチェーンのイメージはスワップチェーンが破棄するのでメモリーを持たない
+    memory: Option<vk::DeviceMemory>,
+
+    format: vk::Format,
+    width: u32,
+    height: u32,
+    mip_count: u32,
+    array_layers: u32,
+}
+
+impl Image {
+    // イメージのレイアウトは作ってから破棄するまで常に GENERAL にしておく
+    pub fn new(
+        device: Arc<Device>,
+        format: vk::Format,
+        width: u32,
+        height: u32,
+        mip_count: u32,
+        array_layers: u32,
+        usage: vk::ImageUsageFlags,
+    ) -> Self {
+        // コピーとクリアはどのイメージにもできるようにする
+        let usage = usage | vk::ImageUsageFlags::TRANSFER_SRC | vk::ImageUsageFlags::TRANSFER_DST;
+        let create_info = vk::ImageCreateInfo::builder()
+            .image_type(vk::ImageType::TYPE_2D)
+            .format(format)
+            .extent(vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .mip_levels(mip_count)
+            .array_layers(array_layers)
+            .samples(vk::SampleCountFlags::TYPE_1)
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+        let handle = unsafe { device.handle.create_image(&create_info, None) }.unwrap();
+        let requirements = unsafe { device.handle.get_image_memory_requirements(handle) };
+        let memory = allocate_memory(
+            &device,
+            &requirements,
+            vk::MemoryPropertyFlags::DEVICE_LOCAL,
+        );
+        unsafe { device.handle.bind_image_memory(handle, memory, 0) }.unwrap();
+
+        execute_immediately(&device, |device, command_buffer| {
+            record_image_layout_transition(
+                device,
+                command_buffer,
+                handle,
+                util::get_aspect_mask(format),
+                vk::ImageLayout::UNDEFINED,
+                vk::ImageLayout::GENERAL,
+            );
+        });
+
+        Self {
+            device,
+            handle,
+            memory: Some(memory),
+            format,
+            width,
+            height,
+            mip_count,
+            array_layers,
+        }
+    }
+
+    pub fn new_from_swap_chain(
+        device: Arc<Device>,
+        handle: vk::Image,
+        format: vk::Format,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        Self {
+            device,
+            handle,
+            memory: None,
+            format,
+            width,
+            height,
+            mip_count: 1,
+            array_layers: 1,
+        }
+    }
+
+    pub fn get_handle(&self) -> vk::Image {
+        self.handle
+    }
+
+    pub fn get_format(&self) -> vk::Format {
+        self.format
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get_mip_count(&self) -> u32 {
+        self.mip_count
+    }
+
+    pub fn get_array_layers(&self) -> u32 {
+        self.array_layers
+    }
+
+    pub fn get_aspect_mask(&self) -> vk::ImageAspectFlags {
+        util::get_aspect_mask(self.format)
+    }
+
+    pub fn get_mip_size(&self, mip_level: u32) -> (u32, u32) {
+        (
+            (self.width >> mip_level).max(1),
+            (self.height >> mip_level).max(1),
+        )
+    }
+}
+
+impl Drop for Image {
+    fn drop(&mut self) {
+        if let Some(memory) = self.memory {
+            unsafe {
+                self.device.handle.destroy_image(self.handle, None);
+                self.device.handle.free_memory(memory, None);
+            }
+        }
+    }
+}
+
+pub struct ImageView {
+    device: Arc<Device>,
+    handle: vk::ImageView,
+
+    // ビューより先にイメージが破棄されないように保持しておく
+    image: Arc<Image>,
+}
+
+impl ImageView {
+    pub fn new(image: Arc<Image>, format: vk::Format, mip_count: u32) -> Self {
+        let view_type = if image.get_array_layers() > 1 {
+            vk::ImageViewType::TYPE_2D_ARRAY
+        } else {
+            vk::ImageViewType::TYPE_2D
+        };
+        let create_info = vk::ImageViewCreateInfo::builder()
+            .image(image.get_handle())
+            .view_type(view_type)
+            .format(format)
+            .subresource_range(vk::ImageSubresourceRange {
+                aspect_mask: image.get_aspect_mask(),
+                base_mip_level: 0,
+                level_count: mip_count,
+                base_array_layer: 0,
+                layer_count: image.get_array_layers(),
+            });
+        let device = image.device.clone();
+        let handle = unsafe { device.handle.create_image_view(&create_info, None) }.unwrap();
+        Self {
+            device,
+            handle,
+            image,
+        }
+    }
+
+    pub fn get_handle(&self) -> vk::ImageView {
+        self.handle
+    }
+
+    pub fn get_image(&self) -> &Arc<Image> {
+        &self.image
+    }
+}
+
+impl Drop for ImageView {
+    fn drop(&mut self) {
+        unsafe { self.device.handle.destroy_image_view(self.handle, None) };
+    }
+}
+
+pub struct Buffer {
+    device: Arc<Device>,
+    handle: vk::Buffer,
+    memory: vk::DeviceMemory,
+    size: usize,
+
+    // CPU からアクセスするバッファーは作ったときからずっとマップしておく
+    mapped_ptr: Option<*mut u8>,
+}
+
+// マップしたポインターはバッファーが生きている間ずっと有効
+unsafe impl Send for Buffer {}
+unsafe impl Sync for Buffer {}
+
+impl Buffer {
+    pub fn new(
+        device: Arc<Device>,
+        size: usize,
+        usage: vk::BufferUsageFlags,
+        is_mappable: bool,
+    ) -> Self {
+        // 大きさ 0 のバッファーは作れない
+        let create_info = vk::BufferCreateInfo::builder()
+            .size(size.max(1) as u64)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let handle = unsafe { device.handle.create_buffer(&create_info, None) }.unwrap();
+        let requirements = unsafe { device.handle.get_buffer_memory_requirements(handle) };
+        let memory_flags = if is_mappable {
+            vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT
+        } else {
+            vk::MemoryPropertyFlags::DEVICE_LOCAL
+        };
+        let memory = allocate_memory(&device, &requirements, memory_flags);
+        unsafe { device.handle.bind_buffer_memory(handle, memory, 0) }.unwrap();
+
+        let mapped_ptr = if is_mappable {
+            let ptr = unsafe {
+                device
+                    .handle
+                    .map_memory(memory, 0, vk::WHOLE_SIZE, vk::MemoryMapFlags::empty())
+            }
+            .unwrap();
+            Some(ptr as *mut u8)
+        } else {
+            None
+        };
+
+        Self {
+            device,
+            handle,
+            memory,
+            size,
+            mapped_ptr,
+        }
+    }
+
+    pub fn get_handle(&self) -> vk::Buffer {
+        self.handle
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_mapped(&self) -> bool {
+        self.mapped_ptr.is_some()
+    }
+
+    pub fn get_mapped_ptr(&self) -> *mut u8 {
+        self.mapped_ptr.unwrap()
+    }
+}
+
+impl Drop for Buffer {
+    fn drop(&mut self) {
+        unsafe {
+            if self.mapped_ptr.is_some() {
+                self.device.handle.unmap_memory(self.memory);
+            }
+            self.device.handle.destroy_buffer(self.handle, None);
+            self.device.handle.free_memory(self.memory, None);
+        }
+    }
+}
+
+pub struct Sampler {
+    device: Arc<Device>,
+    handle: vk::Sampler,
+}
+
+impl Sampler {
+    pub fn new(device: Arc<Device>, create_info: &vk::SamplerCreateInfo) -> Self {
+        let handle = unsafe { device.handle.create_sampler(create_info, None) }.unwrap();
+        Self { device, handle }
+    }
+
+    pub fn get_handle(&self) -> vk::Sampler {
+        self.handle
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        unsafe { self.device.handle.destroy_sampler(self.handle, None) };
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use ash::vk;
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
+
+use super::Instance;
+
+pub struct Surface {
+    loader: ash::extensions::khr::Surface,
+    handle: vk::SurfaceKHR,
+
+    // サーフェスより先にインスタンスが破棄されないように保持しておく
+    _instance: Arc<Instance>,
+}
+
+impl Surface {
+    pub fn new<W>(instance: Arc<Instance>, window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let handle = Self::create_surface(
+            &instance,
+            window.raw_display_handle(),
+            window.raw_window_handle(),
+        );
+        let loader = ash::extensions::khr::Surface::new(&instance.entry, &instance.handle);
+        Self {
+            loader,
+            handle,
+            _instance: instance,
+        }
+    }
+
+    // ウィンドウシステムごとのサーフェスの拡張は DeviceAsh がインスタンスを作るときに有効にしている
+    fn create_surface(
+        instance: &Instance,
+        display_handle: RawDisplayHandle,
+        window_handle: RawWindowHandle,
+    ) -> vk::SurfaceKHR {
+        let entry = &instance.entry;
+        let instance = &instance.handle;
+        let result = match (display_handle, window_handle) {
+            (RawDisplayHandle::Xlib(display), RawWindowHandle::Xlib(window)) => {
+                let create_info = vk::XlibSurfaceCreateInfoKHR::builder()
+                    .dpy(display.display as *mut _)
+                    .window(window.window);
+                let loader = ash::extensions::khr::XlibSurface::new(entry, instance);
+                unsafe { loader.create_xlib_surface(&create_info, None) }
+            }
+            (RawDisplayHandle::Xcb(display), RawWindowHandle::Xcb(window)) => {
+                let create_info = vk::XcbSurfaceCreateInfoKHR::builder()
+                    .connection(display.connection)
+                    .window(window.window);
+                let loader = ash::extensions::khr::XcbSurface::new(entry, instance);
+                unsafe { loader.create_xcb_surface(&create_info, None) }
+            }
+            (RawDisplayHandle::Wayland(display), RawWindowHandle::Wayland(window)) => {
+                let create_info = vk::WaylandSurfaceCreateInfoKHR::builder()
+                    .display(display.display)
+                    .surface(window.surface);
+                let loader = ash::extensions::khr::WaylandSurface::new(entry, instance);
+                unsafe { loader.create_wayland_surface(&create_info, None) }
+            }
+            (RawDisplayHandle::Windows(_), RawWindowHandle::Win32(window)) => {
+                let create_info = vk::Win32SurfaceCreateInfoKHR::builder()
+                    .hinstance(window.hinstance)
+                    .hwnd(window.hwnd);
+                let loader = ash::extensions::khr::Win32Surface::new(entry, instance);
+                unsafe { loader.create_win32_surface(&create_info, None) }
+            }
+            (RawDisplayHandle::Android(_), RawWindowHandle::AndroidNdk(window)) => {
+                let create_info =
+                    vk::AndroidSurfaceCreateInfoKHR::builder().window(window.a_native_window);
+                let loader = ash::extensions::khr::AndroidSurface::new(entry, instance);
+                unsafe { loader.create_android_surface(&create_info, None) }
+            }
+            _ => panic!("unsupported window system"),
+        };
+        result.unwrap()
+    }
+
+    pub fn get_loader(&self) -> &ash::extensions::khr::Surface {
+        &self.loader
+    }
+
+    pub fn get_handle(&self) -> vk::SurfaceKHR {
+        self.handle
+    }
+
+    pub fn is_supported(
+        &self,
+        physical_device: vk::PhysicalDevice,
+        queue_family_index: u32,
+    ) -> bool {
+        unsafe {
+            self.loader.get_physical_device_surface_support(
+                physical_device,
+                queue_family_index,
+                self.handle,
+            )
+        }
+        .unwrap_or(false)
+    }
+}
+
+impl Drop for Surface {
+    fn drop(&mut self) {
+        unsafe { self.loader.destroy_surface(self.handle, None) };
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::Arc;
+
+use ash::vk;
+use sjgfx_interface::{compute_scaled_rect, ScalingMode, SwapChainInfo, UpscaleFilter};
+
+use super::{Device, Image, RecordContext};
+use crate::ColorTargetViewAsh;
+
+// 内部のカラーターゲットをスキャンバッファに転送する
+// 拡大縮小は vkCmdBlitImage で行うので、出力の色の変換とシャープ化はしない
+pub struct SwapChainBlit {
+    device: Arc<Device>,
+    render_format: vk::Format,
+    scaling_mode: ScalingMode,
+    filter: vk::Filter,
+
+    // スキャンバッファの大きさ
+    width: u32,
+    height: u32,
+
+    color_target_view: ColorTargetViewAsh,
+}
+
+impl SwapChainBlit {
+    pub fn new(device: Arc<Device>, render_format: vk::Format, info: &SwapChainInfo) -> Self {
+        let filter = match info.get_upscale_filter() {
+            UpscaleFilter::Nearest => vk::Filter::NEAREST,
+            UpscaleFilter::Linear | UpscaleFilter::Sharpen => vk::Filter::LINEAR,
+        };
+        let color_target_view = Self::create_render_target(
+            &device,
+            render_format,
+            info.get_width().max(1),
+            info.get_height().max(1),
+        );
+        Self {
+            device,
+            render_format,
+            scaling_mode: info.get_scaling_mode(),
+            filter,
+            width: info.get_width(),
+            height: info.get_height(),
+            color_target_view,
+        }
+    }
+
+    fn create_render_target(
+        device: &Arc<Device>,
+        render_format: vk::Format,
+        width: u32,
+        height: u32,
+    ) -> ColorTargetViewAsh {
+        let image = Image::new(
+            device.clone(),
+            render_format,
+            width,
+            height,
+            1,
+            1,
+            vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::SAMPLED,
+        );
+        ColorTargetViewAsh::new_direct(device.clone(), Arc::new(image))
+    }
+
+    // dst_image は dst_layout で転送先にできる状態にしておくこと
+    pub fn record(
+        &self,
+        device: &ash::Device,
+        command_buffer: vk::CommandBuffer,
+        dst_image: vk::Image,
+        dst_layout: vk::ImageLayout,
+        context: &mut RecordContext,
+    ) {
+        let src_image = self.color_target_view.get_image();
+        context.retain(self.color_target_view.clone_image_view());
+
+        let subresource = vk::ImageSubresourceLayers {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let (x, y, width, height) = compute_scaled_rect(
+            (src_image.get_width(), src_image.get_height()),
+            (self.width, self.height),
+            self.scaling_mode,
+        );
+        let blit = vk::ImageBlit {
+            src_subresource: subresource,
+            src_offsets: [
+                vk::Offset3D::default(),
+                vk::Offset3D {
+                    x: src_image.get_width() as i32,
+                    y: src_image.get_height() as i32,
+                    z: 1,
+                },
+            ],
+            dst_subresource: subresource,
+            dst_offsets: [
+                vk::Offset3D {
+                    x: x as i32,
+                    y: y as i32,
+                    z: 0,
+                },
+                vk::Offset3D {
+                    x: (x + width).round() as i32,
+                    y: (y + height).round() as i32,
+                    z: 1,
+                },
+            ],
+        };
+
+        // レターボックスの余白は黒にする
+        let clear_color = vk::ClearColorValue {
+            float32: [0.0, 0.0, 0.0, 1.0],
+        };
+        let range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let barrier = vk::MemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .build();
+        unsafe {
+            device.cmd_clear_color_image(
+                command_buffer,
+                dst_image,
+                dst_layout,
+                &clear_color,
+                &[range],
+            );
+            device.cmd_pipeline_barrier(
+                command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            );
+            device.cmd_blit_image(
+                command_buffer,
+                src_image.get_handle(),
+                vk::ImageLayout::GENERAL,
+                dst_image,
+                dst_layout,
+                &[blit],
+                self.filter,
+            );
+        }
+    }
+
+    pub fn get_color_target_view(&self) -> &ColorTargetViewAsh {
+        &self.color_target_view
+    }
+
+    pub fn get_color_target_view_mut(&mut self) -> &mut ColorTargetViewAsh {
+        &mut self.color_target_view
+    }
+
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn get_render_width(&self) -> u32 {
+        self.color_target_view.get_width()
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        self.color_target_view.get_height()
+    }
+
+    // 大きさが変わったときだけ内部のカラーターゲットを作りなおす
+    // 古いカラーターゲットは転送に使ったサブミットが保持しているので、完了するまで破棄されない
+    pub fn set_render_size(&mut self, width: u32, height: u32) {
+        if self.get_render_width() == width && self.get_render_height() == height {
+            return;
+        }
+
+        self.color_target_view =
+            Self::create_render_target(&self.device, self.render_format, width, height);
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    ffi::{c_char, CStr},
+    sync::Arc,
+};
+
+use ash::vk;
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    AdapterInfo, BackendMask, DeviceCapabilities, DeviceFeatures, DeviceInfo, IDevice, ImageFormat,
+    ImageFormatCapability,
+};
+
+use crate::{
+    detail::{Device, Instance, Surface},
+    util,
+};
+
+pub struct DeviceAsh {
+    device: Arc<Device>,
+    adapter_index: usize,
+    features: DeviceFeatures,
+
+    // スワップチェーンの拡張が使えないデバイスではオフスクリーンにしか表示できない
+    is_swap_chain_supported: bool,
+
+    surface_opt: Option<Arc<Surface>>,
+}
+
+impl DeviceAsh {
+    pub fn new(info: &DeviceInfo) -> Self {
+        Self::new_impl(info, |_| None)
+    }
+
+    pub fn new_as_graphics<W>(info: &DeviceInfo, window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_impl(info, |instance| {
+            Some(Surface::new(instance.clone(), window))
+        })
+    }
+
+    fn new_impl<F>(info: &DeviceInfo, create_surface: F) -> Self
+    where
+        F: FnOnce(&Arc<Instance>) -> Option<Surface>,
+    {
+        // 後から別のウィンドウのサーフェスを作れるように、使えるサーフェスの拡張はすべて有効にしておく
+        // 検証レイヤーが必要なときは VK_INSTANCE_LAYERS でローダーに読み込ませる
+        let instance = Arc::new(Instance::new_with_extensions(
+            &Self::enumerate_surface_extensions(),
+            false,
+        ));
+        let surface_opt = create_surface(&instance);
+
+        let (physical_device, queue_family_index, adapter_index) =
+            Self::select_physical_device(&instance, info, surface_opt.as_ref());
+
+        // 要求された機能のうちデバイスがサポートしているものだけを有効にする
+        let physical_device_wrapper = instance
+            .enumerate_physical_devices()
+            .into_iter()
+            .nth(adapter_index)
+            .unwrap();
+        let supported_features = util::convert_from_features(
+            &physical_device_wrapper.get_features(),
+            &physical_device_wrapper.get_properties().limits,
+        );
+        let features = info.get_optional_features() & supported_features;
+        let is_swap_chain_supported =
+            physical_device_wrapper.is_extension_supported(ash::extensions::khr::Swapchain::name());
+        let extension_names = if is_swap_chain_supported {
+            vec![ash::extensions::khr::Swapchain::name().as_ptr()]
+        } else {
+            Vec::new()
+        };
+
+        let device = Device::new(
+            instance.clone(),
+            physical_device,
+            queue_family_index,
+            &extension_names,
+            &util::convert_to_features(features),
+        );
+
+        Self {
+            device: Arc::new(device),
+            adapter_index,
+            features,
+            is_swap_chain_supported,
+            surface_opt: surface_opt.map(Arc::new),
+        }
+    }
+
+    // デバイスを作ったときとは別のウィンドウのサーフェスを作る
+    // アダプターが表示できないウィンドウだったら失敗する
+    pub fn create_surface<W>(&self, window: &W) -> Arc<Surface>
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let surface = Surface::new(self.device.clone_instance(), window);
+        assert!(surface.is_supported(self.device.physical_device, self.device.queue_family_index));
+        Arc::new(surface)
+    }
+
+    pub fn get_device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn clone_device(&self) -> Arc<Device> {
+        self.device.clone()
+    }
+
+    // DeviceAsh::new で作ったデバイスにはサーフェスがない
+    pub fn clone_surface_opt(&self) -> Option<Arc<Surface>> {
+        self.surface_opt.clone()
+    }
+
+    pub fn is_swap_chain_supported(&self) -> bool {
+        self.is_swap_chain_supported
+    }
+
+    pub fn enumerate_adapters(_info: &DeviceInfo) -> Vec<AdapterInfo> {
+        let instance = Instance::new();
+        instance
+            .enumerate_physical_devices()
+            .iter()
+            .enumerate()
+            .map(|(index, physical_device)| {
+                Self::convert_to_adapter_info(index, &physical_device.get_properties())
+            })
+            .collect()
+    }
+
+    pub fn get_adapter_info(&self) -> AdapterInfo {
+        Self::convert_to_adapter_info(self.adapter_index, &self.device.properties)
+    }
+
+    pub fn get_capabilities(&self) -> DeviceCapabilities {
+        let instance = &self.device.get_instance().handle;
+        let limits = &self.device.properties.limits;
+        let image_format_capabilities = ImageFormat::ALL.iter().map(|format| {
+            let vk_format = util::convert_format(format.clone());
+            let format_properties = unsafe {
+                instance
+                    .get_physical_device_format_properties(self.device.physical_device, vk_format)
+            };
+            let sample_counts = if util::is_depth_format(vk_format) {
+                limits.framebuffer_depth_sample_counts
+            } else {
+                limits.framebuffer_color_sample_counts
+            };
+            ImageFormatCapability::new(
+                format.clone(),
+                util::convert_from_format_features(
+                    format_properties.optimal_tiling_features,
+                    sample_counts,
+                ),
+            )
+        });
+
+        DeviceCapabilities::new()
+            .set_max_texture_size(limits.max_image_dimension2_d)
+            .set_max_texture_array_layers(limits.max_image_array_layers)
+            .set_max_constant_buffers_per_stage(limits.max_per_stage_descriptor_uniform_buffers)
+            .set_max_unordered_access_buffers_per_stage(
+                limits.max_per_stage_descriptor_storage_buffers,
+            )
+            .set_max_textures_per_stage(limits.max_per_stage_descriptor_sampled_images)
+            .set_max_images_per_stage(limits.max_per_stage_descriptor_storage_images)
+            .set_max_samplers_per_stage(limits.max_per_stage_descriptor_samplers)
+            .set_max_compute_workgroup_size(limits.max_compute_work_group_size)
+            .set_max_compute_invocations_per_workgroup(limits.max_compute_work_group_invocations)
+            .set_max_compute_workgroups_per_dimension(
+                *limits.max_compute_work_group_count.iter().min().unwrap(),
+            )
+            .set_constant_buffer_offset_alignment(limits.min_uniform_buffer_offset_alignment as u32)
+            .set_unordered_access_buffer_offset_alignment(
+                limits.min_storage_buffer_offset_alignment as u32,
+            )
+            .set_image_format_capabilities(image_format_capabilities)
+            .set_features(self.features)
+    }
+
+    fn enumerate_surface_extensions() -> Vec<*const c_char> {
+        let entry = unsafe { ash::Entry::load() }.expect("Vulkan loader is not found");
+        let properties = entry.enumerate_instance_extension_properties(None).unwrap();
+        let is_supported = |name: &CStr| {
+            properties
+                .iter()
+                .any(|property| unsafe { CStr::from_ptr(property.extension_name.as_ptr()) == name })
+        };
+        if !is_supported(ash::extensions::khr::Surface::name()) {
+            return Vec::new();
+        }
+
+        [
+            ash::extensions::khr::Surface::name(),
+            ash::extensions::khr::XlibSurface::name(),
+            ash::extensions::khr::XcbSurface::name(),
+            ash::extensions::khr::WaylandSurface::name(),
+            ash::extensions::khr::Win32Surface::name(),
+            ash::extensions::khr::AndroidSurface::name(),
+        ]
+        .into_iter()
+        .filter(|name| is_supported(name))
+        .map(|name| name.as_ptr())
+        .collect()
+    }
+
+    // タイムラインセマフォを使うので Vulkan 1.2 に対応したデバイスだけを候補にする
+    fn select_physical_device(
+        instance: &Instance,
+        info: &DeviceInfo,
+        surface: Option<&Surface>,
+    ) -> (vk::PhysicalDevice, u32, usize) {
+        let candidates = instance
+            .enumerate_physical_devices()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, physical_device)| {
+                physical_device.get_properties().api_version >= vk::API_VERSION_1_2
+            })
+            .filter_map(|(index, physical_device)| {
+                // グラフィックスと演算の両方ができるキューをひとつだけ使う
+                let queue_family_index = physical_device
+                    .get_queue_family_properties()
+                    .iter()
+                    .enumerate()
+                    .position(|(queue_family_index, properties)| {
+                        properties
+                            .queue_flags
+                            .contains(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+                            && surface.map_or(true, |surface| {
+                                surface
+                                    .is_supported(physical_device.handle, queue_family_index as u32)
+                            })
+                    })?;
+                let properties = physical_device.get_properties();
+                Some((
+                    index,
+                    physical_device.handle,
+                    queue_family_index as u32,
+                    properties,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(adapter_index) = info.get_adapter_index() {
+            let (index, handle, queue_family_index, _) = candidates
+                .into_iter()
+                .find(|(index, ..)| *index == adapter_index)
+                .unwrap();
+            return (handle, queue_family_index, index);
+        }
+
+        candidates
+            .into_iter()
+            .filter(|(.., properties)| {
+                !info.is_fallback_adapter_only()
+                    || properties.device_type == vk::PhysicalDeviceType::CPU
+            })
+            .min_by_key(|(.., properties)| {
+                let adapter_type = util::convert_from_device_type(properties.device_type);
+                info.get_power_preference()
+                    .get_adapter_priority(&adapter_type)
+            })
+            .map(|(index, handle, queue_family_index, _)| (handle, queue_family_index, index))
+            .expect("Couldn't find suitable device.")
+    }
+
+    fn convert_to_adapter_info(
+        index: usize,
+        properties: &vk::PhysicalDeviceProperties,
+    ) -> AdapterInfo {
+        let name = unsafe { CStr::from_ptr(properties.device_name.as_ptr()) };
+        AdapterInfo::new()
+            .set_index(index)
+            .set_name(&name.to_string_lossy())
+            .set_vendor(properties.vendor_id)
+            .set_adapter_type(util::convert_from_device_type(properties.device_type))
+            .set_backend(BackendMask::VULKAN)
+    }
+}
+
+impl IDevice for DeviceAsh {
+    fn new(info: &DeviceInfo) -> Self {
+        Self::new(info)
+    }
+
+    fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_as_graphics(info, raw_handle)
+    }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        Self::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.get_adapter_info()
+    }
+}
@@ -0,0 +1,108 @@
+use std::{sync::Arc, time::Duration};
+
+use ash::vk;
+use sjgfx_interface::{FenceInfo, IFence};
+
+use crate::{detail::Device, DeviceAsh};
+
+pub struct FenceAsh {
+    device: Arc<Device>,
+    fence: vk::Fence,
+
+    // 一度もサブミットされていないフェンスを待つと返ってこないので覚えておく
+    is_submitted: bool,
+}
+
+impl FenceAsh {
+    pub fn new(device: &DeviceAsh, info: &FenceInfo) -> Self {
+        let flags = if info.is_signaled() {
+            vk::FenceCreateFlags::SIGNALED
+        } else {
+            vk::FenceCreateFlags::empty()
+        };
+        let fence = unsafe {
+            device
+                .get_device()
+                .handle
+                .create_fence(&vk::FenceCreateInfo::builder().flags(flags), None)
+        }
+        .unwrap();
+
+        Self {
+            device: device.clone_device(),
+            fence,
+            is_submitted: false,
+        }
+    }
+
+    pub fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        if self.is_signaled() {
+            return true;
+        }
+        if !self.is_submitted {
+            return false;
+        }
+
+        let timeout = timeout.map_or(u64::MAX, |timeout| timeout.as_nanos() as u64);
+        match unsafe {
+            self.device
+                .handle
+                .wait_for_fences(&[self.fence], true, timeout)
+        } {
+            Ok(()) => true,
+            Err(vk::Result::TIMEOUT) => false,
+            Err(result) => panic!("{:?}", result),
+        }
+    }
+
+    pub fn is_signaled(&self) -> bool {
+        unsafe { self.device.handle.get_fence_status(self.fence) }.unwrap()
+    }
+
+    pub fn reset(&mut self) {
+        unsafe { self.device.handle.reset_fences(&[self.fence]) }.unwrap();
+        self.is_submitted = false;
+    }
+
+    // サブミットに渡すフェンスを未シグナルにして返す
+    pub(crate) fn prepare_submit(&mut self) -> vk::Fence {
+        unsafe { self.device.handle.reset_fences(&[self.fence]) }.unwrap();
+        self.is_submitted = true;
+        self.fence
+    }
+}
+
+impl Drop for FenceAsh {
+    fn drop(&mut self) {
+        unsafe {
+            // GPU が使っている間は破棄できない
+            if self.is_submitted {
+                self.device
+                    .handle
+                    .wait_for_fences(&[self.fence], true, u64::MAX)
+                    .unwrap();
+            }
+            self.device.handle.destroy_fence(self.fence, None);
+        }
+    }
+}
+
+impl IFence for FenceAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        self.wait(timeout)
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+}
@@ -0,0 +1,31 @@
+mod buffer_ash;
+mod color_target_view_ash;
+mod command_buffer_ash;
+mod depth_stencil_view_ash;
+mod detail;
+mod device_ash;
+mod fence_ash;
+mod queue_ash;
+mod sampler_ash;
+mod semaphore_ash;
+mod shader_ash;
+mod swap_chain_ash;
+mod texture_ash;
+mod texture_view_ash;
+pub mod util;
+mod vertex_state_ash;
+
+pub use buffer_ash::BufferAsh;
+pub use color_target_view_ash::ColorTargetViewAsh;
+pub use command_buffer_ash::CommandBufferAsh;
+pub use depth_stencil_view_ash::DepthStencilViewAsh;
+pub use device_ash::DeviceAsh;
+pub use fence_ash::FenceAsh;
+pub use queue_ash::QueueAsh;
+pub use sampler_ash::SamplerAsh;
+pub use semaphore_ash::SemaphoreAsh;
+pub use shader_ash::ShaderAsh;
+pub use swap_chain_ash::SwapChainAsh;
+pub use texture_ash::TextureAsh;
+pub use texture_view_ash::TextureViewAsh;
+pub use vertex_state_ash::VertexStateAsh;
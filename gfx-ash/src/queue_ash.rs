@@ -0,0 +1,369 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use ash::vk;
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+
+use crate::{
+    detail::{Device, RecordContext},
+    CommandBufferAsh, DeviceAsh, FenceAsh, SemaphoreAsh, SwapChainAsh,
+};
+
+// サブミットしたコマンドバッファーと、完了するまで破棄できないオブジェクト
+struct Submission {
+    value: u64,
+    command_buffers: Vec<vk::CommandBuffer>,
+    context: RecordContext,
+}
+
+pub struct QueueAsh {
+    device: Arc<Device>,
+
+    // Vulkan のキューは 1 つしか作っていないのでどの種類でも同じキューにサブミットする
+    queue_type: QueueType,
+
+    command_pool: vk::CommandPool,
+
+    // サブミットのたびに値を進めるタイムラインセマフォ。完了したサブミットの判定に使う
+    timeline: vk::Semaphore,
+    last_value: u64,
+
+    // flush されるまでサブミットせずにためておくコマンド
+    pending_command_buffers: Vec<vk::CommandBuffer>,
+    pending_context: RecordContext,
+
+    submissions: VecDeque<Submission>,
+}
+
+impl QueueAsh {
+    pub fn new(device: &DeviceAsh, info: &QueueInfo) -> Self {
+        let device = device.clone_device();
+        let create_info = vk::CommandPoolCreateInfo::builder()
+            .flags(vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER)
+            .queue_family_index(device.queue_family_index);
+        let command_pool =
+            unsafe { device.handle.create_command_pool(&create_info, None) }.unwrap();
+
+        let mut type_create_info = vk::SemaphoreTypeCreateInfo::builder()
+            .semaphore_type(vk::SemaphoreType::TIMELINE)
+            .initial_value(0);
+        let create_info = vk::SemaphoreCreateInfo::builder().push_next(&mut type_create_info);
+        let timeline = unsafe { device.handle.create_semaphore(&create_info, None) }.unwrap();
+
+        Self {
+            device,
+            queue_type: info.get_queue_type().clone(),
+            command_pool,
+            timeline,
+            last_value: 0,
+            pending_command_buffers: Vec::new(),
+            pending_context: RecordContext::new(),
+            submissions: VecDeque::new(),
+        }
+    }
+
+    pub fn execute(&mut self, command_buffer: &CommandBufferAsh) {
+        let vk_command_buffer = self.begin_command_buffer();
+        let mut context = RecordContext::new();
+        let is_recorded = command_buffer.record(vk_command_buffer, &mut context);
+        unsafe {
+            self.device
+                .handle
+                .end_command_buffer(vk_command_buffer)
+                .unwrap()
+        };
+
+        if is_recorded {
+            self.pending_command_buffers.push(vk_command_buffer);
+            self.pending_context.append(context);
+        } else {
+            unsafe {
+                self.device
+                    .handle
+                    .free_command_buffers(self.command_pool, &[vk_command_buffer])
+            };
+        }
+    }
+
+    // ためているコマンドとあわせて一度にサブミットする
+    pub fn execute_many(&mut self, command_buffers: &[&CommandBufferAsh]) {
+        for command_buffer in command_buffers {
+            self.execute(command_buffer);
+        }
+        self.flush();
+    }
+
+    pub fn execute_with_fence(&mut self, command_buffer: &CommandBufferAsh, fence: &mut FenceAsh) {
+        self.execute(command_buffer);
+        self.submit_pending_command_buffers(&[], &[], fence.prepare_submit());
+    }
+
+    // 待つ値とシグナルする値は GPU 側でタイムラインセマフォが処理する
+    pub fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &CommandBufferAsh,
+        wait_semaphores: &[(&SemaphoreAsh, u64)],
+        signal_semaphores: &[(&SemaphoreAsh, u64)],
+    ) {
+        let wait_semaphores = wait_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.get_handle(), *value))
+            .collect::<Vec<_>>();
+        let signal_semaphores = signal_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.get_handle(), *value))
+            .collect::<Vec<_>>();
+
+        self.execute(command_buffer);
+        self.submit_pending_command_buffers(
+            &wait_semaphores,
+            &signal_semaphores,
+            vk::Fence::null(),
+        );
+    }
+
+    pub fn get_queue_type(&self) -> &QueueType {
+        &self.queue_type
+    }
+
+    pub fn present(&mut self, swap_chain: &mut SwapChainAsh) {
+        // スキャンバッファへのコピーより先に描画コマンドをサブミットしておく
+        self.flush();
+        swap_chain.present(self);
+    }
+
+    pub fn flush(&mut self) {
+        if !self.pending_command_buffers.is_empty() {
+            self.submit_pending_command_buffers(&[], &[], vk::Fence::null());
+        }
+
+        // 完了したサブミットのオブジェクトを破棄する
+        self.collect_completed_submissions();
+    }
+
+    pub fn sync(&mut self) {
+        self.flush();
+        self.wait_submission(self.last_value);
+        self.collect_completed_submissions();
+    }
+
+    // ためているコマンドとは別に、その場で記録したコマンドをサブミットする
+    // バイナリセマフォはスワップチェーンのイメージの取得と表示の同期に使う
+    pub(crate) fn submit_direct<F>(
+        &mut self,
+        record: F,
+        wait_semaphores: &[vk::Semaphore],
+        signal_semaphores: &[vk::Semaphore],
+    ) -> u64
+    where
+        F: FnOnce(&ash::Device, vk::CommandBuffer, &mut RecordContext),
+    {
+        let command_buffer = self.begin_command_buffer();
+        let mut context = RecordContext::new();
+        record(&self.device.handle, command_buffer, &mut context);
+        unsafe { self.device.handle.end_command_buffer(command_buffer) }.unwrap();
+
+        let wait_semaphores = wait_semaphores
+            .iter()
+            .map(|semaphore| (*semaphore, 0))
+            .collect::<Vec<_>>();
+        let signal_semaphores = signal_semaphores
+            .iter()
+            .map(|semaphore| (*semaphore, 0))
+            .collect::<Vec<_>>();
+        self.submit(
+            vec![command_buffer],
+            context,
+            &wait_semaphores,
+            &signal_semaphores,
+            vk::Fence::null(),
+        )
+    }
+
+    // submit_direct が返した値のサブミットが完了するまで待つ
+    pub(crate) fn wait_submission(&self, value: u64) {
+        let semaphores = [self.timeline];
+        let values = [value];
+        let wait_info = vk::SemaphoreWaitInfo::builder()
+            .semaphores(&semaphores)
+            .values(&values);
+        unsafe { self.device.handle.wait_semaphores(&wait_info, u64::MAX) }.unwrap();
+    }
+
+    fn begin_command_buffer(&self) -> vk::CommandBuffer {
+        let allocate_info = vk::CommandBufferAllocateInfo::builder()
+            .command_pool(self.command_pool)
+            .level(vk::CommandBufferLevel::PRIMARY)
+            .command_buffer_count(1);
+        let command_buffer =
+            unsafe { self.device.handle.allocate_command_buffers(&allocate_info) }.unwrap()[0];
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::ONE_TIME_SUBMIT);
+        unsafe {
+            self.device
+                .handle
+                .begin_command_buffer(command_buffer, &begin_info)
+        }
+        .unwrap();
+        command_buffer
+    }
+
+    fn submit_pending_command_buffers(
+        &mut self,
+        wait_semaphores: &[(vk::Semaphore, u64)],
+        signal_semaphores: &[(vk::Semaphore, u64)],
+        fence: vk::Fence,
+    ) -> u64 {
+        let command_buffers = std::mem::take(&mut self.pending_command_buffers);
+        let context = std::mem::take(&mut self.pending_context);
+        self.submit(
+            command_buffers,
+            context,
+            wait_semaphores,
+            signal_semaphores,
+            fence,
+        )
+    }
+
+    // バイナリセマフォの値は無視されるが、値の配列の長さはセマフォの数とあわせる必要がある
+    fn submit(
+        &mut self,
+        command_buffers: Vec<vk::CommandBuffer>,
+        context: RecordContext,
+        wait_semaphores: &[(vk::Semaphore, u64)],
+        signal_semaphores: &[(vk::Semaphore, u64)],
+        fence: vk::Fence,
+    ) -> u64 {
+        self.last_value += 1;
+        let value = self.last_value;
+
+        let (wait_handles, wait_values): (Vec<_>, Vec<_>) = wait_semaphores.iter().copied().unzip();
+        let wait_stages = vec![vk::PipelineStageFlags::ALL_COMMANDS; wait_handles.len()];
+        let (mut signal_handles, mut signal_values): (Vec<_>, Vec<_>) =
+            signal_semaphores.iter().copied().unzip();
+        signal_handles.push(self.timeline);
+        signal_values.push(value);
+
+        let mut timeline_submit_info = vk::TimelineSemaphoreSubmitInfo::builder()
+            .wait_semaphore_values(&wait_values)
+            .signal_semaphore_values(&signal_values);
+        let submit_info = vk::SubmitInfo::builder()
+            .wait_semaphores(&wait_handles)
+            .wait_dst_stage_mask(&wait_stages)
+            .command_buffers(&command_buffers)
+            .signal_semaphores(&signal_handles)
+            .push_next(&mut timeline_submit_info)
+            .build();
+        {
+            let queue = self.device.queue.lock().unwrap();
+            unsafe {
+                self.device
+                    .handle
+                    .queue_submit(*queue, &[submit_info], fence)
+            }
+            .unwrap();
+        }
+
+        self.submissions.push_back(Submission {
+            value,
+            command_buffers,
+            context,
+        });
+        value
+    }
+
+    fn collect_completed_submissions(&mut self) {
+        let completed_value = unsafe {
+            self.device
+                .handle
+                .get_semaphore_counter_value(self.timeline)
+        }
+        .unwrap();
+        while self
+            .submissions
+            .front()
+            .is_some_and(|submission| submission.value <= completed_value)
+        {
+            let submission = self.submissions.pop_front().unwrap();
+            self.destroy_submission(submission);
+        }
+    }
+
+    fn destroy_submission(&self, submission: Submission) {
+        let device = &self.device.handle;
+        if !submission.command_buffers.is_empty() {
+            unsafe { device.free_command_buffers(self.command_pool, &submission.command_buffers) };
+        }
+        submission.context.destroy(device);
+    }
+}
+
+impl Drop for QueueAsh {
+    fn drop(&mut self) {
+        // サブミットしたコマンドが終わるまで待ってから破棄する
+        self.wait_submission(self.last_value);
+        while let Some(submission) = self.submissions.pop_front() {
+            self.destroy_submission(submission);
+        }
+        std::mem::take(&mut self.pending_context).destroy(&self.device.handle);
+
+        unsafe {
+            self.device.handle.destroy_semaphore(self.timeline, None);
+            self.device
+                .handle
+                .destroy_command_pool(self.command_pool, None);
+        }
+    }
+}
+
+impl IQueue for QueueAsh {
+    type DeviceType = DeviceAsh;
+    type CommandBufferType = CommandBufferAsh;
+    type FenceType = FenceAsh;
+    type SemaphoreType = SemaphoreAsh;
+    type SwapChainType = SwapChainAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn execute(&mut self, command_buffer: &Self::CommandBufferType) {
+        self.execute(command_buffer)
+    }
+
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        self.execute_many(command_buffers)
+    }
+
+    fn execute_with_fence(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
+    ) {
+        self.execute_with_fence(command_buffer, fence)
+    }
+
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        self.execute_with_semaphores(command_buffer, wait_semaphores, signal_semaphores)
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.get_queue_type()
+    }
+
+    fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
+        QueueAsh::present(self, swap_chain);
+    }
+
+    fn flush(&mut self) {
+        QueueAsh::flush(self);
+    }
+
+    fn sync(&mut self) {
+        QueueAsh::sync(self);
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use ash::vk;
+use sjgfx_interface::{ISampler, SamplerInfo};
+
+use crate::{detail::Sampler, DeviceAsh};
+
+pub struct SamplerAsh {
+    sampler: Arc<Sampler>,
+}
+
+impl SamplerAsh {
+    // wgpu の既定値にあわせて最近傍補間、範囲外はクランプにする
+    pub fn new(device: &DeviceAsh, _info: &SamplerInfo) -> Self {
+        let create_info = vk::SamplerCreateInfo::builder()
+            .mag_filter(vk::Filter::NEAREST)
+            .min_filter(vk::Filter::NEAREST)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .min_lod(0.0)
+            .max_lod(vk::LOD_CLAMP_NONE);
+        Self {
+            sampler: Arc::new(Sampler::new(device.clone_device(), &create_info)),
+        }
+    }
+
+    pub fn clone_sampler(&self) -> Arc<Sampler> {
+        self.sampler.clone()
+    }
+}
+
+impl ISampler for SamplerAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &SamplerInfo) -> Self {
+        Self::new(device, info)
+    }
+}
@@ -0,0 +1,97 @@
+use std::{sync::Arc, time::Duration};
+
+use ash::vk;
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+
+use crate::{detail::Device, DeviceAsh};
+
+// Vulkan 1.2 のタイムラインセマフォをそのまま使う
+pub struct SemaphoreAsh {
+    device: Arc<Device>,
+    semaphore: vk::Semaphore,
+}
+
+impl SemaphoreAsh {
+    pub fn new(device: &DeviceAsh, info: &SemaphoreInfo) -> Self {
+        let mut type_create_info = vk::SemaphoreTypeCreateInfo::builder()
+            .semaphore_type(vk::SemaphoreType::TIMELINE)
+            .initial_value(info.get_initial_value());
+        let create_info = vk::SemaphoreCreateInfo::builder().push_next(&mut type_create_info);
+        let semaphore = unsafe {
+            device
+                .get_device()
+                .handle
+                .create_semaphore(&create_info, None)
+        }
+        .unwrap();
+
+        Self {
+            device: device.clone_device(),
+            semaphore,
+        }
+    }
+
+    pub fn get_handle(&self) -> vk::Semaphore {
+        self.semaphore
+    }
+
+    pub fn get_value(&self) -> u64 {
+        unsafe {
+            self.device
+                .handle
+                .get_semaphore_counter_value(self.semaphore)
+        }
+        .unwrap()
+    }
+
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        let semaphores = [self.semaphore];
+        let values = [value];
+        let wait_info = vk::SemaphoreWaitInfo::builder()
+            .semaphores(&semaphores)
+            .values(&values);
+        let timeout = timeout.map_or(u64::MAX, |timeout| timeout.as_nanos() as u64);
+        match unsafe { self.device.handle.wait_semaphores(&wait_info, timeout) } {
+            Ok(()) => true,
+            Err(vk::Result::TIMEOUT) => false,
+            Err(result) => panic!("{:?}", result),
+        }
+    }
+
+    // タイムラインの値は戻せないので、現在の値以下のシグナルは無視する
+    pub fn signal(&self, value: u64) {
+        if value <= self.get_value() {
+            return;
+        }
+        let signal_info = vk::SemaphoreSignalInfo::builder()
+            .semaphore(self.semaphore)
+            .value(value);
+        unsafe { self.device.handle.signal_semaphore(&signal_info) }.unwrap();
+    }
+}
+
+impl Drop for SemaphoreAsh {
+    fn drop(&mut self) {
+        unsafe { self.device.handle.destroy_semaphore(self.semaphore, None) };
+    }
+}
+
+impl ISemaphore for SemaphoreAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn get_value(&self) -> u64 {
+        self.get_value()
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.wait(value, timeout)
+    }
+
+    fn signal(&self, value: u64) {
+        self.signal(value)
+    }
+}
@@ -0,0 +1,242 @@
+use std::{collections::BTreeMap, ffi::CStr, io::Cursor, sync::Arc};
+
+use ash::vk;
+use sjgfx_interface::{IShader, ShaderInfo};
+use spirv_reflect::types::ReflectDescriptorType;
+use uuid::Uuid;
+
+use crate::{detail::Device, DeviceAsh};
+
+pub struct ShaderAsh {
+    shader_data: Arc<ShaderData>,
+}
+
+impl ShaderAsh {
+    pub fn new(device: &DeviceAsh, info: &ShaderInfo) -> Self {
+        if let Some(compute_shader_binary) = info.get_compute_shader_binary() {
+            Self::new_as_compute(device, compute_shader_binary)
+        } else {
+            Self::new_as_graphics(
+                device,
+                info.get_vertex_shader_binary().unwrap(),
+                info.get_pixel_shader_binary().unwrap(),
+            )
+        }
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.shader_data.id
+    }
+
+    pub fn view(&self) -> ShaderView {
+        self.shader_data.clone()
+    }
+
+    fn new_as_compute(device: &DeviceAsh, shader_binary: &[u8]) -> Self {
+        let device = device.clone_device();
+        let compute_module = Self::create_shader_module(&device, shader_binary);
+        let bindings = Self::collect_bindings(&[shader_binary]);
+        let (descriptor_set_layout, pipeline_layout) =
+            Self::create_pipeline_layout(&device, &bindings, vk::ShaderStageFlags::COMPUTE);
+
+        let entry_point = CStr::from_bytes_with_nul(b"main\0").unwrap();
+        let stage = vk::PipelineShaderStageCreateInfo::builder()
+            .stage(vk::ShaderStageFlags::COMPUTE)
+            .module(compute_module)
+            .name(entry_point);
+        let create_info = vk::ComputePipelineCreateInfo::builder()
+            .stage(*stage)
+            .layout(pipeline_layout)
+            .build();
+        let compute_pipeline = unsafe {
+            device
+                .handle
+                .create_compute_pipelines(vk::PipelineCache::null(), &[create_info], None)
+        }
+        .unwrap()[0];
+
+        Self {
+            shader_data: Arc::new(ShaderData {
+                device,
+                compute_module: Some(compute_module),
+                vertex_module: None,
+                pixel_module: None,
+                compute_pipeline: Some(compute_pipeline),
+                descriptor_set_layout,
+                pipeline_layout,
+                bindings,
+                id: Uuid::new_v4(),
+            }),
+        }
+    }
+
+    fn new_as_graphics(
+        device: &DeviceAsh,
+        vertex_shader_binary: &[u8],
+        pixel_shader_binary: &[u8],
+    ) -> Self {
+        let device = device.clone_device();
+        let vertex_module = Self::create_shader_module(&device, vertex_shader_binary);
+        let pixel_module = Self::create_shader_module(&device, pixel_shader_binary);
+        let bindings = Self::collect_bindings(&[vertex_shader_binary, pixel_shader_binary]);
+        let (descriptor_set_layout, pipeline_layout) = Self::create_pipeline_layout(
+            &device,
+            &bindings,
+            vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT,
+        );
+
+        Self {
+            shader_data: Arc::new(ShaderData {
+                device,
+                compute_module: None,
+                vertex_module: Some(vertex_module),
+                pixel_module: Some(pixel_module),
+                compute_pipeline: None,
+                descriptor_set_layout,
+                pipeline_layout,
+                bindings,
+                id: Uuid::new_v4(),
+            }),
+        }
+    }
+
+    fn create_shader_module(device: &Device, shader_binary: &[u8]) -> vk::ShaderModule {
+        // バイト列のアライメントが 4 とは限らないのでコピーしてから渡す
+        let code = ash::util::read_spv(&mut Cursor::new(shader_binary)).unwrap();
+        let create_info = vk::ShaderModuleCreateInfo::builder().code(&code);
+        unsafe { device.handle.create_shader_module(&create_info, None) }.unwrap()
+    }
+
+    // デスクリプタセットは 0 番だけを使い、各ステージのバインディングをまとめる
+    fn collect_bindings(shader_binaries: &[&[u8]]) -> Vec<(u32, vk::DescriptorType)> {
+        let mut bindings = BTreeMap::new();
+        for shader_binary in shader_binaries {
+            let module = spirv_reflect::ShaderModule::load_u8_data(shader_binary).unwrap();
+            for binding in module.enumerate_descriptor_bindings(None).unwrap() {
+                assert_eq!(binding.set, 0, "only descriptor set 0 is supported");
+                let descriptor_type = Self::convert_descriptor_type(&binding.descriptor_type);
+                bindings.insert(binding.binding, descriptor_type);
+            }
+        }
+        bindings.into_iter().collect()
+    }
+
+    fn convert_descriptor_type(descriptor_type: &ReflectDescriptorType) -> vk::DescriptorType {
+        match descriptor_type {
+            ReflectDescriptorType::Sampler => vk::DescriptorType::SAMPLER,
+            ReflectDescriptorType::SampledImage => vk::DescriptorType::SAMPLED_IMAGE,
+            ReflectDescriptorType::StorageImage => vk::DescriptorType::STORAGE_IMAGE,
+            ReflectDescriptorType::UniformBuffer => vk::DescriptorType::UNIFORM_BUFFER,
+            ReflectDescriptorType::StorageBuffer => vk::DescriptorType::STORAGE_BUFFER,
+            _ => panic!("{:?} is not supported", descriptor_type),
+        }
+    }
+
+    fn create_pipeline_layout(
+        device: &Device,
+        bindings: &[(u32, vk::DescriptorType)],
+        stage_flags: vk::ShaderStageFlags,
+    ) -> (vk::DescriptorSetLayout, vk::PipelineLayout) {
+        let layout_bindings = bindings
+            .iter()
+            .map(
+                |(binding, descriptor_type)| vk::DescriptorSetLayoutBinding {
+                    binding: *binding,
+                    descriptor_type: *descriptor_type,
+                    descriptor_count: 1,
+                    stage_flags,
+                    ..Default::default()
+                },
+            )
+            .collect::<Vec<_>>();
+        let create_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&layout_bindings);
+        let descriptor_set_layout = unsafe {
+            device
+                .handle
+                .create_descriptor_set_layout(&create_info, None)
+        }
+        .unwrap();
+
+        let set_layouts = [descriptor_set_layout];
+        let create_info = vk::PipelineLayoutCreateInfo::builder().set_layouts(&set_layouts);
+        let pipeline_layout =
+            unsafe { device.handle.create_pipeline_layout(&create_info, None) }.unwrap();
+        (descriptor_set_layout, pipeline_layout)
+    }
+}
+
+impl IShader for ShaderAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &ShaderInfo) -> Self {
+        Self::new(device, info)
+    }
+}
+
+// コマンドバッファーが記録し終わるまでシェーダーのオブジェクトを保持する
+pub type ShaderView = Arc<ShaderData>;
+
+pub struct ShaderData {
+    device: Arc<Device>,
+    compute_module: Option<vk::ShaderModule>,
+    vertex_module: Option<vk::ShaderModule>,
+    pixel_module: Option<vk::ShaderModule>,
+    compute_pipeline: Option<vk::Pipeline>,
+    descriptor_set_layout: vk::DescriptorSetLayout,
+    pipeline_layout: vk::PipelineLayout,
+    bindings: Vec<(u32, vk::DescriptorType)>,
+    id: Uuid,
+}
+
+impl ShaderData {
+    pub fn is_compute(&self) -> bool {
+        self.compute_module.is_some()
+    }
+
+    pub fn get_vertex_module(&self) -> vk::ShaderModule {
+        self.vertex_module.unwrap()
+    }
+
+    pub fn get_pixel_module(&self) -> vk::ShaderModule {
+        self.pixel_module.unwrap()
+    }
+
+    pub fn get_compute_pipeline(&self) -> vk::Pipeline {
+        self.compute_pipeline.unwrap()
+    }
+
+    pub fn get_descriptor_set_layout(&self) -> vk::DescriptorSetLayout {
+        self.descriptor_set_layout
+    }
+
+    pub fn get_pipeline_layout(&self) -> vk::PipelineLayout {
+        self.pipeline_layout
+    }
+
+    pub fn get_bindings(&self) -> &[(u32, vk::DescriptorType)] {
+        &self.bindings
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+}
+
+impl Drop for ShaderData {
+    fn drop(&mut self) {
+        let device = &self.device.handle;
+        unsafe {
+            if let Some(compute_pipeline) = self.compute_pipeline {
+                device.destroy_pipeline(compute_pipeline, None);
+            }
+            for module in [self.compute_module, self.vertex_module, self.pixel_module]
+                .into_iter()
+                .flatten()
+            {
+                device.destroy_shader_module(module, None);
+            }
+            device.destroy_pipeline_layout(self.pipeline_layout, None);
+            device.destroy_descriptor_set_layout(self.descriptor_set_layout, None);
+        }
+    }
+}
@@ -0,0 +1,785 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use ash::{extensions::khr, vk};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    select_format, select_present_mode, CompositeAlphaMode, IDisplayEventListener, ISwapChain,
+    ImageData, ImageFormat, OutputTransform, PresentMode, SwapChainInfo, SwapChainStatus,
+};
+
+use crate::{
+    detail::{
+        record_image_layout_transition, Device, Image, OffscreenScanBuffers, Surface, SwapChainBlit,
+    },
+    util, ColorTargetViewAsh, DeviceAsh, FenceAsh, QueueAsh, SemaphoreAsh,
+};
+
+// ウィンドウに表示するときの Vulkan のスワップチェーン
+struct SurfaceSwapchain {
+    surface: Arc<Surface>,
+    loader: khr::Swapchain,
+    handle: vk::SwapchainKHR,
+    images: Vec<vk::Image>,
+
+    // イメージの取得を待つセマフォは取得するたびに順番に使う
+    acquire_semaphores: Vec<vk::Semaphore>,
+    next_acquire_semaphore_index: usize,
+
+    // 表示がスキャンバッファへのコピーを待つセマフォ。イメージごとに用意する
+    render_semaphores: Vec<vk::Semaphore>,
+}
+
+pub struct SwapChainAsh {
+    device: Arc<Device>,
+
+    // サーフェスがないデバイスではオフスクリーンのスキャンバッファに表示する
+    surface_swapchain: Option<SurfaceSwapchain>,
+    offscreen_scan_buffers: Option<OffscreenScanBuffers>,
+    surface_format: vk::SurfaceFormatKHR,
+    present_mode: PresentMode,
+    alpha_mode: vk::CompositeAlphaFlagsKHR,
+    frame_latency: u32,
+    is_direct_rendering_enabled: bool,
+    width: u32,
+    height: u32,
+    render_scale: f32,
+    render_size: Option<(u32, u32)>,
+    status: SwapChainStatus,
+    next_offscreen_index: Option<usize>,
+
+    // 直接描画するときのスキャンバッファのビュー
+    scan_buffer_view: Option<ColorTargetViewAsh>,
+
+    // 直接描画するときにスキャンバッファを取得できなかったら代わりに描画させる
+    fallback_view: Option<ColorTargetViewAsh>,
+
+    // 表示したフレームのサブミット。frame_latency を超えたら古いものの完了を待つ
+    submission_values: VecDeque<u64>,
+
+    // ウィンドウのスワップチェーンのイメージには直接描画せず、必ず内部のカラーターゲットから転送する
+    // オフスクリーンで直接描画するときだけ使わない
+    blit: Option<SwapChainBlit>,
+
+    // 出力の変換は未対応なので保持するだけ
+    output_transform: OutputTransform,
+}
+
+impl SwapChainAsh {
+    pub fn new(device: &mut DeviceAsh, info: &SwapChainInfo) -> Self {
+        let surface = device.clone_surface_opt();
+        Self::new_impl(device, surface, info)
+    }
+
+    // 既存のデバイスで別のウィンドウに表示する
+    // リソースはデバイスを共有するほかのスワップチェーンと同じものを使える
+    pub fn new_with_handle<W>(device: &mut DeviceAsh, info: &SwapChainInfo, window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let surface = device.create_surface(window);
+        Self::new_impl(device, Some(surface), info)
+    }
+
+    fn new_impl(device: &DeviceAsh, surface: Option<Arc<Surface>>, info: &SwapChainInfo) -> Self {
+        let surface = surface.filter(|_| device.is_swap_chain_supported());
+        let (surface_format, present_mode, alpha_mode) = match &surface {
+            Some(surface) => Self::select_surface_settings(device.get_device(), surface, info),
+            None => Self::select_offscreen_settings(info),
+        };
+
+        let surface_swapchain = surface.map(|surface| {
+            let instance = device.get_device().get_instance();
+            SurfaceSwapchain {
+                surface,
+                loader: khr::Swapchain::new(&instance.handle, &device.get_device().handle),
+                handle: vk::SwapchainKHR::null(),
+                images: Vec::new(),
+                acquire_semaphores: Vec::new(),
+                next_acquire_semaphore_index: 0,
+                render_semaphores: Vec::new(),
+            }
+        });
+
+        // 直接描画するときはウィンドウの大きさのカラーターゲットに描画してそのまま転送する
+        let blit = if info.is_direct_rendering_enabled() {
+            surface_swapchain
+                .as_ref()
+                .map(|_| SwapChainBlit::new(device.clone_device(), surface_format.format, info))
+        } else {
+            let render_format = util::convert_format(info.get_render_format().clone());
+            Some(SwapChainBlit::new(
+                device.clone_device(),
+                render_format,
+                info,
+            ))
+        };
+
+        // 表示待ちのフレームに加えて描画中のものが 1 枚必要
+        let offscreen_scan_buffers = match surface_swapchain {
+            Some(_) => None,
+            None => Some(OffscreenScanBuffers::new(
+                device.clone_device(),
+                surface_format.format,
+                info.get_frame_latency() as usize + 1,
+            )),
+        };
+
+        let mut result = Self {
+            device: device.clone_device(),
+            surface_swapchain,
+            offscreen_scan_buffers,
+            surface_format,
+            present_mode,
+            alpha_mode,
+            frame_latency: info.get_frame_latency(),
+            is_direct_rendering_enabled: info.is_direct_rendering_enabled(),
+            width: 0,
+            height: 0,
+            render_scale: info.get_render_scale(),
+            render_size: info.get_render_size(),
+            status: SwapChainStatus::Optimal,
+            next_offscreen_index: None,
+            scan_buffer_view: None,
+            fallback_view: None,
+            submission_values: VecDeque::new(),
+            blit,
+            output_transform: info.get_output_transform().clone(),
+        };
+
+        result.on_resized(info.get_width(), info.get_height());
+        result
+    }
+
+    // 要求された設定のうちサポートされていないものはサポートされているものに置き換える
+    fn select_surface_settings(
+        device: &Device,
+        surface: &Surface,
+        info: &SwapChainInfo,
+    ) -> (
+        vk::SurfaceFormatKHR,
+        PresentMode,
+        vk::CompositeAlphaFlagsKHR,
+    ) {
+        let loader = surface.get_loader();
+        let (surface_formats, surface_present_modes, capabilities) = unsafe {
+            (
+                loader
+                    .get_physical_device_surface_formats(
+                        device.physical_device,
+                        surface.get_handle(),
+                    )
+                    .unwrap(),
+                loader
+                    .get_physical_device_surface_present_modes(
+                        device.physical_device,
+                        surface.get_handle(),
+                    )
+                    .unwrap(),
+                loader
+                    .get_physical_device_surface_capabilities(
+                        device.physical_device,
+                        surface.get_handle(),
+                    )
+                    .unwrap(),
+            )
+        };
+
+        let supported_formats: Vec<ImageFormat> = surface_formats
+            .iter()
+            .filter_map(|surface_format| util::convert_from_format(surface_format.format))
+            .collect();
+        let surface_format = select_format(info.get_preferred_formats(), &supported_formats)
+            .map(util::convert_format)
+            .and_then(|format| {
+                surface_formats
+                    .iter()
+                    .find(|surface_format| surface_format.format == format)
+            })
+            .copied()
+            .unwrap_or(surface_formats[0]);
+        let supported_present_modes: Vec<PresentMode> = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+        ]
+        .into_iter()
+        .filter(|present_mode| {
+            surface_present_modes.contains(&util::convert_present_mode(*present_mode))
+        })
+        .collect();
+        let present_mode = select_present_mode(info.get_present_mode(), &supported_present_modes);
+        let alpha_mode = [
+            util::convert_composite_alpha_mode(info.get_alpha_mode()),
+            vk::CompositeAlphaFlagsKHR::OPAQUE,
+            vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+            vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+            vk::CompositeAlphaFlagsKHR::INHERIT,
+        ]
+        .into_iter()
+        .find(|alpha_mode| capabilities.supported_composite_alpha.contains(*alpha_mode))
+        .unwrap();
+        (surface_format, present_mode, alpha_mode)
+    }
+
+    // 表示先がないので表示方法とアルファは要求されたものをそのまま使う
+    fn select_offscreen_settings(
+        info: &SwapChainInfo,
+    ) -> (
+        vk::SurfaceFormatKHR,
+        PresentMode,
+        vk::CompositeAlphaFlagsKHR,
+    ) {
+        let supported_formats = [
+            ImageFormat::R8G8B8A8Unorm,
+            ImageFormat::R8G8B8A8UnormSrgb,
+            ImageFormat::B8G8R8A8Unorm,
+            ImageFormat::B8G8R8A8UnormSrgb,
+        ];
+        let format = select_format(info.get_preferred_formats(), &supported_formats).unwrap();
+        let surface_format = vk::SurfaceFormatKHR {
+            format: util::convert_format(format),
+            color_space: vk::ColorSpaceKHR::SRGB_NONLINEAR,
+        };
+        let alpha_mode = match info.get_alpha_mode() {
+            CompositeAlphaMode::Auto => vk::CompositeAlphaFlagsKHR::OPAQUE,
+            alpha_mode => util::convert_composite_alpha_mode(alpha_mode),
+        };
+        (surface_format, info.get_present_mode(), alpha_mode)
+    }
+
+    pub fn acquire_next_scan_buffer_view(
+        &mut self,
+        _semaphore: Option<&mut SemaphoreAsh>,
+        fence: Option<&mut FenceAsh>,
+    ) -> &mut ColorTargetViewAsh {
+        // 前のフレームのスキャンバッファへのコピーが終わったらカラーターゲットを再利用できる
+        // 空のサブミットのフェンスはそれまでにサブミットしたコマンドの完了でシグナルされる
+        if let Some(fence) = fence {
+            let queue = self.device.queue.lock().unwrap();
+            unsafe {
+                self.device
+                    .handle
+                    .queue_submit(*queue, &[], fence.prepare_submit())
+            }
+            .unwrap();
+        }
+
+        if self.blit.is_some() {
+            self.get_scan_buffer_view_mut()
+        } else {
+            self.acquire_offscreen_view()
+        }
+    }
+
+    fn acquire_offscreen_view(&mut self) -> &mut ColorTargetViewAsh {
+        // 表示する前に再度呼ばれたときは同じスキャンバッファを返す
+        if self.scan_buffer_view.is_none() {
+            // 最小化されているときはサイズが 0 で表示できない
+            if self.width == 0 || self.height == 0 {
+                self.status = SwapChainStatus::Skipped;
+                return self.acquire_fallback_view();
+            }
+
+            self.status = SwapChainStatus::Optimal;
+            let offscreen_scan_buffers = self.offscreen_scan_buffers.as_mut().unwrap();
+            let index = offscreen_scan_buffers.acquire();
+            self.scan_buffer_view = Some(offscreen_scan_buffers.get_view(index).clone());
+            self.next_offscreen_index = Some(index);
+        }
+
+        self.scan_buffer_view.as_mut().unwrap()
+    }
+
+    // 描画先がないときも描画コマンドが失敗しないように、表示されないカラーターゲットを返す
+    fn acquire_fallback_view(&mut self) -> &mut ColorTargetViewAsh {
+        if self.fallback_view.is_none() {
+            let image = Image::new(
+                self.device.clone(),
+                self.surface_format.format,
+                self.width.max(1),
+                self.height.max(1),
+                1,
+                1,
+                vk::ImageUsageFlags::COLOR_ATTACHMENT,
+            );
+            self.fallback_view = Some(ColorTargetViewAsh::new_direct(
+                self.device.clone(),
+                Arc::new(image),
+            ));
+        }
+
+        self.fallback_view.as_mut().unwrap()
+    }
+
+    // 古いスワップチェーンを渡して作りなおす。イメージの大きさはサーフェスにあわせる
+    fn create_swapchain(&mut self) {
+        let device = self.device.clone();
+        let Some(surface_swapchain) = &mut self.surface_swapchain else {
+            return;
+        };
+
+        let surface = &surface_swapchain.surface;
+        let capabilities = unsafe {
+            surface
+                .get_loader()
+                .get_physical_device_surface_capabilities(
+                    device.physical_device,
+                    surface.get_handle(),
+                )
+        }
+        .unwrap();
+        let extent = if capabilities.current_extent.width != u32::MAX {
+            capabilities.current_extent
+        } else {
+            vk::Extent2D {
+                width: self.width.clamp(
+                    capabilities.min_image_extent.width,
+                    capabilities.max_image_extent.width,
+                ),
+                height: self.height.clamp(
+                    capabilities.min_image_extent.height,
+                    capabilities.max_image_extent.height,
+                ),
+            }
+        };
+
+        // 最小化されているとサーフェスの大きさが 0 になり作れない
+        if extent.width == 0 || extent.height == 0 {
+            return;
+        }
+
+        // 表示待ちのフレームに加えて描画中のものが 1 枚必要
+        let mut image_count = capabilities.min_image_count.max(self.frame_latency + 1);
+        if capabilities.max_image_count > 0 {
+            image_count = image_count.min(capabilities.max_image_count);
+        }
+
+        let old_swapchain = surface_swapchain.handle;
+        let create_info = vk::SwapchainCreateInfoKHR::builder()
+            .surface(surface.get_handle())
+            .min_image_count(image_count)
+            .image_format(self.surface_format.format)
+            .image_color_space(self.surface_format.color_space)
+            .image_extent(extent)
+            .image_array_layers(1)
+            .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT | vk::ImageUsageFlags::TRANSFER_DST)
+            .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .pre_transform(capabilities.current_transform)
+            .composite_alpha(self.alpha_mode)
+            .present_mode(util::convert_present_mode(self.present_mode))
+            .clipped(true)
+            .old_swapchain(old_swapchain);
+
+        // 古いスワップチェーンのイメージを使っているコマンドが終わるまで待つ
+        unsafe { device.handle.device_wait_idle() }.unwrap();
+        let handle = unsafe {
+            surface_swapchain
+                .loader
+                .create_swapchain(&create_info, None)
+        }
+        .unwrap();
+        surface_swapchain.destroy(&device.handle);
+        surface_swapchain.handle = handle;
+        surface_swapchain.images =
+            unsafe { surface_swapchain.loader.get_swapchain_images(handle) }.unwrap();
+
+        let create_semaphore = || {
+            unsafe {
+                device
+                    .handle
+                    .create_semaphore(&vk::SemaphoreCreateInfo::default(), None)
+            }
+            .unwrap()
+        };
+        let image_count = surface_swapchain.images.len();
+        surface_swapchain.acquire_semaphores =
+            (0..(image_count + 1)).map(|_| create_semaphore()).collect();
+        surface_swapchain.next_acquire_semaphore_index = 0;
+        surface_swapchain.render_semaphores =
+            (0..image_count).map(|_| create_semaphore()).collect();
+
+        self.width = extent.width;
+        self.height = extent.height;
+    }
+
+    // スワップチェーンが使えなくなっていたら作りなおして 1 回だけ取得しなおす
+    // それでも取得できなければフレームを飛ばす
+    fn acquire_swapchain_image(&mut self) -> Option<(u32, vk::Semaphore)> {
+        for _ in 0..2 {
+            let surface_swapchain = self.surface_swapchain.as_mut().unwrap();
+            if surface_swapchain.handle == vk::SwapchainKHR::null() {
+                break;
+            }
+
+            let semaphore = surface_swapchain.acquire_semaphores
+                [surface_swapchain.next_acquire_semaphore_index];
+            let result = unsafe {
+                surface_swapchain.loader.acquire_next_image(
+                    surface_swapchain.handle,
+                    u64::MAX,
+                    semaphore,
+                    vk::Fence::null(),
+                )
+            };
+            match result {
+                // 最適でなくても表示はできるので、表示したあとに作りなおす
+                Ok((index, _is_suboptimal)) => {
+                    surface_swapchain.next_acquire_semaphore_index =
+                        (surface_swapchain.next_acquire_semaphore_index + 1)
+                            % surface_swapchain.acquire_semaphores.len();
+                    return Some((index, semaphore));
+                }
+                Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                    self.create_swapchain();
+                    self.status = SwapChainStatus::Reconfigured;
+                }
+                Err(vk::Result::ERROR_SURFACE_LOST_KHR) | Err(vk::Result::TIMEOUT) => break,
+                Err(result) => panic!("failed to acquire a swap chain image: {:?}", result),
+            }
+        }
+
+        self.status = SwapChainStatus::Skipped;
+        None
+    }
+
+    pub fn present(&mut self, queue: &mut QueueAsh) {
+        // 最小化されているときはサイズが 0 で表示できない
+        if self.blit.is_some() {
+            if self.width == 0 || self.height == 0 {
+                self.status = SwapChainStatus::Skipped;
+                return;
+            }
+            self.status = SwapChainStatus::Optimal;
+        }
+
+        let submission_value = if self.surface_swapchain.is_some() {
+            let Some(submission_value) = self.present_to_surface(queue) else {
+                return;
+            };
+            submission_value
+        } else if let Some(blit) = &self.blit {
+            // カラーターゲットの内容をスキャンバッファにコピー
+            let offscreen_scan_buffers = self.offscreen_scan_buffers.as_mut().unwrap();
+            let index = offscreen_scan_buffers.acquire();
+            let scan_buffer_view = offscreen_scan_buffers.get_view(index).clone();
+            let submission_value = queue.submit_direct(
+                |device, command_buffer, context| {
+                    context.retain(scan_buffer_view.clone_image_view());
+                    blit.record(
+                        device,
+                        command_buffer,
+                        scan_buffer_view.get_image().get_handle(),
+                        vk::ImageLayout::GENERAL,
+                        context,
+                    );
+                },
+                &[],
+                &[],
+            );
+            offscreen_scan_buffers.present(index);
+            submission_value
+        } else {
+            // 描画済みのスキャンバッファをそのまま表示する
+            // 取得できずに代わりのカラーターゲットに描画したフレームは表示しない
+            self.scan_buffer_view = None;
+            let Some(index) = self.next_offscreen_index.take() else {
+                return;
+            };
+            let submission_value = queue.submit_direct(|_, _, _| {}, &[], &[]);
+            self.offscreen_scan_buffers.as_mut().unwrap().present(index);
+            submission_value
+        };
+
+        // CPU が先行しすぎないように古いフレームの完了を待つ
+        self.submission_values.push_back(submission_value);
+        while self.submission_values.len() > self.frame_latency as usize {
+            let submission_value = self.submission_values.pop_front().unwrap();
+            queue.wait_submission(submission_value);
+        }
+    }
+
+    fn present_to_surface(&mut self, queue: &mut QueueAsh) -> Option<u64> {
+        let (image_index, acquire_semaphore) = self.acquire_swapchain_image()?;
+        let surface_swapchain = self.surface_swapchain.as_ref().unwrap();
+        let image = surface_swapchain.images[image_index as usize];
+        let render_semaphore = surface_swapchain.render_semaphores[image_index as usize];
+
+        // 前の内容は使わないので UNDEFINED から遷移させる
+        let blit = self.blit.as_ref().unwrap();
+        let submission_value = queue.submit_direct(
+            |device, command_buffer, context| {
+                record_image_layout_transition(
+                    device,
+                    command_buffer,
+                    image,
+                    vk::ImageAspectFlags::COLOR,
+                    vk::ImageLayout::UNDEFINED,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                );
+                blit.record(
+                    device,
+                    command_buffer,
+                    image,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    context,
+                );
+                record_image_layout_transition(
+                    device,
+                    command_buffer,
+                    image,
+                    vk::ImageAspectFlags::COLOR,
+                    vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                    vk::ImageLayout::PRESENT_SRC_KHR,
+                );
+            },
+            &[acquire_semaphore],
+            &[render_semaphore],
+        );
+
+        let wait_semaphores = [render_semaphore];
+        let swapchains = [surface_swapchain.handle];
+        let image_indices = [image_index];
+        let present_info = vk::PresentInfoKHR::builder()
+            .wait_semaphores(&wait_semaphores)
+            .swapchains(&swapchains)
+            .image_indices(&image_indices);
+        let result = {
+            let queue = self.device.queue.lock().unwrap();
+            unsafe {
+                surface_swapchain
+                    .loader
+                    .queue_present(*queue, &present_info)
+            }
+        };
+        match result {
+            Ok(false) => {}
+            Ok(true) | Err(vk::Result::ERROR_OUT_OF_DATE_KHR) => {
+                self.create_swapchain();
+                self.update_render_size();
+                self.status = SwapChainStatus::Reconfigured;
+            }
+            Err(vk::Result::ERROR_SURFACE_LOST_KHR) => self.status = SwapChainStatus::Skipped,
+            Err(result) => panic!("failed to present a swap chain image: {:?}", result),
+        }
+
+        Some(submission_value)
+    }
+
+    pub fn get_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn get_format(&self) -> ImageFormat {
+        util::convert_from_format(self.surface_format.format).unwrap()
+    }
+
+    pub fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        util::convert_from_composite_alpha_mode(self.alpha_mode)
+    }
+
+    pub fn get_frame_latency(&self) -> u32 {
+        self.frame_latency
+    }
+
+    pub fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled
+    }
+
+    pub fn get_status(&self) -> SwapChainStatus {
+        self.status
+    }
+
+    // オフスクリーンのときだけ直前に表示したフレームを読み戻せる
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        self.offscreen_scan_buffers
+            .as_ref()
+            .and_then(|offscreen_scan_buffers| offscreen_scan_buffers.read_presented_image())
+    }
+
+    pub fn get_render_width(&self) -> u32 {
+        match &self.blit {
+            Some(blit) => blit.get_render_width(),
+            None => self.width,
+        }
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        match &self.blit {
+            Some(blit) => blit.get_render_height(),
+            None => self.height,
+        }
+    }
+
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        assert!(render_scale > 0.0);
+        self.render_scale = render_scale;
+        self.update_render_size();
+    }
+
+    // 露出やトーンマップは vkCmdBlitImage ではかけられないので反映しない
+    pub fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.output_transform = output_transform.clone();
+    }
+
+    fn update_render_size(&mut self) {
+        let Some(blit) = &mut self.blit else {
+            return;
+        };
+
+        // 最小化されているときは元に戻るまで作りなおさない
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        // 直接描画するときはスキャンバッファと同じ大きさにする
+        let (width, height) = if self.is_direct_rendering_enabled {
+            (self.width, self.height)
+        } else {
+            self.render_size.unwrap_or_else(|| {
+                let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+                (scale(self.width), scale(self.height))
+            })
+        };
+        blit.set_size(self.width, self.height);
+        blit.set_render_size(width, height);
+    }
+
+    pub fn get_scan_buffer_view(&self) -> &ColorTargetViewAsh {
+        match &self.blit {
+            Some(blit) => blit.get_color_target_view(),
+            None => self.scan_buffer_view.as_ref().unwrap(),
+        }
+    }
+
+    pub fn get_scan_buffer_view_mut(&mut self) -> &mut ColorTargetViewAsh {
+        match &mut self.blit {
+            Some(blit) => blit.get_color_target_view_mut(),
+            None => self.scan_buffer_view.as_mut().unwrap(),
+        }
+    }
+}
+
+impl SurfaceSwapchain {
+    fn destroy(&mut self, device: &ash::Device) {
+        unsafe {
+            for semaphore in self
+                .acquire_semaphores
+                .drain(..)
+                .chain(self.render_semaphores.drain(..))
+            {
+                device.destroy_semaphore(semaphore, None);
+            }
+            if self.handle != vk::SwapchainKHR::null() {
+                self.loader.destroy_swapchain(self.handle, None);
+            }
+        }
+        self.handle = vk::SwapchainKHR::null();
+        self.images.clear();
+    }
+}
+
+impl Drop for SwapChainAsh {
+    fn drop(&mut self) {
+        if let Some(surface_swapchain) = &mut self.surface_swapchain {
+            // 表示中のイメージを使っているコマンドが終わるまで待つ
+            unsafe { self.device.handle.device_wait_idle() }.unwrap();
+            surface_swapchain.destroy(&self.device.handle);
+        }
+    }
+}
+
+impl ISwapChain for SwapChainAsh {
+    type ColorTargetViewType = ColorTargetViewAsh;
+    type DeviceType = DeviceAsh;
+    type SemaphoreType = SemaphoreAsh;
+    type FenceType = FenceAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(device, info, raw_handle)
+    }
+
+    fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
+    ) -> &mut Self::ColorTargetViewType {
+        self.acquire_next_scan_buffer_view(semaphore, fence)
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.set_output_transform(output_transform);
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        self.read_presented_image()
+    }
+}
+
+impl IDisplayEventListener for SwapChainAsh {
+    fn on_resized(&mut self, width: u32, height: u32) {
+        // 取得済みのスキャンバッファは古いサイズなので捨てる
+        self.scan_buffer_view = None;
+        self.next_offscreen_index = None;
+        self.fallback_view = None;
+
+        // 最小化されるとサイズが 0 になるので、元に戻るまで設定しない
+        self.width = width;
+        self.height = height;
+        if width > 0 && height > 0 {
+            self.create_swapchain();
+            if let Some(offscreen_scan_buffers) = &mut self.offscreen_scan_buffers {
+                offscreen_scan_buffers.resize(width, height);
+            }
+        }
+
+        self.update_render_size();
+    }
+}
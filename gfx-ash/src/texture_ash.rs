@@ -0,0 +1,278 @@
+use std::sync::Arc;
+
+use ash::vk;
+use sjgfx_interface::{
+    validate_texture_write, GpuAccess, ITexture, ImageData, ImageFormat, TextureCopyRegion,
+    TextureInfo,
+};
+
+use crate::{
+    detail::{execute_immediately, Buffer, Device, Image},
+    util, DeviceAsh,
+};
+
+pub struct TextureAsh {
+    device: Arc<Device>,
+    image: Arc<Image>,
+    image_format: ImageFormat,
+    gpu_access: GpuAccess,
+}
+
+impl TextureAsh {
+    pub fn new(device: &DeviceAsh, info: &TextureInfo) -> Self {
+        let format = util::convert_format(info.get_image_format().clone());
+        let image = Image::new(
+            device.clone_device(),
+            format,
+            info.get_width() as u32,
+            info.get_height() as u32,
+            info.get_mip_count() as u32,
+            info.get_depth() as u32,
+            util::convert_to_image_usage(info.get_gpu_access_flags(), format),
+        );
+
+        Self {
+            device: device.clone_device(),
+            image: Arc::new(image),
+            image_format: info.get_image_format().clone(),
+            gpu_access: *info.get_gpu_access_flags(),
+        }
+    }
+
+    // ミップレベル 0 のすべての配列要素を詰めたデータで初期化する
+    pub fn new_with_data(device: &DeviceAsh, info: &TextureInfo, data: &[u8]) -> Self {
+        let texture = Self::new(device, info);
+        let width = info.get_width();
+        let height = info.get_height();
+        let region = TextureCopyRegion::new()
+            .set_width(width)
+            .set_height(height)
+            .set_array_length(info.get_depth());
+        let bytes_per_row = width as usize * texture.image_format.get_bytes_per_pixel();
+        texture.write_impl(&region, data, bytes_per_row, height as usize);
+        texture
+    }
+
+    pub fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        assert!(
+            self.gpu_access.contains(GpuAccess::WRITE),
+            "texture must be created with GpuAccess::WRITE to be written"
+        );
+        self.write_impl(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn write_impl(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        let required_size = validate_texture_write(
+            region,
+            &self.image_format,
+            data.len(),
+            bytes_per_row,
+            rows_per_image,
+        );
+        if required_size == 0 {
+            return;
+        }
+
+        let subresource = region.get_texture_subresource();
+        let mip_level = subresource.get_mip_level() as u32;
+        assert!(
+            mip_level < self.image.get_mip_count(),
+            "texture region is out of mip level {}",
+            mip_level
+        );
+        let (mip_width, mip_height) = self.image.get_mip_size(mip_level);
+        assert!(
+            region.get_offset_u() + region.get_width() <= mip_width as i32
+                && region.get_offset_v() + region.get_height() <= mip_height as i32,
+            "texture region is out of mip level {}",
+            mip_level
+        );
+        let base_array_layer = subresource.get_array_index() as u32;
+        let array_layers = self.image.get_array_layers();
+        assert!(
+            base_array_layer + region.get_array_length() as u32 <= array_layers,
+            "texture layers {}..{} are out of {} array layers",
+            base_array_layer,
+            base_array_layer + region.get_array_length() as u32,
+            array_layers
+        );
+
+        // 行のアライメントを気にしなくていいように、ステージングバッファーには行を詰めて並べる
+        // RGB は RGBA のテクスチャーで扱っているのでアルファを補う
+        let width = region.get_width() as usize;
+        let height = region.get_height() as usize;
+        let array_length = region.get_array_length() as usize;
+        let bytes_per_pixel = self.image_format.get_bytes_per_pixel();
+        let mut texels = Vec::with_capacity(width * height * array_length * 4);
+        for layer in 0..array_length {
+            for row in 0..height {
+                let start = bytes_per_row * (rows_per_image * layer + row);
+                let row_data = &data[start..(start + width * bytes_per_pixel)];
+                if self.image_format == ImageFormat::R8G8B8Unorm {
+                    for pixel in row_data.chunks_exact(3) {
+                        texels.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
+                    }
+                } else {
+                    texels.extend_from_slice(row_data);
+                }
+            }
+        }
+
+        let staging_buffer = Buffer::new(
+            self.device.clone(),
+            texels.len(),
+            vk::BufferUsageFlags::TRANSFER_SRC,
+            true,
+        );
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                texels.as_ptr(),
+                staging_buffer.get_mapped_ptr(),
+                texels.len(),
+            );
+        }
+
+        let copy_region = vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask: self.image.get_aspect_mask(),
+                mip_level,
+                base_array_layer,
+                layer_count: array_length as u32,
+            },
+            image_offset: vk::Offset3D {
+                x: region.get_offset_u(),
+                y: region.get_offset_v(),
+                z: 0,
+            },
+            image_extent: vk::Extent3D {
+                width: width as u32,
+                height: height as u32,
+                depth: 1,
+            },
+        };
+        execute_immediately(&self.device, |device, command_buffer| unsafe {
+            device.cmd_copy_buffer_to_image(
+                command_buffer,
+                staging_buffer.get_handle(),
+                self.image.get_handle(),
+                vk::ImageLayout::GENERAL,
+                &[copy_region],
+            );
+        });
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        assert!(
+            self.gpu_access.contains(GpuAccess::READ),
+            "texture must be created with GpuAccess::READ to be read back"
+        );
+
+        // RGB は RGBA のテクスチャーで扱っている
+        let image_format = match self.image_format {
+            ImageFormat::R8G8B8Unorm => ImageFormat::R8G8B8A8Unorm,
+            _ => self.image_format.clone(),
+        };
+        Self::read_image_impl(&self.device, &self.image, image_format)
+    }
+
+    // ミップレベル 0、配列要素 0 を読み戻して RGBA に変換する
+    // image_format はイメージのテクセルの並びと一致していること
+    pub(crate) fn read_image_impl(
+        device: &Arc<Device>,
+        image: &Image,
+        image_format: ImageFormat,
+    ) -> ImageData {
+        let width = image.get_width();
+        let height = image.get_height();
+        let size = (width * height) as usize * image_format.get_bytes_per_pixel();
+        let staging_buffer = Buffer::new(
+            device.clone(),
+            size,
+            vk::BufferUsageFlags::TRANSFER_DST,
+            true,
+        );
+
+        let copy_region = vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask: image.get_aspect_mask(),
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            image_offset: vk::Offset3D::default(),
+            image_extent: vk::Extent3D {
+                width,
+                height,
+                depth: 1,
+            },
+        };
+        execute_immediately(device, |device, command_buffer| unsafe {
+            device.cmd_copy_image_to_buffer(
+                command_buffer,
+                image.get_handle(),
+                vk::ImageLayout::GENERAL,
+                staging_buffer.get_handle(),
+                &[copy_region],
+            );
+        });
+
+        let texels = unsafe { std::slice::from_raw_parts(staging_buffer.get_mapped_ptr(), size) };
+        ImageData::from_texels(width, height, image_format, texels)
+    }
+
+    pub fn get_image(&self) -> &Image {
+        &self.image
+    }
+
+    pub fn clone_image(&self) -> Arc<Image> {
+        self.image.clone()
+    }
+
+    pub fn get_image_format(&self) -> &ImageFormat {
+        &self.image_format
+    }
+}
+
+impl ITexture for TextureAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        Self::new_with_data(device, info, data)
+    }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use sjgfx_interface::{ITextureView, TextureViewInfo};
+
+use crate::{detail::ImageView, util, DeviceAsh, TextureAsh};
+
+pub struct TextureViewAsh {
+    image_view: Arc<ImageView>,
+
+    // ストレージイメージとしてバインドするときはミップレベルを 1 つに絞る
+    storage_image_view: Arc<ImageView>,
+}
+
+impl TextureViewAsh {
+    pub fn new(_device: &DeviceAsh, info: &TextureViewInfo, texture: &TextureAsh) -> Self {
+        let format = util::convert_format(info.get_format());
+        let image = texture.clone_image();
+        let mip_count = image.get_mip_count();
+        let image_view = ImageView::new(image.clone(), format, mip_count);
+        let storage_image_view = if mip_count == 1 {
+            None
+        } else {
+            Some(ImageView::new(image, format, 1))
+        };
+
+        let image_view = Arc::new(image_view);
+        Self {
+            storage_image_view: storage_image_view
+                .map(Arc::new)
+                .unwrap_or_else(|| image_view.clone()),
+            image_view,
+        }
+    }
+
+    pub fn clone_image_view(&self) -> Arc<ImageView> {
+        self.image_view.clone()
+    }
+
+    pub fn clone_storage_image_view(&self) -> Arc<ImageView> {
+        self.storage_image_view.clone()
+    }
+}
+
+impl ITextureView for TextureViewAsh {
+    type DeviceType = DeviceAsh;
+    type TextureType = TextureAsh;
+
+    fn new(device: &Self::DeviceType, info: &TextureViewInfo, texture: &Self::TextureType) -> Self {
+        Self::new(device, info, texture)
+    }
+}
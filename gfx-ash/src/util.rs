@@ -0,0 +1,252 @@
+use ash::vk;
+use sjgfx_interface::{
+    AdapterType, AttributeFormat, CompositeAlphaMode, DeviceFeatures, GpuAccess, ImageFormat,
+    ImageFormatUsage, PresentMode, PrimitiveTopology,
+};
+
+// R8G8B8Unorm は RGBA のテクスチャーで扱う
+pub fn convert_format(format: ImageFormat) -> vk::Format {
+    match format {
+        ImageFormat::R8Unorm => vk::Format::R8_UNORM,
+        ImageFormat::R8Snorm => vk::Format::R8_SNORM,
+        ImageFormat::R8Uint => vk::Format::R8_UINT,
+        ImageFormat::R8Sint => vk::Format::R8_SINT,
+        ImageFormat::R32Uint => vk::Format::R32_UINT,
+        ImageFormat::R32Sint => vk::Format::R32_SINT,
+        ImageFormat::R8G8B8A8Uint => vk::Format::R8G8B8A8_UINT,
+        ImageFormat::R8G8B8A8Sint => vk::Format::R8G8B8A8_SINT,
+        ImageFormat::R8G8B8Unorm => vk::Format::R8G8B8A8_UNORM,
+        ImageFormat::R8G8B8A8Unorm => vk::Format::R8G8B8A8_UNORM,
+        ImageFormat::R8G8B8A8UnormSrgb => vk::Format::R8G8B8A8_SRGB,
+        ImageFormat::B8G8R8A8Unorm => vk::Format::B8G8R8A8_UNORM,
+        ImageFormat::B8G8R8A8UnormSrgb => vk::Format::B8G8R8A8_SRGB,
+        ImageFormat::R16G16B16A16Float => vk::Format::R16G16B16A16_SFLOAT,
+        ImageFormat::D32 => vk::Format::D32_SFLOAT,
+    }
+}
+
+pub fn convert_from_format(format: vk::Format) -> Option<ImageFormat> {
+    match format {
+        vk::Format::R8_UNORM => Some(ImageFormat::R8Unorm),
+        vk::Format::R8_SNORM => Some(ImageFormat::R8Snorm),
+        vk::Format::R8_UINT => Some(ImageFormat::R8Uint),
+        vk::Format::R8_SINT => Some(ImageFormat::R8Sint),
+        vk::Format::R32_UINT => Some(ImageFormat::R32Uint),
+        vk::Format::R32_SINT => Some(ImageFormat::R32Sint),
+        vk::Format::R8G8B8A8_UINT => Some(ImageFormat::R8G8B8A8Uint),
+        vk::Format::R8G8B8A8_SINT => Some(ImageFormat::R8G8B8A8Sint),
+        vk::Format::R8G8B8A8_UNORM => Some(ImageFormat::R8G8B8A8Unorm),
+        vk::Format::R8G8B8A8_SRGB => Some(ImageFormat::R8G8B8A8UnormSrgb),
+        vk::Format::B8G8R8A8_UNORM => Some(ImageFormat::B8G8R8A8Unorm),
+        vk::Format::B8G8R8A8_SRGB => Some(ImageFormat::B8G8R8A8UnormSrgb),
+        vk::Format::R16G16B16A16_SFLOAT => Some(ImageFormat::R16G16B16A16Float),
+        vk::Format::D32_SFLOAT => Some(ImageFormat::D32),
+        _ => None,
+    }
+}
+
+pub fn is_depth_format(format: vk::Format) -> bool {
+    format == vk::Format::D32_SFLOAT
+}
+
+pub fn get_aspect_mask(format: vk::Format) -> vk::ImageAspectFlags {
+    if is_depth_format(format) {
+        vk::ImageAspectFlags::DEPTH
+    } else {
+        vk::ImageAspectFlags::COLOR
+    }
+}
+
+// アップロードと読み戻しはステージングバッファーからのコピーで行うので、転送は常に許可しておく
+pub fn convert_to_buffer_usage(gpu_access: GpuAccess) -> vk::BufferUsageFlags {
+    let mut result = vk::BufferUsageFlags::TRANSFER_SRC | vk::BufferUsageFlags::TRANSFER_DST;
+    if gpu_access.contains(GpuAccess::VERTEX_BUFFER) {
+        result |= vk::BufferUsageFlags::VERTEX_BUFFER;
+    }
+    if gpu_access.contains(GpuAccess::INDEX_BUFFER) {
+        result |= vk::BufferUsageFlags::INDEX_BUFFER;
+    }
+    if gpu_access.contains(GpuAccess::UNORDERED_ACCESS_BUFFER) {
+        result |= vk::BufferUsageFlags::STORAGE_BUFFER;
+    }
+    if gpu_access.contains(GpuAccess::CONSTANT_BUFFER) {
+        result |= vk::BufferUsageFlags::UNIFORM_BUFFER;
+    }
+    if gpu_access.contains(GpuAccess::INDIRECT_BUFFER) {
+        result |= vk::BufferUsageFlags::INDIRECT_BUFFER;
+    }
+    result
+}
+
+pub fn convert_to_image_usage(gpu_access: &GpuAccess, format: vk::Format) -> vk::ImageUsageFlags {
+    let mut result = vk::ImageUsageFlags::empty();
+    if gpu_access.contains(GpuAccess::TEXTURE) {
+        result |= vk::ImageUsageFlags::SAMPLED;
+    }
+    if gpu_access.contains(GpuAccess::IMAGE) {
+        result |= vk::ImageUsageFlags::STORAGE;
+    }
+    if gpu_access.contains(GpuAccess::COLOR_BUFFER) || gpu_access.contains(GpuAccess::DEPTH_STENCIL)
+    {
+        if is_depth_format(format) {
+            result |= vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT;
+        } else {
+            result |= vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        }
+    }
+    if gpu_access.contains(GpuAccess::READ) {
+        result |= vk::ImageUsageFlags::TRANSFER_SRC;
+    }
+    if gpu_access.contains(GpuAccess::WRITE) {
+        result |= vk::ImageUsageFlags::TRANSFER_DST;
+    }
+    result
+}
+
+pub fn convert_from_format_features(
+    features: vk::FormatFeatureFlags,
+    sample_counts: vk::SampleCountFlags,
+) -> ImageFormatUsage {
+    let mut result = ImageFormatUsage::empty();
+    if features.contains(vk::FormatFeatureFlags::SAMPLED_IMAGE) {
+        result |= ImageFormatUsage::SAMPLED;
+    }
+    if features.contains(vk::FormatFeatureFlags::STORAGE_IMAGE) {
+        result |= ImageFormatUsage::STORAGE;
+    }
+    let is_render_target = features.intersects(
+        vk::FormatFeatureFlags::COLOR_ATTACHMENT | vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT,
+    );
+    if is_render_target {
+        result |= ImageFormatUsage::RENDER_TARGET;
+    }
+    if features.contains(vk::FormatFeatureFlags::COLOR_ATTACHMENT_BLEND) {
+        result |= ImageFormatUsage::BLENDABLE;
+    }
+
+    // サンプル数はフォーマットごとには取れないので、描画先になれるフォーマットはデバイスの上限に従う
+    if is_render_target && sample_counts.intersects(!vk::SampleCountFlags::TYPE_1) {
+        result |= ImageFormatUsage::MULTISAMPLE;
+    }
+    result
+}
+
+pub fn convert_from_device_type(device_type: vk::PhysicalDeviceType) -> AdapterType {
+    match device_type {
+        vk::PhysicalDeviceType::DISCRETE_GPU => AdapterType::DiscreteGpu,
+        vk::PhysicalDeviceType::INTEGRATED_GPU => AdapterType::IntegratedGpu,
+        vk::PhysicalDeviceType::VIRTUAL_GPU => AdapterType::VirtualGpu,
+        vk::PhysicalDeviceType::CPU => AdapterType::Cpu,
+        _ => AdapterType::Other,
+    }
+}
+
+// タイムスタンプは機能ではなくリミットで判定するので別に扱う
+pub fn convert_from_features(
+    features: &vk::PhysicalDeviceFeatures,
+    limits: &vk::PhysicalDeviceLimits,
+) -> DeviceFeatures {
+    let table = [
+        (
+            DeviceFeatures::TIMESTAMP_QUERY,
+            limits.timestamp_compute_and_graphics,
+        ),
+        (
+            DeviceFeatures::PIPELINE_STATISTICS_QUERY,
+            features.pipeline_statistics_query,
+        ),
+        (
+            DeviceFeatures::TEXTURE_COMPRESSION_BC,
+            features.texture_compression_bc,
+        ),
+        (
+            DeviceFeatures::TEXTURE_COMPRESSION_ETC2,
+            features.texture_compression_etc2,
+        ),
+        (
+            DeviceFeatures::TEXTURE_COMPRESSION_ASTC,
+            features.texture_compression_astc_ldr,
+        ),
+        (
+            DeviceFeatures::INDIRECT_FIRST_INSTANCE,
+            features.draw_indirect_first_instance,
+        ),
+        (
+            DeviceFeatures::MULTI_DRAW_INDIRECT,
+            features.multi_draw_indirect,
+        ),
+        (DeviceFeatures::SHADER_FLOAT64, features.shader_float64),
+        (
+            DeviceFeatures::POLYGON_MODE_LINE,
+            features.fill_mode_non_solid,
+        ),
+        (DeviceFeatures::DEPTH_CLIP_CONTROL, features.depth_clamp),
+    ];
+    table
+        .iter()
+        .filter(|(_, is_supported)| *is_supported == vk::TRUE)
+        .fold(DeviceFeatures::empty(), |result, (device_features, _)| {
+            result | *device_features
+        })
+}
+
+pub fn convert_to_features(features: DeviceFeatures) -> vk::PhysicalDeviceFeatures {
+    let to_bool32 = |feature: DeviceFeatures| features.contains(feature) as vk::Bool32;
+    vk::PhysicalDeviceFeatures {
+        pipeline_statistics_query: to_bool32(DeviceFeatures::PIPELINE_STATISTICS_QUERY),
+        texture_compression_bc: to_bool32(DeviceFeatures::TEXTURE_COMPRESSION_BC),
+        texture_compression_etc2: to_bool32(DeviceFeatures::TEXTURE_COMPRESSION_ETC2),
+        texture_compression_astc_ldr: to_bool32(DeviceFeatures::TEXTURE_COMPRESSION_ASTC),
+        draw_indirect_first_instance: to_bool32(DeviceFeatures::INDIRECT_FIRST_INSTANCE),
+        multi_draw_indirect: to_bool32(DeviceFeatures::MULTI_DRAW_INDIRECT),
+        shader_float64: to_bool32(DeviceFeatures::SHADER_FLOAT64),
+        fill_mode_non_solid: to_bool32(DeviceFeatures::POLYGON_MODE_LINE),
+        depth_clamp: to_bool32(DeviceFeatures::DEPTH_CLIP_CONTROL),
+        ..Default::default()
+    }
+}
+
+pub fn convert_attribute_format(format: &AttributeFormat) -> vk::Format {
+    match format {
+        AttributeFormat::Uint32 => vk::Format::R32_UINT,
+        AttributeFormat::Float32_32 => vk::Format::R32G32_SFLOAT,
+        AttributeFormat::Float32_32_32 => vk::Format::R32G32B32_SFLOAT,
+        AttributeFormat::Float32_32_32_32 => vk::Format::R32G32B32A32_SFLOAT,
+    }
+}
+
+pub fn convert_primitive_topology(primitive_topology: &PrimitiveTopology) -> vk::PrimitiveTopology {
+    match primitive_topology {
+        PrimitiveTopology::PointList => vk::PrimitiveTopology::POINT_LIST,
+        PrimitiveTopology::TriangleList => vk::PrimitiveTopology::TRIANGLE_LIST,
+    }
+}
+
+pub fn convert_present_mode(present_mode: PresentMode) -> vk::PresentModeKHR {
+    match present_mode {
+        PresentMode::Fifo => vk::PresentModeKHR::FIFO,
+        PresentMode::FifoRelaxed => vk::PresentModeKHR::FIFO_RELAXED,
+        PresentMode::Mailbox => vk::PresentModeKHR::MAILBOX,
+        PresentMode::Immediate => vk::PresentModeKHR::IMMEDIATE,
+    }
+}
+
+pub fn convert_composite_alpha_mode(alpha_mode: CompositeAlphaMode) -> vk::CompositeAlphaFlagsKHR {
+    match alpha_mode {
+        CompositeAlphaMode::Auto | CompositeAlphaMode::Opaque => vk::CompositeAlphaFlagsKHR::OPAQUE,
+        CompositeAlphaMode::PreMultiplied => vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED,
+        CompositeAlphaMode::PostMultiplied => vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED,
+        CompositeAlphaMode::Inherit => vk::CompositeAlphaFlagsKHR::INHERIT,
+    }
+}
+
+pub fn convert_from_composite_alpha_mode(
+    alpha_mode: vk::CompositeAlphaFlagsKHR,
+) -> CompositeAlphaMode {
+    match alpha_mode {
+        vk::CompositeAlphaFlagsKHR::PRE_MULTIPLIED => CompositeAlphaMode::PreMultiplied,
+        vk::CompositeAlphaFlagsKHR::POST_MULTIPLIED => CompositeAlphaMode::PostMultiplied,
+        vk::CompositeAlphaFlagsKHR::INHERIT => CompositeAlphaMode::Inherit,
+        _ => CompositeAlphaMode::Opaque,
+    }
+}
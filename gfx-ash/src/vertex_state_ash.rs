@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use ash::vk;
+use sjgfx_interface::{IVertexState, VertexStateInfo};
+use uuid::Uuid;
+
+use crate::{util, DeviceAsh};
+
+pub struct VertexStateAsh {
+    bindings: Arc<Vec<vk::VertexInputBindingDescription>>,
+    attributes: Arc<Vec<vk::VertexInputAttributeDescription>>,
+    id: Uuid,
+}
+
+impl VertexStateAsh {
+    pub fn new(_device: &DeviceAsh, info: &VertexStateInfo) -> Self {
+        let bindings = info
+            .get_buffer_state_info_array()
+            .iter()
+            .enumerate()
+            .map(|(index, buffer_state_info)| {
+                let input_rate = if buffer_state_info.get_divisor() == 0 {
+                    vk::VertexInputRate::VERTEX
+                } else {
+                    vk::VertexInputRate::INSTANCE
+                };
+                vk::VertexInputBindingDescription {
+                    binding: index as u32,
+                    stride: buffer_state_info.get_stride() as u32,
+                    input_rate,
+                }
+            })
+            .collect();
+        let attributes = info
+            .get_attribute_state_info_array()
+            .iter()
+            .map(|attribute_info| vk::VertexInputAttributeDescription {
+                location: attribute_info.get_slot() as u32,
+                binding: attribute_info.get_buffer_index() as u32,
+                format: util::convert_attribute_format(attribute_info.get_format()),
+                offset: attribute_info.get_offset() as u32,
+            })
+            .collect();
+
+        Self {
+            bindings: Arc::new(bindings),
+            attributes: Arc::new(attributes),
+            id: Uuid::new_v4(),
+        }
+    }
+
+    pub fn view(&self) -> VertexStateView {
+        VertexStateView {
+            bindings: self.bindings.clone(),
+            attributes: self.attributes.clone(),
+            id: self.id,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct VertexStateView {
+    pub bindings: Arc<Vec<vk::VertexInputBindingDescription>>,
+    pub attributes: Arc<Vec<vk::VertexInputAttributeDescription>>,
+    pub id: Uuid,
+}
+
+impl IVertexState for VertexStateAsh {
+    type DeviceType = DeviceAsh;
+
+    fn new(device: &Self::DeviceType, info: &VertexStateInfo) -> Self {
+        Self::new(device, info)
+    }
+}
@@ -0,0 +1,111 @@
+use sjgfx_ash::{BufferAsh, CommandBufferAsh, DeviceAsh, QueueAsh, ShaderAsh};
+use sjgfx_interface::{
+    BufferInfo, CommandBufferInfo, DeviceInfo, GpuAccess, QueueInfo, ShaderInfo,
+};
+
+#[test]
+fn new() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let _command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+}
+
+#[test]
+fn begin_end() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let mut command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+
+    command_buffer.begin();
+    command_buffer.end();
+}
+
+#[test]
+fn simple_compute_command() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+
+    let shader_source = include_str!("../../resources/tests/simple_compute.glsl");
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let shader_binary = compiler
+        .compile_into_spirv(
+            &shader_source,
+            shaderc::ShaderKind::Compute,
+            "test.glsl",
+            "main",
+            None,
+        )
+        .unwrap();
+    let shader = ShaderAsh::new(
+        &device,
+        &ShaderInfo::new().set_compute_shader_binary(shader_binary.as_binary_u8()),
+    );
+
+    let buffer = BufferAsh::new(
+        &device,
+        &BufferInfo::new()
+            .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER)
+            .set_size(1024),
+    );
+    let mut command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+
+    command_buffer.begin();
+    command_buffer.set_shader(&shader);
+    command_buffer.set_unordered_access_buffer(0, &buffer);
+    command_buffer.dispatch(1, 1, 1);
+    command_buffer.end();
+}
+
+#[test]
+fn simple_graphics_command() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let mut command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+
+    command_buffer.begin();
+    command_buffer.end();
+}
+
+#[test]
+fn debug_label_compute_command() {
+    let device = DeviceAsh::new(&DeviceInfo::new().set_debug_label("TestDevice"));
+
+    let shader_source = include_str!("../../resources/tests/simple_compute.glsl");
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let shader_binary = compiler
+        .compile_into_spirv(
+            &shader_source,
+            shaderc::ShaderKind::Compute,
+            "test.glsl",
+            "main",
+            None,
+        )
+        .unwrap();
+    let shader = ShaderAsh::new(
+        &device,
+        &ShaderInfo::new()
+            .set_compute_shader_binary(shader_binary.as_binary_u8())
+            .set_debug_label("TestShader"),
+    );
+
+    let buffer = BufferAsh::new(
+        &device,
+        &BufferInfo::new()
+            .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER)
+            .set_size(1024)
+            .set_debug_label("TestBuffer"),
+    );
+    let mut command_buffer = CommandBufferAsh::new(
+        &device,
+        &CommandBufferInfo::new().set_debug_label("TestCommandBuffer"),
+    );
+    let mut queue = QueueAsh::new(&device, &QueueInfo::new());
+
+    command_buffer.begin();
+    command_buffer.push_debug_group("Compute");
+    command_buffer.insert_debug_marker("Dispatch");
+    command_buffer.set_shader(&shader);
+    command_buffer.set_unordered_access_buffer(0, &buffer);
+    command_buffer.dispatch(1, 1, 1);
+    command_buffer.pop_debug_group();
+    command_buffer.end();
+
+    queue.execute(&command_buffer);
+    queue.sync();
+}
@@ -0,0 +1,67 @@
+use sjgfx_ash::DeviceAsh;
+use sjgfx_interface::{AdapterType, DeviceFeatures, DeviceInfo, ImageFormat, ImageFormatUsage};
+
+#[test]
+fn get_capabilities() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let capabilities = device.get_capabilities();
+
+    assert!(capabilities.get_max_texture_size() > 0);
+    assert!(capabilities.get_max_compute_invocations_per_workgroup() > 0);
+    assert_eq!(
+        capabilities.get_image_format_capabilities().len(),
+        ImageFormat::ALL.len()
+    );
+    assert!(capabilities
+        .get_image_format_usage(&ImageFormat::R8G8B8A8Unorm)
+        .contains(ImageFormatUsage::SAMPLED | ImageFormatUsage::RENDER_TARGET));
+}
+
+#[test]
+fn request_optional_features() {
+    let info = DeviceInfo::new().set_optional_features(DeviceFeatures::all());
+    let device = DeviceAsh::new(&info);
+
+    // サポートされていない機能は無視されてデバイスが作られる
+    let features = device.get_capabilities().get_features();
+    assert!(DeviceFeatures::all().contains(features));
+}
+
+#[test]
+fn enumerate_adapters() {
+    let adapters = DeviceAsh::enumerate_adapters(&DeviceInfo::new());
+    assert!(!adapters.is_empty());
+
+    for (index, adapter) in adapters.iter().enumerate() {
+        assert_eq!(adapter.get_index(), index);
+        assert!(!adapter.get_backend().is_empty());
+    }
+}
+
+#[test]
+fn new_with_adapter_index() {
+    let adapters = DeviceAsh::enumerate_adapters(&DeviceInfo::new());
+    let last_index = adapters.len() - 1;
+
+    let device = DeviceAsh::new(&DeviceInfo::new().set_adapter_index(last_index));
+    let adapter_info = device.get_adapter_info();
+    assert_eq!(adapter_info.get_index(), last_index);
+    assert_eq!(adapter_info.get_name(), adapters[last_index].get_name());
+}
+
+#[test]
+fn new_fallback_adapter_only() {
+    let info = DeviceInfo::new().set_fallback_adapter_only(true);
+    let has_fallback_adapter = DeviceAsh::enumerate_adapters(&info)
+        .iter()
+        .any(|x| x.get_adapter_type() == &AdapterType::Cpu);
+    if !has_fallback_adapter {
+        return;
+    }
+
+    let device = DeviceAsh::new(&info);
+    assert_eq!(
+        device.get_adapter_info().get_adapter_type(),
+        &AdapterType::Cpu
+    );
+}
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use sjgfx_ash::{CommandBufferAsh, DeviceAsh, FenceAsh, QueueAsh};
+use sjgfx_interface::{CommandBufferInfo, DeviceInfo, FenceInfo, QueueInfo};
+
+#[test]
+fn new() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let fence = FenceAsh::new(&device, &FenceInfo::new());
+    assert!(!fence.is_signaled());
+}
+
+#[test]
+fn new_signaled() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let mut fence = FenceAsh::new(&device, &FenceInfo::new().set_signaled(true));
+    assert!(fence.is_signaled());
+    assert!(fence.wait(None));
+
+    fence.reset();
+    assert!(!fence.is_signaled());
+    assert!(!fence.wait(Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn execute_with_fence() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let mut queue = QueueAsh::new(&device, &QueueInfo::new());
+    let mut fence = FenceAsh::new(&device, &FenceInfo::new());
+
+    let mut command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.end();
+
+    for _ in 0..2 {
+        queue.execute_with_fence(&command_buffer, &mut fence);
+        assert!(fence.wait(None));
+        assert!(fence.is_signaled());
+
+        fence.reset();
+        assert!(!fence.is_signaled());
+    }
+}
@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use sjgfx_ash::{CommandBufferAsh, DeviceAsh, QueueAsh, SemaphoreAsh};
+use sjgfx_interface::{CommandBufferInfo, DeviceInfo, QueueInfo, QueueType, SemaphoreInfo};
+
+#[test]
+fn new() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let semaphore = SemaphoreAsh::new(&device, &SemaphoreInfo::new().set_initial_value(3));
+    assert_eq!(semaphore.get_value(), 3);
+    assert!(semaphore.wait(3, None));
+    assert!(!semaphore.wait(4, Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn signal() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let semaphore = SemaphoreAsh::new(&device, &SemaphoreInfo::new());
+    semaphore.signal(2);
+    assert_eq!(semaphore.get_value(), 2);
+
+    // 値は単調増加
+    semaphore.signal(1);
+    assert_eq!(semaphore.get_value(), 2);
+}
+
+#[test]
+fn execute_with_semaphores() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let mut graphics_queue = QueueAsh::new(&device, &QueueInfo::new());
+    let mut compute_queue = QueueAsh::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Compute),
+    );
+    assert_eq!(compute_queue.get_queue_type(), &QueueType::Compute);
+    let semaphore = SemaphoreAsh::new(&device, &SemaphoreInfo::new());
+
+    let mut command_buffer = CommandBufferAsh::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.end();
+
+    for value in 1..3 {
+        compute_queue.execute_with_semaphores(&command_buffer, &[], &[(&semaphore, value * 2 - 1)]);
+        graphics_queue.execute_with_semaphores(
+            &command_buffer,
+            &[(&semaphore, value * 2 - 1)],
+            &[(&semaphore, value * 2)],
+        );
+        assert!(semaphore.wait(value * 2, None));
+    }
+    assert_eq!(semaphore.get_value(), 4);
+}
@@ -0,0 +1,98 @@
+use sjgfx_ash::{CommandBufferAsh, DeviceAsh, QueueAsh, SwapChainAsh};
+use sjgfx_interface::{
+    CommandBufferInfo, DeviceInfo, IDisplayEventListener, QueueInfo, SwapChainInfo,
+    SwapChainStatus, TextureArrayRange,
+};
+
+// サーフェスのないデバイスで 1 フレーム塗りつぶして表示する
+fn present_clear_color(
+    device: &DeviceAsh,
+    queue: &mut QueueAsh,
+    swap_chain: &mut SwapChainAsh,
+    red: f32,
+) {
+    let mut command_buffer = CommandBufferAsh::new(device, &CommandBufferInfo::new());
+    let view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.clear_color(view, red, 0.0, 0.0, 1.0, TextureArrayRange::new());
+    queue.present(swap_chain);
+}
+
+#[test]
+fn new_headless() {
+    let mut device = DeviceAsh::new(&DeviceInfo::new());
+    let swap_chain = SwapChainAsh::new(
+        &mut device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+    assert_eq!(swap_chain.get_status(), SwapChainStatus::Optimal);
+    assert!(swap_chain.read_presented_image().is_none());
+}
+
+#[test]
+fn read_presented_image() {
+    let mut device = DeviceAsh::new(&DeviceInfo::new());
+    let mut queue = QueueAsh::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainAsh::new(
+        &mut device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert_eq!(image.get_width(), 4);
+    assert_eq!(image.get_height(), 4);
+    assert!(image.get_data().chunks(4).all(|p| p == [255, 0, 0, 255]));
+
+    // リングの次のテクスチャに描画したフレームが読み戻せる
+    present_clear_color(&device, &mut queue, &mut swap_chain, 0.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert!(image.get_data().chunks(4).all(|p| p == [0, 0, 0, 255]));
+}
+
+#[test]
+fn read_presented_image_after_resize() {
+    let mut device = DeviceAsh::new(&DeviceInfo::new());
+    let mut queue = QueueAsh::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainAsh::new(
+        &mut device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+
+    // 大きさが変わると表示済みのフレームは失われる
+    swap_chain.on_resized(8, 2);
+    assert!(swap_chain.read_presented_image().is_none());
+
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert_eq!(image.get_width(), 8);
+    assert_eq!(image.get_height(), 2);
+}
+
+#[test]
+fn skip_zero_size() {
+    let mut device = DeviceAsh::new(&DeviceInfo::new());
+    let mut queue = QueueAsh::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainAsh::new(
+        &mut device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+
+    // 最小化されたときと同じようにフレームを飛ばす
+    swap_chain.on_resized(0, 0);
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    assert_eq!(swap_chain.get_status(), SwapChainStatus::Skipped);
+    assert!(swap_chain.read_presented_image().is_none());
+}
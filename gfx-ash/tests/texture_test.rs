@@ -0,0 +1,257 @@
+use ash::vk;
+use sjgfx_ash::{ColorTargetViewAsh, DeviceAsh, TextureAsh};
+use sjgfx_interface::{
+    ColorTargetViewInfo, DeviceInfo, GpuAccess, ImageFormat, TextureCopyRegion, TextureInfo,
+};
+
+// テクスチャーの 1 ミップレベルを詰めて読み戻す
+// イメージは常に GENERAL レイアウトに置かれている
+fn read_texture(
+    device: &DeviceAsh,
+    texture: &TextureAsh,
+    mip_level: u32,
+    bytes_per_pixel: u32,
+) -> Vec<u8> {
+    let device = device.get_device();
+    let handle = &device.handle;
+    let (width, height) = texture.get_image().get_mip_size(mip_level);
+    let size = (width * height * bytes_per_pixel) as u64;
+
+    unsafe {
+        let buffer = handle
+            .create_buffer(
+                &vk::BufferCreateInfo::builder()
+                    .size(size)
+                    .usage(vk::BufferUsageFlags::TRANSFER_DST),
+                None,
+            )
+            .unwrap();
+        let requirements = handle.get_buffer_memory_requirements(buffer);
+        let memory_type_index = device
+            .find_memory_type_index(
+                requirements.memory_type_bits,
+                vk::MemoryPropertyFlags::HOST_VISIBLE | vk::MemoryPropertyFlags::HOST_COHERENT,
+            )
+            .unwrap();
+        let memory = handle
+            .allocate_memory(
+                &vk::MemoryAllocateInfo::builder()
+                    .allocation_size(requirements.size)
+                    .memory_type_index(memory_type_index),
+                None,
+            )
+            .unwrap();
+        handle.bind_buffer_memory(buffer, memory, 0).unwrap();
+
+        let command_pool = handle
+            .create_command_pool(
+                &vk::CommandPoolCreateInfo::builder().queue_family_index(device.queue_family_index),
+                None,
+            )
+            .unwrap();
+        let command_buffer = handle
+            .allocate_command_buffers(
+                &vk::CommandBufferAllocateInfo::builder()
+                    .command_pool(command_pool)
+                    .level(vk::CommandBufferLevel::PRIMARY)
+                    .command_buffer_count(1),
+            )
+            .unwrap()[0];
+        handle
+            .begin_command_buffer(command_buffer, &vk::CommandBufferBeginInfo::default())
+            .unwrap();
+        handle.cmd_copy_image_to_buffer(
+            command_buffer,
+            texture.get_image().get_handle(),
+            vk::ImageLayout::GENERAL,
+            buffer,
+            &[vk::BufferImageCopy {
+                buffer_offset: 0,
+                buffer_row_length: 0,
+                buffer_image_height: 0,
+                image_subresource: vk::ImageSubresourceLayers {
+                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                    mip_level,
+                    base_array_layer: 0,
+                    layer_count: 1,
+                },
+                image_offset: vk::Offset3D::default(),
+                image_extent: vk::Extent3D {
+                    width,
+                    height,
+                    depth: 1,
+                },
+            }],
+        );
+        handle.end_command_buffer(command_buffer).unwrap();
+
+        let command_buffers = [command_buffer];
+        let submit_info = vk::SubmitInfo::builder()
+            .command_buffers(&command_buffers)
+            .build();
+        handle
+            .queue_submit(
+                *device.queue.lock().unwrap(),
+                &[submit_info],
+                vk::Fence::null(),
+            )
+            .unwrap();
+        handle.device_wait_idle().unwrap();
+
+        let ptr = handle
+            .map_memory(memory, 0, size, vk::MemoryMapFlags::empty())
+            .unwrap() as *const u8;
+        let result = std::slice::from_raw_parts(ptr, size as usize).to_vec();
+
+        handle.unmap_memory(memory);
+        handle.destroy_command_pool(command_pool, None);
+        handle.destroy_buffer(buffer, None);
+        handle.free_memory(memory, None);
+        result
+    }
+}
+
+#[test]
+fn write_sub_region() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(8)
+            .set_height(8)
+            .set_mip_count(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    // 1 行 3 ピクセルのデータを 5 バイト間隔で並べる
+    let data = [1, 2, 3, 0, 0, 4, 5, 6];
+    let region = TextureCopyRegion::new()
+        .set_offset_u(1)
+        .set_offset_v(2)
+        .set_width(3)
+        .set_height(2)
+        .edit_texture_subresource(|x| x.set_mip_level(1));
+    texture.write(&region, &data, 5, 2);
+
+    let result = read_texture(&device, &texture, 1, 1);
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 0,
+        0, 0, 0, 0,
+        0, 1, 2, 3,
+        0, 4, 5, 6,
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn write_rgb() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8G8B8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    let region = TextureCopyRegion::new().set_width(2).set_height(1);
+    texture.write(&region, &[10, 20, 30, 40, 50, 60], 6, 1);
+
+    let image = texture.read_image();
+    assert_eq!(image.get_data(), [10, 20, 30, 255, 40, 50, 60, 255]);
+}
+
+#[test]
+fn read_image() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let region = TextureCopyRegion::new().set_width(2).set_height(2);
+    texture.write(&region, &[1, 2, 3, 4], 2, 2);
+
+    // 1 チャンネルはグレースケールになる
+    let image = texture.read_image();
+    assert_eq!(image.get_width(), 2);
+    assert_eq!(image.get_height(), 2);
+    assert_eq!(image.get_format(), &ImageFormat::R8Unorm);
+    assert_eq!(image.get_pixel(1, 0), [2, 2, 2, 255]);
+    assert_eq!(image.get_pixel(0, 1), [3, 3, 3, 255]);
+}
+
+#[test]
+fn read_color_target_view() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(3)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::COLOR_BUFFER | GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let data: Vec<u8> = (0..24).collect();
+    let region = TextureCopyRegion::new().set_width(3).set_height(2);
+    texture.write(&region, &data, 12, 2);
+
+    let color_target_view = ColorTargetViewAsh::new(
+        &device,
+        &ColorTargetViewInfo::new().set_image_format(ImageFormat::R8G8B8A8Unorm),
+        &texture,
+    );
+    let image = color_target_view.read_image();
+    assert_eq!(image.get_data(), data);
+}
+
+#[test]
+#[should_panic]
+fn write_out_of_mip() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_mip_level(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
+
+#[test]
+#[should_panic]
+fn write_out_of_array() {
+    let device = DeviceAsh::new(&DeviceInfo::new());
+    let texture = TextureAsh::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_array_index(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
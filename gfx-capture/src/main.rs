@@ -0,0 +1,74 @@
+use std::path::{Path, PathBuf};
+
+use sjgfx::{
+    api::{AnyApi, Ash, IApi, Null, Wgpu},
+    capture::{Capture, TCaptureReplayer},
+};
+use sjgfx_interface::DeviceInfo;
+
+const USAGE: &str = "usage:
+    sjgfx-capture dump <capture>
+    sjgfx-capture replay <capture> <output-dir> [--api any|wgpu|ash|null]";
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(|x| x.as_str()).collect::<Vec<_>>();
+    match args.as_slice() {
+        ["dump", capture_path] => dump(capture_path),
+        ["replay", capture_path, output_dir] => {
+            replay::<AnyApi>(capture_path, output_dir);
+        }
+        ["replay", capture_path, output_dir, "--api", api] => match *api {
+            "any" => replay::<AnyApi>(capture_path, output_dir),
+            "wgpu" => replay::<Wgpu>(capture_path, output_dir),
+            "ash" => replay::<Ash>(capture_path, output_dir),
+            "null" => replay::<Null>(capture_path, output_dir),
+            _ => exit_with_usage(),
+        },
+        _ => exit_with_usage(),
+    }
+}
+
+fn load_capture(capture_path: &str) -> Capture {
+    Capture::load(capture_path).unwrap_or_else(|error| {
+        eprintln!("failed to load {}: {}", capture_path, error);
+        std::process::exit(1);
+    })
+}
+
+fn dump(capture_path: &str) {
+    let capture = load_capture(capture_path);
+    // head などでパイプが閉じられても失敗扱いにしない
+    match capture.write_text(std::io::stdout().lock()) {
+        Ok(()) => {}
+        Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => {}
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn replay<TApi: IApi>(capture_path: &str, output_dir: &str)
+where
+    TApi::ColorTargetView: Clone,
+{
+    let capture = load_capture(capture_path);
+    let output_dir = Path::new(output_dir);
+    std::fs::create_dir_all(output_dir).unwrap();
+
+    // 表示したフレームを順番に frame_0000.png, frame_0001.png, ... として保存する
+    let mut frame_index = 0;
+    let result = TCaptureReplayer::<TApi>::new(&DeviceInfo::new()).replay(&capture, |image| {
+        let path: PathBuf = output_dir.join(format!("frame_{:04}.png", frame_index));
+        image.save_png(&path).unwrap();
+        println!("{}", path.display());
+        frame_index += 1;
+    });
+    if let Err(error) = result {
+        eprintln!("failed to replay {}: {}", capture_path, error);
+        std::process::exit(1);
+    }
+}
+
+fn exit_with_usage() -> ! {
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
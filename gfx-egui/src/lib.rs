@@ -296,9 +296,8 @@ impl<TApi: IApi> RenderPass<TApi> {
         } else {
             // データを更新
             let buffer = &mut buffer_cache.buffer;
-            buffer.map_as_slice_mut(|x: &mut [u8]| {
-                let t = &mut x[0..data.len()];
-                t.clone_from_slice(data);
+            buffer.map_range_mut(0..data.len(), |x: &mut [u8]| {
+                x.clone_from_slice(data);
             });
             buffer.flush_mapped_range(0, data.len());
 
@@ -0,0 +1,62 @@
+// ピクセルの色の差を測る方法
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ColorMetric {
+    // RGBA の各チャンネルの差の最大値。単位は 0..255
+    Channel,
+
+    // sRGB を CIE L*a*b* にしたときの距離 (CIE76 の ΔE)。2.3 くらいから人が差に気づく
+    // アルファの差は 0..100 に直して大きいほうを使う
+    DeltaE,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareInfo {
+    metric: ColorMetric,
+    tolerance: f32,
+    max_mismatched_pixel_ratio: f32,
+}
+
+impl CompareInfo {
+    pub fn new() -> Self {
+        Self {
+            metric: ColorMetric::Channel,
+            tolerance: 1.0,
+            max_mismatched_pixel_ratio: 0.0,
+        }
+    }
+
+    pub fn get_metric(&self) -> ColorMetric {
+        self.metric
+    }
+
+    pub fn set_metric(mut self, metric: ColorMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    // 差がこの値を超えたピクセルを不一致とする。単位は metric による
+    pub fn get_tolerance(&self) -> f32 {
+        self.tolerance
+    }
+
+    pub fn set_tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    // 不一致のピクセルが全体に対してこの割合以下なら一致とみなす
+    pub fn get_max_mismatched_pixel_ratio(&self) -> f32 {
+        self.max_mismatched_pixel_ratio
+    }
+
+    pub fn set_max_mismatched_pixel_ratio(mut self, max_mismatched_pixel_ratio: f32) -> Self {
+        self.max_mismatched_pixel_ratio = max_mismatched_pixel_ratio;
+        self
+    }
+}
+
+impl Default for CompareInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
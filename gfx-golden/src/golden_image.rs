@@ -0,0 +1,141 @@
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+use sjgfx_interface::{ImageData, ImageFormat};
+
+use crate::{CompareInfo, ImageComparison};
+
+// 1 などを入れておくと、比べずに描画結果で基準画像を書き換える
+pub const BLESS_ENV: &str = "SJGFX_BLESS";
+
+// 一致しなかったときに描画結果と差分画像を書き出すディレクトリ。未設定なら一時ディレクトリ
+pub const OUTPUT_DIRECTORY_ENV: &str = "SJGFX_GOLDEN_OUTPUT_DIR";
+
+// 描画結果を基準画像 (PNG) と比べて、一致しなければ差分画像を書き出してからパニックする
+// BLESS_ENV が設定されていれば比べずに基準画像を書き換える
+pub fn assert_golden_image<P: AsRef<Path>>(
+    image: &ImageData,
+    reference_path: P,
+    info: &CompareInfo,
+) {
+    let reference_path = reference_path.as_ref();
+    if is_bless_enabled() {
+        if let Some(parent) = reference_path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        image.save_png(reference_path).unwrap();
+        return;
+    }
+
+    if let Err(message) = compare_golden_image(image, reference_path, info) {
+        panic!("{}", message);
+    }
+}
+
+// 描画結果を基準画像 (PNG) と比べる。一致しなければ描画結果と差分画像を書き出してエラーを返す
+// BLESS_ENV は見ないので、基準画像を書き換えることはない
+pub fn compare_golden_image<P: AsRef<Path>>(
+    image: &ImageData,
+    reference_path: P,
+    info: &CompareInfo,
+) -> Result<(), String> {
+    let reference_path = reference_path.as_ref();
+    if !reference_path.exists() {
+        let actual_path = save_output(image, reference_path, "actual");
+        return Err(format!(
+            "reference image {} does not exist (actual image: {}). run with {}=1 to create it",
+            reference_path.display(),
+            actual_path.display(),
+            BLESS_ENV
+        ));
+    }
+
+    let reference = load_png(reference_path);
+    if (image.get_width(), image.get_height()) != (reference.get_width(), reference.get_height()) {
+        let actual_path = save_output(image, reference_path, "actual");
+        return Err(format!(
+            "image size {}x{} does not match reference image {} of size {}x{} (actual image: {})",
+            image.get_width(),
+            image.get_height(),
+            reference_path.display(),
+            reference.get_width(),
+            reference.get_height(),
+            actual_path.display()
+        ));
+    }
+
+    let comparison = ImageComparison::new(image, &reference, info);
+    if !comparison.is_matched() {
+        let actual_path = save_output(image, reference_path, "actual");
+        let diff_path = save_output(comparison.get_diff_image(), reference_path, "diff");
+        return Err(format!(
+            "image does not match reference image {}: {} of {} pixels differ by more than {} (max {:?} difference {}). actual image: {}, diff image: {}",
+            reference_path.display(),
+            comparison.get_mismatched_pixel_count(),
+            comparison.get_pixel_count(),
+            info.get_tolerance(),
+            info.get_metric(),
+            comparison.get_max_difference(),
+            actual_path.display(),
+            diff_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+// PNG を RGBA 8 bit の画像として読む
+pub fn load_png<P: AsRef<Path>>(path: P) -> ImageData {
+    let file = File::open(path).unwrap();
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().unwrap();
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let output_info = reader.next_frame(&mut buffer).unwrap();
+    let buffer = &buffer[..output_info.buffer_size()];
+
+    let data = match output_info.color_type {
+        png::ColorType::Rgba => buffer.to_vec(),
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|x| [x[0], x[1], x[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|x| [x[0], x[0], x[0], x[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|x| [*x, *x, *x, 255]).collect(),
+        // normalize_to_color8 で展開されるので来ない
+        png::ColorType::Indexed => unreachable!(),
+    };
+    ImageData::new(
+        output_info.width,
+        output_info.height,
+        ImageFormat::R8G8B8A8Unorm,
+        data,
+    )
+}
+
+fn is_bless_enabled() -> bool {
+    match std::env::var(BLESS_ENV) {
+        Ok(value) => !value.is_empty() && value != "0",
+        Err(_) => false,
+    }
+}
+
+// 基準画像のファイル名に suffix をつけて出力ディレクトリに保存する
+fn save_output(image: &ImageData, reference_path: &Path, suffix: &str) -> PathBuf {
+    let output_directory = match std::env::var_os(OUTPUT_DIRECTORY_ENV) {
+        Some(output_directory) => PathBuf::from(output_directory),
+        None => std::env::temp_dir().join("sjgfx-golden"),
+    };
+    std::fs::create_dir_all(&output_directory).unwrap();
+
+    let stem = reference_path.file_stem().unwrap().to_string_lossy();
+    let path = output_directory.join(format!("{}.{}.png", stem, suffix));
+    image.save_png(&path).unwrap();
+    path
+}
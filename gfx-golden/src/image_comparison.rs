@@ -0,0 +1,224 @@
+use sjgfx_interface::{ImageData, ImageFormat};
+
+use crate::{ColorMetric, CompareInfo};
+
+// 2 枚の画像をピクセルごとに比べた結果
+pub struct ImageComparison {
+    pixel_count: u32,
+    mismatched_pixel_count: u32,
+    max_difference: f32,
+    is_matched: bool,
+    diff_image: ImageData,
+}
+
+impl ImageComparison {
+    pub fn new(actual: &ImageData, reference: &ImageData, info: &CompareInfo) -> Self {
+        assert_eq!(
+            (actual.get_width(), actual.get_height()),
+            (reference.get_width(), reference.get_height()),
+            "images to compare must have the same size"
+        );
+
+        let pixel_count = actual.get_width() * actual.get_height();
+        let mut mismatched_pixel_count = 0;
+        let mut max_difference = 0.0f32;
+        let mut diff_data = Vec::with_capacity(pixel_count as usize * 4);
+        for (actual, reference) in actual
+            .get_data()
+            .chunks_exact(4)
+            .zip(reference.get_data().chunks_exact(4))
+        {
+            let difference = match info.get_metric() {
+                ColorMetric::Channel => Self::calculate_channel_difference(actual, reference),
+                ColorMetric::DeltaE => Self::calculate_delta_e(actual, reference),
+            };
+            max_difference = max_difference.max(difference);
+
+            // 不一致のピクセルは赤にする
+            // それ以外は許容値に対する差の割合を明るさにして、許容値ぎりぎりの差が白く見えるようにする
+            if difference > info.get_tolerance() {
+                mismatched_pixel_count += 1;
+                diff_data.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                let ratio = if info.get_tolerance() > 0.0 {
+                    difference / info.get_tolerance()
+                } else {
+                    0.0
+                };
+                let value = (ratio * 255.0).round() as u8;
+                diff_data.extend_from_slice(&[value, value, value, 255]);
+            }
+        }
+
+        let max_mismatched_pixel_count =
+            (pixel_count as f32 * info.get_max_mismatched_pixel_ratio()).floor() as u32;
+        Self {
+            pixel_count,
+            mismatched_pixel_count,
+            max_difference,
+            is_matched: mismatched_pixel_count <= max_mismatched_pixel_count,
+            diff_image: ImageData::new(
+                reference.get_width(),
+                reference.get_height(),
+                ImageFormat::R8G8B8A8Unorm,
+                diff_data,
+            ),
+        }
+    }
+
+    pub fn is_matched(&self) -> bool {
+        self.is_matched
+    }
+
+    pub fn get_pixel_count(&self) -> u32 {
+        self.pixel_count
+    }
+
+    pub fn get_mismatched_pixel_count(&self) -> u32 {
+        self.mismatched_pixel_count
+    }
+
+    // 全ピクセルでいちばん大きかった差。単位は比較に使った metric による
+    pub fn get_max_difference(&self) -> f32 {
+        self.max_difference
+    }
+
+    pub fn get_diff_image(&self) -> &ImageData {
+        &self.diff_image
+    }
+
+    fn calculate_channel_difference(actual: &[u8], reference: &[u8]) -> f32 {
+        actual
+            .iter()
+            .zip(reference)
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap() as f32
+    }
+
+    fn calculate_delta_e(actual: &[u8], reference: &[u8]) -> f32 {
+        let actual_lab = Self::convert_to_lab(actual);
+        let reference_lab = Self::convert_to_lab(reference);
+        let delta_e = actual_lab
+            .iter()
+            .zip(&reference_lab)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f32>()
+            .sqrt();
+        let delta_alpha = actual[3].abs_diff(reference[3]) as f32 * 100.0 / 255.0;
+        delta_e.max(delta_alpha)
+    }
+
+    // sRGB (D65) を CIE L*a*b* にする
+    fn convert_to_lab(rgba: &[u8]) -> [f32; 3] {
+        let to_linear = |x: u8| {
+            let x = x as f32 / 255.0;
+            if x <= 0.04045 {
+                x / 12.92
+            } else {
+                ((x + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        let r = to_linear(rgba[0]);
+        let g = to_linear(rgba[1]);
+        let b = to_linear(rgba[2]);
+
+        // 白色点で割っておく
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.950_47;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.088_83;
+
+        let f = |t: f32| {
+            if t > 216.0 / 24389.0 {
+                t.cbrt()
+            } else {
+                (24389.0 / 27.0 * t + 16.0) / 116.0
+            }
+        };
+        let fx = f(x);
+        let fy = f(y);
+        let fz = f(z);
+        [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::{ImageData, ImageFormat};
+
+    use crate::{ColorMetric, CompareInfo, ImageComparison};
+
+    fn create_image(pixels: &[[u8; 4]]) -> ImageData {
+        ImageData::new(
+            pixels.len() as u32,
+            1,
+            ImageFormat::R8G8B8A8Unorm,
+            pixels.concat(),
+        )
+    }
+
+    #[test]
+    fn same_image() {
+        let image = create_image(&[[255, 0, 0, 255], [0, 255, 0, 255]]);
+        let comparison = ImageComparison::new(&image, &image, &CompareInfo::new());
+        assert!(comparison.is_matched());
+        assert_eq!(comparison.get_mismatched_pixel_count(), 0);
+        assert_eq!(comparison.get_max_difference(), 0.0);
+    }
+
+    #[test]
+    fn channel_tolerance() {
+        let actual = create_image(&[[100, 0, 0, 255], [0, 0, 0, 255]]);
+        let reference = create_image(&[[103, 0, 0, 255], [0, 0, 0, 255]]);
+
+        let comparison = ImageComparison::new(&actual, &reference, &CompareInfo::new());
+        assert!(!comparison.is_matched());
+        assert_eq!(comparison.get_mismatched_pixel_count(), 1);
+        assert_eq!(comparison.get_max_difference(), 3.0);
+        assert_eq!(
+            comparison.get_diff_image().get_pixel(0, 0),
+            [255, 0, 0, 255]
+        );
+
+        let comparison =
+            ImageComparison::new(&actual, &reference, &CompareInfo::new().set_tolerance(3.0));
+        assert!(comparison.is_matched());
+    }
+
+    #[test]
+    fn max_mismatched_pixel_ratio() {
+        let actual = create_image(&[[255, 255, 255, 255], [0, 0, 0, 255]]);
+        let reference = create_image(&[[0, 0, 0, 255], [0, 0, 0, 255]]);
+
+        let info = CompareInfo::new().set_max_mismatched_pixel_ratio(0.5);
+        assert!(ImageComparison::new(&actual, &reference, &info).is_matched());
+
+        let info = CompareInfo::new().set_max_mismatched_pixel_ratio(0.49);
+        assert!(!ImageComparison::new(&actual, &reference, &info).is_matched());
+    }
+
+    #[test]
+    fn delta_e() {
+        let info = CompareInfo::new()
+            .set_metric(ColorMetric::DeltaE)
+            .set_tolerance(2.3);
+
+        // 緑に少し赤が混ざっても目には見えないので、チャンネルの差が大きくても一致とみなす
+        let actual = create_image(&[[5, 128, 0, 255]]);
+        let reference = create_image(&[[0, 128, 0, 255]]);
+        assert!(ImageComparison::new(&actual, &reference, &info).is_matched());
+        assert!(!ImageComparison::new(&actual, &reference, &CompareInfo::new()).is_matched());
+
+        let actual = create_image(&[[128, 40, 0, 255]]);
+        let reference = create_image(&[[128, 0, 0, 255]]);
+        assert!(!ImageComparison::new(&actual, &reference, &info).is_matched());
+
+        // 白と黒は L* が 100 離れている
+        let comparison = ImageComparison::new(
+            &create_image(&[[0, 0, 0, 255]]),
+            &create_image(&[[255, 255, 255, 255]]),
+            &info,
+        );
+        assert!((comparison.get_max_difference() - 100.0).abs() < 0.1);
+    }
+}
@@ -0,0 +1,11 @@
+mod compare_info;
+mod golden_image;
+mod image_comparison;
+mod offscreen_renderer;
+
+pub use compare_info::{ColorMetric, CompareInfo};
+pub use golden_image::{
+    assert_golden_image, compare_golden_image, load_png, BLESS_ENV, OUTPUT_DIRECTORY_ENV,
+};
+pub use image_comparison::ImageComparison;
+pub use offscreen_renderer::{render_offscreen, IScene};
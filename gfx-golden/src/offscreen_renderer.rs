@@ -0,0 +1,36 @@
+use sjgfx::{api::IApi, TCommandBufferBuilder, TDeviceBuilder, TQueueBuilder};
+use sjgfx_interface::{ICommandBuffer, IQueue, ISwapChain, ImageData, SwapChainInfo};
+
+// オフスクリーンで描画するシーン
+// new で作ったリソースは描画が終わるまで生きている
+pub trait IScene<TApi: IApi> {
+    fn new(device: &mut TApi::Device) -> Self;
+
+    fn make_command(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        color_target_view: &mut TApi::ColorTargetView,
+    );
+}
+
+// ウィンドウを作らずにシーンを 1 フレーム描画して、表示した画像を読み戻す
+// 描画先の大きさや表示方法は info で指定する
+pub fn render_offscreen<TApi: IApi, TScene: IScene<TApi>>(info: &SwapChainInfo) -> ImageData {
+    let mut device = TDeviceBuilder::<TApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut queue = TQueueBuilder::<TApi>::new().build(&mut device);
+    let mut command_buffer = TCommandBufferBuilder::<TApi>::new().build(&device);
+    let mut swap_chain = TApi::SwapChain::new(&mut device, info);
+    let scene = TScene::new(&mut device);
+
+    let color_target_view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.begin();
+    scene.make_command(&mut command_buffer, color_target_view);
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.present(&mut swap_chain);
+    queue.sync();
+
+    swap_chain.read_presented_image().unwrap()
+}
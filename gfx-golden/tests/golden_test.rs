@@ -0,0 +1,240 @@
+use std::marker::PhantomData;
+
+use sjgfx::{
+    api::{IApi, Null, Wgpu},
+    TBufferBuilder, TShaderBuilder, TVertexStateBuilder,
+};
+use sjgfx_golden::{
+    assert_golden_image, compare_golden_image, load_png, render_offscreen, CompareInfo, IScene,
+    ImageComparison,
+};
+use sjgfx_interface::{
+    AttributeFormat, IBuffer, ICommandBuffer, PrimitiveTopology, ScalingMode, SwapChainInfo,
+    TextureArrayRange, VertexAttributeStateInfo, VertexBufferStateInfo,
+};
+
+const CLEAR_COLOR_REFERENCE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../resources/tests/golden/clear_color.png"
+);
+const LETTERBOX_REFERENCE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../resources/tests/golden/letterbox.png"
+);
+const HELLO_TRIANGLE_REFERENCE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../resources/tests/golden/hello_triangle.png"
+);
+
+struct ClearColorScene<TApi: IApi> {
+    color: [f32; 4],
+    _marker: PhantomData<TApi>,
+}
+
+impl<TApi: IApi> IScene<TApi> for ClearColorScene<TApi> {
+    fn new(_device: &mut TApi::Device) -> Self {
+        Self {
+            color: [0.25, 0.5, 0.75, 1.0],
+            _marker: PhantomData,
+        }
+    }
+
+    fn make_command(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        color_target_view: &mut TApi::ColorTargetView,
+    ) {
+        command_buffer.clear_color(
+            color_target_view,
+            self.color[0],
+            self.color[1],
+            self.color[2],
+            self.color[3],
+            TextureArrayRange::new(),
+        );
+    }
+}
+
+// 基準画像とは違う色でクリアする
+struct ClearRedScene<TApi: IApi>(ClearColorScene<TApi>);
+
+impl<TApi: IApi> IScene<TApi> for ClearRedScene<TApi> {
+    fn new(_device: &mut TApi::Device) -> Self {
+        Self(ClearColorScene {
+            color: [1.0, 0.0, 0.0, 1.0],
+            _marker: PhantomData,
+        })
+    }
+
+    fn make_command(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        color_target_view: &mut TApi::ColorTargetView,
+    ) {
+        self.0.make_command(command_buffer, color_target_view);
+    }
+}
+
+// 黒でクリアしてから白い三角形を描く
+struct HelloTriangleScene<TApi: IApi> {
+    shader: TApi::Shader,
+    vertex_buffer: TApi::Buffer,
+    vertex_state: TApi::VertexState,
+}
+
+impl<TApi: IApi> IScene<TApi> for HelloTriangleScene<TApi> {
+    fn new(device: &mut TApi::Device) -> Self {
+        let mut compiler = shaderc::Compiler::new().unwrap();
+        let vertex_shader_binary = compiler
+            .compile_into_spirv(
+                include_str!("../../resources/tests/hello_triangle.vs"),
+                shaderc::ShaderKind::Vertex,
+                "hello_triangle.vs",
+                "main",
+                None,
+            )
+            .unwrap();
+        let pixel_shader_binary = compiler
+            .compile_into_spirv(
+                include_str!("../../resources/tests/hello_triangle.fs"),
+                shaderc::ShaderKind::Fragment,
+                "hello_triangle.fs",
+                "main",
+                None,
+            )
+            .unwrap();
+        let shader = TShaderBuilder::<TApi>::new()
+            .set_vertex_shader_binary(vertex_shader_binary.as_binary_u8())
+            .set_pixel_shader_binary(pixel_shader_binary.as_binary_u8())
+            .build(device);
+
+        let vertex_buffer = TBufferBuilder::<TApi>::new()
+            .enable_vertex_buffer()
+            .with_size(std::mem::size_of::<[f32; 2]>() * 3)
+            .build(device);
+        vertex_buffer.map_as_slice_mut(|x: &mut [[f32; 2]]| {
+            x.copy_from_slice(&[[-0.5, -0.5], [0.5, -0.5], [0.0, 0.5]]);
+        });
+
+        let vertex_state = TVertexStateBuilder::<TApi>::new()
+            .set_vertex_attribute_states(
+                [VertexAttributeStateInfo::new()
+                    .set_buffer_index(0)
+                    .set_format(AttributeFormat::Float32_32)
+                    .set_offset(0)
+                    .set_slot(0)]
+                .into_iter(),
+            )
+            .set_vertex_buffer_states(
+                [VertexBufferStateInfo::new().set_stride(std::mem::size_of::<[f32; 2]>() as i64)]
+                    .into_iter(),
+            )
+            .build(device);
+
+        Self {
+            shader,
+            vertex_buffer,
+            vertex_state,
+        }
+    }
+
+    fn make_command(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        color_target_view: &mut TApi::ColorTargetView,
+    ) {
+        command_buffer.clear_color(
+            color_target_view,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            TextureArrayRange::new(),
+        );
+        command_buffer.set_render_targets(&[&*color_target_view], None);
+        command_buffer.set_shader(&self.shader);
+        command_buffer.set_vertex_buffer(0, &self.vertex_buffer);
+        command_buffer.set_vertex_state(&self.vertex_state);
+        command_buffer.draw(PrimitiveTopology::TriangleList, 3, 0);
+    }
+}
+
+#[test]
+fn clear_color_null() {
+    let image = render_offscreen::<Null, ClearColorScene<Null>>(
+        &SwapChainInfo::new().with_width(16).with_height(16),
+    );
+    assert_golden_image(&image, CLEAR_COLOR_REFERENCE, &CompareInfo::new());
+}
+
+#[test]
+fn letterbox_null() {
+    let image = render_offscreen::<Null, ClearColorScene<Null>>(
+        &SwapChainInfo::new()
+            .with_width(16)
+            .with_height(8)
+            .with_render_size(8, 8)
+            .with_scaling_mode(ScalingMode::Letterbox),
+    );
+    assert_golden_image(&image, LETTERBOX_REFERENCE, &CompareInfo::new());
+}
+
+// ソフトウェアのアダプターでも動くので GPU がなくても実行できる
+// 三角形の縁のピクセルはアダプターによって塗られ方が変わるので、少しだけ不一致を許す
+#[test]
+fn hello_triangle_wgpu() {
+    let image = render_offscreen::<Wgpu, HelloTriangleScene<Wgpu>>(
+        &SwapChainInfo::new().with_width(32).with_height(32),
+    );
+    assert_golden_image(
+        &image,
+        HELLO_TRIANGLE_REFERENCE,
+        &CompareInfo::new().set_max_mismatched_pixel_ratio(0.02),
+    );
+}
+
+#[test]
+fn clear_color_wgpu() {
+    let image = render_offscreen::<Wgpu, ClearColorScene<Wgpu>>(
+        &SwapChainInfo::new().with_width(16).with_height(16),
+    );
+    assert_golden_image(&image, CLEAR_COLOR_REFERENCE, &CompareInfo::new());
+}
+
+// 失敗する場合は BLESS_ENV を見ない compare_golden_image で確かめて、基準画像を書き換えないようにする
+#[test]
+fn mismatch_writes_diff_image() {
+    let image = render_offscreen::<Null, ClearRedScene<Null>>(
+        &SwapChainInfo::new().with_width(16).with_height(16),
+    );
+    let message =
+        compare_golden_image(&image, CLEAR_COLOR_REFERENCE, &CompareInfo::new()).unwrap_err();
+    assert!(message.contains("256 of 256 pixels differ"));
+
+    // 差分画像は不一致のピクセルが赤になる
+    let diff_path = message.split("diff image: ").nth(1).unwrap();
+    let diff_image = load_png(diff_path);
+    assert_eq!(diff_image.get_pixel(0, 0), [255, 0, 0, 255]);
+
+    let comparison = ImageComparison::new(
+        &image,
+        &load_png(CLEAR_COLOR_REFERENCE),
+        &CompareInfo::new(),
+    );
+    assert!(!comparison.is_matched());
+    assert_eq!(comparison.get_diff_image(), &diff_image);
+}
+
+#[test]
+fn missing_reference() {
+    let image = render_offscreen::<Null, ClearColorScene<Null>>(
+        &SwapChainInfo::new().with_width(4).with_height(4),
+    );
+    let reference_path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../resources/tests/golden/missing.png"
+    );
+    let message = compare_golden_image(&image, reference_path, &CompareInfo::new()).unwrap_err();
+    assert!(message.contains("does not exist"));
+    assert!(!std::path::Path::new(reference_path).exists());
+}
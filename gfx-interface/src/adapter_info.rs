@@ -0,0 +1,68 @@
+use crate::{AdapterType, BackendMask};
+
+#[derive(Clone, Debug)]
+pub struct AdapterInfo {
+    index: usize,
+    name: String,
+    vendor: u32,
+    adapter_type: AdapterType,
+    backend: BackendMask,
+}
+
+impl AdapterInfo {
+    pub fn new() -> Self {
+        Self {
+            index: 0,
+            name: String::new(),
+            vendor: 0,
+            adapter_type: AdapterType::Other,
+            backend: BackendMask::empty(),
+        }
+    }
+
+    // DeviceInfo::set_adapter_index に渡すインデックス
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    pub fn set_index(mut self, index: usize) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn get_vendor(&self) -> u32 {
+        self.vendor
+    }
+
+    pub fn set_vendor(mut self, vendor: u32) -> Self {
+        self.vendor = vendor;
+        self
+    }
+
+    pub fn get_adapter_type(&self) -> &AdapterType {
+        &self.adapter_type
+    }
+
+    pub fn set_adapter_type(mut self, adapter_type: AdapterType) -> Self {
+        self.adapter_type = adapter_type;
+        self
+    }
+
+    pub fn get_backend(&self) -> BackendMask {
+        self.backend
+    }
+
+    pub fn set_backend(mut self, backend: BackendMask) -> Self {
+        self.backend = backend;
+        self
+    }
+}
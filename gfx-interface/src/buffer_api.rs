@@ -1,8 +1,12 @@
-use crate::GpuAccess;
+use std::ops::Range;
+
+use crate::{CpuAccessMode, GpuAccess};
 
 pub struct BufferInfo {
     size: usize,
     gpu_access_flags: GpuAccess,
+    cpu_access_mode: CpuAccessMode,
+    debug_label: Option<String>,
 }
 
 impl BufferInfo {
@@ -10,6 +14,8 @@ impl BufferInfo {
         BufferInfo {
             size: 0,
             gpu_access_flags: GpuAccess::empty(),
+            cpu_access_mode: CpuAccessMode::Upload,
+            debug_label: None,
         }
     }
 
@@ -30,6 +36,52 @@ impl BufferInfo {
         self.gpu_access_flags = buffer_usage;
         self
     }
+
+    pub fn get_cpu_access_mode(&self) -> CpuAccessMode {
+        self.cpu_access_mode
+    }
+
+    pub fn set_cpu_access_mode(mut self, cpu_access_mode: CpuAccessMode) -> Self {
+        self.cpu_access_mode = cpu_access_mode;
+        self
+    }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
+    }
+}
+
+// マップする範囲がバッファーに収まっていて T の配列として解釈できるか調べる
+pub fn validate_map_range<T: bytemuck::Pod>(buffer_size: usize, range: &Range<usize>) {
+    assert!(
+        range.start <= range.end && range.end <= buffer_size,
+        "map range {:?} is out of buffer size {}",
+        range,
+        buffer_size
+    );
+    assert!(
+        range.start % std::mem::align_of::<T>() == 0,
+        "map range start {} is not aligned to {}",
+        range.start,
+        std::mem::align_of::<T>()
+    );
+    assert!(
+        (range.end - range.start) % std::mem::size_of::<T>() == 0,
+        "map range size {} is not a multiple of {}",
+        range.end - range.start,
+        std::mem::size_of::<T>()
+    );
+}
+
+// バッファー全体を T の配列としてマップするときの範囲
+pub fn get_whole_map_range<T: bytemuck::Pod>(buffer_size: usize) -> Range<usize> {
+    let size = std::mem::size_of::<T>();
+    0..(buffer_size / size * size)
 }
 
 pub trait IBuffer {
@@ -37,15 +89,59 @@ pub trait IBuffer {
 
     fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self;
 
-    fn map<T, F: Fn(&T)>(&self, func: F);
+    // 読み戻し用。GPU が書き込んだ内容を CPU から読む
+    fn map<T: bytemuck::Pod, F: FnOnce(&T)>(&self, func: F);
 
-    fn map_mut<T, F: Fn(&mut T)>(&self, func: F);
+    fn map_as_slice<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, func: F);
 
-    fn map_as_slice<T, F: Fn(&[T])>(&self, func: F);
+    // range はバイト単位
+    fn map_range<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F);
 
-    fn map_as_slice_mut<T, F: Fn(&mut [T])>(&self, func: F);
+    // 書き込み用。func に渡される内容は不定なので範囲全体を書き込むこと
+    fn map_mut<T: bytemuck::Pod, F: FnOnce(&mut T)>(&self, func: F);
+
+    fn map_as_slice_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, func: F);
+
+    // range はバイト単位
+    fn map_range_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F);
 
     fn flush_mapped_range(&self, offset: isize, size: usize);
 
     fn invalidate_mapped_range(&self, offset: isize, size: usize);
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::buffer_api::{get_whole_map_range, validate_map_range};
+
+    #[test]
+    fn whole_map_range() {
+        assert_eq!(get_whole_map_range::<u32>(16), 0..16);
+        assert_eq!(get_whole_map_range::<u32>(18), 0..16);
+        assert_eq!(get_whole_map_range::<[f32; 3]>(32), 0..24);
+    }
+
+    #[test]
+    fn valid_map_range() {
+        validate_map_range::<u32>(16, &(4..12));
+        validate_map_range::<u8>(16, &(3..16));
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_range_out_of_buffer() {
+        validate_map_range::<u32>(16, &(8..20));
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_range_not_multiple_of_size() {
+        validate_map_range::<u32>(16, &(0..6));
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_range_unaligned() {
+        validate_map_range::<u32>(16, &(2..6));
+    }
+}
@@ -1,13 +1,15 @@
-use crate::{IDevice, ITexture, ImageFormat};
+use crate::{IDevice, ITexture, ImageData, ImageFormat};
 
 pub struct ColorTargetViewInfo {
     image_format: ImageFormat,
+    debug_label: Option<String>,
 }
 
 impl ColorTargetViewInfo {
     pub fn new() -> Self {
         Self {
             image_format: ImageFormat::R8G8B8A8Unorm,
+            debug_label: None,
         }
     }
 
@@ -19,6 +21,15 @@ impl ColorTargetViewInfo {
         self.image_format = image_format;
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
+    }
 }
 
 pub trait IColorTargetView {
@@ -30,4 +41,7 @@ pub trait IColorTargetView {
         info: &ColorTargetViewInfo,
         texture: &Self::TextureType,
     ) -> Self;
+
+    // 描画結果を読み戻す。描画コマンドはサブミット済みであること
+    fn read_image(&self) -> ImageData;
 }
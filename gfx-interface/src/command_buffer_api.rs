@@ -3,11 +3,22 @@ use crate::{
     ITextureView, IndexFormat, PrimitiveTopology, TextureArrayRange, ScissorStateInfo,
 };
 
-pub struct CommandBufferInfo {}
+pub struct CommandBufferInfo {
+    debug_label: Option<String>,
+}
 
 impl CommandBufferInfo {
     pub fn new() -> Self {
-        CommandBufferInfo {}
+        CommandBufferInfo { debug_label: None }
+    }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
     }
 }
 
@@ -28,6 +39,12 @@ pub trait ICommandBuffer {
 
     fn end(&mut self);
 
+    fn push_debug_group(&mut self, label: &str);
+
+    fn pop_debug_group(&mut self);
+
+    fn insert_debug_marker(&mut self, label: &str);
+
     fn clear_color(
         &mut self,
         color_target_view: &mut Self::ColorTargetViewType,
@@ -54,10 +71,27 @@ pub trait ICommandBuffer {
 
     fn set_constant_buffer(&mut self, index: i32, buffer: &Self::BufferType);
 
+    // offset と size はバイト単位。offset は定数バッファーのオフセットのアライメントに揃えること
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    );
+
     fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType);
 
     fn set_vertex_buffer(&mut self, index: i32, buffer: &Self::BufferType);
 
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    );
+
     fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType);
 
     fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo);
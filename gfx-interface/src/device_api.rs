@@ -1,14 +1,31 @@
 use crate::enums::DebugMode;
+use crate::{
+    AdapterInfo, ApiType, BackendMask, DeviceCapabilities, DeviceFeatures, PowerPreference,
+};
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
 pub struct DeviceInfo {
     debug_mode: DebugMode,
+    debug_label: Option<String>,
+    optional_features: DeviceFeatures,
+    adapter_index: Option<usize>,
+    power_preference: PowerPreference,
+    backends: BackendMask,
+    is_fallback_adapter_only: bool,
+    api_type: Option<ApiType>,
 }
 
 impl DeviceInfo {
     pub fn new() -> Self {
         Self {
             debug_mode: DebugMode::Full,
+            debug_label: None,
+            optional_features: DeviceFeatures::empty(),
+            adapter_index: None,
+            power_preference: PowerPreference::Default,
+            backends: BackendMask::all(),
+            is_fallback_adapter_only: false,
+            api_type: None,
         }
     }
 
@@ -20,6 +37,76 @@ impl DeviceInfo {
         self.debug_mode = debug_mode;
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
+    }
+
+    pub fn get_optional_features(&self) -> DeviceFeatures {
+        self.optional_features
+    }
+
+    // アダプターがサポートしている機能だけが有効になる
+    pub fn set_optional_features(mut self, optional_features: DeviceFeatures) -> Self {
+        self.optional_features = optional_features;
+        self
+    }
+
+    pub fn get_adapter_index(&self) -> Option<usize> {
+        self.adapter_index
+    }
+
+    // IDevice::enumerate_adapters で列挙されたアダプターのインデックスを指定する
+    // 指定されていたら電力設定やソフトウェア指定より優先する
+    // 範囲外のインデックスは無視され、指定がないときと同じ方法で選ばれる
+    pub fn set_adapter_index(mut self, adapter_index: usize) -> Self {
+        self.adapter_index = Some(adapter_index);
+        self
+    }
+
+    pub fn get_power_preference(&self) -> &PowerPreference {
+        &self.power_preference
+    }
+
+    pub fn set_power_preference(mut self, power_preference: PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    pub fn get_backends(&self) -> BackendMask {
+        self.backends
+    }
+
+    pub fn set_backends(mut self, backends: BackendMask) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    pub fn is_fallback_adapter_only(&self) -> bool {
+        self.is_fallback_adapter_only
+    }
+
+    // ソフトウェアラスタライザーだけを選ぶ
+    pub fn set_fallback_adapter_only(mut self, is_fallback_adapter_only: bool) -> Self {
+        self.is_fallback_adapter_only = is_fallback_adapter_only;
+        self
+    }
+
+    pub fn get_api_type(&self) -> Option<ApiType> {
+        self.api_type
+    }
+
+    // 実行時にバックエンドを選ぶときに優先して試す実装
+    // 初期化に失敗したらほかの実装にフォールバックする
+    pub fn set_api_type(mut self, api_type: ApiType) -> Self {
+        self.api_type = Some(api_type);
+        self
+    }
 }
 
 pub trait IDevice {
@@ -28,4 +115,11 @@ pub trait IDevice {
     fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
     where
         T: HasRawWindowHandle + HasRawDisplayHandle;
+
+    fn get_capabilities(&self) -> DeviceCapabilities;
+
+    // DeviceInfo のバックエンド指定で絞り込んだアダプターを列挙する
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo>;
+
+    fn get_adapter_info(&self) -> AdapterInfo;
 }
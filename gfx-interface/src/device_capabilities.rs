@@ -0,0 +1,254 @@
+use bitflags::bitflags;
+
+use crate::ImageFormat;
+
+bitflags! {
+    pub struct DeviceFeatures: u32 {
+        const TIMESTAMP_QUERY = 1;
+        const PIPELINE_STATISTICS_QUERY = 1 << 1;
+        const TEXTURE_COMPRESSION_BC = 1 << 2;
+        const TEXTURE_COMPRESSION_ETC2 = 1 << 3;
+        const TEXTURE_COMPRESSION_ASTC = 1 << 4;
+        const INDIRECT_FIRST_INSTANCE = 1 << 5;
+        const MULTI_DRAW_INDIRECT = 1 << 6;
+        const SHADER_FLOAT64 = 1 << 7;
+        const POLYGON_MODE_LINE = 1 << 8;
+        const DEPTH_CLIP_CONTROL = 1 << 9;
+    }
+}
+
+bitflags! {
+    pub struct ImageFormatUsage: u32 {
+        const SAMPLED = 1;
+        const STORAGE = 1 << 1;
+        const RENDER_TARGET = 1 << 2;
+        const BLENDABLE = 1 << 3;
+        const MULTISAMPLE = 1 << 4;
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImageFormatCapability {
+    format: ImageFormat,
+    usage: ImageFormatUsage,
+}
+
+impl ImageFormatCapability {
+    pub fn new(format: ImageFormat, usage: ImageFormatUsage) -> Self {
+        Self { format, usage }
+    }
+
+    pub fn get_format(&self) -> &ImageFormat {
+        &self.format
+    }
+
+    pub fn get_usage(&self) -> ImageFormatUsage {
+        self.usage
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DeviceCapabilities {
+    max_texture_size: u32,
+    max_texture_array_layers: u32,
+    max_constant_buffers_per_stage: u32,
+    max_unordered_access_buffers_per_stage: u32,
+    max_textures_per_stage: u32,
+    max_images_per_stage: u32,
+    max_samplers_per_stage: u32,
+    max_compute_workgroup_size: [u32; 3],
+    max_compute_invocations_per_workgroup: u32,
+    max_compute_workgroups_per_dimension: u32,
+    constant_buffer_offset_alignment: u32,
+    unordered_access_buffer_offset_alignment: u32,
+    image_format_capabilities: Vec<ImageFormatCapability>,
+    features: DeviceFeatures,
+}
+
+impl DeviceCapabilities {
+    pub fn new() -> Self {
+        Self {
+            max_texture_size: 0,
+            max_texture_array_layers: 0,
+            max_constant_buffers_per_stage: 0,
+            max_unordered_access_buffers_per_stage: 0,
+            max_textures_per_stage: 0,
+            max_images_per_stage: 0,
+            max_samplers_per_stage: 0,
+            max_compute_workgroup_size: [0, 0, 0],
+            max_compute_invocations_per_workgroup: 0,
+            max_compute_workgroups_per_dimension: 0,
+            constant_buffer_offset_alignment: 1,
+            unordered_access_buffer_offset_alignment: 1,
+            image_format_capabilities: Vec::new(),
+            features: DeviceFeatures::empty(),
+        }
+    }
+
+    pub fn get_max_texture_size(&self) -> u32 {
+        self.max_texture_size
+    }
+
+    pub fn set_max_texture_size(mut self, max_texture_size: u32) -> Self {
+        self.max_texture_size = max_texture_size;
+        self
+    }
+
+    pub fn get_max_texture_array_layers(&self) -> u32 {
+        self.max_texture_array_layers
+    }
+
+    pub fn set_max_texture_array_layers(mut self, max_texture_array_layers: u32) -> Self {
+        self.max_texture_array_layers = max_texture_array_layers;
+        self
+    }
+
+    pub fn get_max_constant_buffers_per_stage(&self) -> u32 {
+        self.max_constant_buffers_per_stage
+    }
+
+    pub fn set_max_constant_buffers_per_stage(mut self, count: u32) -> Self {
+        self.max_constant_buffers_per_stage = count;
+        self
+    }
+
+    pub fn get_max_unordered_access_buffers_per_stage(&self) -> u32 {
+        self.max_unordered_access_buffers_per_stage
+    }
+
+    pub fn set_max_unordered_access_buffers_per_stage(mut self, count: u32) -> Self {
+        self.max_unordered_access_buffers_per_stage = count;
+        self
+    }
+
+    pub fn get_max_textures_per_stage(&self) -> u32 {
+        self.max_textures_per_stage
+    }
+
+    pub fn set_max_textures_per_stage(mut self, count: u32) -> Self {
+        self.max_textures_per_stage = count;
+        self
+    }
+
+    pub fn get_max_images_per_stage(&self) -> u32 {
+        self.max_images_per_stage
+    }
+
+    pub fn set_max_images_per_stage(mut self, count: u32) -> Self {
+        self.max_images_per_stage = count;
+        self
+    }
+
+    pub fn get_max_samplers_per_stage(&self) -> u32 {
+        self.max_samplers_per_stage
+    }
+
+    pub fn set_max_samplers_per_stage(mut self, count: u32) -> Self {
+        self.max_samplers_per_stage = count;
+        self
+    }
+
+    pub fn get_max_compute_workgroup_size(&self) -> [u32; 3] {
+        self.max_compute_workgroup_size
+    }
+
+    pub fn set_max_compute_workgroup_size(mut self, size: [u32; 3]) -> Self {
+        self.max_compute_workgroup_size = size;
+        self
+    }
+
+    pub fn get_max_compute_invocations_per_workgroup(&self) -> u32 {
+        self.max_compute_invocations_per_workgroup
+    }
+
+    pub fn set_max_compute_invocations_per_workgroup(mut self, count: u32) -> Self {
+        self.max_compute_invocations_per_workgroup = count;
+        self
+    }
+
+    pub fn get_max_compute_workgroups_per_dimension(&self) -> u32 {
+        self.max_compute_workgroups_per_dimension
+    }
+
+    pub fn set_max_compute_workgroups_per_dimension(mut self, count: u32) -> Self {
+        self.max_compute_workgroups_per_dimension = count;
+        self
+    }
+
+    // 定数バッファーを範囲でバインドするときのオフセットのアライメント
+    pub fn get_constant_buffer_offset_alignment(&self) -> u32 {
+        self.constant_buffer_offset_alignment
+    }
+
+    pub fn set_constant_buffer_offset_alignment(mut self, alignment: u32) -> Self {
+        self.constant_buffer_offset_alignment = alignment;
+        self
+    }
+
+    pub fn get_unordered_access_buffer_offset_alignment(&self) -> u32 {
+        self.unordered_access_buffer_offset_alignment
+    }
+
+    pub fn set_unordered_access_buffer_offset_alignment(mut self, alignment: u32) -> Self {
+        self.unordered_access_buffer_offset_alignment = alignment;
+        self
+    }
+
+    pub fn get_image_format_capabilities(&self) -> &[ImageFormatCapability] {
+        &self.image_format_capabilities
+    }
+
+    pub fn set_image_format_capabilities<TIterator>(mut self, capabilities: TIterator) -> Self
+    where
+        TIterator: IntoIterator<Item = ImageFormatCapability>,
+    {
+        self.image_format_capabilities.clear();
+        self.image_format_capabilities.extend(capabilities);
+        self
+    }
+
+    pub fn get_image_format_usage(&self, format: &ImageFormat) -> ImageFormatUsage {
+        self.image_format_capabilities
+            .iter()
+            .find(|x| x.get_format() == format)
+            .map(|x| x.get_usage())
+            .unwrap_or(ImageFormatUsage::empty())
+    }
+
+    pub fn get_features(&self) -> DeviceFeatures {
+        self.features
+    }
+
+    pub fn set_features(mut self, features: DeviceFeatures) -> Self {
+        self.features = features;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DeviceCapabilities, ImageFormat, ImageFormatCapability, ImageFormatUsage};
+
+    #[test]
+    fn get_image_format_usage() {
+        let capabilities = DeviceCapabilities::new().set_image_format_capabilities([
+            ImageFormatCapability::new(
+                ImageFormat::R8G8B8A8Unorm,
+                ImageFormatUsage::SAMPLED | ImageFormatUsage::RENDER_TARGET,
+            ),
+            ImageFormatCapability::new(ImageFormat::R32Uint, ImageFormatUsage::STORAGE),
+        ]);
+
+        assert_eq!(
+            capabilities.get_image_format_usage(&ImageFormat::R8G8B8A8Unorm),
+            ImageFormatUsage::SAMPLED | ImageFormatUsage::RENDER_TARGET
+        );
+        assert_eq!(
+            capabilities.get_image_format_usage(&ImageFormat::R32Uint),
+            ImageFormatUsage::STORAGE
+        );
+        assert!(capabilities
+            .get_image_format_usage(&ImageFormat::D32)
+            .is_empty());
+    }
+}
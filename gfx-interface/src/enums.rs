@@ -5,7 +5,6 @@ bitflags! {
         const VERTEX_BUFFER = 1;
         const INDEX_BUFFER = 1 << 1;
         const CONSTANT_BUFFER = 1 << 2;
-        const TEXTURE = 0x08;
         const UNORDERED_ACCESS_BUFFER = 1 << 3;
         const COLOR_BUFFER = 1 << 4;
         const DEPTH_STENCIL = 1 << 5;
@@ -13,16 +12,190 @@ bitflags! {
         const WRITE = 1 << 7;
         const INDIRECT_BUFFER = 1 << 8;
         const IMAGE = 1 << 9;
+        const TEXTURE = 1 << 10;
     }
 }
 
-#[derive(Clone)]
+bitflags! {
+    pub struct BackendMask: u32 {
+        const VULKAN = 1;
+        const METAL = 1 << 1;
+        const DX12 = 1 << 2;
+        const DX11 = 1 << 3;
+        const GL = 1 << 4;
+        const BROWSER_WEBGPU = 1 << 5;
+    }
+}
+
+// 実行時にバックエンドを選ぶときの実装
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiType {
+    Wgpu,
+    Vulkano,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdapterType {
+    DiscreteGpu,
+    IntegratedGpu,
+    VirtualGpu,
+    Cpu,
+    Other,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PowerPreference {
+    Default,
+    LowPower,
+    HighPerformance,
+}
+
+impl PowerPreference {
+    // 値が小さいアダプターほど優先される
+    pub fn get_adapter_priority(&self, adapter_type: &AdapterType) -> u32 {
+        match self {
+            PowerPreference::LowPower => match adapter_type {
+                AdapterType::IntegratedGpu => 0,
+                AdapterType::DiscreteGpu => 1,
+                AdapterType::VirtualGpu => 2,
+                AdapterType::Cpu => 3,
+                AdapterType::Other => 4,
+            },
+            PowerPreference::Default | PowerPreference::HighPerformance => match adapter_type {
+                AdapterType::DiscreteGpu => 0,
+                AdapterType::IntegratedGpu => 1,
+                AdapterType::VirtualGpu => 2,
+                AdapterType::Cpu => 3,
+                AdapterType::Other => 4,
+            },
+        }
+    }
+}
+
+// CPU からバッファーにどうアクセスするか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CpuAccessMode {
+    // CPU からはアクセスしない。GPU 専用のメモリーに置かれる
+    None,
+
+    // CPU から書き込んで GPU で読む
+    Upload,
+
+    // GPU が書き込んだ内容を CPU で読む
+    Readback,
+
+    // 常に CPU から読み書きできる
+    Persistent,
+}
+
+impl CpuAccessMode {
+    pub fn is_mappable(&self) -> bool {
+        *self != CpuAccessMode::None
+    }
+}
+
+// スワップチェインの表示方法。サポートされていなければ Fifo になる
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresentMode {
+    // 垂直同期を待つ。必ずサポートされている
+    Fifo,
+
+    // 垂直同期に間に合わなかったフレームは待たずに表示する
+    FifoRelaxed,
+
+    // 垂直同期を待つが、待っている間に新しいフレームが来たら置き換える
+    Mailbox,
+
+    // 垂直同期を待たない。ティアリングが起きる
+    Immediate,
+}
+
+// スワップチェインのアルファをウィンドウシステムがどう合成するか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompositeAlphaMode {
+    // サポートされているものから選ぶ
+    Auto,
+    Opaque,
+    PreMultiplied,
+    PostMultiplied,
+    Inherit,
+}
+
+// 内部のカラーターゲットをスキャンバッファのどこに表示するか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScalingMode {
+    // アスペクト比を無視してスキャンバッファ全体に引き伸ばす
+    Stretch,
+
+    // アスペクト比を保って収まるだけ拡大し、余白は黒で埋める
+    Letterbox,
+
+    // Letterbox と同じだが拡大率を整数に切り捨てる。ドット絵向け
+    IntegerLetterbox,
+}
+
+// 内部のカラーターゲットを拡大縮小するときのフィルター
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpscaleFilter {
+    Nearest,
+    Linear,
+
+    // Linear に加えて輪郭を強調する。低い解像度で描画したときのぼやけを抑える
+    Sharpen,
+}
+
+// 表示するときに HDR の色をどう表示できる範囲に収めるか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tonemap {
+    // 何もせずに 0..1 に切り詰める
+    None,
+    Reinhard,
+    Aces,
+}
+
+// 表示するときの色の符号化
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputEncoding {
+    // 線形のまま出力する
+    Linear,
+
+    // sRGB のスキャンバッファではハードウェアが符号化するのでシェーダーでは何もしない
+    Srgb,
+
+    // 1 / gamma 乗する
+    Gamma(f32),
+}
+
+// 直前のスキャンバッファの取得と表示がどうなったか
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapChainStatus {
+    // 問題なく表示できた
+    Optimal,
+
+    // サーフェスが古くなっていたか最適でなかったので設定しなおした
+    Reconfigured,
+
+    // サーフェスが失われたのでスワップチェインを作りなおした
+    Recreated,
+
+    // タイムアウトしたかウィンドウが最小化されているのでフレームを表示しなかった
+    Skipped,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueueType {
+    Graphics,
+    Compute,
+    Transfer,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DebugMode {
     Full,
     FullAssertion,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ImageFormat {
     R8Unorm,
     R8Snorm,
@@ -33,11 +206,62 @@ pub enum ImageFormat {
     R8G8B8A8Sint,
     R8G8B8A8Uint,
     R8G8B8A8Unorm,
+    R8G8B8A8UnormSrgb,
+    B8G8R8A8Unorm,
+    B8G8R8A8UnormSrgb,
     R8G8B8Unorm,
+    R16G16B16A16Float,
     D32,
 }
 
-#[derive(Clone, PartialEq)]
+impl ImageFormat {
+    pub const ALL: [ImageFormat; 15] = [
+        ImageFormat::R8Unorm,
+        ImageFormat::R8Snorm,
+        ImageFormat::R8Uint,
+        ImageFormat::R8Sint,
+        ImageFormat::R32Uint,
+        ImageFormat::R32Sint,
+        ImageFormat::R8G8B8A8Sint,
+        ImageFormat::R8G8B8A8Uint,
+        ImageFormat::R8G8B8A8Unorm,
+        ImageFormat::R8G8B8A8UnormSrgb,
+        ImageFormat::B8G8R8A8Unorm,
+        ImageFormat::B8G8R8A8UnormSrgb,
+        ImageFormat::R8G8B8Unorm,
+        ImageFormat::R16G16B16A16Float,
+        ImageFormat::D32,
+    ];
+
+    pub fn get_bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageFormat::R8Unorm
+            | ImageFormat::R8Snorm
+            | ImageFormat::R8Uint
+            | ImageFormat::R8Sint => 1,
+            ImageFormat::R8G8B8Unorm => 3,
+            ImageFormat::R32Uint
+            | ImageFormat::R32Sint
+            | ImageFormat::R8G8B8A8Sint
+            | ImageFormat::R8G8B8A8Uint
+            | ImageFormat::R8G8B8A8Unorm
+            | ImageFormat::R8G8B8A8UnormSrgb
+            | ImageFormat::B8G8R8A8Unorm
+            | ImageFormat::B8G8R8A8UnormSrgb
+            | ImageFormat::D32 => 4,
+            ImageFormat::R16G16B16A16Float => 8,
+        }
+    }
+
+    pub fn is_srgb(&self) -> bool {
+        matches!(
+            self,
+            ImageFormat::R8G8B8A8UnormSrgb | ImageFormat::B8G8R8A8UnormSrgb
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum PrimitiveTopology {
     PointList,
     TriangleList,
@@ -51,7 +275,7 @@ pub enum AttributeFormat {
     Float32_32_32_32,
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum IndexFormat {
     Uint32,
 }
@@ -1,10 +1,37 @@
+use std::time::Duration;
+
 use crate::IDevice;
 
-pub struct FenceInfo {}
+pub struct FenceInfo {
+    is_signaled: bool,
+    debug_label: Option<String>,
+}
 
 impl FenceInfo {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            is_signaled: false,
+            debug_label: None,
+        }
+    }
+
+    pub fn is_signaled(&self) -> bool {
+        self.is_signaled
+    }
+
+    // シグナル状態で作っておくと最初のフレームの wait が即座に返る
+    pub fn set_signaled(mut self, is_signaled: bool) -> Self {
+        self.is_signaled = is_signaled;
+        self
+    }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
     }
 }
 
@@ -12,4 +39,12 @@ pub trait IFence {
     type DeviceType: IDevice;
 
     fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self;
+
+    // シグナルされたら true、タイムアウトしたら false を返す
+    // timeout が None のときはシグナルされるまで待つ
+    fn wait(&mut self, timeout: Option<Duration>) -> bool;
+
+    fn is_signaled(&self) -> bool;
+
+    fn reset(&mut self);
 }
@@ -0,0 +1,208 @@
+use crate::ImageFormat;
+
+// GPU から読み戻した画像。data は 1 ピクセル 4 バイトの RGBA で行の間に詰め物はない
+// format は読み戻し元のフォーマット
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageData {
+    width: u32,
+    height: u32,
+    format: ImageFormat,
+    data: Vec<u8>,
+}
+
+impl ImageData {
+    pub fn new(width: u32, height: u32, format: ImageFormat, data: Vec<u8>) -> Self {
+        assert_eq!(
+            data.len(),
+            (width * height * 4) as usize,
+            "image data must be tightly packed RGBA"
+        );
+        Self {
+            width,
+            height,
+            format,
+            data,
+        }
+    }
+
+    // 詰め物のないテクセル列を RGBA に変換する
+    // 1 チャンネルのフォーマットはグレースケールとして扱い、0..255 に収まらない値は切り詰める
+    pub fn from_texels(width: u32, height: u32, format: ImageFormat, texels: &[u8]) -> Self {
+        let bytes_per_pixel = format.get_bytes_per_pixel();
+        assert_eq!(
+            texels.len(),
+            width as usize * height as usize * bytes_per_pixel,
+            "texels must be tightly packed"
+        );
+
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for texel in texels.chunks_exact(bytes_per_pixel) {
+            let rgba = match format {
+                ImageFormat::R8Unorm | ImageFormat::R8Uint => Self::gray(texel[0]),
+                ImageFormat::R8Snorm => Self::gray(Self::convert_snorm(texel[0] as i8)),
+                ImageFormat::R8Sint => Self::gray((texel[0] as i8).max(0) as u8),
+                ImageFormat::R32Uint => {
+                    let value = u32::from_ne_bytes(texel.try_into().unwrap());
+                    Self::gray(value.min(255) as u8)
+                }
+                ImageFormat::R32Sint => {
+                    let value = i32::from_ne_bytes(texel.try_into().unwrap());
+                    Self::gray(value.clamp(0, 255) as u8)
+                }
+                ImageFormat::R8G8B8A8Unorm
+                | ImageFormat::R8G8B8A8UnormSrgb
+                | ImageFormat::R8G8B8A8Uint => [texel[0], texel[1], texel[2], texel[3]],
+                ImageFormat::B8G8R8A8Unorm | ImageFormat::B8G8R8A8UnormSrgb => {
+                    [texel[2], texel[1], texel[0], texel[3]]
+                }
+                ImageFormat::R8G8B8A8Sint => {
+                    let convert = |x: u8| (x as i8).max(0) as u8;
+                    [
+                        convert(texel[0]),
+                        convert(texel[1]),
+                        convert(texel[2]),
+                        convert(texel[3]),
+                    ]
+                }
+                ImageFormat::R8G8B8Unorm => [texel[0], texel[1], texel[2], 255],
+                ImageFormat::R16G16B16A16Float => {
+                    let convert = |offset: usize| {
+                        let value = Self::convert_half([texel[offset], texel[offset + 1]]);
+                        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+                    };
+                    [convert(0), convert(2), convert(4), convert(6)]
+                }
+                ImageFormat::D32 => {
+                    let depth = f32::from_ne_bytes(texel.try_into().unwrap());
+                    Self::gray((depth.clamp(0.0, 1.0) * 255.0).round() as u8)
+                }
+            };
+            data.extend_from_slice(&rgba);
+        }
+
+        Self::new(width, height, format, data)
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get_format(&self) -> &ImageFormat {
+        &self.format
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        assert!(x < self.width && y < self.height);
+        let offset = ((y * self.width + x) * 4) as usize;
+        self.data[offset..(offset + 4)].try_into().unwrap()
+    }
+
+    #[cfg(feature = "png")]
+    pub fn write_png<W: std::io::Write>(&self, writer: W) -> Result<(), png::EncodingError> {
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)
+    }
+
+    #[cfg(feature = "png")]
+    pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), png::EncodingError> {
+        let file = std::fs::File::create(path)?;
+        self.write_png(std::io::BufWriter::new(file))
+    }
+
+    fn gray(value: u8) -> [u8; 4] {
+        [value, value, value, 255]
+    }
+
+    // 半精度浮動小数点数を単精度に変換する
+    fn convert_half(bytes: [u8; 2]) -> f32 {
+        let bits = u16::from_ne_bytes(bytes) as u32;
+        let sign = (bits >> 15) << 31;
+        let exponent = (bits >> 10) & 0x1f;
+        let mantissa = bits & 0x3ff;
+        let bits = match exponent {
+            // 0 と非正規化数
+            0 => {
+                let value = mantissa as f32 / (1 << 24) as f32;
+                return if sign == 0 { value } else { -value };
+            }
+            // 無限大と NaN
+            0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+            _ => sign | ((exponent + 127 - 15) << 23) | (mantissa << 13),
+        };
+        f32::from_bits(bits)
+    }
+
+    // 負の値は 0 に切り詰める
+    fn convert_snorm(value: i8) -> u8 {
+        ((value.max(0) as f32 / 127.0) * 255.0).round() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ImageData, ImageFormat};
+
+    #[test]
+    fn from_texels_rgba() {
+        let image =
+            ImageData::from_texels(2, 1, ImageFormat::R8G8B8A8Unorm, &[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(image.get_pixel(0, 0), [1, 2, 3, 4]);
+        assert_eq!(image.get_pixel(1, 0), [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn from_texels_bgra() {
+        let image = ImageData::from_texels(1, 1, ImageFormat::B8G8R8A8Unorm, &[1, 2, 3, 4]);
+        assert_eq!(image.get_data(), [3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn from_texels_gray() {
+        let image = ImageData::from_texels(2, 1, ImageFormat::R8Unorm, &[10, 20]);
+        assert_eq!(image.get_data(), [10, 10, 10, 255, 20, 20, 20, 255]);
+
+        let depth: Vec<u8> = [0.0f32, 1.0].iter().flat_map(|x| x.to_ne_bytes()).collect();
+        let image = ImageData::from_texels(2, 1, ImageFormat::D32, &depth);
+        assert_eq!(image.get_data(), [0, 0, 0, 255, 255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn from_texels_half() {
+        // 0.0, 0.5, 1.0, 2.0
+        let texels: Vec<u8> = [0x0000u16, 0x3800, 0x3c00, 0x4000]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect();
+        let image = ImageData::from_texels(1, 1, ImageFormat::R16G16B16A16Float, &texels);
+        assert_eq!(image.get_data(), [0, 128, 255, 255]);
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn write_png() {
+        let image = ImageData::from_texels(1, 1, ImageFormat::R8G8B8A8Unorm, &[1, 2, 3, 4]);
+        let mut png_data = Vec::new();
+        image.write_png(&mut png_data).unwrap();
+
+        let decoder = png::Decoder::new(png_data.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut data).unwrap();
+        assert_eq!(data, [1, 2, 3, 4]);
+    }
+}
@@ -1,12 +1,15 @@
+mod adapter_info;
 mod buffer_api;
 mod buffer_copy_region;
 mod color_target_view_api;
 mod command_buffer_api;
 mod depth_stencil_view_api;
 mod device_api;
+mod device_capabilities;
 mod display_api;
 mod enums;
 mod fence_api;
+mod image_data;
 mod queue_api;
 mod sampler_api;
 mod semaphore_api;
@@ -17,25 +20,36 @@ mod texture_view_api;
 mod vertex_state_api;
 mod viewport_scissor_state_api;
 
-pub use buffer_api::{BufferInfo, IBuffer};
+pub use adapter_info::AdapterInfo;
+pub use buffer_api::{get_whole_map_range, validate_map_range, BufferInfo, IBuffer};
 pub use buffer_copy_region::BufferCopyRegion;
 pub use color_target_view_api::{ColorTargetViewInfo, IColorTargetView};
 pub use command_buffer_api::{CommandBufferInfo, ICommandBuffer};
 pub use depth_stencil_view_api::{DepthStencilStateInfo, IDepthStencilView};
 pub use device_api::{DeviceInfo, IDevice};
+pub use device_capabilities::{
+    DeviceCapabilities, DeviceFeatures, ImageFormatCapability, ImageFormatUsage,
+};
 pub use display_api::{IDisplay, IDisplayEventListener};
 pub use enums::{
-    AttributeFormat, DebugMode, GpuAccess, ImageFormat, IndexFormat, PrimitiveTopology, ShaderStage,
+    AdapterType, ApiType, AttributeFormat, BackendMask, CompositeAlphaMode, CpuAccessMode, DebugMode,
+    GpuAccess, ImageFormat, IndexFormat, OutputEncoding, PowerPreference, PresentMode,
+    PrimitiveTopology, QueueType, ScalingMode, ShaderStage, SwapChainStatus, Tonemap,
+    UpscaleFilter,
 };
 pub use fence_api::{FenceInfo, IFence};
+pub use image_data::ImageData;
 pub use queue_api::{IQueue, QueueInfo};
 pub use sampler_api::{ISampler, SamplerInfo};
 pub use semaphore_api::{ISemaphore, SemaphoreInfo};
 pub use shader_api::{IShader, ShaderInfo};
-pub use swap_chain_api::{ISwapChain, SwapChainInfo};
+pub use swap_chain_api::{
+    compute_scaled_rect, select_format, select_present_mode, ISwapChain, OutputTransform,
+    SwapChainInfo,
+};
 pub use texture_api::{
-    BufferTextureCopyRegion, ITexture, TextureArrayRange, TextureCopyRegion, TextureInfo,
-    TextureSubresource, TextureSubresourceRange,
+    validate_texture_write, BufferTextureCopyRegion, ITexture, TextureArrayRange,
+    TextureCopyRegion, TextureInfo, TextureSubresource, TextureSubresourceRange,
 };
 pub use texture_view_api::{ITextureView, TextureViewInfo};
 pub use vertex_state_api::{
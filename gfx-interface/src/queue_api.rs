@@ -1,10 +1,24 @@
-use crate::{ICommandBuffer, IDevice, ISwapChain, IFence};
+use crate::{ICommandBuffer, IDevice, ISwapChain, IFence, ISemaphore, QueueType};
 
-pub struct QueueInfo {}
+pub struct QueueInfo {
+    queue_type: QueueType,
+}
 
 impl QueueInfo {
     pub fn new() -> QueueInfo {
-        QueueInfo {}
+        QueueInfo {
+            queue_type: QueueType::Graphics,
+        }
+    }
+
+    pub fn get_queue_type(&self) -> &QueueType {
+        &self.queue_type
+    }
+
+    // 専用のキューがないバックエンドではグラフィックスキューが使われる
+    pub fn set_queue_type(mut self, queue_type: QueueType) -> Self {
+        self.queue_type = queue_type;
+        self
     }
 }
 
@@ -12,21 +26,38 @@ pub trait IQueue {
     type DeviceType: IDevice;
     type CommandBufferType: ICommandBuffer;
     type FenceType: IFence<DeviceType = Self::DeviceType>;
+    type SemaphoreType: ISemaphore<DeviceType = Self::DeviceType>;
     type SwapChainType: ISwapChain;
 
     fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self;
 
+    // コマンドはためておかれて flush でサブミットされる
     fn execute(&mut self, command_buffer: &Self::CommandBufferType);
 
+    // ためているコマンドとあわせて一度にサブミットする
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]);
+
     fn execute_with_fence(
         &mut self,
         command_buffer: &Self::CommandBufferType,
         fence: &mut Self::FenceType,
     );
 
+    // wait_semaphores の値に到達してからコマンドを実行し、完了したら signal_semaphores の値をシグナルする
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    );
+
+    fn get_queue_type(&self) -> &QueueType;
+
     fn present(&mut self, swap_chain: &mut Self::SwapChainType);
 
+    // ためているコマンドをサブミットする。GPU の完了は待たない
     fn flush(&mut self);
 
+    // サブミットしたコマンドが GPU で完了するまで待つ
     fn sync(&mut self);
 }
@@ -1,10 +1,21 @@
 use crate::IDevice;
 
-pub struct SamplerInfo {}
+pub struct SamplerInfo {
+    debug_label: Option<String>,
+}
 
 impl SamplerInfo {
     pub fn new() -> Self {
-        Self {}
+        Self { debug_label: None }
+    }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
     }
 }
 
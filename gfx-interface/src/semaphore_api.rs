@@ -1,9 +1,36 @@
+use std::time::Duration;
+
 use crate::IDevice;
 
-pub struct SemaphoreInfo;
+pub struct SemaphoreInfo {
+    initial_value: u64,
+    debug_label: Option<String>,
+}
+
 impl SemaphoreInfo {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            initial_value: 0,
+            debug_label: None,
+        }
+    }
+
+    pub fn get_initial_value(&self) -> u64 {
+        self.initial_value
+    }
+
+    pub fn set_initial_value(mut self, initial_value: u64) -> Self {
+        self.initial_value = initial_value;
+        self
+    }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
     }
 }
 
@@ -11,4 +38,14 @@ pub trait ISemaphore {
     type DeviceType: IDevice;
 
     fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self;
+
+    // タイムラインの現在の値
+    fn get_value(&self) -> u64;
+
+    // タイムラインの値が value 以上になるまで待つ
+    // 到達したら true、タイムアウトしたら false を返す
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool;
+
+    // CPU からタイムラインの値を進める
+    fn signal(&self, value: u64);
 }
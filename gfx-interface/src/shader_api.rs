@@ -6,6 +6,7 @@ pub struct ShaderInfo<'a> {
     _pixel_shader_binary: Option<&'a [u8]>,
     vertex_shader_source: Option<&'a str>,
     pixel_shader_source: Option<&'a str>,
+    debug_label: Option<&'a str>,
 }
 
 impl<'a> ShaderInfo<'a> {
@@ -16,6 +17,7 @@ impl<'a> ShaderInfo<'a> {
             _pixel_shader_binary: None,
             vertex_shader_source: None,
             pixel_shader_source: None,
+            debug_label: None,
         }
     }
 
@@ -72,6 +74,15 @@ impl<'a> ShaderInfo<'a> {
         self._compute_shader_binary = Some(shader_binary);
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&'a str> {
+        self.debug_label
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &'a str) -> Self {
+        self.debug_label = Some(debug_label);
+        self
+    }
 }
 
 pub trait IShader {
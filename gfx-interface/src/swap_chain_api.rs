@@ -1,8 +1,86 @@
-use crate::IDisplayEventListener;
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+
+use crate::{
+    CompositeAlphaMode, IDisplayEventListener, ImageData, ImageFormat, OutputEncoding, PresentMode,
+    ScalingMode, SwapChainStatus, Tonemap, UpscaleFilter,
+};
+
+// 内部のカラーターゲットをスキャンバッファに転送するときの色の変換
+// 露出をかけてからトーンマップし、符号化してからディザをかける
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutputTransform {
+    exposure: f32,
+    tonemap: Tonemap,
+    encoding: OutputEncoding,
+    is_dithering_enabled: bool,
+}
+
+impl OutputTransform {
+    // 既定では何も変換しない
+    pub fn new() -> Self {
+        Self {
+            exposure: 1.0,
+            tonemap: Tonemap::None,
+            encoding: OutputEncoding::Linear,
+            is_dithering_enabled: false,
+        }
+    }
+
+    pub fn get_exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    pub fn set_exposure(mut self, exposure: f32) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    pub fn get_tonemap(&self) -> Tonemap {
+        self.tonemap
+    }
+
+    pub fn set_tonemap(mut self, tonemap: Tonemap) -> Self {
+        self.tonemap = tonemap;
+        self
+    }
+
+    pub fn get_encoding(&self) -> OutputEncoding {
+        self.encoding
+    }
+
+    pub fn set_encoding(mut self, encoding: OutputEncoding) -> Self {
+        if let OutputEncoding::Gamma(gamma) = encoding {
+            assert!(gamma > 0.0);
+        }
+        self.encoding = encoding;
+        self
+    }
+
+    // 8 ビットのスキャンバッファに量子化するときの縞模様を抑える
+    pub fn is_dithering_enabled(&self) -> bool {
+        self.is_dithering_enabled
+    }
+
+    pub fn set_dithering_enabled(mut self, is_enabled: bool) -> Self {
+        self.is_dithering_enabled = is_enabled;
+        self
+    }
+}
 
 pub struct SwapChainInfo {
     width: u32,
     height: u32,
+    present_mode: PresentMode,
+    preferred_formats: Vec<ImageFormat>,
+    alpha_mode: CompositeAlphaMode,
+    frame_latency: u32,
+    is_direct_rendering_enabled: bool,
+    render_scale: f32,
+    render_size: Option<(u32, u32)>,
+    scaling_mode: ScalingMode,
+    upscale_filter: UpscaleFilter,
+    render_format: ImageFormat,
+    output_transform: OutputTransform,
 }
 
 impl SwapChainInfo {
@@ -10,6 +88,17 @@ impl SwapChainInfo {
         Self {
             width: 640,
             height: 480,
+            present_mode: PresentMode::Fifo,
+            preferred_formats: Vec::new(),
+            alpha_mode: CompositeAlphaMode::Auto,
+            frame_latency: 2,
+            is_direct_rendering_enabled: false,
+            render_scale: 1.0,
+            render_size: None,
+            scaling_mode: ScalingMode::Stretch,
+            upscale_filter: UpscaleFilter::Linear,
+            render_format: ImageFormat::R8G8B8A8Unorm,
+            output_transform: OutputTransform::new(),
         }
     }
 
@@ -30,6 +119,118 @@ impl SwapChainInfo {
         self.height = height;
         self
     }
+
+    pub fn get_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn with_present_mode(mut self, present_mode: PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    // 先頭から順にサーフェスがサポートしているものを選ぶ
+    // どれもサポートされていないか空のときはサーフェスの既定のフォーマットになる
+    pub fn get_preferred_formats(&self) -> &[ImageFormat] {
+        &self.preferred_formats
+    }
+
+    pub fn with_preferred_formats(mut self, preferred_formats: &[ImageFormat]) -> Self {
+        self.preferred_formats = preferred_formats.to_vec();
+        self
+    }
+
+    pub fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.alpha_mode
+    }
+
+    pub fn with_alpha_mode(mut self, alpha_mode: CompositeAlphaMode) -> Self {
+        self.alpha_mode = alpha_mode;
+        self
+    }
+
+    // CPU が GPU より先行できるフレーム数
+    pub fn get_frame_latency(&self) -> u32 {
+        self.frame_latency
+    }
+
+    pub fn with_frame_latency(mut self, frame_latency: u32) -> Self {
+        assert!(frame_latency > 0);
+        self.frame_latency = frame_latency;
+        self
+    }
+
+    // スキャンバッファに直接描画する。無効のときは内部のカラーターゲットをスキャンバッファに転送する
+    // 直接描画するときはカラーターゲットのフォーマットが get_format になり、サイズはウィンドウと同じになる
+    pub fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled
+    }
+
+    pub fn with_direct_rendering_enabled(mut self, is_enabled: bool) -> Self {
+        self.is_direct_rendering_enabled = is_enabled;
+        self
+    }
+
+    // 以下は内部のカラーターゲットをスキャンバッファに転送するときの設定で、直接描画するときは使われない
+
+    // 内部のカラーターゲットのウィンドウに対する大きさ。render_size が指定されていたらそちらを優先する
+    pub fn get_render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    pub fn with_render_scale(mut self, render_scale: f32) -> Self {
+        assert!(render_scale > 0.0);
+        self.render_scale = render_scale;
+        self
+    }
+
+    // ウィンドウの大きさによらない内部のカラーターゲットの大きさ
+    pub fn get_render_size(&self) -> Option<(u32, u32)> {
+        self.render_size
+    }
+
+    pub fn with_render_size(mut self, width: u32, height: u32) -> Self {
+        assert!(width > 0 && height > 0);
+        self.render_size = Some((width, height));
+        self
+    }
+
+    pub fn get_scaling_mode(&self) -> ScalingMode {
+        self.scaling_mode
+    }
+
+    pub fn with_scaling_mode(mut self, scaling_mode: ScalingMode) -> Self {
+        self.scaling_mode = scaling_mode;
+        self
+    }
+
+    pub fn get_upscale_filter(&self) -> UpscaleFilter {
+        self.upscale_filter
+    }
+
+    pub fn with_upscale_filter(mut self, upscale_filter: UpscaleFilter) -> Self {
+        self.upscale_filter = upscale_filter;
+        self
+    }
+
+    // HDR で描画するときは R16G16B16A16Float にする
+    pub fn get_render_format(&self) -> &ImageFormat {
+        &self.render_format
+    }
+
+    pub fn with_render_format(mut self, render_format: ImageFormat) -> Self {
+        self.render_format = render_format;
+        self
+    }
+
+    pub fn get_output_transform(&self) -> &OutputTransform {
+        &self.output_transform
+    }
+
+    pub fn with_output_transform(mut self, output_transform: OutputTransform) -> Self {
+        self.output_transform = output_transform;
+        self
+    }
 }
 
 pub trait ISwapChain: IDisplayEventListener {
@@ -40,9 +241,158 @@ pub trait ISwapChain: IDisplayEventListener {
 
     fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self;
 
+    // デバイスを作ったときとは別のウィンドウに表示する。1 つのデバイスで複数のウィンドウを扱える
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle;
+
     fn acquire_next_scan_buffer_view(
         &mut self,
         semaphore: Option<&mut Self::SemaphoreType>,
         fence: Option<&mut Self::FenceType>,
     ) -> &mut Self::ColorTargetViewType;
+
+    // 以下は実際に選ばれた設定を返す
+    fn get_present_mode(&self) -> PresentMode;
+
+    fn get_format(&self) -> ImageFormat;
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode;
+
+    fn get_frame_latency(&self) -> u32;
+
+    fn is_direct_rendering_enabled(&self) -> bool;
+
+    // サーフェスが使えなくなっても取得と表示は失敗せず、どう回復したかをここで返す
+    // Skipped のときに取得したカラーターゲットへの描画は表示されない
+    fn get_status(&self) -> SwapChainStatus;
+
+    // acquire_next_scan_buffer_view で返すカラーターゲットの大きさ
+    fn get_render_width(&self) -> u32;
+
+    fn get_render_height(&self) -> u32;
+
+    // 動的解像度用。次のフレームから内部のカラーターゲットの大きさが変わる
+    // 内部のカラーターゲットを持たない vulkano バックエンドは 1.0 しか受け付けない
+    fn set_render_scale(&mut self, render_scale: f32);
+
+    // 露出の調整用。次の表示から反映される
+    // vulkano バックエンドは変換できないので既定値 (OutputTransform::new) しか受け付けない
+    fn set_output_transform(&mut self, output_transform: &OutputTransform);
+
+    // 直前に表示したフレームを読み戻す。ウィンドウに表示したものは読み戻せないので None
+    // オフスクリーンのスワップチェーンは wgpu と ash バックエンドにしかないので、vulkano では常に None
+    fn read_presented_image(&self) -> Option<ImageData>;
+}
+
+// 要求された表示方法がサポートされていなければ必ずサポートされている Fifo にする
+pub fn select_present_mode(requested: PresentMode, supported: &[PresentMode]) -> PresentMode {
+    if supported.contains(&requested) {
+        requested
+    } else {
+        PresentMode::Fifo
+    }
+}
+
+// 大きさ render_size の画像を大きさ target_size のスキャンバッファに表示する範囲 (x, y, width, height)
+pub fn compute_scaled_rect(
+    render_size: (u32, u32),
+    target_size: (u32, u32),
+    scaling_mode: ScalingMode,
+) -> (f32, f32, f32, f32) {
+    let (render_width, render_height) = (render_size.0 as f32, render_size.1 as f32);
+    let (target_width, target_height) = (target_size.0 as f32, target_size.1 as f32);
+    let fit_scale = (target_width / render_width).min(target_height / render_height);
+    let scale = match scaling_mode {
+        ScalingMode::Stretch => return (0.0, 0.0, target_width, target_height),
+        ScalingMode::Letterbox => fit_scale,
+        // 等倍でも収まらないときは縮小するしかない
+        ScalingMode::IntegerLetterbox if fit_scale < 1.0 => fit_scale,
+        ScalingMode::IntegerLetterbox => fit_scale.floor(),
+    };
+
+    let width = render_width * scale;
+    let height = render_height * scale;
+    let x = ((target_width - width) * 0.5).floor();
+    let y = ((target_height - height) * 0.5).floor();
+    (x, y, width, height)
+}
+
+// 候補の中で最初にサポートされているものを選ぶ。なければサポートされているものの先頭
+pub fn select_format(preferred: &[ImageFormat], supported: &[ImageFormat]) -> Option<ImageFormat> {
+    preferred
+        .iter()
+        .find(|format| supported.contains(format))
+        .or(supported.first())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compute_scaled_rect, select_format, select_present_mode, ImageFormat, PresentMode,
+        ScalingMode,
+    };
+
+    #[test]
+    fn select_present_mode_fallback() {
+        let supported = [PresentMode::Fifo, PresentMode::Immediate];
+        assert_eq!(
+            select_present_mode(PresentMode::Immediate, &supported),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            select_present_mode(PresentMode::Mailbox, &supported),
+            PresentMode::Fifo
+        );
+    }
+
+    #[test]
+    fn select_format_preferred() {
+        let supported = [ImageFormat::B8G8R8A8UnormSrgb, ImageFormat::B8G8R8A8Unorm];
+        assert_eq!(
+            select_format(
+                &[ImageFormat::R8G8B8A8Unorm, ImageFormat::B8G8R8A8Unorm],
+                &supported
+            ),
+            Some(ImageFormat::B8G8R8A8Unorm)
+        );
+        assert_eq!(
+            select_format(&[ImageFormat::R8G8B8A8Unorm], &supported),
+            Some(ImageFormat::B8G8R8A8UnormSrgb)
+        );
+        assert_eq!(select_format(&[], &[]), None);
+    }
+
+    #[test]
+    fn compute_scaled_rect_stretch() {
+        assert_eq!(
+            compute_scaled_rect((320, 240), (1000, 500), ScalingMode::Stretch),
+            (0.0, 0.0, 1000.0, 500.0)
+        );
+    }
+
+    #[test]
+    fn compute_scaled_rect_letterbox() {
+        assert_eq!(
+            compute_scaled_rect((320, 240), (1000, 480), ScalingMode::Letterbox),
+            (180.0, 0.0, 640.0, 480.0)
+        );
+        assert_eq!(
+            compute_scaled_rect((320, 240), (1000, 500), ScalingMode::IntegerLetterbox),
+            (180.0, 10.0, 640.0, 480.0)
+        );
+    }
+
+    #[test]
+    fn compute_scaled_rect_integer_letterbox_shrink() {
+        assert_eq!(
+            compute_scaled_rect((320, 240), (160, 240), ScalingMode::IntegerLetterbox),
+            (0.0, 60.0, 160.0, 120.0)
+        );
+    }
 }
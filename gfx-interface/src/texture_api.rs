@@ -1,11 +1,13 @@
-use crate::{GpuAccess, IDevice, ImageFormat};
+use crate::{GpuAccess, IDevice, ImageData, ImageFormat};
 
 pub struct TextureInfo {
     _width: i32,
     _height: i32,
     _depth: i32,
+    _mip_count: i32,
     _gpu_access_flags: GpuAccess,
     _image_format: ImageFormat,
+    _debug_label: Option<String>,
 }
 
 impl TextureInfo {
@@ -14,8 +16,10 @@ impl TextureInfo {
             _width: 1,
             _height: 1,
             _depth: 1,
+            _mip_count: 1,
             _gpu_access_flags: GpuAccess::empty(),
             _image_format: ImageFormat::R8G8B8A8Unorm,
+            _debug_label: None,
         }
     }
 
@@ -46,6 +50,15 @@ impl TextureInfo {
         self
     }
 
+    pub fn get_mip_count(&self) -> i32 {
+        self._mip_count
+    }
+
+    pub fn set_mip_count(mut self, mip_count: i32) -> Self {
+        self._mip_count = mip_count;
+        self
+    }
+
     pub fn get_gpu_access_flags(&self) -> &GpuAccess {
         &self._gpu_access_flags
     }
@@ -63,6 +76,15 @@ impl TextureInfo {
         self._image_format = image_format;
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self._debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self._debug_label = Some(debug_label.to_string());
+        self
+    }
 }
 
 pub struct TextureCopyRegion {
@@ -164,6 +186,11 @@ impl TextureCopyRegion {
         self._texture_subresource = updater(self._texture_subresource);
         self
     }
+
+    pub fn set_texture_subresource(mut self, texture_subresource: TextureSubresource) -> Self {
+        self._texture_subresource = texture_subresource;
+        self
+    }
 }
 
 pub struct BufferTextureCopyRegion {
@@ -354,10 +381,105 @@ impl TextureSubresource {
     }
 }
 
+// テクスチャーへの書き込みに必要なデータのバイト数を返す
+// 行の間 (bytes_per_row) と配列要素の間 (rows_per_image) には詰め物があってもよい
+pub fn validate_texture_write(
+    region: &TextureCopyRegion,
+    image_format: &ImageFormat,
+    data_size: usize,
+    bytes_per_row: usize,
+    rows_per_image: usize,
+) -> usize {
+    let width = region.get_width() as usize;
+    let height = region.get_height() as usize;
+    let layer_count = region.get_array_length() as usize;
+    let row_size = width * image_format.get_bytes_per_pixel();
+    assert!(
+        region.get_offset_u() >= 0 && region.get_offset_v() >= 0,
+        "texture region offset must not be negative"
+    );
+    assert!(
+        bytes_per_row >= row_size,
+        "bytes_per_row {} is less than the row size {}",
+        bytes_per_row,
+        row_size
+    );
+    assert!(
+        rows_per_image >= height,
+        "rows_per_image {} is less than the region height {}",
+        rows_per_image,
+        height
+    );
+
+    // 最後の行のあとには詰め物がなくてもよい
+    let required_size = if width == 0 || height == 0 || layer_count == 0 {
+        0
+    } else {
+        bytes_per_row * (rows_per_image * (layer_count - 1) + height - 1) + row_size
+    };
+    assert!(
+        data_size >= required_size,
+        "texture data size {} is less than the required size {}",
+        data_size,
+        required_size
+    );
+    required_size
+}
+
 pub trait ITexture {
     type DeviceType: IDevice;
 
     fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self;
 
     fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self;
+
+    // region で指定したミップレベル、配列要素、矩形にデータを書き込む
+    // data は bytes_per_row バイトごとに 1 行、rows_per_image 行ごとに 1 配列要素が並んでいるものとする
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    );
+
+    // ミップレベル 0、配列要素 0 を読み戻す
+    fn read_image(&self) -> ImageData;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{validate_texture_write, ImageFormat, TextureCopyRegion};
+
+    #[test]
+    fn validate_texture_write_tightly_packed() {
+        let region = TextureCopyRegion::new().set_width(4).set_height(2);
+        let size = validate_texture_write(&region, &ImageFormat::R8G8B8A8Unorm, 32, 16, 2);
+        assert_eq!(size, 32);
+    }
+
+    #[test]
+    fn validate_texture_write_padded() {
+        // 最後の行の詰め物は不要
+        let region = TextureCopyRegion::new()
+            .set_width(3)
+            .set_height(2)
+            .set_array_length(2);
+        let size = validate_texture_write(&region, &ImageFormat::R8Unorm, 256 * 5 + 3, 256, 4);
+        assert_eq!(size, 256 * 5 + 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_texture_write_short_row() {
+        let region = TextureCopyRegion::new().set_width(4).set_height(2);
+        validate_texture_write(&region, &ImageFormat::R8G8B8A8Unorm, 32, 8, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn validate_texture_write_short_data() {
+        let region = TextureCopyRegion::new().set_width(4).set_height(2);
+        validate_texture_write(&region, &ImageFormat::R8G8B8A8Unorm, 31, 16, 2);
+    }
 }
@@ -2,12 +2,14 @@ use crate::{IDevice, ITexture, ImageFormat};
 
 pub struct TextureViewInfo {
     image_format: ImageFormat,
+    debug_label: Option<String>,
 }
 
 impl TextureViewInfo {
     pub fn new() -> Self {
         Self {
             image_format: ImageFormat::R8G8B8A8Unorm,
+            debug_label: None,
         }
     }
 
@@ -19,6 +21,15 @@ impl TextureViewInfo {
         self.image_format = image_format;
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self.debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self.debug_label = Some(debug_label.to_string());
+        self
+    }
 }
 
 pub trait ITextureView {
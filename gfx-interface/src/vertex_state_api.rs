@@ -91,6 +91,7 @@ impl VertexBufferStateInfo {
 pub struct VertexStateInfo {
     _attribute_state_info_array: Vec<VertexAttributeStateInfo>,
     _buffer_state_info_array: Vec<VertexBufferStateInfo>,
+    _debug_label: Option<String>,
 }
 
 impl VertexStateInfo {
@@ -98,6 +99,7 @@ impl VertexStateInfo {
         Self {
             _attribute_state_info_array: Vec::new(),
             _buffer_state_info_array: Vec::new(),
+            _debug_label: None,
         }
     }
 
@@ -130,6 +132,15 @@ impl VertexStateInfo {
         self._buffer_state_info_array.extend(buffer_state_infos);
         self
     }
+
+    pub fn get_debug_label(&self) -> Option<&str> {
+        self._debug_label.as_deref()
+    }
+
+    pub fn set_debug_label(mut self, debug_label: &str) -> Self {
+        self._debug_label = Some(debug_label.to_string());
+        self
+    }
 }
 
 pub trait IVertexState {
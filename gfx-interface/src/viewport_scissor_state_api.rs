@@ -91,7 +91,7 @@ impl ViewportStateInfo {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ScissorStateInfo {
     _origin_x: i32,
     _origin_y: i32,
@@ -0,0 +1,125 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex},
+};
+
+use bytemuck::Pod;
+use sjgfx_interface::{
+    get_whole_map_range, validate_map_range, BufferInfo, CpuAccessMode, IBuffer,
+};
+
+use crate::DeviceNull;
+
+pub struct BufferNull {
+    data: Arc<Mutex<Vec<u8>>>,
+    cpu_access_mode: CpuAccessMode,
+}
+
+impl BufferNull {
+    // 中身は 0 で初期化しておく
+    pub fn new(_device: &DeviceNull, info: &BufferInfo) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(vec![0; info.get_size()])),
+            cpu_access_mode: info.get_cpu_access_mode(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.data.lock().unwrap().len()
+    }
+
+    pub fn clone_data(&self) -> Arc<Mutex<Vec<u8>>> {
+        self.data.clone()
+    }
+
+    pub fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map_range(0..std::mem::size_of::<T>(), |x: &[T]| func(&x[0]));
+    }
+
+    pub fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_range(get_whole_map_range::<T>(self.get_size()), func);
+    }
+
+    pub fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        let data = self.data.lock().unwrap();
+        let data = &data[range];
+        match bytemuck::try_cast_slice(data) {
+            Ok(slice) => func(slice),
+            Err(_) => func(&Self::copy_to_vec::<T>(data)),
+        }
+    }
+
+    pub fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_range_mut(0..std::mem::size_of::<T>(), |x: &mut [T]| func(&mut x[0]));
+    }
+
+    pub fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_range_mut(get_whole_map_range::<T>(self.get_size()), func);
+    }
+
+    pub fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        let mut data = self.data.lock().unwrap();
+        let data = &mut data[range];
+        match bytemuck::try_cast_slice_mut(data) {
+            Ok(slice) => func(slice),
+            Err(_) => {
+                let mut temp = Self::copy_to_vec::<T>(data);
+                func(&mut temp);
+                data.copy_from_slice(bytemuck::cast_slice(&temp));
+            }
+        }
+    }
+
+    fn validate_map_range<T: Pod>(&self, range: &Range<usize>) {
+        assert!(
+            self.cpu_access_mode.is_mappable(),
+            "buffer created with CpuAccessMode::None can't be mapped"
+        );
+        validate_map_range::<T>(self.get_size(), range);
+    }
+
+    // バイト列が T のアライメントを満たしていなければコピーしてから渡す
+    fn copy_to_vec<T: Pod>(data: &[u8]) -> Vec<T> {
+        let mut result = vec![T::zeroed(); data.len() / std::mem::size_of::<T>()];
+        bytemuck::cast_slice_mut(&mut result).copy_from_slice(data);
+        result
+    }
+}
+
+impl IBuffer for BufferNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map(func);
+    }
+
+    fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_as_slice(func);
+    }
+
+    fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.map_range(range, func);
+    }
+
+    fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_mut(func);
+    }
+
+    fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_as_slice_mut(func);
+    }
+
+    fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.map_range_mut(range, func);
+    }
+
+    fn flush_mapped_range(&self, _offset: isize, _size: usize) {}
+
+    fn invalidate_mapped_range(&self, _offset: isize, _size: usize) {}
+}
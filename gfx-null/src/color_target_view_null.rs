@@ -0,0 +1,57 @@
+use std::sync::{Arc, Mutex};
+
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData};
+
+use crate::{detail::TextureData, DeviceNull, TextureNull};
+
+#[derive(Clone)]
+pub struct ColorTargetViewNull {
+    data: Arc<Mutex<TextureData>>,
+}
+
+impl ColorTargetViewNull {
+    pub fn new(_device: &DeviceNull, _info: &ColorTargetViewInfo, texture: &TextureNull) -> Self {
+        Self {
+            data: texture.clone_data(),
+        }
+    }
+
+    pub(crate) fn new_direct(data: TextureData) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(data)),
+        }
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.data.lock().unwrap().get_width()
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.data.lock().unwrap().get_height()
+    }
+
+    pub(crate) fn clone_data(&self) -> Arc<Mutex<TextureData>> {
+        self.data.clone()
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        self.data.lock().unwrap().read_image()
+    }
+}
+
+impl IColorTargetView for ColorTargetViewNull {
+    type DeviceType = DeviceNull;
+    type TextureType = TextureNull;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &ColorTargetViewInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        Self::new(device, info, texture)
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
+}
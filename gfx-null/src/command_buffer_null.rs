@@ -0,0 +1,413 @@
+use sjgfx_interface::{
+    BufferCopyRegion, BufferTextureCopyRegion, CommandBufferInfo, ICommandBuffer, IndexFormat,
+    PrimitiveTopology, ScissorStateInfo, TextureArrayRange,
+};
+
+use crate::{
+    detail::encode_color, BufferNull, ColorTargetViewNull, DepthStencilViewNull, DeviceNull,
+    SamplerNull, ShaderNull, TextureNull, TextureViewNull, VertexStateNull,
+};
+
+// キューで実行されたときに記録されるコマンド
+// 描画とディスパッチは GPU の処理をしないので、何が発行されたかだけ残す
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandNull {
+    PushDebugGroup(String),
+    PopDebugGroup,
+    InsertDebugMarker(String),
+    Dispatch {
+        count_x: i32,
+        count_y: i32,
+        count_z: i32,
+    },
+    Draw {
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    },
+    DrawIndexed {
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    },
+}
+
+pub struct CommandBufferNull {
+    commands: Vec<CommandNull>,
+
+    // 描画とディスパッチに合ったシェーダーが設定されているかの確認用
+    is_compute_shader: Option<bool>,
+}
+
+impl CommandBufferNull {
+    pub fn new(_device: &DeviceNull, _info: &CommandBufferInfo) -> Self {
+        Self {
+            commands: Vec::new(),
+            is_compute_shader: None,
+        }
+    }
+
+    pub fn get_commands(&self) -> &[CommandNull] {
+        &self.commands
+    }
+
+    pub fn begin(&mut self) {
+        self.commands.clear();
+        self.is_compute_shader = None;
+    }
+
+    pub fn end(&mut self) {}
+
+    pub fn push_debug_group(&mut self, label: &str) {
+        self.commands
+            .push(CommandNull::PushDebugGroup(label.to_string()));
+    }
+
+    pub fn pop_debug_group(&mut self) {
+        self.commands.push(CommandNull::PopDebugGroup);
+    }
+
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        self.commands
+            .push(CommandNull::InsertDebugMarker(label.to_string()));
+    }
+
+    // ほかのバックエンドと同じく記録せずにその場で塗りつぶす
+    pub fn clear_color(
+        &mut self,
+        color_target_view: &mut ColorTargetViewNull,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        let data = color_target_view.clone_data();
+        let mut data = data.lock().unwrap();
+        let texel = encode_color(data.get_format(), [red, green, blue, alpha]);
+        data.fill(
+            0,
+            texture_array_range.get_base_index() as u32,
+            texture_array_range.get_length() as u32,
+            &texel,
+        );
+    }
+
+    pub fn set_shader(&mut self, shader: &ShaderNull) {
+        self.is_compute_shader = Some(shader.is_compute());
+    }
+
+    pub fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        assert_eq!(
+            self.is_compute_shader,
+            Some(true),
+            "dispatch needs a compute shader"
+        );
+        self.commands.push(CommandNull::Dispatch {
+            count_x,
+            count_y,
+            count_z,
+        });
+    }
+
+    pub fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        assert_eq!(
+            self.is_compute_shader,
+            Some(false),
+            "draw needs a graphics shader"
+        );
+        self.commands.push(CommandNull::Draw {
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        _index_buffer: &BufferNull,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        assert_eq!(
+            self.is_compute_shader,
+            Some(false),
+            "draw needs a graphics shader"
+        );
+        self.commands.push(CommandNull::DrawIndexed {
+            primitive_topology,
+            index_format,
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        });
+    }
+
+    // コピーは wgpu と同じくその場で実行する
+    pub fn copy_buffer_to_buffer(
+        &self,
+        dst_buffer: &mut BufferNull,
+        src_buffer: &BufferNull,
+        region: &BufferCopyRegion,
+    ) {
+        let src_offset = region.get_src_offset() as usize;
+        let dst_offset = region.get_dst_offset() as usize;
+        let copy_size = region.get_copy_size();
+
+        // 同じバッファーどうしのコピーでロックが二重にならないように一度取り出す
+        let bytes =
+            src_buffer.clone_data().lock().unwrap()[src_offset..(src_offset + copy_size)].to_vec();
+        dst_buffer.clone_data().lock().unwrap()[dst_offset..(dst_offset + copy_size)]
+            .copy_from_slice(&bytes);
+    }
+
+    // バッファーには行を詰めて書き込む
+    pub fn copy_image_to_buffer(
+        &mut self,
+        buffer: &BufferNull,
+        texture: &TextureNull,
+        copy_region: BufferTextureCopyRegion,
+    ) {
+        let texture_copy_region = copy_region.get_texture_copy_region();
+        let subresource = texture_copy_region.get_texture_subresource();
+        let texels = texture.clone_data().lock().unwrap().read(
+            subresource.get_mip_level() as u32,
+            subresource.get_array_index() as u32,
+            (
+                texture_copy_region.get_offset_u() as u32,
+                texture_copy_region.get_offset_v() as u32,
+            ),
+            (
+                copy_region.get_image_width() as u32,
+                copy_region.get_image_height() as u32,
+            ),
+        );
+
+        let offset = copy_region.get_offset() as usize;
+        buffer.clone_data().lock().unwrap()[offset..(offset + texels.len())]
+            .copy_from_slice(&texels);
+    }
+
+    // バッファーの行は詰まっているものとする
+    pub fn copy_buffer_to_image(
+        &mut self,
+        texture: &TextureNull,
+        buffer: &BufferNull,
+        copy_region: BufferTextureCopyRegion,
+    ) {
+        let texture_copy_region = copy_region.get_texture_copy_region();
+        let subresource = texture_copy_region.get_texture_subresource();
+        let width = copy_region.get_image_width() as u32;
+        let height = copy_region.get_image_height() as u32;
+        let data = texture.clone_data();
+        let mut data = data.lock().unwrap();
+        let bytes_per_row = width as usize * data.get_format().get_bytes_per_pixel();
+        let offset = copy_region.get_offset() as usize;
+        let bytes = buffer.clone_data().lock().unwrap()
+            [offset..(offset + bytes_per_row * height as usize)]
+            .to_vec();
+        data.write(
+            subresource.get_mip_level() as u32,
+            subresource.get_array_index() as u32,
+            1,
+            (
+                texture_copy_region.get_offset_u() as u32,
+                texture_copy_region.get_offset_v() as u32,
+            ),
+            (width, height),
+            &bytes,
+            bytes_per_row,
+            height as usize,
+        );
+    }
+}
+
+impl ICommandBuffer for CommandBufferNull {
+    type DeviceType = DeviceNull;
+    type BufferType = BufferNull;
+    type ColorTargetViewType = ColorTargetViewNull;
+    type DepthStencilViewType = DepthStencilViewNull;
+    type SamplerType = SamplerNull;
+    type ShaderType = ShaderNull;
+    type TextureType = TextureNull;
+    type TextureViewType = TextureViewNull;
+    type VertexStateType = VertexStateNull;
+
+    fn new(device: &Self::DeviceType, info: &CommandBufferInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn begin(&mut self) {
+        self.begin();
+    }
+
+    fn end(&mut self) {
+        self.end();
+    }
+
+    fn push_debug_group(&mut self, label: &str) {
+        self.push_debug_group(label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.pop_debug_group();
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.insert_debug_marker(label);
+    }
+
+    fn clear_color(
+        &mut self,
+        color_target_view: &mut Self::ColorTargetViewType,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        self.clear_color(
+            color_target_view,
+            red,
+            green,
+            blue,
+            alpha,
+            texture_array_range,
+        );
+    }
+
+    fn set_render_targets(
+        &mut self,
+        _color_target_views: &[&Self::ColorTargetViewType],
+        _depth_stencil_view: Option<&Self::DepthStencilViewType>,
+    ) {
+    }
+
+    fn set_shader(&mut self, shader: &Self::ShaderType) {
+        self.set_shader(shader);
+    }
+
+    fn set_sampler(&mut self, _index: i32, _sampler: &Self::SamplerType) {}
+
+    fn set_texture(&mut self, _index: i32, _texture_view: &Self::TextureViewType) {}
+
+    fn set_image(&mut self, _index: i32, _texture: &Self::TextureViewType) {}
+
+    fn set_constant_buffer(&mut self, _index: i32, _buffer: &Self::BufferType) {}
+
+    fn set_constant_buffer_range(
+        &mut self,
+        _index: i32,
+        _buffer: &Self::BufferType,
+        _offset: usize,
+        _size: usize,
+    ) {
+    }
+
+    fn set_unordered_access_buffer(&mut self, _index: i32, _buffer: &Self::BufferType) {}
+
+    fn set_vertex_buffer(&mut self, _index: i32, _buffer: &Self::BufferType) {}
+
+    fn set_vertex_buffer_range(
+        &mut self,
+        _index: i32,
+        _buffer: &Self::BufferType,
+        _offset: usize,
+        _size: usize,
+    ) {
+    }
+
+    fn set_vertex_state(&mut self, _vertex_state: &Self::VertexStateType) {}
+
+    fn set_scissor(&mut self, _scissor_state_info: &ScissorStateInfo) {}
+
+    fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        self.dispatch(count_x, count_y, count_z);
+    }
+
+    fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        self.draw_instanced(primitive_topology, vertex_count, vertex_offset, 1, 0);
+    }
+
+    fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.draw_instanced(
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        );
+    }
+
+    fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        self.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer,
+            index_count,
+            base_vertex,
+            1,
+            0,
+        );
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer,
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::{Arc, Mutex};
+
+use sjgfx_interface::{DepthStencilStateInfo, IDepthStencilView};
+
+use crate::{detail::TextureData, DeviceNull, TextureNull};
+
+pub struct DepthStencilViewNull {
+    // シェーダーを実行しないので読まないが、テクスチャーの中身は共有しておく
+    _data: Arc<Mutex<TextureData>>,
+}
+
+impl DepthStencilViewNull {
+    pub fn new(_device: &DeviceNull, _info: &DepthStencilStateInfo, texture: &TextureNull) -> Self {
+        Self {
+            _data: texture.clone_data(),
+        }
+    }
+}
+
+impl IDepthStencilView for DepthStencilViewNull {
+    type DeviceType = DeviceNull;
+    type TextureType = TextureNull;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &DepthStencilStateInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        Self::new(device, info, texture)
+    }
+}
@@ -0,0 +1,119 @@
+use sjgfx_interface::ImageFormat;
+
+// 塗りつぶしの色をフォーマットのテクセルに変換する
+// 整数のフォーマットは色の値をそのまま整数にする
+pub fn encode_color(format: &ImageFormat, color: [f32; 4]) -> Vec<u8> {
+    let unorm = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let srgb = |value: f32| unorm(convert_linear_to_srgb(value));
+    let snorm = |value: f32| (value.clamp(-1.0, 1.0) * 127.0).round() as i8 as u8;
+    let uint = |value: f32| value.clamp(0.0, 255.0) as u8;
+    let sint = |value: f32| value.clamp(-128.0, 127.0) as i8 as u8;
+    let [red, green, blue, alpha] = color;
+    match format {
+        ImageFormat::R8Unorm => vec![unorm(red)],
+        ImageFormat::R8Snorm => vec![snorm(red)],
+        ImageFormat::R8Uint => vec![uint(red)],
+        ImageFormat::R8Sint => vec![sint(red)],
+        ImageFormat::R32Uint => (red.max(0.0) as u32).to_ne_bytes().to_vec(),
+        ImageFormat::R32Sint => (red as i32).to_ne_bytes().to_vec(),
+        ImageFormat::R8G8B8A8Sint => color.iter().map(|x| sint(*x)).collect(),
+        ImageFormat::R8G8B8A8Uint => color.iter().map(|x| uint(*x)).collect(),
+        ImageFormat::R8G8B8A8Unorm => color.iter().map(|x| unorm(*x)).collect(),
+        ImageFormat::R8G8B8A8UnormSrgb => vec![srgb(red), srgb(green), srgb(blue), unorm(alpha)],
+        ImageFormat::B8G8R8A8Unorm => vec![unorm(blue), unorm(green), unorm(red), unorm(alpha)],
+        ImageFormat::B8G8R8A8UnormSrgb => {
+            vec![srgb(blue), srgb(green), srgb(red), unorm(alpha)]
+        }
+        ImageFormat::R8G8B8Unorm => vec![unorm(red), unorm(green), unorm(blue)],
+        ImageFormat::R16G16B16A16Float => color
+            .iter()
+            .flat_map(|x| convert_to_half(*x).to_ne_bytes())
+            .collect(),
+        ImageFormat::D32 => red.to_ne_bytes().to_vec(),
+    }
+}
+
+// ImageData の RGBA をスキャンバッファのフォーマットのテクセルに変換する
+pub fn encode_rgba8(format: &ImageFormat, rgba: [u8; 4]) -> Vec<u8> {
+    match format {
+        ImageFormat::R8G8B8A8Unorm | ImageFormat::R8G8B8A8UnormSrgb => rgba.to_vec(),
+        ImageFormat::B8G8R8A8Unorm | ImageFormat::B8G8R8A8UnormSrgb => {
+            vec![rgba[2], rgba[1], rgba[0], rgba[3]]
+        }
+        _ => panic!("{:?} is not a scan buffer format", format),
+    }
+}
+
+fn convert_linear_to_srgb(value: f32) -> f32 {
+    let value = value.clamp(0.0, 1.0);
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+// 単精度浮動小数点数を半精度に変換する。仮数部は切り捨てる
+fn convert_to_half(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    match exponent {
+        // 無限大と NaN
+        0xff => sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 },
+        _ => {
+            let exponent = exponent - 127 + 15;
+            if exponent >= 0x1f {
+                // 表せない大きさは無限大にする
+                sign | 0x7c00
+            } else if exponent <= 0 {
+                // 非正規化数。小さすぎる値は 0 にする
+                if exponent < -10 {
+                    return sign;
+                }
+                let mantissa = (mantissa | 0x80_0000) >> (1 - exponent);
+                sign | (mantissa >> 13) as u16
+            } else {
+                sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::ImageFormat;
+
+    use super::{convert_to_half, encode_color};
+
+    #[test]
+    fn encode_unorm() {
+        assert_eq!(
+            encode_color(&ImageFormat::R8G8B8A8Unorm, [1.0, 0.5, 0.0, 1.0]),
+            [255, 128, 0, 255]
+        );
+        assert_eq!(
+            encode_color(&ImageFormat::B8G8R8A8Unorm, [1.0, 0.5, 0.0, 1.0]),
+            [0, 128, 255, 255]
+        );
+    }
+
+    #[test]
+    fn encode_srgb() {
+        assert_eq!(
+            encode_color(&ImageFormat::R8G8B8A8UnormSrgb, [0.0, 0.5, 1.0, 0.5]),
+            [0, 188, 255, 128]
+        );
+    }
+
+    #[test]
+    fn half() {
+        assert_eq!(convert_to_half(0.0), 0x0000);
+        assert_eq!(convert_to_half(0.5), 0x3800);
+        assert_eq!(convert_to_half(1.0), 0x3c00);
+        assert_eq!(convert_to_half(-2.0), 0xc000);
+        assert_eq!(convert_to_half(f32::INFINITY), 0x7c00);
+        assert_eq!(convert_to_half(1.0e6), 0x7c00);
+    }
+}
@@ -0,0 +1,7 @@
+mod color;
+mod offscreen_scan_buffers;
+mod texture_data;
+
+pub use color::{encode_color, encode_rgba8};
+pub use offscreen_scan_buffers::OffscreenScanBuffers;
+pub use texture_data::TextureData;
@@ -0,0 +1,62 @@
+use sjgfx_interface::{ImageData, ImageFormat};
+
+use super::TextureData;
+use crate::ColorTargetViewNull;
+
+// 表示先のウィンドウの代わりにするスキャンバッファのリング
+// 表示されたフレームは次に取得されるまで読み戻せる
+pub struct OffscreenScanBuffers {
+    format: ImageFormat,
+    count: usize,
+    views: Vec<ColorTargetViewNull>,
+    next_index: usize,
+    presented_index: Option<usize>,
+}
+
+impl OffscreenScanBuffers {
+    pub fn new(format: ImageFormat, count: usize) -> Self {
+        assert!(count > 0);
+        Self {
+            format,
+            count,
+            views: Vec::new(),
+            next_index: 0,
+            presented_index: None,
+        }
+    }
+
+    // 大きさが変わると中身は失われる
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.views = (0..self.count)
+            .map(|_| {
+                let data = TextureData::new(self.format.clone(), width, height, 1, 1);
+                ColorTargetViewNull::new_direct(data)
+            })
+            .collect();
+        self.next_index = 0;
+        self.presented_index = None;
+    }
+
+    pub fn acquire(&mut self) -> usize {
+        assert!(
+            !self.views.is_empty(),
+            "offscreen scan buffers are not sized"
+        );
+        let index = self.next_index;
+        self.next_index = (self.next_index + 1) % self.views.len();
+        index
+    }
+
+    pub fn get_view(&self, index: usize) -> &ColorTargetViewNull {
+        &self.views[index]
+    }
+
+    pub fn present(&mut self, index: usize) {
+        self.presented_index = Some(index);
+    }
+
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        let index = self.presented_index?;
+        Some(self.views[index].read_image())
+    }
+}
@@ -0,0 +1,238 @@
+use sjgfx_interface::{compute_scaled_rect, ImageData, ImageFormat, ScalingMode};
+
+// CPU のメモリーに置いたテクスチャーの中身
+// ミップレベルごとに、配列要素を順に行を詰めて並べる
+pub struct TextureData {
+    format: ImageFormat,
+    width: u32,
+    height: u32,
+    array_layers: u32,
+    mips: Vec<Vec<u8>>,
+}
+
+impl TextureData {
+    pub fn new(
+        format: ImageFormat,
+        width: u32,
+        height: u32,
+        mip_count: u32,
+        array_layers: u32,
+    ) -> Self {
+        assert!(width > 0 && height > 0 && mip_count > 0 && array_layers > 0);
+        let bytes_per_pixel = format.get_bytes_per_pixel();
+        let mips = (0..mip_count)
+            .map(|mip_level| {
+                let (mip_width, mip_height) = Self::compute_mip_size(width, height, mip_level);
+                vec![0; (mip_width * mip_height * array_layers) as usize * bytes_per_pixel]
+            })
+            .collect();
+
+        Self {
+            format,
+            width,
+            height,
+            array_layers,
+            mips,
+        }
+    }
+
+    pub fn get_format(&self) -> &ImageFormat {
+        &self.format
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get_mip_count(&self) -> u32 {
+        self.mips.len() as u32
+    }
+
+    pub fn get_mip_size(&self, mip_level: u32) -> (u32, u32) {
+        Self::compute_mip_size(self.width, self.height, mip_level)
+    }
+
+    // data は bytes_per_row バイトごとに 1 行、rows_per_image 行ごとに 1 配列要素が並んでいるものとする
+    #[allow(clippy::too_many_arguments)]
+    pub fn write(
+        &mut self,
+        mip_level: u32,
+        base_array_layer: u32,
+        array_length: u32,
+        offset: (u32, u32),
+        size: (u32, u32),
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.validate_region(mip_level, base_array_layer, array_length, offset, size);
+        let row_size = size.0 as usize * self.format.get_bytes_per_pixel();
+        for layer in 0..array_length {
+            for row in 0..size.1 {
+                let src_start = bytes_per_row * (rows_per_image * layer as usize + row as usize);
+                let dst_start = self.get_texel_offset(
+                    mip_level,
+                    base_array_layer + layer,
+                    offset.0,
+                    offset.1 + row,
+                );
+                self.mips[mip_level as usize][dst_start..(dst_start + row_size)]
+                    .copy_from_slice(&data[src_start..(src_start + row_size)]);
+            }
+        }
+    }
+
+    // 行を詰めて読み出す
+    pub fn read(
+        &self,
+        mip_level: u32,
+        array_layer: u32,
+        offset: (u32, u32),
+        size: (u32, u32),
+    ) -> Vec<u8> {
+        self.validate_region(mip_level, array_layer, 1, offset, size);
+        let row_size = size.0 as usize * self.format.get_bytes_per_pixel();
+        let mut result = Vec::with_capacity(row_size * size.1 as usize);
+        for row in 0..size.1 {
+            let start = self.get_texel_offset(mip_level, array_layer, offset.0, offset.1 + row);
+            result.extend_from_slice(&self.mips[mip_level as usize][start..(start + row_size)]);
+        }
+        result
+    }
+
+    // ミップレベルの指定した配列要素をすべて texel で埋める
+    pub fn fill(&mut self, mip_level: u32, base_array_layer: u32, array_length: u32, texel: &[u8]) {
+        assert_eq!(texel.len(), self.format.get_bytes_per_pixel());
+        let size = self.get_mip_size(mip_level);
+        self.validate_region(mip_level, base_array_layer, array_length, (0, 0), size);
+        let start = self.get_texel_offset(mip_level, base_array_layer, 0, 0);
+        let end = self.get_texel_offset(mip_level, base_array_layer + array_length, 0, 0);
+        for dst in self.mips[mip_level as usize][start..end].chunks_exact_mut(texel.len()) {
+            dst.copy_from_slice(texel);
+        }
+    }
+
+    // ミップレベル 0、配列要素 0 を RGBA に変換する
+    pub fn read_image(&self) -> ImageData {
+        let texels = self.read(0, 0, (0, 0), (self.width, self.height));
+        ImageData::from_texels(self.width, self.height, self.format.clone(), &texels)
+    }
+
+    // src のミップレベル 0 を拡大縮小して書き込む。余白は黒にする
+    // CPU で処理するので最近傍でしか拡大縮小しない
+    pub fn blit(&mut self, src: &TextureData, scaling_mode: ScalingMode) {
+        let black = super::encode_color(&self.format, [0.0, 0.0, 0.0, 1.0]);
+        self.fill(0, 0, 1, &black);
+
+        let (x, y, width, height) = compute_scaled_rect(
+            (src.width, src.height),
+            (self.width, self.height),
+            scaling_mode,
+        );
+        let image = src.read_image();
+        let left = (x.max(0.0) as u32).min(self.width);
+        let top = (y.max(0.0) as u32).min(self.height);
+        let right = ((x + width).round().max(0.0) as u32).min(self.width);
+        let bottom = ((y + height).round().max(0.0) as u32).min(self.height);
+        for dst_y in top..bottom {
+            for dst_x in left..right {
+                let src_x = (((dst_x as f32 + 0.5 - x) / width * src.width as f32) as u32)
+                    .min(src.width - 1);
+                let src_y = (((dst_y as f32 + 0.5 - y) / height * src.height as f32) as u32)
+                    .min(src.height - 1);
+                let texel = super::encode_rgba8(&self.format, image.get_pixel(src_x, src_y));
+                let start = self.get_texel_offset(0, 0, dst_x, dst_y);
+                self.mips[0][start..(start + texel.len())].copy_from_slice(&texel);
+            }
+        }
+    }
+
+    fn validate_region(
+        &self,
+        mip_level: u32,
+        base_array_layer: u32,
+        array_length: u32,
+        offset: (u32, u32),
+        size: (u32, u32),
+    ) {
+        assert!(
+            mip_level < self.get_mip_count(),
+            "texture region is out of mip level {}",
+            mip_level
+        );
+        let (mip_width, mip_height) = self.get_mip_size(mip_level);
+        assert!(
+            offset.0 + size.0 <= mip_width && offset.1 + size.1 <= mip_height,
+            "texture region is out of mip level {}",
+            mip_level
+        );
+        assert!(
+            base_array_layer + array_length <= self.array_layers,
+            "texture region is out of array layers {}",
+            self.array_layers
+        );
+    }
+
+    fn get_texel_offset(&self, mip_level: u32, array_layer: u32, x: u32, y: u32) -> usize {
+        let (mip_width, mip_height) = self.get_mip_size(mip_level);
+        let texel_index = (array_layer * mip_height + y) * mip_width + x;
+        texel_index as usize * self.format.get_bytes_per_pixel()
+    }
+
+    fn compute_mip_size(width: u32, height: u32, mip_level: u32) -> (u32, u32) {
+        ((width >> mip_level).max(1), (height >> mip_level).max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::{ImageFormat, ScalingMode};
+
+    use super::TextureData;
+
+    #[test]
+    fn write_read() {
+        let mut texture_data = TextureData::new(ImageFormat::R8Unorm, 4, 4, 2, 2);
+        texture_data.write(1, 1, 1, (1, 0), (1, 2), &[1, 0, 2, 0], 2, 2);
+        assert_eq!(texture_data.read(1, 1, (0, 0), (2, 2)), [0, 1, 0, 2]);
+        assert_eq!(texture_data.read(1, 0, (0, 0), (2, 2)), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill() {
+        let mut texture_data = TextureData::new(ImageFormat::R8G8B8A8Unorm, 2, 1, 1, 3);
+        texture_data.fill(0, 1, 2, &[1, 2, 3, 4]);
+        assert_eq!(texture_data.read(0, 0, (0, 0), (2, 1)), [0; 8]);
+        assert_eq!(
+            texture_data.read(0, 2, (0, 0), (2, 1)),
+            [1, 2, 3, 4, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn blit_letterbox() {
+        let mut src = TextureData::new(ImageFormat::R8G8B8A8Unorm, 1, 1, 1, 1);
+        src.fill(0, 0, 1, &[255, 0, 0, 255]);
+        let mut dst = TextureData::new(ImageFormat::B8G8R8A8Unorm, 3, 1, 1, 1);
+        dst.blit(&src, ScalingMode::Letterbox);
+
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 255,
+            0, 0, 255, 255,
+            0, 0, 0, 255,
+        ];
+        assert_eq!(dst.read(0, 0, (0, 0), (3, 1)), expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_out_of_mip() {
+        let mut texture_data = TextureData::new(ImageFormat::R8Unorm, 4, 4, 1, 1);
+        texture_data.write(0, 0, 1, (2, 0), (4, 1), &[0; 4], 4, 1);
+    }
+}
@@ -0,0 +1,115 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    AdapterInfo, AdapterType, BackendMask, DeviceCapabilities, DeviceFeatures, DeviceInfo, IDevice,
+    ImageFormat, ImageFormatCapability, ImageFormatUsage,
+};
+
+// GPU を使わないデバイス。リソースはすべて CPU のメモリーに置く
+pub struct DeviceNull {
+    features: DeviceFeatures,
+}
+
+impl DeviceNull {
+    // 要求された機能はすべてサポートしているものとして扱う
+    pub fn new(info: &DeviceInfo) -> Self {
+        // アダプターは 1 つしかない
+        if let Some(adapter_index) = info.get_adapter_index() {
+            assert_eq!(adapter_index, 0, "null device has only one adapter");
+        }
+
+        Self {
+            features: info.get_optional_features(),
+        }
+    }
+
+    // 表示先がないのでウィンドウは使わない
+    pub fn new_as_graphics<W>(info: &DeviceInfo, _window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new(info)
+    }
+
+    // どのバックエンドの指定でも同じアダプターを返す
+    pub fn enumerate_adapters(_info: &DeviceInfo) -> Vec<AdapterInfo> {
+        vec![Self::create_adapter_info()]
+    }
+
+    pub fn get_adapter_info(&self) -> AdapterInfo {
+        Self::create_adapter_info()
+    }
+
+    pub fn get_capabilities(&self) -> DeviceCapabilities {
+        let image_format_capabilities = ImageFormat::ALL.iter().map(|format| {
+            let usage = match format {
+                ImageFormat::D32 => ImageFormatUsage::SAMPLED | ImageFormatUsage::RENDER_TARGET,
+                ImageFormat::R8Uint
+                | ImageFormat::R8Sint
+                | ImageFormat::R32Uint
+                | ImageFormat::R32Sint
+                | ImageFormat::R8G8B8A8Uint
+                | ImageFormat::R8G8B8A8Sint => {
+                    ImageFormatUsage::SAMPLED
+                        | ImageFormatUsage::STORAGE
+                        | ImageFormatUsage::RENDER_TARGET
+                }
+                _ => {
+                    ImageFormatUsage::SAMPLED
+                        | ImageFormatUsage::STORAGE
+                        | ImageFormatUsage::RENDER_TARGET
+                        | ImageFormatUsage::BLENDABLE
+                }
+            };
+            ImageFormatCapability::new(format.clone(), usage)
+        });
+
+        DeviceCapabilities::new()
+            .set_max_texture_size(8192)
+            .set_max_texture_array_layers(256)
+            .set_max_constant_buffers_per_stage(12)
+            .set_max_unordered_access_buffers_per_stage(8)
+            .set_max_textures_per_stage(16)
+            .set_max_images_per_stage(4)
+            .set_max_samplers_per_stage(16)
+            .set_max_compute_workgroup_size([256, 256, 64])
+            .set_max_compute_invocations_per_workgroup(256)
+            .set_max_compute_workgroups_per_dimension(65535)
+            .set_constant_buffer_offset_alignment(256)
+            .set_unordered_access_buffer_offset_alignment(256)
+            .set_image_format_capabilities(image_format_capabilities)
+            .set_features(self.features)
+    }
+
+    fn create_adapter_info() -> AdapterInfo {
+        AdapterInfo::new()
+            .set_index(0)
+            .set_name("Null")
+            .set_adapter_type(AdapterType::Cpu)
+            .set_backend(BackendMask::empty())
+    }
+}
+
+impl IDevice for DeviceNull {
+    fn new(info: &DeviceInfo) -> Self {
+        Self::new(info)
+    }
+
+    fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_as_graphics(info, raw_handle)
+    }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        Self::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.get_adapter_info()
+    }
+}
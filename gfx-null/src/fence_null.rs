@@ -0,0 +1,67 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use sjgfx_interface::{FenceInfo, IFence};
+
+use crate::DeviceNull;
+
+pub struct FenceNull {
+    is_signaled: Arc<AtomicBool>,
+}
+
+impl FenceNull {
+    pub fn new(_device: &DeviceNull, info: &FenceInfo) -> Self {
+        Self {
+            is_signaled: Arc::new(AtomicBool::new(info.is_signaled())),
+        }
+    }
+
+    // キューはサブミットしたコマンドをその場で実行するので待つことはない
+    // セマフォ待ちで実行されていないときは false を返す
+    pub fn wait(&mut self, _timeout: Option<Duration>) -> bool {
+        self.is_signaled()
+    }
+
+    pub fn is_signaled(&self) -> bool {
+        self.is_signaled.load(Ordering::Acquire)
+    }
+
+    pub fn reset(&mut self) {
+        self.is_signaled.store(false, Ordering::Release);
+    }
+
+    // サブミットに渡すフェンスを未シグナルにして返す
+    pub(crate) fn prepare_submit(&mut self) -> Arc<AtomicBool> {
+        self.reset();
+        self.is_signaled.clone()
+    }
+
+    pub(crate) fn signal(&mut self) {
+        self.is_signaled.store(true, Ordering::Release);
+    }
+}
+
+impl IFence for FenceNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        self.wait(timeout)
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+}
@@ -0,0 +1,30 @@
+mod buffer_null;
+mod color_target_view_null;
+mod command_buffer_null;
+mod depth_stencil_view_null;
+mod detail;
+mod device_null;
+mod fence_null;
+mod queue_null;
+mod sampler_null;
+mod semaphore_null;
+mod shader_null;
+mod swap_chain_null;
+mod texture_null;
+mod texture_view_null;
+mod vertex_state_null;
+
+pub use buffer_null::BufferNull;
+pub use color_target_view_null::ColorTargetViewNull;
+pub use command_buffer_null::{CommandBufferNull, CommandNull};
+pub use depth_stencil_view_null::DepthStencilViewNull;
+pub use device_null::DeviceNull;
+pub use fence_null::FenceNull;
+pub use queue_null::QueueNull;
+pub use sampler_null::SamplerNull;
+pub use semaphore_null::SemaphoreNull;
+pub use shader_null::ShaderNull;
+pub use swap_chain_null::SwapChainNull;
+pub use texture_null::TextureNull;
+pub use texture_view_null::TextureViewNull;
+pub use vertex_state_null::VertexStateNull;
@@ -0,0 +1,214 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+
+use crate::{
+    semaphore_null::SemaphoreData, CommandBufferNull, CommandNull, DeviceNull, FenceNull,
+    SemaphoreNull, SwapChainNull,
+};
+
+// サブミットされたコマンドと、実行の前後に待つセマフォとシグナルするセマフォ
+struct Submission {
+    commands: Vec<CommandNull>,
+    wait_semaphores: Vec<(Arc<SemaphoreData>, u64)>,
+    signal_semaphores: Vec<(Arc<SemaphoreData>, u64)>,
+    fence: Option<Arc<AtomicBool>>,
+}
+
+pub struct QueueNull {
+    queue_type: QueueType,
+
+    // flush されるまでサブミットせずにためておくコマンド
+    pending_commands: Vec<CommandNull>,
+
+    // セマフォの値に到達していないので実行できていないサブミット。サブミットした順に実行する
+    submissions: VecDeque<Submission>,
+
+    // 実行したコマンドの記録
+    executed_commands: Vec<CommandNull>,
+}
+
+impl QueueNull {
+    pub fn new(_device: &DeviceNull, info: &QueueInfo) -> Self {
+        Self {
+            queue_type: info.get_queue_type().clone(),
+            pending_commands: Vec::new(),
+            submissions: VecDeque::new(),
+            executed_commands: Vec::new(),
+        }
+    }
+
+    // これまでに実行したコマンドを実行した順に返す
+    pub fn get_executed_commands(&self) -> &[CommandNull] {
+        &self.executed_commands
+    }
+
+    pub fn clear_executed_commands(&mut self) {
+        self.executed_commands.clear();
+    }
+
+    pub fn execute(&mut self, command_buffer: &CommandBufferNull) {
+        self.pending_commands
+            .extend_from_slice(command_buffer.get_commands());
+    }
+
+    pub fn execute_many(&mut self, command_buffers: &[&CommandBufferNull]) {
+        for command_buffer in command_buffers {
+            self.execute(command_buffer);
+        }
+        self.flush();
+    }
+
+    pub fn execute_with_fence(
+        &mut self,
+        command_buffer: &CommandBufferNull,
+        fence: &mut FenceNull,
+    ) {
+        self.execute(command_buffer);
+        self.submit_pending_commands(Vec::new(), Vec::new(), Some(fence.prepare_submit()));
+    }
+
+    pub fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &CommandBufferNull,
+        wait_semaphores: &[(&SemaphoreNull, u64)],
+        signal_semaphores: &[(&SemaphoreNull, u64)],
+    ) {
+        let wait_semaphores = wait_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.clone_data(), *value))
+            .collect();
+        let signal_semaphores = signal_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.clone_data(), *value))
+            .collect();
+
+        self.execute(command_buffer);
+        self.submit_pending_commands(wait_semaphores, signal_semaphores, None);
+    }
+
+    pub fn get_queue_type(&self) -> &QueueType {
+        &self.queue_type
+    }
+
+    pub fn present(&mut self, swap_chain: &mut SwapChainNull) {
+        self.flush();
+        swap_chain.present();
+    }
+
+    // CPU からセマフォがシグナルされていれば、待っていたサブミットもここで実行される
+    pub fn flush(&mut self) {
+        if !self.pending_commands.is_empty() {
+            self.submit_pending_commands(Vec::new(), Vec::new(), None);
+        } else {
+            self.execute_ready_submissions();
+        }
+    }
+
+    pub fn sync(&mut self) {
+        self.flush();
+
+        // GPU と違って待っていても誰もセマフォを進めないので、完了しないサブミットはエラーにする
+        assert!(
+            self.submissions.is_empty(),
+            "{} submission(s) are waiting for semaphores that are never signaled",
+            self.submissions.len()
+        );
+    }
+
+    fn submit_pending_commands(
+        &mut self,
+        wait_semaphores: Vec<(Arc<SemaphoreData>, u64)>,
+        signal_semaphores: Vec<(Arc<SemaphoreData>, u64)>,
+        fence: Option<Arc<AtomicBool>>,
+    ) {
+        let commands = std::mem::take(&mut self.pending_commands);
+        self.submissions.push_back(Submission {
+            commands,
+            wait_semaphores,
+            signal_semaphores,
+            fence,
+        });
+        self.execute_ready_submissions();
+    }
+
+    fn execute_ready_submissions(&mut self) {
+        while let Some(submission) = self.submissions.front() {
+            let is_ready = submission
+                .wait_semaphores
+                .iter()
+                .all(|(semaphore, value)| semaphore.get_value() >= *value);
+            if !is_ready {
+                break;
+            }
+
+            let submission = self.submissions.pop_front().unwrap();
+            self.executed_commands.extend(submission.commands);
+            for (semaphore, value) in submission.signal_semaphores {
+                semaphore.signal(value);
+            }
+            if let Some(fence) = submission.fence {
+                fence.store(true, Ordering::Release);
+            }
+        }
+    }
+}
+
+impl IQueue for QueueNull {
+    type DeviceType = DeviceNull;
+    type CommandBufferType = CommandBufferNull;
+    type FenceType = FenceNull;
+    type SemaphoreType = SemaphoreNull;
+    type SwapChainType = SwapChainNull;
+
+    fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn execute(&mut self, command_buffer: &Self::CommandBufferType) {
+        self.execute(command_buffer);
+    }
+
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        self.execute_many(command_buffers);
+    }
+
+    fn execute_with_fence(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
+    ) {
+        self.execute_with_fence(command_buffer, fence);
+    }
+
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        self.execute_with_semaphores(command_buffer, wait_semaphores, signal_semaphores);
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.get_queue_type()
+    }
+
+    fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
+        self.present(swap_chain);
+    }
+
+    fn flush(&mut self) {
+        self.flush();
+    }
+
+    fn sync(&mut self) {
+        self.sync();
+    }
+}
@@ -0,0 +1,19 @@
+use sjgfx_interface::{ISampler, SamplerInfo};
+
+use crate::DeviceNull;
+
+pub struct SamplerNull;
+
+impl SamplerNull {
+    pub fn new(_device: &DeviceNull, _info: &SamplerInfo) -> Self {
+        Self
+    }
+}
+
+impl ISampler for SamplerNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &mut Self::DeviceType, info: &SamplerInfo) -> Self {
+        Self::new(device, info)
+    }
+}
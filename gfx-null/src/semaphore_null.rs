@@ -0,0 +1,99 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+
+use crate::DeviceNull;
+
+pub(crate) struct SemaphoreData {
+    value: Mutex<u64>,
+    condvar: Condvar,
+}
+
+impl SemaphoreData {
+    pub fn get_value(&self) -> u64 {
+        *self.value.lock().unwrap()
+    }
+
+    // タイムラインの値は戻せないので、現在の値以下のシグナルは無視する
+    pub fn signal(&self, value: u64) {
+        let mut current = self.value.lock().unwrap();
+        if value <= *current {
+            return;
+        }
+        *current = value;
+        self.condvar.notify_all();
+    }
+}
+
+// CPU だけで動くタイムラインセマフォ。別スレッドからのシグナルも待てる
+pub struct SemaphoreNull {
+    data: Arc<SemaphoreData>,
+}
+
+impl SemaphoreNull {
+    pub fn new(_device: &DeviceNull, info: &SemaphoreInfo) -> Self {
+        Self {
+            data: Arc::new(SemaphoreData {
+                value: Mutex::new(info.get_initial_value()),
+                condvar: Condvar::new(),
+            }),
+        }
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.data.get_value()
+    }
+
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut current = self.data.value.lock().unwrap();
+        while *current < value {
+            current = match deadline {
+                None => self.data.condvar.wait(current).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if deadline <= now {
+                        return false;
+                    }
+                    self.data
+                        .condvar
+                        .wait_timeout(current, deadline - now)
+                        .unwrap()
+                        .0
+                }
+            };
+        }
+        true
+    }
+
+    pub fn signal(&self, value: u64) {
+        self.data.signal(value);
+    }
+
+    pub(crate) fn clone_data(&self) -> Arc<SemaphoreData> {
+        self.data.clone()
+    }
+}
+
+impl ISemaphore for SemaphoreNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn get_value(&self) -> u64 {
+        self.get_value()
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.wait(value, timeout)
+    }
+
+    fn signal(&self, value: u64) {
+        self.signal(value)
+    }
+}
@@ -0,0 +1,34 @@
+use sjgfx_interface::{IShader, ShaderInfo};
+
+use crate::DeviceNull;
+
+// シェーダーは実行しないので、バイナリは解釈せずにどちらのパイプラインかだけ覚えておく
+pub struct ShaderNull {
+    is_compute: bool,
+}
+
+impl ShaderNull {
+    pub fn new(_device: &DeviceNull, info: &ShaderInfo) -> Self {
+        let is_compute = info.get_compute_shader_binary().is_some();
+        if !is_compute {
+            assert!(
+                info.get_vertex_shader_binary().is_some()
+                    && info.get_pixel_shader_binary().is_some(),
+                "graphics shader needs both vertex and pixel shader binaries"
+            );
+        }
+        Self { is_compute }
+    }
+
+    pub fn is_compute(&self) -> bool {
+        self.is_compute
+    }
+}
+
+impl IShader for ShaderNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &mut Self::DeviceType, info: &ShaderInfo) -> Self {
+        Self::new(device, info)
+    }
+}
@@ -0,0 +1,341 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    select_format, CompositeAlphaMode, IDisplayEventListener, ISwapChain, ImageData, ImageFormat,
+    OutputTransform, PresentMode, ScalingMode, SwapChainInfo, SwapChainStatus,
+};
+
+use crate::{
+    detail::{OffscreenScanBuffers, TextureData},
+    ColorTargetViewNull, DeviceNull, FenceNull, SemaphoreNull,
+};
+
+// ウィンドウには表示せず、常にオフスクリーンのスキャンバッファに表示する
+pub struct SwapChainNull {
+    offscreen_scan_buffers: OffscreenScanBuffers,
+    format: ImageFormat,
+    present_mode: PresentMode,
+    alpha_mode: CompositeAlphaMode,
+    frame_latency: u32,
+    is_direct_rendering_enabled: bool,
+    width: u32,
+    height: u32,
+    render_scale: f32,
+    render_size: Option<(u32, u32)>,
+    render_format: ImageFormat,
+    scaling_mode: ScalingMode,
+    status: SwapChainStatus,
+    next_offscreen_index: Option<usize>,
+
+    // 直接描画するときのスキャンバッファのビュー
+    scan_buffer_view: Option<ColorTargetViewNull>,
+
+    // 直接描画するときにスキャンバッファを取得できなかったら代わりに描画させる
+    fallback_view: Option<ColorTargetViewNull>,
+
+    // 直接描画しないときの内部のカラーターゲット
+    render_target_view: Option<ColorTargetViewNull>,
+
+    // 出力の変換は未対応なので保持するだけ
+    output_transform: OutputTransform,
+}
+
+impl SwapChainNull {
+    pub fn new(_device: &DeviceNull, info: &SwapChainInfo) -> Self {
+        let supported_formats = [
+            ImageFormat::R8G8B8A8Unorm,
+            ImageFormat::R8G8B8A8UnormSrgb,
+            ImageFormat::B8G8R8A8Unorm,
+            ImageFormat::B8G8R8A8UnormSrgb,
+        ];
+        let format = select_format(info.get_preferred_formats(), &supported_formats).unwrap();
+
+        // 表示先がないので表示方法とアルファは要求されたものをそのまま使う
+        let alpha_mode = match info.get_alpha_mode() {
+            CompositeAlphaMode::Auto => CompositeAlphaMode::Opaque,
+            alpha_mode => alpha_mode,
+        };
+
+        // 表示待ちのフレームに加えて描画中のものが 1 枚必要
+        let offscreen_scan_buffers =
+            OffscreenScanBuffers::new(format.clone(), info.get_frame_latency() as usize + 1);
+
+        let mut result = Self {
+            offscreen_scan_buffers,
+            format,
+            present_mode: info.get_present_mode(),
+            alpha_mode,
+            frame_latency: info.get_frame_latency(),
+            is_direct_rendering_enabled: info.is_direct_rendering_enabled(),
+            width: 0,
+            height: 0,
+            render_scale: info.get_render_scale(),
+            render_size: info.get_render_size(),
+            render_format: info.get_render_format().clone(),
+            scaling_mode: info.get_scaling_mode(),
+            status: SwapChainStatus::Optimal,
+            next_offscreen_index: None,
+            scan_buffer_view: None,
+            fallback_view: None,
+            render_target_view: None,
+            output_transform: info.get_output_transform().clone(),
+        };
+
+        result.on_resized(info.get_width(), info.get_height());
+        result
+    }
+
+    // ウィンドウには表示しないので new と同じ
+    pub fn new_with_handle<W>(device: &DeviceNull, info: &SwapChainInfo, _window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new(device, info)
+    }
+
+    // コマンドはサブミットしたときに実行済みなので、フェンスはすぐにシグナルされる
+    pub fn acquire_next_scan_buffer_view(
+        &mut self,
+        _semaphore: Option<&mut SemaphoreNull>,
+        fence: Option<&mut FenceNull>,
+    ) -> &mut ColorTargetViewNull {
+        if let Some(fence) = fence {
+            fence.signal();
+        }
+
+        if self.is_direct_rendering_enabled {
+            self.acquire_offscreen_view()
+        } else if self.width == 0 || self.height == 0 {
+            self.status = SwapChainStatus::Skipped;
+            self.render_target_view.as_mut().unwrap()
+        } else {
+            self.status = SwapChainStatus::Optimal;
+            self.render_target_view.as_mut().unwrap()
+        }
+    }
+
+    fn acquire_offscreen_view(&mut self) -> &mut ColorTargetViewNull {
+        // 表示する前に再度呼ばれたときは同じスキャンバッファを返す
+        if self.scan_buffer_view.is_none() {
+            // 最小化されているときはサイズが 0 で表示できない
+            if self.width == 0 || self.height == 0 {
+                self.status = SwapChainStatus::Skipped;
+                return self.fallback_view.get_or_insert_with(|| {
+                    ColorTargetViewNull::new_direct(TextureData::new(
+                        self.format.clone(),
+                        1,
+                        1,
+                        1,
+                        1,
+                    ))
+                });
+            }
+
+            self.status = SwapChainStatus::Optimal;
+            let index = self.offscreen_scan_buffers.acquire();
+            self.scan_buffer_view = Some(self.offscreen_scan_buffers.get_view(index).clone());
+            self.next_offscreen_index = Some(index);
+        }
+
+        self.scan_buffer_view.as_mut().unwrap()
+    }
+
+    pub fn present(&mut self) {
+        if self.is_direct_rendering_enabled {
+            // 取得できずに代わりのカラーターゲットに描画したフレームは表示しない
+            self.scan_buffer_view = None;
+            if let Some(index) = self.next_offscreen_index.take() {
+                self.offscreen_scan_buffers.present(index);
+            }
+            return;
+        }
+
+        // 最小化されているときはサイズが 0 で表示できない
+        if self.width == 0 || self.height == 0 {
+            self.status = SwapChainStatus::Skipped;
+            return;
+        }
+        self.status = SwapChainStatus::Optimal;
+
+        // カラーターゲットの内容をスキャンバッファに拡大縮小して転送する
+        let index = self.offscreen_scan_buffers.acquire();
+        let src = self.render_target_view.as_ref().unwrap().clone_data();
+        let dst = self.offscreen_scan_buffers.get_view(index).clone_data();
+        dst.lock()
+            .unwrap()
+            .blit(&src.lock().unwrap(), self.scaling_mode);
+        self.offscreen_scan_buffers.present(index);
+    }
+
+    pub fn get_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn get_format(&self) -> ImageFormat {
+        self.format.clone()
+    }
+
+    pub fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.alpha_mode
+    }
+
+    pub fn get_frame_latency(&self) -> u32 {
+        self.frame_latency
+    }
+
+    pub fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled
+    }
+
+    pub fn get_status(&self) -> SwapChainStatus {
+        self.status
+    }
+
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        self.offscreen_scan_buffers.read_presented_image()
+    }
+
+    pub fn get_render_width(&self) -> u32 {
+        match &self.render_target_view {
+            Some(render_target_view) => render_target_view.get_width(),
+            None => self.width,
+        }
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        match &self.render_target_view {
+            Some(render_target_view) => render_target_view.get_height(),
+            None => self.height,
+        }
+    }
+
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        assert!(render_scale > 0.0);
+        self.render_scale = render_scale;
+        self.update_render_size();
+    }
+
+    // 露出やトーンマップは CPU では反映しない
+    pub fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.output_transform = output_transform.clone();
+    }
+
+    fn update_render_size(&mut self) {
+        if self.is_direct_rendering_enabled {
+            return;
+        }
+
+        // 最小化されているときは元に戻るまで作りなおさない
+        // ただし一度も作っていなければ取得できるように最小の大きさで作っておく
+        let (width, height) = if self.width == 0 || self.height == 0 {
+            if self.render_target_view.is_some() {
+                return;
+            }
+            self.render_size.unwrap_or((1, 1))
+        } else {
+            self.render_size.unwrap_or_else(|| {
+                let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+                (scale(self.width), scale(self.height))
+            })
+        };
+
+        let is_same_size = self
+            .render_target_view
+            .as_ref()
+            .is_some_and(|view| view.get_width() == width && view.get_height() == height);
+        if !is_same_size {
+            let data = TextureData::new(self.render_format.clone(), width, height, 1, 1);
+            self.render_target_view = Some(ColorTargetViewNull::new_direct(data));
+        }
+    }
+}
+
+impl ISwapChain for SwapChainNull {
+    type ColorTargetViewType = ColorTargetViewNull;
+    type DeviceType = DeviceNull;
+    type SemaphoreType = SemaphoreNull;
+    type FenceType = FenceNull;
+
+    fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(device, info, raw_handle)
+    }
+
+    fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
+    ) -> &mut Self::ColorTargetViewType {
+        self.acquire_next_scan_buffer_view(semaphore, fence)
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.set_output_transform(output_transform);
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        self.read_presented_image()
+    }
+}
+
+impl IDisplayEventListener for SwapChainNull {
+    fn on_resized(&mut self, width: u32, height: u32) {
+        // 取得済みのスキャンバッファは古いサイズなので捨てる
+        self.scan_buffer_view = None;
+        self.next_offscreen_index = None;
+
+        // 最小化されるとサイズが 0 になるので、元に戻るまで設定しない
+        self.width = width;
+        self.height = height;
+        if width > 0 && height > 0 {
+            self.offscreen_scan_buffers.resize(width, height);
+        }
+
+        self.update_render_size();
+    }
+}
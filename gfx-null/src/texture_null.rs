@@ -0,0 +1,126 @@
+use std::sync::{Arc, Mutex};
+
+use sjgfx_interface::{
+    validate_texture_write, GpuAccess, ITexture, ImageData, TextureCopyRegion, TextureInfo,
+};
+
+use crate::{detail::TextureData, DeviceNull};
+
+pub struct TextureNull {
+    data: Arc<Mutex<TextureData>>,
+    gpu_access: GpuAccess,
+}
+
+impl TextureNull {
+    pub fn new(_device: &DeviceNull, info: &TextureInfo) -> Self {
+        let data = TextureData::new(
+            info.get_image_format().clone(),
+            info.get_width() as u32,
+            info.get_height() as u32,
+            info.get_mip_count() as u32,
+            info.get_depth() as u32,
+        );
+        Self {
+            data: Arc::new(Mutex::new(data)),
+            gpu_access: *info.get_gpu_access_flags(),
+        }
+    }
+
+    // ミップレベル 0 のすべての配列要素を詰めたデータで初期化する
+    pub fn new_with_data(device: &DeviceNull, info: &TextureInfo, data: &[u8]) -> Self {
+        let texture = Self::new(device, info);
+        let width = info.get_width();
+        let height = info.get_height();
+        let region = TextureCopyRegion::new()
+            .set_width(width)
+            .set_height(height)
+            .set_array_length(info.get_depth());
+        let bytes_per_row = width as usize * info.get_image_format().get_bytes_per_pixel();
+        texture.write_impl(&region, data, bytes_per_row, height as usize);
+        texture
+    }
+
+    pub fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        assert!(
+            self.gpu_access.contains(GpuAccess::WRITE),
+            "texture must be created with GpuAccess::WRITE to be written"
+        );
+        self.write_impl(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn write_impl(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        let mut texture_data = self.data.lock().unwrap();
+        let required_size = validate_texture_write(
+            region,
+            texture_data.get_format(),
+            data.len(),
+            bytes_per_row,
+            rows_per_image,
+        );
+        if required_size == 0 {
+            return;
+        }
+
+        let subresource = region.get_texture_subresource();
+        texture_data.write(
+            subresource.get_mip_level() as u32,
+            subresource.get_array_index() as u32,
+            region.get_array_length() as u32,
+            (region.get_offset_u() as u32, region.get_offset_v() as u32),
+            (region.get_width() as u32, region.get_height() as u32),
+            data,
+            bytes_per_row,
+            rows_per_image,
+        );
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        assert!(
+            self.gpu_access.contains(GpuAccess::READ),
+            "texture must be created with GpuAccess::READ to be read back"
+        );
+        self.data.lock().unwrap().read_image()
+    }
+
+    pub(crate) fn clone_data(&self) -> Arc<Mutex<TextureData>> {
+        self.data.clone()
+    }
+}
+
+impl ITexture for TextureNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        Self::new_with_data(device, info, data)
+    }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
+}
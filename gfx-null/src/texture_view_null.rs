@@ -0,0 +1,27 @@
+use std::sync::{Arc, Mutex};
+
+use sjgfx_interface::{ITextureView, TextureViewInfo};
+
+use crate::{detail::TextureData, DeviceNull, TextureNull};
+
+pub struct TextureViewNull {
+    // シェーダーを実行しないので読まないが、テクスチャーの中身は共有しておく
+    _data: Arc<Mutex<TextureData>>,
+}
+
+impl TextureViewNull {
+    pub fn new(_device: &DeviceNull, _info: &TextureViewInfo, texture: &TextureNull) -> Self {
+        Self {
+            _data: texture.clone_data(),
+        }
+    }
+}
+
+impl ITextureView for TextureViewNull {
+    type DeviceType = DeviceNull;
+    type TextureType = TextureNull;
+
+    fn new(device: &Self::DeviceType, info: &TextureViewInfo, texture: &Self::TextureType) -> Self {
+        Self::new(device, info, texture)
+    }
+}
@@ -0,0 +1,19 @@
+use sjgfx_interface::{IVertexState, VertexStateInfo};
+
+use crate::DeviceNull;
+
+pub struct VertexStateNull;
+
+impl VertexStateNull {
+    pub fn new(_device: &DeviceNull, _info: &VertexStateInfo) -> Self {
+        Self
+    }
+}
+
+impl IVertexState for VertexStateNull {
+    type DeviceType = DeviceNull;
+
+    fn new(device: &Self::DeviceType, info: &VertexStateInfo) -> Self {
+        Self::new(device, info)
+    }
+}
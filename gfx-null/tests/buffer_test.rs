@@ -0,0 +1,66 @@
+use sjgfx_interface::{BufferInfo, CpuAccessMode, DeviceInfo};
+use sjgfx_null::{BufferNull, DeviceNull};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct Data {
+    x: f32,
+    y: f32,
+    z: u32,
+    w: u32,
+}
+
+#[test]
+fn new_zeroed() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let buffer = BufferNull::new(&device, &BufferInfo::new().set_size(16));
+    assert_eq!(buffer.get_size(), 16);
+    buffer.map_as_slice(|x: &[u32]| assert_eq!(x, [0; 4]));
+}
+
+#[test]
+fn map_mut() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let buffer = BufferNull::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(std::mem::size_of::<Data>())
+            .set_cpu_access_mode(CpuAccessMode::Persistent),
+    );
+
+    let data = Data {
+        x: 1.0,
+        y: 2.0,
+        z: 3,
+        w: 4,
+    };
+    buffer.map_mut(|x: &mut Data| *x = data);
+    buffer.map(|x: &Data| assert_eq!(*x, data));
+}
+
+#[test]
+fn map_range() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let buffer = BufferNull::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(std::mem::size_of::<u32>() * 8)
+            .set_cpu_access_mode(CpuAccessMode::Readback),
+    );
+
+    buffer.map_as_slice_mut(|x: &mut [u32]| {
+        for (index, value) in x.iter_mut().enumerate() {
+            *value = index as u32;
+        }
+    });
+    buffer.map_range_mut(8..16, |x: &mut [u32]| x.copy_from_slice(&[20, 30]));
+    buffer.map_as_slice(|x: &[u32]| assert_eq!(x, [0, 1, 20, 30, 4, 5, 6, 7]));
+}
+
+#[test]
+#[should_panic]
+fn map_out_of_range() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let buffer = BufferNull::new(&device, &BufferInfo::new().set_size(8));
+    buffer.map_range(4..12, |_: &[u8]| {});
+}
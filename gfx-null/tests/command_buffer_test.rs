@@ -0,0 +1,130 @@
+use sjgfx_interface::{
+    BufferCopyRegion, BufferInfo, BufferTextureCopyRegion, ColorTargetViewInfo, CommandBufferInfo,
+    DeviceInfo, GpuAccess, ImageFormat, TextureArrayRange, TextureCopyRegion, TextureInfo,
+};
+use sjgfx_null::{BufferNull, ColorTargetViewNull, CommandBufferNull, DeviceNull, TextureNull};
+
+#[test]
+fn copy_buffer_to_buffer() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let src_buffer = BufferNull::new(&device, &BufferInfo::new().set_size(8));
+    let mut dst_buffer = BufferNull::new(&device, &BufferInfo::new().set_size(8));
+    src_buffer.map_as_slice_mut(|x: &mut [u8]| x.copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]));
+
+    let command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.copy_buffer_to_buffer(
+        &mut dst_buffer,
+        &src_buffer,
+        &BufferCopyRegion::default()
+            .set_src_offset(2)
+            .set_dst_offset(4)
+            .set_copy_size(3),
+    );
+    dst_buffer.map_as_slice(|x: &[u8]| assert_eq!(x, [0, 0, 0, 0, 3, 4, 5, 0]));
+}
+
+#[test]
+fn copy_image_to_buffer() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+    let data: Vec<u8> = (0..16).collect();
+    texture.write(
+        &TextureCopyRegion::new().set_width(4).set_height(4),
+        &data,
+        4,
+        4,
+    );
+
+    // 右下の 2x2 をバッファーの 1 バイト目から詰めて書き込む
+    let buffer = BufferNull::new(&device, &BufferInfo::new().set_size(5));
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.copy_image_to_buffer(
+        &buffer,
+        &texture,
+        BufferTextureCopyRegion::new()
+            .set_offset(1)
+            .set_image_width(2)
+            .set_image_height(2)
+            .edit_texture_copy_region(|x| x.set_offset_u(2).set_offset_v(2)),
+    );
+    buffer.map_as_slice(|x: &[u8]| assert_eq!(x, [0, 10, 11, 14, 15]));
+}
+
+#[test]
+fn copy_buffer_to_image() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_depth(2)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::READ),
+    );
+    let buffer = BufferNull::new(&device, &BufferInfo::new().set_size(16));
+    buffer.map_as_slice_mut(|x: &mut [u32]| x.copy_from_slice(&[1, 2, 3, 4]));
+
+    // 配列要素 1 に書き込むので、読み戻す配列要素 0 は変わらない
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.copy_buffer_to_image(
+        &texture,
+        &buffer,
+        BufferTextureCopyRegion::new()
+            .set_image_width(2)
+            .set_image_height(2)
+            .edit_texture_copy_region(|x| x.edit_texture_subresource(|x| x.set_array_index(1))),
+    );
+    assert!(texture.read_image().get_data().iter().all(|x| *x == 0));
+
+    let copied = BufferNull::new(&device, &BufferInfo::new().set_size(16));
+    command_buffer.copy_image_to_buffer(
+        &copied,
+        &texture,
+        BufferTextureCopyRegion::new()
+            .set_image_width(2)
+            .set_image_height(2)
+            .edit_texture_copy_region(|x| x.edit_texture_subresource(|x| x.set_array_index(1))),
+    );
+    copied.map_as_slice(|x: &[u32]| assert_eq!(x, [1, 2, 3, 4]));
+}
+
+#[test]
+fn clear_color() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_image_format(ImageFormat::B8G8R8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::COLOR_BUFFER | GpuAccess::READ),
+    );
+    let mut color_target_view = ColorTargetViewNull::new(
+        &device,
+        &ColorTargetViewInfo::new().set_image_format(ImageFormat::B8G8R8A8Unorm),
+        &texture,
+    );
+
+    // 記録せずにその場で塗りつぶす
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.clear_color(
+        &mut color_target_view,
+        1.0,
+        0.5,
+        0.0,
+        1.0,
+        TextureArrayRange::new(),
+    );
+    assert!(command_buffer.get_commands().is_empty());
+
+    let image = texture.read_image();
+    assert!(image.get_data().chunks(4).all(|p| p == [255, 128, 0, 255]));
+}
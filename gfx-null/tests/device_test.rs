@@ -0,0 +1,44 @@
+use sjgfx_interface::{AdapterType, DeviceFeatures, DeviceInfo, ImageFormat, ImageFormatUsage};
+use sjgfx_null::DeviceNull;
+
+#[test]
+fn get_capabilities() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let capabilities = device.get_capabilities();
+
+    assert!(capabilities.get_max_texture_size() > 0);
+    assert_eq!(
+        capabilities.get_image_format_capabilities().len(),
+        ImageFormat::ALL.len()
+    );
+    assert!(capabilities
+        .get_image_format_usage(&ImageFormat::R8G8B8A8Unorm)
+        .contains(ImageFormatUsage::SAMPLED | ImageFormatUsage::RENDER_TARGET));
+}
+
+#[test]
+fn request_optional_features() {
+    let info = DeviceInfo::new().set_optional_features(DeviceFeatures::all());
+    let device = DeviceNull::new(&info);
+    assert_eq!(
+        device.get_capabilities().get_features(),
+        DeviceFeatures::all()
+    );
+}
+
+#[test]
+fn enumerate_adapters() {
+    let adapters = DeviceNull::enumerate_adapters(&DeviceInfo::new());
+    assert_eq!(adapters.len(), 1);
+    assert_eq!(adapters[0].get_index(), 0);
+    assert_eq!(adapters[0].get_adapter_type(), &AdapterType::Cpu);
+
+    let device = DeviceNull::new(&DeviceInfo::new().set_adapter_index(0));
+    assert_eq!(device.get_adapter_info().get_name(), adapters[0].get_name());
+}
+
+#[test]
+#[should_panic]
+fn adapter_out_of_range() {
+    DeviceNull::new(&DeviceInfo::new().set_adapter_index(1));
+}
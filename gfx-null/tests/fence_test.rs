@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use sjgfx_interface::{CommandBufferInfo, DeviceInfo, FenceInfo, QueueInfo};
+use sjgfx_null::{CommandBufferNull, DeviceNull, FenceNull, QueueNull};
+
+#[test]
+fn new_signaled() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut fence = FenceNull::new(&device, &FenceInfo::new().set_signaled(true));
+    assert!(fence.is_signaled());
+    assert!(fence.wait(None));
+
+    fence.reset();
+    assert!(!fence.is_signaled());
+    assert!(!fence.wait(Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn execute_with_fence() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let mut fence = FenceNull::new(&device, &FenceInfo::new());
+
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.end();
+
+    for _ in 0..2 {
+        queue.execute_with_fence(&command_buffer, &mut fence);
+        assert!(fence.wait(None));
+
+        fence.reset();
+        assert!(!fence.is_signaled());
+    }
+}
@@ -0,0 +1,121 @@
+use sjgfx_interface::{
+    BufferInfo, CommandBufferInfo, DeviceInfo, IndexFormat, PrimitiveTopology, QueueInfo,
+    SemaphoreInfo, ShaderInfo,
+};
+use sjgfx_null::{
+    BufferNull, CommandBufferNull, CommandNull, DeviceNull, QueueNull, SemaphoreNull, ShaderNull,
+};
+
+#[test]
+fn record_draw_and_dispatch() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let graphics_shader = ShaderNull::new(
+        &device,
+        &ShaderInfo::new()
+            .set_vertex_shader_binary(&[])
+            .set_pixel_shader_binary(&[]),
+    );
+    let compute_shader =
+        ShaderNull::new(&device, &ShaderInfo::new().set_compute_shader_binary(&[]));
+    let index_buffer = BufferNull::new(&device, &BufferInfo::new().set_size(12));
+
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.push_debug_group("frame");
+    command_buffer.set_shader(&graphics_shader);
+    command_buffer.draw_instanced(PrimitiveTopology::TriangleList, 3, 0, 2, 1);
+    command_buffer.draw_indexed_instanced(
+        PrimitiveTopology::TriangleList,
+        IndexFormat::Uint32,
+        &index_buffer,
+        3,
+        0,
+        1,
+        0,
+    );
+    command_buffer.set_shader(&compute_shader);
+    command_buffer.dispatch(4, 2, 1);
+    command_buffer.pop_debug_group();
+    command_buffer.end();
+
+    // flush されるまでは実行されない
+    queue.execute(&command_buffer);
+    assert!(queue.get_executed_commands().is_empty());
+    queue.flush();
+
+    let expected = [
+        CommandNull::PushDebugGroup("frame".to_string()),
+        CommandNull::Draw {
+            primitive_topology: PrimitiveTopology::TriangleList,
+            vertex_count: 3,
+            vertex_offset: 0,
+            instance_count: 2,
+            base_instance: 1,
+        },
+        CommandNull::DrawIndexed {
+            primitive_topology: PrimitiveTopology::TriangleList,
+            index_format: IndexFormat::Uint32,
+            index_count: 3,
+            base_vertex: 0,
+            instance_count: 1,
+            base_instance: 0,
+        },
+        CommandNull::Dispatch {
+            count_x: 4,
+            count_y: 2,
+            count_z: 1,
+        },
+        CommandNull::PopDebugGroup,
+    ];
+    assert_eq!(queue.get_executed_commands(), expected);
+}
+
+#[test]
+#[should_panic]
+fn dispatch_without_compute_shader() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.dispatch(1, 1, 1);
+}
+
+#[test]
+fn wait_semaphore() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let wait_semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new());
+    let signal_semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new());
+
+    let mut command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.insert_debug_marker("after wait");
+    command_buffer.end();
+
+    // 待つ値に到達するまでは後からサブミットしたものも実行されない
+    queue.execute_with_semaphores(
+        &command_buffer,
+        &[(&wait_semaphore, 1)],
+        &[(&signal_semaphore, 1)],
+    );
+    queue.execute(&command_buffer);
+    queue.flush();
+    assert!(queue.get_executed_commands().is_empty());
+    assert_eq!(signal_semaphore.get_value(), 0);
+
+    wait_semaphore.signal(1);
+    queue.sync();
+    assert_eq!(queue.get_executed_commands().len(), 2);
+    assert_eq!(signal_semaphore.get_value(), 1);
+}
+
+#[test]
+#[should_panic]
+fn sync_with_unsignaled_semaphore() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new());
+    let command_buffer = CommandBufferNull::new(&device, &CommandBufferInfo::new());
+    queue.execute_with_semaphores(&command_buffer, &[(&semaphore, 1)], &[]);
+    queue.sync();
+}
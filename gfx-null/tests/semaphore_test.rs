@@ -0,0 +1,38 @@
+use std::{thread, time::Duration};
+
+use sjgfx_interface::{DeviceInfo, SemaphoreInfo};
+use sjgfx_null::{DeviceNull, SemaphoreNull};
+
+#[test]
+fn new() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new().set_initial_value(3));
+    assert_eq!(semaphore.get_value(), 3);
+    assert!(semaphore.wait(3, None));
+    assert!(!semaphore.wait(4, Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn signal() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new());
+    semaphore.signal(2);
+    assert_eq!(semaphore.get_value(), 2);
+
+    // 値は単調増加
+    semaphore.signal(1);
+    assert_eq!(semaphore.get_value(), 2);
+}
+
+#[test]
+fn signal_from_other_thread() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let semaphore = SemaphoreNull::new(&device, &SemaphoreInfo::new());
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(Duration::from_millis(10));
+            semaphore.signal(1);
+        });
+        assert!(semaphore.wait(1, None));
+    });
+}
@@ -0,0 +1,119 @@
+use sjgfx_interface::{
+    CommandBufferInfo, CompositeAlphaMode, DeviceInfo, FenceInfo, IDisplayEventListener,
+    ImageFormat, QueueInfo, ScalingMode, SwapChainInfo, SwapChainStatus, TextureArrayRange,
+};
+use sjgfx_null::{CommandBufferNull, DeviceNull, FenceNull, QueueNull, SwapChainNull};
+
+// 1 フレーム塗りつぶして表示する
+fn present_clear_color(
+    device: &DeviceNull,
+    queue: &mut QueueNull,
+    swap_chain: &mut SwapChainNull,
+    red: f32,
+) {
+    let mut command_buffer = CommandBufferNull::new(device, &CommandBufferInfo::new());
+    let view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.clear_color(view, red, 0.0, 0.0, 1.0, TextureArrayRange::new());
+    queue.present(swap_chain);
+}
+
+#[test]
+fn new() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let swap_chain = SwapChainNull::new(
+        &device,
+        &SwapChainInfo::new()
+            .with_preferred_formats(&[ImageFormat::R16G16B16A16Float, ImageFormat::B8G8R8A8Unorm]),
+    );
+    assert_eq!(swap_chain.get_format(), ImageFormat::B8G8R8A8Unorm);
+    assert_eq!(swap_chain.get_alpha_mode(), CompositeAlphaMode::Opaque);
+    assert_eq!(swap_chain.get_status(), SwapChainStatus::Optimal);
+    assert!(swap_chain.read_presented_image().is_none());
+}
+
+#[test]
+fn read_presented_image() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainNull::new(
+        &device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert_eq!(image.get_width(), 4);
+    assert_eq!(image.get_height(), 4);
+    assert!(image.get_data().chunks(4).all(|p| p == [255, 0, 0, 255]));
+
+    // リングの次のテクスチャに描画したフレームが読み戻せる
+    present_clear_color(&device, &mut queue, &mut swap_chain, 0.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert!(image.get_data().chunks(4).all(|p| p == [0, 0, 0, 255]));
+}
+
+#[test]
+fn present_render_target_letterbox() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainNull::new(
+        &device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(2)
+            .with_render_size(2, 2)
+            .with_scaling_mode(ScalingMode::Letterbox),
+    );
+    assert_eq!(swap_chain.get_render_width(), 2);
+    assert_eq!(swap_chain.get_render_height(), 2);
+
+    // 左右の余白は黒になる
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    let image = swap_chain.read_presented_image().unwrap();
+    assert_eq!(image.get_width(), 4);
+    assert_eq!(image.get_pixel(0, 0), [0, 0, 0, 255]);
+    assert_eq!(image.get_pixel(1, 1), [255, 0, 0, 255]);
+    assert_eq!(image.get_pixel(2, 0), [255, 0, 0, 255]);
+    assert_eq!(image.get_pixel(3, 1), [0, 0, 0, 255]);
+}
+
+#[test]
+fn set_render_scale() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut swap_chain =
+        SwapChainNull::new(&device, &SwapChainInfo::new().with_width(8).with_height(4));
+    swap_chain.set_render_scale(0.5);
+    assert_eq!(swap_chain.get_render_width(), 4);
+    assert_eq!(swap_chain.get_render_height(), 2);
+}
+
+#[test]
+fn acquire_with_fence() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut swap_chain = SwapChainNull::new(&device, &SwapChainInfo::new());
+    let mut fence = FenceNull::new(&device, &FenceInfo::new());
+    swap_chain.acquire_next_scan_buffer_view(None, Some(&mut fence));
+    assert!(fence.is_signaled());
+}
+
+#[test]
+fn skip_zero_size() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let mut queue = QueueNull::new(&device, &QueueInfo::new());
+    let mut swap_chain = SwapChainNull::new(
+        &device,
+        &SwapChainInfo::new()
+            .with_width(4)
+            .with_height(4)
+            .with_direct_rendering_enabled(true),
+    );
+
+    // 最小化されたときと同じようにフレームを飛ばす
+    swap_chain.on_resized(0, 0);
+    present_clear_color(&device, &mut queue, &mut swap_chain, 1.0);
+    assert_eq!(swap_chain.get_status(), SwapChainStatus::Skipped);
+    assert!(swap_chain.read_presented_image().is_none());
+}
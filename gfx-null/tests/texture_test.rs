@@ -0,0 +1,103 @@
+use sjgfx_interface::{
+    ColorTargetViewInfo, DeviceInfo, GpuAccess, ImageFormat, TextureCopyRegion, TextureInfo,
+};
+use sjgfx_null::{ColorTargetViewNull, DeviceNull, TextureNull};
+
+#[test]
+fn new_with_data() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new_with_data(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::READ),
+        &[1, 2, 3, 4, 5, 6, 7, 8],
+    );
+    assert_eq!(texture.read_image().get_data(), [1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn write_rgb() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8G8B8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    let region = TextureCopyRegion::new().set_width(2).set_height(1);
+    texture.write(&region, &[10, 20, 30, 40, 50, 60], 6, 1);
+
+    let image = texture.read_image();
+    assert_eq!(image.get_data(), [10, 20, 30, 255, 40, 50, 60, 255]);
+}
+
+#[test]
+fn read_color_target_view() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(3)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::COLOR_BUFFER | GpuAccess::WRITE),
+    );
+    let data: Vec<u8> = (0..24).collect();
+    let region = TextureCopyRegion::new().set_width(3).set_height(2);
+    texture.write(&region, &data, 12, 2);
+
+    let color_target_view = ColorTargetViewNull::new(
+        &device,
+        &ColorTargetViewInfo::new().set_image_format(ImageFormat::R8G8B8A8Unorm),
+        &texture,
+    );
+    assert_eq!(color_target_view.read_image().get_data(), data);
+}
+
+#[test]
+#[should_panic]
+fn write_without_write_access() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(1)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::READ),
+    );
+    texture.write(
+        &TextureCopyRegion::new().set_width(1).set_height(1),
+        &[1],
+        1,
+        1,
+    );
+}
+
+#[test]
+#[should_panic]
+fn write_out_of_mip() {
+    let device = DeviceNull::new(&DeviceInfo::new());
+    let texture = TextureNull::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_mip_level(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
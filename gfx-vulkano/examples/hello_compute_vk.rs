@@ -33,7 +33,7 @@ fn main() {
     queue.flush();
     queue.sync();
 
-    buffer.map_as_array(|x: &[u32]| {
+    buffer.map_as_slice(|x: &[u32]| {
         for item in x {
             print!("{} ", item);
         }
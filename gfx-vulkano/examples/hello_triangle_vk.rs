@@ -61,7 +61,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<f32>() * 2 * 3),
     );
-    vertex_buffer.map_as_array_mut(|x: &mut [f32]| {
+    vertex_buffer.map_as_slice_mut(|x: &mut [f32]| {
         x[0] = 0.0;
         x[1] = 0.0;
 
@@ -98,8 +98,9 @@ fn main() {
                 Event::RedrawRequested(_) => {
                     queue.sync();
 
-                    let next_scan_buffer_view =
-                        swap_chain.acquire_next_scan_buffer_view(None, Some(&mut fence));
+                    let next_scan_buffer_view = swap_chain
+                        .acquire_next_scan_buffer_view(None, Some(&mut fence))
+                        .clone();
 
                     {
                         command_buffer.begin();
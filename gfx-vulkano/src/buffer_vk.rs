@@ -1,79 +1,180 @@
-use sjgfx_interface::{BufferInfo, GpuAccess, IBuffer};
-use std::sync::Arc;
+use bytemuck::Pod;
+use sjgfx_interface::{
+    get_whole_map_range, validate_map_range, BufferInfo, CpuAccessMode, GpuAccess, IBuffer,
+};
+use std::{ops::Range, sync::Arc};
 use vulkano::{
     buffer::{Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
     device::Device,
-    memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
+    memory::allocator::{AllocationCreateInfo, MemoryUsage},
     pipeline::graphics::vertex_input::VertexBuffersCollection,
+    DeviceSize,
 };
 
-use crate::{interop, DeviceVk};
+use crate::{interop, resource_pool_vk::BufferDescriptor, DeviceVk};
 
 pub struct BufferVk {
     device: Arc<Device>,
     buffer: Subbuffer<[u8]>,
+    cpu_access_mode: CpuAccessMode,
 }
 
 impl BufferVk {
     pub fn new(device: &DeviceVk, info: &BufferInfo) -> Self {
-        let memory_allocator =
-            Arc::new(StandardMemoryAllocator::new_default(device.clone_device()));
         let buffer_usage = Self::convert_usage(&info.get_gpu_access_flags());
-        let buffer = Buffer::from_iter(
-            &memory_allocator,
-            BufferCreateInfo {
+        let memory_usage = Self::convert_memory_usage(info.get_cpu_access_mode());
+        let buffer = if Self::is_staging(info) {
+            // コピーにしか使わないステージングバッファーは使いまわす
+            // 前の持ち主の中身が見えないように新しく作ったときと同じくゼロで埋める
+            let buffer = device.get_resource_pool().acquire_buffer(BufferDescriptor {
+                size: info.get_size() as DeviceSize,
                 usage: buffer_usage,
-                ..Default::default()
-            },
-            AllocationCreateInfo {
-                usage: MemoryUsage::Upload,
-                ..Default::default()
-            },
-            vec![0u8; info.get_size()],
-        )
-        .unwrap();
+                memory_usage,
+            });
+            buffer.write().unwrap().fill(0);
+            buffer
+        } else {
+            Self::create_buffer(device, info.get_size(), buffer_usage, memory_usage)
+        };
+        device.set_debug_label(buffer.buffer().as_ref(), info.get_debug_label());
 
         Self {
             device: device.clone_device(),
             buffer,
+            cpu_access_mode: info.get_cpu_access_mode(),
+        }
+    }
+
+    fn create_buffer(
+        device: &DeviceVk,
+        size: usize,
+        buffer_usage: BufferUsage,
+        memory_usage: MemoryUsage,
+    ) -> Subbuffer<[u8]> {
+        let buffer_create_info = BufferCreateInfo {
+            usage: buffer_usage,
+            ..Default::default()
+        };
+        let allocation_create_info = AllocationCreateInfo {
+            usage: memory_usage,
+            ..Default::default()
+        };
+        if memory_usage != MemoryUsage::DeviceOnly {
+            Buffer::from_iter(
+                device.get_memory_allocator(),
+                buffer_create_info,
+                allocation_create_info,
+                vec![0u8; size],
+            )
+            .unwrap()
+        } else {
+            // CPU から見えないメモリーは初期化できない
+            Buffer::new_slice::<u8>(
+                device.get_memory_allocator(),
+                buffer_create_info,
+                allocation_create_info,
+                size as DeviceSize,
+            )
+            .unwrap()
         }
     }
 
-    pub fn map<T, F: Fn(&T)>(&self, func: F) {
-        let data = self.buffer.read().unwrap();
-        let ptr = data.as_ptr();
-        let casted = unsafe { (ptr as *const T).as_ref().unwrap() };
-        func(casted);
+    // コピー元かコピー先にしか使わず CPU からアクセスするバッファー
+    fn is_staging(info: &BufferInfo) -> bool {
+        let copy_access = GpuAccess::READ | GpuAccess::WRITE;
+        let gpu_access = info.get_gpu_access_flags();
+        info.get_cpu_access_mode().is_mappable()
+            && !gpu_access.is_empty()
+            && copy_access.contains(gpu_access)
+    }
+
+    pub fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map_range(0..std::mem::size_of::<T>(), |x: &[T]| func(&x[0]));
+    }
+
+    pub fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_range(get_whole_map_range::<T>(self.get_size()), func);
+    }
+
+    pub fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        if range.is_empty() {
+            func(&[]);
+            return;
+        }
+
+        let data = self.slice(&range).read().unwrap();
+        func(bytemuck::cast_slice(&data));
+    }
+
+    pub fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_range_mut(0..std::mem::size_of::<T>(), |x: &mut [T]| func(&mut x[0]));
+    }
+
+    pub fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_range_mut(get_whole_map_range::<T>(self.get_size()), func);
+    }
+
+    pub fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        if range.is_empty() {
+            func(&mut []);
+            return;
+        }
+
+        let mut data = self.slice(&range).write().unwrap();
+        func(bytemuck::cast_slice_mut(&mut data));
     }
 
-    pub fn map_mut<T, F: Fn(&mut T)>(&self, func: F) {
-        let ptr = self.buffer.mapped_ptr().unwrap().as_ptr();
-        let casted = unsafe { (ptr as *mut T).as_mut().unwrap() };
-        func(casted);
+    fn validate_map_range<T: Pod>(&self, range: &Range<usize>) {
+        assert!(
+            self.cpu_access_mode.is_mappable(),
+            "buffer created with CpuAccessMode::None can't be mapped"
+        );
+        validate_map_range::<T>(self.get_size(), range);
     }
 
-    pub fn map_as_array<T, F: Fn(&[T])>(&self, func: F) {
-        let ptr = self.buffer.mapped_ptr().unwrap().as_ptr() as *mut T;
-        let size = (self.buffer.len() as usize) / std::mem::size_of::<T>();
-        let slice = unsafe { std::slice::from_raw_parts::<T>(ptr, size) };
-        func(slice);
+    fn get_size(&self) -> usize {
+        self.buffer.len() as usize
     }
 
-    pub fn map_as_array_mut<T, F: Fn(&mut [T])>(&self, func: F) {
-        let ptr = self.buffer.mapped_ptr().unwrap().as_ptr() as *mut T;
-        let size = (self.buffer.len() as usize) / std::mem::size_of::<T>();
-        let slice = unsafe { std::slice::from_raw_parts_mut::<T>(ptr, size) };
-        func(slice);
+    fn slice(&self, range: &Range<usize>) -> Subbuffer<[u8]> {
+        self.buffer
+            .clone()
+            .slice(range.start as DeviceSize..range.end as DeviceSize)
     }
 
     pub fn view(&self) -> BufferView {
         BufferView::new(self)
     }
 
+    pub fn view_range(&self, offset: usize, size: usize) -> BufferView {
+        assert!(
+            offset + size <= self.get_size(),
+            "buffer range {}..{} is out of buffer size {}",
+            offset,
+            offset + size,
+            self.get_size()
+        );
+        BufferView {
+            device: self.device.clone(),
+            buffer: self.slice(&(offset..(offset + size))),
+        }
+    }
+
     fn convert_usage(gpu_access: &GpuAccess) -> BufferUsage {
         interop::convert_usage(gpu_access)
     }
 
+    fn convert_memory_usage(cpu_access_mode: CpuAccessMode) -> MemoryUsage {
+        match cpu_access_mode {
+            CpuAccessMode::None => MemoryUsage::DeviceOnly,
+            CpuAccessMode::Upload => MemoryUsage::Upload,
+            CpuAccessMode::Readback => MemoryUsage::Download,
+            CpuAccessMode::Persistent => MemoryUsage::Upload,
+        }
+    }
+
     fn clone_buffer(&self) -> Subbuffer<[u8]> {
         self.buffer.clone()
     }
@@ -86,20 +187,28 @@ impl IBuffer for BufferVk {
         Self::new(device, info)
     }
 
-    fn map<T, F: Fn(&T)>(&self, func: F) {
+    fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
         self.map(func);
     }
 
-    fn map_mut<T, F: Fn(&mut T)>(&self, func: F) {
+    fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_as_slice(func);
+    }
+
+    fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.map_range(range, func);
+    }
+
+    fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
         self.map_mut(func);
     }
 
-    fn map_as_slice<T, F: Fn(&[T])>(&self, func: F) {
-        self.map_as_array(func);
+    fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_as_slice_mut(func);
     }
 
-    fn map_as_slice_mut<T, F: Fn(&mut [T])>(&self, func: F) {
-        self.map_as_array_mut(func);
+    fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.map_range_mut(range, func);
     }
 
     fn flush_mapped_range(&self, _offset: isize, _size: usize) {}
@@ -136,7 +245,7 @@ impl VertexBuffersCollection for BufferView {
 
 #[cfg(test)]
 mod tests {
-    use sjgfx_interface::{BufferInfo, DeviceInfo, GpuAccess};
+    use sjgfx_interface::{BufferInfo, CpuAccessMode, DeviceInfo, GpuAccess};
 
     use crate::{BufferVk, DeviceVk};
 
@@ -170,6 +279,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn new_as_vertex_and_unordered_access_buffer() {
+        new_impl(GpuAccess::VERTEX_BUFFER | GpuAccess::UNORDERED_ACCESS_BUFFER);
+    }
+
+    #[test]
+    fn new_device_only() {
+        let device = DeviceVk::new(&DeviceInfo::new());
+        let _ = BufferVk::new(
+            &device,
+            &BufferInfo::new()
+                .set_size(64)
+                .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER)
+                .set_cpu_access_mode(CpuAccessMode::None),
+        );
+    }
+
     #[test]
     fn map_as_slice() {
         let device = DeviceVk::new(&DeviceInfo::new());
@@ -179,17 +305,61 @@ mod tests {
                 .set_size(std::mem::size_of::<f32>() * 4)
                 .set_gpu_access_flags(GpuAccess::CONSTANT_BUFFER),
         );
-        buffer.map_as_array_mut(|x: &mut [f32]| {
+        buffer.map_as_slice_mut(|x: &mut [f32]| {
             x[0] = 10.0;
             x[1] = 20.0;
             x[2] = 30.0;
             x[3] = 40.0;
         });
-        buffer.map_as_array(|x: &[f32]| {
+        buffer.map_as_slice(|x: &[f32]| {
             assert!(x[0] == 10.0);
             assert!(x[1] == 20.0);
             assert!(x[2] == 30.0);
             assert!(x[3] == 40.0);
         });
+        buffer.map_range(8..16, |x: &[f32]| {
+            assert!(x == [30.0, 40.0]);
+        });
+    }
+
+    #[test]
+    fn staging_buffer_is_recycled() {
+        let device = DeviceVk::new(&DeviceInfo::new());
+        let info = BufferInfo::new()
+            .set_size(64)
+            .set_gpu_access_flags(GpuAccess::READ)
+            .set_cpu_access_mode(CpuAccessMode::Upload);
+
+        // 使用中のバッファーは再利用されない
+        let buffer_0 = BufferVk::new(&device, &info);
+        let buffer_1 = BufferVk::new(&device, &info);
+        assert_eq!(device.get_resource_pool().get_buffer_count(), 2);
+
+        // 解放したバッファーは再利用される
+        drop(buffer_0);
+        drop(buffer_1);
+        let _buffer_2 = BufferVk::new(&device, &info);
+        assert_eq!(device.get_resource_pool().get_buffer_count(), 2);
+
+        device.trim_resource_pool();
+        assert_eq!(device.get_resource_pool().get_buffer_count(), 1);
+    }
+
+    #[test]
+    fn recycled_staging_buffer_is_cleared() {
+        let device = DeviceVk::new(&DeviceInfo::new());
+        let info = BufferInfo::new()
+            .set_size(16)
+            .set_gpu_access_flags(GpuAccess::READ)
+            .set_cpu_access_mode(CpuAccessMode::Upload);
+
+        let buffer_0 = BufferVk::new(&device, &info);
+        buffer_0.map_as_slice_mut(|x: &mut [u32]| x.fill(1));
+        drop(buffer_0);
+
+        // 再利用されたバッファーに前の中身は残っていない
+        let buffer_1 = BufferVk::new(&device, &info);
+        assert_eq!(device.get_resource_pool().get_buffer_count(), 1);
+        buffer_1.map_as_slice(|x: &[u32]| assert_eq!(x, [0, 0, 0, 0]));
     }
 }
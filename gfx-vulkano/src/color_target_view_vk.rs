@@ -1,38 +1,68 @@
 use std::sync::Arc;
 
-use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageFormat};
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData, ImageFormat};
 use vulkano::{
     format::Format,
-    image::{view::ImageView, AttachmentImage, ImageViewAbstract},
+    image::{view::ImageView, AttachmentImage, ImageAccess, ImageViewAbstract, SwapchainImage},
 };
 
-use crate::{util, DeviceVk, SwapChainVk, TextureVk};
+use crate::{transfer_vk::TransferVk, util, DeviceVk, QueueVk, SwapChainVk, TextureVk};
 
+#[derive(Clone)]
 pub struct ColorTargetViewVk {
+    transfer: TransferVk,
     texture: Option<Arc<ImageView<AttachmentImage>>>,
     image_view: Option<Arc<dyn ImageViewAbstract>>,
     format: Format,
 }
 
 impl ColorTargetViewVk {
-    pub fn new(_device: &DeviceVk, info: &ColorTargetViewInfo, texture: &TextureVk) -> Self {
+    pub fn new(device: &DeviceVk, info: &ColorTargetViewInfo, texture: &TextureVk) -> Self {
         let image_view = ImageView::new_default(texture.clone_image()).unwrap();
         Self {
-            texture: Some(texture.clone_attachment_image()),
+            transfer: TransferVk::new(device),
+            texture: texture.clone_attachment_image(),
             image_view: Some(image_view),
             format: Converter.convert_format(info.get_image_format()),
         }
     }
 
+    pub(crate) fn new_from_swap_chain_image(
+        transfer: TransferVk,
+        image: Arc<SwapchainImage>,
+        format: Format,
+    ) -> Self {
+        Self {
+            transfer,
+            texture: None,
+            image_view: Some(ImageView::new_default(image).unwrap()),
+            format,
+        }
+    }
+
     pub fn new_from_swap_chain(swap_chain: &SwapChainVk) -> Self {
         let image_view = swap_chain.clone_current_image_view();
         Self {
+            transfer: swap_chain.clone_transfer(),
             texture: None,
             image_view: Some(image_view),
             format: swap_chain.get_swap_chain().image_format(),
         }
     }
 
+    pub(crate) fn new_from_attachment_image(
+        transfer: TransferVk,
+        image: Arc<AttachmentImage>,
+    ) -> Self {
+        let format = image.format();
+        Self {
+            transfer,
+            texture: Some(ImageView::new_default(image).unwrap()),
+            image_view: None,
+            format,
+        }
+    }
+
     pub fn clone_image_view(&self) -> Arc<dyn ImageViewAbstract> {
         if let Some(texture) = &self.texture {
             texture.clone()
@@ -46,6 +76,16 @@ impl ColorTargetViewVk {
     pub fn get_format(&self) -> Format {
         self.format
     }
+
+    pub fn read_image(&self) -> ImageData {
+        self.transfer.read_image(self.clone_image_view().image())
+    }
+
+    // キューにためているコマンドの完了を待ってから読み戻す
+    pub fn read_image_with_queue(&self, queue: &mut QueueVk) -> ImageData {
+        queue.sync();
+        self.read_image()
+    }
 }
 
 struct Converter;
@@ -66,4 +106,8 @@ impl IColorTargetView for ColorTargetViewVk {
     ) -> Self {
         Self::new(device, info, texture)
     }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
 }
@@ -4,19 +4,17 @@ use sjgfx_interface::{
     CommandBufferInfo, ICommandBuffer, PrimitiveTopology, ScissorStateInfo, TextureArrayRange,
     ViewportScissorStateInfo, ViewportStateInfo,
 };
-use vulkano::command_buffer::allocator::{
-    CommandBufferAllocator, StandardCommandBufferAllocator,
-    StandardCommandBufferAllocatorCreateInfo,
-};
+use vulkano::command_buffer::allocator::{CommandBufferAllocator, StandardCommandBufferAllocator};
 use vulkano::command_buffer::{RenderPassBeginInfo, SubpassContents};
 use vulkano::descriptor_set::allocator::StandardDescriptorSetAllocator;
+use vulkano::instance::debug::DebugUtilsLabel;
 use vulkano::pipeline::Pipeline;
 use vulkano::render_pass::FramebufferCreateInfo;
 use vulkano::shader::ShaderModule;
 use vulkano::{
     command_buffer::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer},
     descriptor_set::{PersistentDescriptorSet, WriteDescriptorSet},
-    device::{Device, Queue},
+    device::Device,
     format::Format,
     image::ImageViewAbstract,
     pipeline::{
@@ -60,11 +58,17 @@ enum DrawCommand {
     DrawIndexed(DrawIndexedInfo),
 }
 
+enum DebugCommand {
+    PushGroup(String),
+    PopGroup,
+    InsertMarker(String),
+}
+
 pub struct CommandBufferVk {
     device: Arc<Device>,
-    queue: Arc<Queue>,
-    command_buffer_allocator: StandardCommandBufferAllocator,
-    descriptor_set_allocator: StandardDescriptorSetAllocator,
+    label: Option<String>,
+    command_buffer_allocator: Arc<StandardCommandBufferAllocator>,
+    descriptor_set_allocator: Arc<StandardDescriptorSetAllocator>,
 
     // シェーダ
     compute_shader_module: Option<Arc<ShaderModule>>,
@@ -90,19 +94,14 @@ pub struct CommandBufferVk {
     // 描画
     draw_command: Option<DrawCommand>,
     render_pass: Option<Arc<RenderPass>>,
+
+    // デバッグ情報。描画コマンドの前後で積まれたものを分けて保持する
+    debug_commands_before_draw: Vec<DebugCommand>,
+    debug_commands_after_draw: Vec<DebugCommand>,
 }
 
 impl CommandBufferVk {
-    pub fn new(device: &DeviceVk, _info: &CommandBufferInfo) -> Self {
-        let command_buffer_allocator = StandardCommandBufferAllocator::new(
-            device.clone_device(),
-            StandardCommandBufferAllocatorCreateInfo {
-                primary_buffer_count: 1,
-                secondary_buffer_count: 1,
-                ..Default::default()
-            },
-        );
-        let descriptor_set_allocator = StandardDescriptorSetAllocator::new(device.clone_device());
+    pub fn new(device: &DeviceVk, info: &CommandBufferInfo) -> Self {
         let viewport_scissor_state = ViewportScissorStateVk::new(
             device,
             &ViewportScissorStateInfo::new()
@@ -116,9 +115,9 @@ impl CommandBufferVk {
 
         Self {
             device: device.clone_device(),
-            queue: device.clone_queue(),
-            command_buffer_allocator,
-            descriptor_set_allocator,
+            label: info.get_debug_label().map(|x| x.to_string()),
+            command_buffer_allocator: device.clone_command_buffer_allocator(),
+            descriptor_set_allocator: device.clone_descriptor_set_allocator(),
 
             // シェーダ
             compute_shader_module: None,
@@ -141,10 +140,31 @@ impl CommandBufferVk {
             dispatch_count: None,
             render_pass: None,
             draw_command: None,
+
+            debug_commands_before_draw: Vec::new(),
+            debug_commands_after_draw: Vec::new(),
         }
     }
 
-    pub fn begin(&mut self) {}
+    pub fn begin(&mut self) {
+        // 描画とディスパッチは記録ごとに積みなおすので、前回の記録のものを残さない
+        self.debug_commands_before_draw.clear();
+        self.debug_commands_after_draw.clear();
+        self.draw_command = None;
+        self.dispatch_count = None;
+    }
+
+    pub fn push_debug_group(&mut self, label: &str) {
+        self.push_debug_command(DebugCommand::PushGroup(label.to_string()));
+    }
+
+    pub fn pop_debug_group(&mut self) {
+        self.push_debug_command(DebugCommand::PopGroup);
+    }
+
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        self.push_debug_command(DebugCommand::InsertMarker(label.to_string()));
+    }
 
     pub fn end(&mut self) {}
 
@@ -198,6 +218,16 @@ impl CommandBufferVk {
         self.constant_buffers[slot as usize] = Some(buffer.view());
     }
 
+    pub fn set_constant_buffer_range(
+        &mut self,
+        slot: i32,
+        buffer: &BufferVk,
+        offset: usize,
+        size: usize,
+    ) {
+        self.constant_buffers[slot as usize] = Some(buffer.view_range(offset, size));
+    }
+
     pub fn set_unordered_access_buffer(&mut self, slot: i32, buffer: &BufferVk) {
         let index = slot as usize;
         self.unordered_access_buffer[index] = Some(buffer.view());
@@ -248,6 +278,16 @@ impl CommandBufferVk {
         self.vertex_buffers[index as usize] = Some(vertex_buffer.view());
     }
 
+    pub fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        vertex_buffer: &BufferVk,
+        offset: usize,
+        size: usize,
+    ) {
+        self.vertex_buffers[index as usize] = Some(vertex_buffer.view_range(offset, size));
+    }
+
     pub fn get_dispatch_count(&self) -> (u32, u32, u32) {
         self.dispatch_count.as_ref().unwrap().clone()
     }
@@ -256,27 +296,94 @@ impl CommandBufferVk {
         self.render_pass.as_ref().unwrap().clone()
     }
 
+    pub(crate) fn is_graphics_command(&self) -> bool {
+        self.compute_shader_module.is_none() && self.vertex_shader_module.is_some()
+    }
+
+    // コマンドバッファはサブミットするキューのファミリー向けに作る
     pub(crate) fn build_command_builder(
         &self,
+        queue_family_index: u32,
     ) -> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer> {
         let mut builder = AutoCommandBufferBuilder::primary(
-            &self.command_buffer_allocator,
-            self.queue.queue_family_index(),
+            self.command_buffer_allocator.as_ref(),
+            queue_family_index,
             vulkano::command_buffer::CommandBufferUsage::OneTimeSubmit,
         )
         .unwrap();
 
+        // コマンドバッファ全体をラベルで囲っておく
+        if let Some(label) = &self.label {
+            self.push_debug_commands(&mut builder, &[DebugCommand::PushGroup(label.clone())]);
+        }
+        self.push_debug_commands(&mut builder, &self.debug_commands_before_draw);
+
         if self.compute_shader_module.is_some() {
-            self.build_compute_command(&mut builder);
+            // ディスパッチせずにデバッグコマンドだけを積んだときは何もしない
+            if self.dispatch_count.is_some() {
+                self.build_compute_command(&mut builder);
+            }
         } else if self.vertex_shader_module.is_some() {
             self.build_graphics_command(&mut builder);
         } else {
             // とくに何もしない
         }
 
+        self.push_debug_commands(&mut builder, &self.debug_commands_after_draw);
+        if self.label.is_some() {
+            self.push_debug_commands(&mut builder, &[DebugCommand::PopGroup]);
+        }
+
         builder
     }
 
+    fn push_debug_command(&mut self, debug_command: DebugCommand) {
+        // 描画コマンドはステートとして保持しているので、描画の前後どちらで積まれたかだけを記録する
+        if self.draw_command.is_some() || self.dispatch_count.is_some() {
+            self.debug_commands_after_draw.push(debug_command);
+        } else {
+            self.debug_commands_before_draw.push(debug_command);
+        }
+    }
+
+    fn push_debug_commands<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        debug_commands: &[DebugCommand],
+    ) where
+        A: CommandBufferAllocator,
+    {
+        // VK_EXT_debug_utils が使えない環境ではなにもしない
+        if !self.device.instance().enabled_extensions().ext_debug_utils {
+            return;
+        }
+
+        for debug_command in debug_commands {
+            match debug_command {
+                DebugCommand::PushGroup(label) => {
+                    builder
+                        .begin_debug_utils_label(Self::create_debug_utils_label(label))
+                        .unwrap();
+                }
+                DebugCommand::PopGroup => unsafe {
+                    builder.end_debug_utils_label().unwrap();
+                },
+                DebugCommand::InsertMarker(label) => {
+                    builder
+                        .insert_debug_utils_label(Self::create_debug_utils_label(label))
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    fn create_debug_utils_label(label: &str) -> DebugUtilsLabel {
+        DebugUtilsLabel {
+            label_name: label.to_string(),
+            ..Default::default()
+        }
+    }
+
     fn build_compute_command<L, A>(&self, builder: &mut AutoCommandBufferBuilder<L, A>)
     where
         A: CommandBufferAllocator,
@@ -471,7 +578,7 @@ impl CommandBufferVk {
         }
 
         let set = PersistentDescriptorSet::new(
-            &self.descriptor_set_allocator,
+            self.descriptor_set_allocator.as_ref(),
             descriptor_set_layout.clone(),
             write_descriptor_sets,
         )
@@ -503,6 +610,18 @@ impl ICommandBuffer for CommandBufferVk {
         self.end();
     }
 
+    fn push_debug_group(&mut self, label: &str) {
+        self.push_debug_group(label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.pop_debug_group();
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.insert_debug_marker(label);
+    }
+
     fn clear_color(
         &mut self,
         _color_target_view: &mut Self::ColorTargetViewType,
@@ -543,6 +662,16 @@ impl ICommandBuffer for CommandBufferVk {
         self.set_constant_buffer(index, buffer);
     }
 
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_constant_buffer_range(index, buffer, offset, size);
+    }
+
     fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
         self.set_unordered_access_buffer(index, buffer)
     }
@@ -551,6 +680,16 @@ impl ICommandBuffer for CommandBufferVk {
         self.set_vertex_buffer(index, buffer);
     }
 
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_vertex_buffer_range(index, buffer, offset, size);
+    }
+
     fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
         self.set_vertex_state(vertex_state);
     }
@@ -1,18 +1,28 @@
 use raw_window_handle::{
     HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
 };
-use sjgfx_interface::{DeviceInfo, IDevice};
-use std::sync::Arc;
+use sjgfx_interface::{
+    AdapterInfo, BackendMask, DeviceCapabilities, DeviceFeatures, DeviceInfo, IDevice, ImageFormat,
+    ImageFormatCapability, ImageFormatUsage, QueueType,
+};
+use std::sync::{atomic::AtomicUsize, Arc};
 use vulkano::{
+    command_buffer::allocator::StandardCommandBufferAllocator,
+    descriptor_set::allocator::StandardDescriptorSetAllocator,
     device::{
         physical::{PhysicalDevice, PhysicalDeviceType},
-        Device, DeviceCreateInfo, DeviceExtensions, Queue, QueueCreateInfo, QueueFlags,
+        Device, DeviceCreateInfo, DeviceExtensions, DeviceOwned, Queue, QueueCreateInfo,
+        QueueFlags,
     },
-    instance::{Instance, InstanceCreateInfo},
+    image::{ImageAspects, SampleCounts},
+    instance::{Instance, InstanceCreateInfo, InstanceExtensions},
+    memory::allocator::StandardMemoryAllocator,
     swapchain::Surface,
-    Version, VulkanLibrary,
+    Version, VulkanLibrary, VulkanObject,
 };
 
+use crate::resource_pool_vk::ResourcePoolVk;
+
 #[derive(Debug)]
 struct Handler {
     raw_window_handle: RawWindowHandle,
@@ -34,23 +44,49 @@ unsafe impl HasRawDisplayHandle for Handler {
     }
 }
 
+struct DeviceQueues {
+    graphics: Arc<Queue>,
+    compute: Option<Arc<Queue>>,
+    transfer: Option<Arc<Queue>>,
+}
+
 pub struct DeviceVk {
     device: Arc<vulkano::device::Device>,
     queue: Arc<vulkano::device::Queue>,
+    compute_queue: Option<Arc<vulkano::device::Queue>>,
+    transfer_queue: Option<Arc<vulkano::device::Queue>>,
     surface: Option<Arc<Surface>>,
+    adapter_index: usize,
+
+    // 各リソースはデバイスごとに共有するアロケーターから確保する
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    command_buffer_allocator: Arc<StandardCommandBufferAllocator>,
+    descriptor_set_allocator: Arc<StandardDescriptorSetAllocator>,
+    resource_pool: Arc<ResourcePoolVk>,
+
+    // キューにためてまだ flush していないコマンドの数。読み戻しの前に確認する
+    unflushed_command_count: Arc<AtomicUsize>,
 }
 
 impl DeviceVk {
-    pub fn new(_info: &DeviceInfo) -> Self {
-        let (_instance, device, queue) = Self::create_device();
-        Self {
-            device,
-            queue,
-            surface: None,
-        }
+    pub fn new(info: &DeviceInfo) -> Self {
+        Self::try_new(info).expect("no Vulkan device is available")
     }
 
-    pub fn new_from_handle<T>(_info: &DeviceInfo, handle: &T) -> Self
+    pub fn new_from_handle<T>(info: &DeviceInfo, handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::try_new_from_handle(info, handle).expect("no Vulkan device is available")
+    }
+
+    // Vulkan のローダーや使える物理デバイスがなければ None を返す
+    pub fn try_new(info: &DeviceInfo) -> Option<Self> {
+        let (_instance, adapter_index, device, queues) = Self::create_device(info)?;
+        Some(Self::new_impl(adapter_index, device, queues, None))
+    }
+
+    pub fn try_new_from_handle<T>(info: &DeviceInfo, handle: &T) -> Option<Self>
     where
         T: HasRawWindowHandle + HasRawDisplayHandle,
     {
@@ -59,12 +95,38 @@ impl DeviceVk {
             raw_display_handle: handle.raw_display_handle(),
         });
 
-        let (instance, device, queue) = Self::create_device();
-        let surface = vulkano_win::create_surface_from_handle(handler, instance).unwrap();
+        let (instance, adapter_index, device, queues) = Self::create_device(info)?;
+        let surface = vulkano_win::create_surface_from_handle(handler, instance).ok()?;
+        Some(Self::new_impl(adapter_index, device, queues, Some(surface)))
+    }
+
+    fn new_impl(
+        adapter_index: usize,
+        device: Arc<Device>,
+        queues: DeviceQueues,
+        surface: Option<Arc<Surface>>,
+    ) -> Self {
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator = Arc::new(StandardCommandBufferAllocator::new(
+            device.clone(),
+            Default::default(),
+        ));
+        let descriptor_set_allocator =
+            Arc::new(StandardDescriptorSetAllocator::new(device.clone()));
+        let resource_pool = Arc::new(ResourcePoolVk::new(memory_allocator.clone()));
+
         Self {
             device,
-            queue,
-            surface: Some(surface),
+            adapter_index,
+            queue: queues.graphics,
+            compute_queue: queues.compute,
+            transfer_queue: queues.transfer,
+            surface,
+            memory_allocator,
+            command_buffer_allocator,
+            descriptor_set_allocator,
+            resource_pool,
+            unflushed_command_count: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -80,37 +142,214 @@ impl DeviceVk {
         self.queue.clone()
     }
 
+    // 専用のキューファミリーがなければグラフィックスキューを返す
+    pub fn clone_queue_of_type(&self, queue_type: &QueueType) -> Arc<vulkano::device::Queue> {
+        let queue = match queue_type {
+            QueueType::Graphics => None,
+            QueueType::Compute => self.compute_queue.as_ref(),
+            QueueType::Transfer => self.transfer_queue.as_ref().or(self.compute_queue.as_ref()),
+        };
+        queue.unwrap_or(&self.queue).clone()
+    }
+
+    pub fn get_memory_allocator(&self) -> &StandardMemoryAllocator {
+        &self.memory_allocator
+    }
+
+    pub fn clone_memory_allocator(&self) -> Arc<StandardMemoryAllocator> {
+        self.memory_allocator.clone()
+    }
+
+    pub fn clone_command_buffer_allocator(&self) -> Arc<StandardCommandBufferAllocator> {
+        self.command_buffer_allocator.clone()
+    }
+
+    pub fn clone_descriptor_set_allocator(&self) -> Arc<StandardDescriptorSetAllocator> {
+        self.descriptor_set_allocator.clone()
+    }
+
+    pub(crate) fn get_resource_pool(&self) -> &ResourcePoolVk {
+        &self.resource_pool
+    }
+
+    pub(crate) fn clone_resource_pool(&self) -> Arc<ResourcePoolVk> {
+        self.resource_pool.clone()
+    }
+
+    pub(crate) fn clone_unflushed_command_count(&self) -> Arc<AtomicUsize> {
+        self.unflushed_command_count.clone()
+    }
+
+    // プールしている一時リソースのうち使われていないものを解放する
+    pub fn trim_resource_pool(&self) {
+        self.resource_pool.trim();
+    }
+
+    // サーフェスなしで作ったデバイスでは呼べない (vulkano はオフスクリーンのスワップチェーンに対応していない)
     pub fn clone_surface(&self) -> Arc<Surface> {
-        self.surface.as_ref().unwrap().clone()
+        self.surface
+            .as_ref()
+            .expect("vulkano backend doesn't support offscreen swap chains")
+            .clone()
+    }
+
+    // デバイスを作ったときとは別のウィンドウのサーフェスを作る
+    pub fn create_surface<T>(&self, handle: &T) -> Arc<Surface>
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let handler = Arc::new(Handler {
+            raw_window_handle: handle.raw_window_handle(),
+            raw_display_handle: handle.raw_display_handle(),
+        });
+        vulkano_win::create_surface_from_handle(handler, self.device.instance().clone()).unwrap()
     }
 
     pub fn get_physical_device(&self) -> &PhysicalDevice {
         self.device.physical_device()
     }
 
-    fn create_device() -> (Arc<Instance>, Arc<Device>, Arc<Queue>) {
-        let vulkan_library = VulkanLibrary::new().unwrap();
+    pub fn is_debug_label_enabled(&self) -> bool {
+        self.device.instance().enabled_extensions().ext_debug_utils
+    }
+
+    pub(crate) fn set_debug_label<T>(&self, object: &T, label: Option<&str>)
+    where
+        T: VulkanObject + DeviceOwned,
+    {
+        // VK_EXT_debug_utils が使えない環境ではなにもしない
+        if label.is_none() || !self.is_debug_label_enabled() {
+            return;
+        }
+
+        self.device
+            .set_debug_utils_object_name(object, label)
+            .unwrap();
+    }
+
+    pub fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        let Some(instance) = Self::create_instance() else {
+            return Vec::new();
+        };
+        Self::enumerate_physical_devices(&instance, info)
+            .iter()
+            .enumerate()
+            .map(|(index, (physical_device, _))| {
+                crate::util::convert_to_adapter_info(index, physical_device)
+            })
+            .collect()
+    }
+
+    pub fn get_adapter_info(&self) -> AdapterInfo {
+        crate::util::convert_to_adapter_info(self.adapter_index, self.device.physical_device())
+    }
+
+    pub fn get_capabilities(&self) -> DeviceCapabilities {
+        let physical_device = self.device.physical_device();
+        let properties = physical_device.properties();
+        let image_format_capabilities = ImageFormat::ALL.iter().map(|format| {
+            let vulkan_format = crate::util::convert_format(format.clone());
+            let format_features = physical_device
+                .format_properties(vulkan_format)
+                .unwrap()
+                .optimal_tiling_features;
+            let mut usage = crate::util::convert_from_format_features(format_features);
+
+            // 4x は Vulkan で必ずサポートされているのでそれ以上を MSAA 対応とみなす
+            let sample_counts = if vulkan_format
+                .aspects()
+                .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+            {
+                properties.framebuffer_depth_sample_counts
+            } else {
+                properties.framebuffer_color_sample_counts
+            };
+            if usage.contains(ImageFormatUsage::RENDER_TARGET)
+                && sample_counts.contains(SampleCounts::SAMPLE_4)
+            {
+                usage |= ImageFormatUsage::MULTISAMPLE;
+            }
+            ImageFormatCapability::new(format.clone(), usage)
+        });
+
+        let mut features = crate::util::convert_from_features(self.device.enabled_features());
+        features.set(
+            DeviceFeatures::TIMESTAMP_QUERY,
+            properties.timestamp_compute_and_graphics,
+        );
+
+        let max_compute_work_group_count = properties.max_compute_work_group_count;
+        DeviceCapabilities::new()
+            .set_max_texture_size(properties.max_image_dimension2_d)
+            .set_max_texture_array_layers(properties.max_image_array_layers)
+            .set_max_constant_buffers_per_stage(properties.max_per_stage_descriptor_uniform_buffers)
+            .set_max_unordered_access_buffers_per_stage(
+                properties.max_per_stage_descriptor_storage_buffers,
+            )
+            .set_max_textures_per_stage(properties.max_per_stage_descriptor_sampled_images)
+            .set_max_images_per_stage(properties.max_per_stage_descriptor_storage_images)
+            .set_max_samplers_per_stage(properties.max_per_stage_descriptor_samplers)
+            .set_max_compute_workgroup_size(properties.max_compute_work_group_size)
+            .set_max_compute_invocations_per_workgroup(
+                properties.max_compute_work_group_invocations,
+            )
+            .set_max_compute_workgroups_per_dimension(
+                *max_compute_work_group_count.iter().min().unwrap(),
+            )
+            .set_constant_buffer_offset_alignment(
+                properties
+                    .min_uniform_buffer_offset_alignment
+                    .as_devicesize() as u32,
+            )
+            .set_unordered_access_buffer_offset_alignment(
+                properties
+                    .min_storage_buffer_offset_alignment
+                    .as_devicesize() as u32,
+            )
+            .set_image_format_capabilities(image_format_capabilities)
+            .set_features(features)
+    }
+
+    fn create_instance() -> Option<Arc<Instance>> {
+        let vulkan_library = VulkanLibrary::new().ok()?;
 
         let required_extensions = vulkano_win::required_extensions(&vulkan_library);
-        let instance = Instance::new(
+
+        // デバッグラベルを設定できるようにサポートされていれば有効にしておく
+        let debug_extensions = InstanceExtensions {
+            ext_debug_utils: vulkan_library.supported_extensions().ext_debug_utils,
+            ..InstanceExtensions::empty()
+        };
+        Instance::new(
             vulkan_library,
             InstanceCreateInfo {
-                enabled_extensions: required_extensions,
+                enabled_extensions: required_extensions.union(&debug_extensions),
                 engine_version: Version::V1_2,
                 ..Default::default()
             },
         )
-        .unwrap();
+        .ok()
+    }
+
+    // 条件を満たす物理デバイスとグラフィックスキューのファミリーを列挙する
+    // ここでの並び順がアダプターのインデックスになる
+    fn enumerate_physical_devices(
+        instance: &Arc<Instance>,
+        info: &DeviceInfo,
+    ) -> Vec<(Arc<PhysicalDevice>, u32)> {
+        if !info.get_backends().contains(BackendMask::VULKAN) {
+            return Vec::new();
+        }
 
-        // 物理デバイスの取得
         let device_ext = vulkano::device::DeviceExtensions {
             khr_swapchain: true,
             khr_maintenance1: true,
             ..vulkano::device::DeviceExtensions::empty()
         };
-        let (physical_device, queue_family_index) = instance
-            .enumerate_physical_devices()
-            .unwrap()
+        let Ok(physical_devices) = instance.enumerate_physical_devices() else {
+            return Vec::new();
+        };
+        physical_devices
             .filter(|p| p.supported_extensions().contains(&device_ext))
             .filter_map(|p| {
                 p.queue_family_properties()
@@ -122,45 +361,111 @@ impl DeviceVk {
                     })
                     .map(|q| (p, q as u32))
             })
-            .min_by_key(|(p, _)| match p.properties().device_type {
-                PhysicalDeviceType::DiscreteGpu => 0,
-                PhysicalDeviceType::IntegratedGpu => 1,
-                PhysicalDeviceType::VirtualGpu => 2,
-                PhysicalDeviceType::Cpu => 3,
-                PhysicalDeviceType::Other => 4,
-                _ => 5,
+            .collect()
+    }
+
+    fn select_physical_device(
+        instance: &Arc<Instance>,
+        info: &DeviceInfo,
+    ) -> Option<(usize, Arc<PhysicalDevice>, u32)> {
+        let mut physical_devices = Self::enumerate_physical_devices(instance, info);
+
+        // 範囲外のインデックスは無視して電力設定から選びなおす
+        if let Some(adapter_index) = info.get_adapter_index() {
+            if adapter_index < physical_devices.len() {
+                let (physical_device, queue_family_index) =
+                    physical_devices.swap_remove(adapter_index);
+                return Some((adapter_index, physical_device, queue_family_index));
+            }
+        }
+
+        physical_devices
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (p, _))| {
+                !info.is_fallback_adapter_only()
+                    || p.properties().device_type == PhysicalDeviceType::Cpu
             })
-            .unwrap();
+            .min_by_key(|(_, (p, _))| {
+                let adapter_type =
+                    crate::util::convert_from_device_type(p.properties().device_type);
+                info.get_power_preference()
+                    .get_adapter_priority(&adapter_type)
+            })
+            .map(|(index, (p, q))| (index, p, q))
+    }
+
+    fn create_device(
+        info: &DeviceInfo,
+    ) -> Option<(Arc<Instance>, usize, Arc<Device>, DeviceQueues)> {
+        let instance = Self::create_instance()?;
+        let (adapter_index, physical_device, queue_family_index) =
+            Self::select_physical_device(&instance, info)?;
 
         let device_extensions = DeviceExtensions {
             khr_swapchain: true,
             ..DeviceExtensions::empty()
         };
+
+        // 要求された機能のうち物理デバイスがサポートしているものだけを有効にする
+        let enabled_features = crate::util::convert_to_features(info.get_optional_features())
+            .intersection(physical_device.supported_features());
+
+        // 非同期コンピュートや転送用に専用のキューファミリーがあれば使う
+        let queue_family_properties = physical_device.queue_family_properties();
+        let compute_queue_family_index = queue_family_properties
+            .iter()
+            .position(|q| {
+                q.queue_flags.intersects(QueueFlags::COMPUTE)
+                    && !q.queue_flags.intersects(QueueFlags::GRAPHICS)
+            })
+            .map(|x| x as u32);
+        let transfer_queue_family_index = queue_family_properties
+            .iter()
+            .position(|q| {
+                q.queue_flags.intersects(QueueFlags::TRANSFER)
+                    && !q
+                        .queue_flags
+                        .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+            })
+            .map(|x| x as u32);
+        let queue_create_infos = [
+            Some(queue_family_index),
+            compute_queue_family_index,
+            transfer_queue_family_index,
+        ]
+        .iter()
+        .flatten()
+        .map(|queue_family_index| QueueCreateInfo {
+            queue_family_index: *queue_family_index,
+            ..Default::default()
+        })
+        .collect();
+
         let (device, mut queues) = Device::new(
             physical_device,
             DeviceCreateInfo {
                 enabled_extensions: device_extensions,
-                queue_create_infos: vec![QueueCreateInfo {
-                    queue_family_index,
-                    ..Default::default()
-                }],
+                enabled_features,
+                queue_create_infos,
                 ..Default::default()
             },
         )
-        .unwrap();
+        .ok()?;
 
-        (instance, device, queues.next().unwrap())
+        // キューは QueueCreateInfo の順番で返ってくる
+        let device_queues = DeviceQueues {
+            graphics: queues.next().unwrap(),
+            compute: compute_queue_family_index.map(|_| queues.next().unwrap()),
+            transfer: transfer_queue_family_index.map(|_| queues.next().unwrap()),
+        };
+        Some((instance, adapter_index, device, device_queues))
     }
 }
 
 impl IDevice for DeviceVk {
-    fn new(_info: &DeviceInfo) -> Self {
-        let (_instance, device, queue) = Self::create_device();
-        Self {
-            device,
-            queue,
-            surface: None,
-        }
+    fn new(info: &DeviceInfo) -> Self {
+        Self::new(info)
     }
 
     fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
@@ -169,6 +474,18 @@ impl IDevice for DeviceVk {
     {
         Self::new_from_handle(info, raw_handle)
     }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        Self::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.get_adapter_info()
+    }
 }
 
 #[cfg(test)]
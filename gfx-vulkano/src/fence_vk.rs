@@ -1,23 +1,84 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
 use sjgfx_interface::{FenceInfo, IFence};
-use vulkano::sync::{self, GpuFuture};
+use vulkano::sync::{future::FenceSignalFuture, FlushError, GpuFuture};
 
 use crate::DeviceVk;
 
+pub(crate) type FenceSignalFutureVk = Arc<FenceSignalFuture<Box<dyn GpuFuture>>>;
+
 pub struct FenceVk {
-    #[allow(dead_code)]
-    previous_frame_end: Option<Box<dyn GpuFuture>>,
+    // キューの flush 時にフューチャーが設定されるので共有しておく
+    future: Arc<Mutex<Option<FenceSignalFutureVk>>>,
+    is_signaled: bool,
 }
 
 impl FenceVk {
-    pub fn new(device: &DeviceVk, _info: &FenceInfo) -> Self {
+    pub fn new(_device: &DeviceVk, info: &FenceInfo) -> Self {
         Self {
-            previous_frame_end: Some(sync::now(device.clone_device()).boxed()),
+            future: Arc::new(Mutex::new(None)),
+            is_signaled: info.is_signaled(),
+        }
+    }
+
+    pub fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        if self.is_signaled {
+            return true;
+        }
+
+        // 一度もサブミットされていないフェンスはシグナルされない
+        let future = match self.future.lock().unwrap().clone() {
+            Some(future) => future,
+            None => return false,
+        };
+
+        match future.wait(timeout) {
+            Ok(_) => {
+                self.is_signaled = true;
+                true
+            }
+            Err(FlushError::Timeout) => false,
+            Err(e) => panic!("Failed to wait fence: {:?}", e),
+        }
+    }
+
+    pub fn is_signaled(&self) -> bool {
+        if self.is_signaled {
+            return true;
+        }
+
+        match self.future.lock().unwrap().as_ref() {
+            Some(future) => future.is_signaled().unwrap(),
+            None => false,
         }
     }
 
-    #[allow(dead_code)]
+    pub fn reset(&mut self) {
+        self.is_signaled = false;
+        *self.future.lock().unwrap() = None;
+    }
+
+    pub(crate) fn signal(&mut self) {
+        self.is_signaled = true;
+        *self.future.lock().unwrap() = None;
+    }
+
+    pub(crate) fn clone_future_slot(&mut self) -> Arc<Mutex<Option<FenceSignalFutureVk>>> {
+        self.is_signaled = false;
+        *self.future.lock().unwrap() = None;
+        self.future.clone()
+    }
+
     pub(crate) fn cleanup_finished(&mut self) {
-        self.previous_frame_end.as_mut().unwrap().cleanup_finished();
+        if let Some(future) = self.future.lock().unwrap().as_mut() {
+            // 他から参照されていなければ後始末できる
+            if let Some(future) = Arc::get_mut(future) {
+                future.cleanup_finished();
+            }
+        }
     }
 }
 
@@ -27,4 +88,16 @@ impl IFence for FenceVk {
     fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
         Self::new(device, info)
     }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        self.wait(timeout)
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
 }
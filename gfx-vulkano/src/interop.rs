@@ -1,5 +1,5 @@
 use sjgfx_interface::GpuAccess;
-use vulkano::buffer::BufferUsage;
+use vulkano::{buffer::BufferUsage, image::ImageUsage};
 
 pub fn convert_usage(gpu_access: &GpuAccess) -> BufferUsage {
     let mut buffer_usage = Default::default();
@@ -18,6 +18,30 @@ pub fn convert_usage(gpu_access: &GpuAccess) -> BufferUsage {
     if gpu_access.contains(GpuAccess::INDIRECT_BUFFER) {
         buffer_usage |= BufferUsage::INDIRECT_BUFFER;
     }
+    if gpu_access.contains(GpuAccess::READ) {
+        buffer_usage |= BufferUsage::TRANSFER_SRC;
+    }
+    if gpu_access.contains(GpuAccess::WRITE) {
+        buffer_usage |= BufferUsage::TRANSFER_DST;
+    }
 
     buffer_usage
 }
+
+pub fn convert_image_usage(gpu_access: &GpuAccess) -> ImageUsage {
+    let mut image_usage = ImageUsage::empty();
+    if gpu_access.contains(GpuAccess::TEXTURE) {
+        image_usage |= ImageUsage::SAMPLED;
+    }
+    if gpu_access.contains(GpuAccess::IMAGE) {
+        image_usage |= ImageUsage::STORAGE;
+    }
+    if gpu_access.contains(GpuAccess::READ) {
+        image_usage |= ImageUsage::TRANSFER_SRC;
+    }
+    if gpu_access.contains(GpuAccess::WRITE) {
+        image_usage |= ImageUsage::TRANSFER_DST;
+    }
+
+    image_usage
+}
@@ -6,12 +6,14 @@ mod device_vk;
 mod fence_vk;
 mod interop;
 mod queue_vk;
+mod resource_pool_vk;
 mod sampler_vk;
 mod semaphore_vk;
 mod shader_vk;
 mod surface_vk;
 mod swap_chain_vk;
 mod texture_view_vk;
+mod transfer_vk;
 mod texture_vk;
 mod util;
 mod vertex_state_vk;
@@ -1,8 +1,13 @@
-use crate::{CommandBufferVk, DeviceVk, FenceVk, SwapChainVk};
-use sjgfx_interface::{IQueue, QueueInfo};
-use std::sync::Arc;
+use crate::fence_vk::FenceSignalFutureVk;
+use crate::semaphore_vk::TimelineVk;
+use crate::{CommandBufferVk, DeviceVk, FenceVk, SemaphoreVk, SwapChainVk};
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
 use vulkano::command_buffer::AutoCommandBufferBuilder;
-use vulkano::device::Queue;
+use vulkano::device::{Queue, QueueFlags};
 use vulkano::swapchain::SwapchainPresentInfo;
 use vulkano::{
     command_buffer::PrimaryAutoCommandBuffer,
@@ -15,131 +20,212 @@ use vulkano::{
 pub struct QueueVk {
     device: Arc<Device>,
     queue: Arc<Queue>,
+    queue_type: QueueType,
     previous_frame_end: Option<Box<dyn GpuFuture>>,
-    command_builder: Option<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>>,
+    command_builders: Vec<AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>>,
     swap_chain: Option<Arc<Swapchain>>,
     swap_chain_acquire_future: Option<SwapchainAcquireFuture>,
     image_index: Option<usize>,
+    swap_chain_needs_recreate: Option<Arc<AtomicBool>>,
+    fence_slot: Option<Arc<Mutex<Option<FenceSignalFutureVk>>>>,
+    signal_semaphores: Vec<(Arc<TimelineVk>, u64)>,
+
+    // デバイスで共有している flush していないコマンドの数
+    unflushed_command_count: Arc<AtomicUsize>,
 }
 
 impl QueueVk {
-    pub fn new(device: &DeviceVk, _info: &QueueInfo) -> Self {
+    pub fn new(device: &DeviceVk, info: &QueueInfo) -> Self {
         Self {
             device: device.clone_device(),
-            queue: device.clone_queue(),
+            queue: device.clone_queue_of_type(info.get_queue_type()),
+            queue_type: info.get_queue_type().clone(),
             previous_frame_end: Some(sync::now(device.clone_device()).boxed()),
-            command_builder: None,
+            command_builders: Vec::new(),
             swap_chain: None,
             swap_chain_acquire_future: None,
             image_index: None,
+            swap_chain_needs_recreate: None,
+            fence_slot: None,
+            signal_semaphores: Vec::new(),
+            unflushed_command_count: device.clone_unflushed_command_count(),
         }
     }
 
     pub fn execute(&mut self, command_buffer: &CommandBufferVk) {
-        let command_builder = command_buffer.build_command_builder();
-        self.command_builder = Some(command_builder);
+        // 専用のコンピュートキューや転送キューでは描画できない
+        let queue_family_index = self.queue.queue_family_index();
+        assert!(
+            !command_buffer.is_graphics_command()
+                || self.device.physical_device().queue_family_properties()
+                    [queue_family_index as usize]
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS),
+            "graphics commands cannot be executed on a {:?} queue",
+            self.queue_type
+        );
+        let command_builder = command_buffer.build_command_builder(queue_family_index);
+        self.command_builders.push(command_builder);
+        self.unflushed_command_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // ためているコマンドとあわせて一度にサブミットする
+    pub fn execute_many(&mut self, command_buffers: &[&CommandBufferVk]) {
+        for command_buffer in command_buffers {
+            self.execute(command_buffer);
+        }
+        self.flush();
+    }
+
+    // フェンスは flush でコマンドがサブミットされたあとシグナルされる
+    pub fn execute_with_fence(&mut self, command_buffer: &CommandBufferVk, fence: &mut FenceVk) {
+        self.execute(command_buffer);
+        self.fence_slot = Some(fence.clone_future_slot());
+    }
+
+    // シグナルは flush でコマンドがサブミットされたあと GPU で完了したときに行われる
+    pub fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &CommandBufferVk,
+        wait_semaphores: &[(&SemaphoreVk, u64)],
+        signal_semaphores: &[(&SemaphoreVk, u64)],
+    ) {
+        // このキューでまだサブミットしていないシグナルを待つときは先にサブミットしておく
+        let is_flush_required = wait_semaphores.iter().any(|(semaphore, value)| {
+            let timeline = semaphore.clone_timeline();
+            self.signal_semaphores
+                .iter()
+                .any(|(x, signal_value)| Arc::ptr_eq(x, &timeline) && *signal_value >= *value)
+        });
+        if is_flush_required {
+            self.flush();
+        }
+
+        // vulkano 0.33 はタイムラインセマフォに対応していないので待ち合わせは CPU で行う
+        // ほかのキューが flush していないシグナルは CPU で待っても完了しないので拒否する
+        for (semaphore, value) in wait_semaphores {
+            assert!(
+                !semaphore.clone_timeline().is_waiting_unflushed_signal(*value),
+                "semaphore value {} is signaled by work that is not flushed on another queue. flush that queue first",
+                value
+            );
+            semaphore.wait(*value, None);
+        }
+
+        self.execute(command_buffer);
+        for (semaphore, value) in signal_semaphores {
+            let timeline = semaphore.clone_timeline();
+            timeline.reserve_signal(*value);
+            self.signal_semaphores.push((timeline, *value));
+        }
+    }
+
+    pub fn get_queue_type(&self) -> &QueueType {
+        &self.queue_type
     }
 
     pub fn flush(&mut self) {
         self.previous_frame_end.as_mut().unwrap().cleanup_finished();
 
-        if self.command_builder.is_none() {
+        if self.command_builders.is_empty() {
             return;
         }
 
         // CommandBuilder
-        let mut command_builder = None;
-        std::mem::swap(&mut command_builder, &mut self.command_builder);
+        let command_builders = std::mem::take(&mut self.command_builders);
+        self.unflushed_command_count
+            .fetch_sub(command_builders.len(), Ordering::Relaxed);
 
         // SwapChain
-        let mut swap_chain = None;
-        std::mem::swap(&mut swap_chain, &mut self.swap_chain);
+        let swap_chain = self.swap_chain.take();
 
         // SwapChain Image Index
-        let mut image_index = None;
-        std::mem::swap(&mut image_index, &mut self.image_index);
+        let image_index = self.image_index.take();
+
+        // 表示に失敗したらスワップチェインに作りなおしてもらう
+        let swap_chain_needs_recreate = self.swap_chain_needs_recreate.take();
 
         // SwapChain Acquire Future
-        let mut swap_chain_acquire_future = None;
-        std::mem::swap(
-            &mut swap_chain_acquire_future,
-            &mut self.swap_chain_acquire_future,
-        );
+        let swap_chain_acquire_future = self.swap_chain_acquire_future.take();
 
-        if swap_chain_acquire_future.is_some() {
-            let future = self
-                .previous_frame_end
-                .take()
-                .unwrap()
-                .join(swap_chain_acquire_future.unwrap())
-                .then_execute(
-                    self.queue.clone(),
-                    command_builder.unwrap().build().unwrap(),
-                )
+        let mut future = self.previous_frame_end.take().unwrap();
+        if let Some(swap_chain_acquire_future) = swap_chain_acquire_future {
+            future = future.join(swap_chain_acquire_future).boxed();
+        }
+        for command_builder in command_builders {
+            future = future
+                .then_execute(self.queue.clone(), command_builder.build().unwrap())
                 .unwrap()
+                .boxed();
+        }
+        if let Some(swap_chain) = swap_chain {
+            future = future
                 .then_swapchain_present(
                     self.queue.clone(),
                     SwapchainPresentInfo::swapchain_image_index(
-                        swap_chain.unwrap(),
+                        swap_chain,
                         image_index.unwrap() as u32,
                     ),
                 )
-                .then_signal_fence_and_flush();
+                .boxed();
+        }
 
-            let next_frame = match future {
-                Ok(future) => {
-                    // TODO
-                    //future.wait(None).unwrap();
-                    Some(future.boxed())
-                }
-                Err(FlushError::OutOfDate) => {
-                    //recreate_swapchain = true;
-                    Some(sync::now(self.device.clone()).boxed())
-                }
-                Err(e) => {
-                    println!("Failed to flush future: {:?}", e);
-                    Some(sync::now(self.device.clone()).boxed())
-                }
-            };
-            self.previous_frame_end = next_frame;
-        } else {
-            let future = self
-                .previous_frame_end
-                .take()
-                .unwrap()
-                .then_execute(
-                    self.queue.clone(),
-                    command_builder.unwrap().build().unwrap(),
-                )
-                .unwrap()
-                .then_signal_fence_and_flush();
+        // Fence
+        let fence_slot = self.fence_slot.take();
+
+        // Semaphore
+        let signal_semaphores = std::mem::take(&mut self.signal_semaphores);
 
-            let next_frame = match future {
-                Ok(future) => {
-                    // TODO
-                    //future.wait(None).unwrap();
-                    Some(future.boxed())
+        let next_frame = match future.then_signal_fence_and_flush() {
+            Ok(future) => {
+                // TODO
+                //future.wait(None).unwrap();
+                let future = Arc::new(future);
+                if let Some(fence_slot) = fence_slot {
+                    *fence_slot.lock().unwrap() = Some(future.clone());
                 }
-                Err(FlushError::OutOfDate) => {
-                    //recreate_swapchain = true;
-                    Some(sync::now(self.device.clone()).boxed())
+                for (timeline, value) in signal_semaphores {
+                    timeline.push_signal_future(value, future.clone());
                 }
-                Err(e) => {
-                    println!("Failed to flush future: {:?}", e);
-                    Some(sync::now(self.device.clone()).boxed())
+                Some(future.boxed())
+            }
+            Err(FlushError::OutOfDate) => {
+                if let Some(needs_recreate) = swap_chain_needs_recreate {
+                    needs_recreate.store(true, Ordering::Relaxed);
                 }
-            };
-            self.previous_frame_end = next_frame;
+                Self::signal_semaphores_immediately(signal_semaphores);
+                Some(sync::now(self.device.clone()).boxed())
+            }
+            Err(e) => {
+                println!("Failed to flush future: {:?}", e);
+                Self::signal_semaphores_immediately(signal_semaphores);
+                Some(sync::now(self.device.clone()).boxed())
+            }
+        };
+        self.previous_frame_end = next_frame;
+    }
+
+    // サブミットに失敗したときに待っている側が止まらないようにする
+    fn signal_semaphores_immediately(signal_semaphores: Vec<(Arc<TimelineVk>, u64)>) {
+        for (timeline, value) in signal_semaphores {
+            timeline.signal_unflushed(value);
         }
     }
 
+    // イメージを取得できなかったフレームは表示しない
     pub fn present(&mut self, swap_chain: &mut SwapChainVk) {
-        self.swap_chain_acquire_future = Some(swap_chain.unwrap_acquire_future());
+        let Some(acquire_future) = swap_chain.take_acquire_future() else {
+            return;
+        };
+        self.swap_chain_acquire_future = Some(acquire_future);
         self.image_index = Some(swap_chain.get_current_index() as usize);
         self.swap_chain = Some(swap_chain.clone_swap_chain());
+        self.swap_chain_needs_recreate = Some(swap_chain.clone_needs_recreate());
     }
 
     pub fn sync(&mut self) {
+        self.flush();
+
         unsafe {
             self.queue.device().wait_idle().unwrap();
         }
@@ -149,10 +235,19 @@ impl QueueVk {
     }
 }
 
+// flush しないまま破棄されたコマンドは読み戻しを止めないように数えなおす
+impl Drop for QueueVk {
+    fn drop(&mut self) {
+        self.unflushed_command_count
+            .fetch_sub(self.command_builders.len(), Ordering::Relaxed);
+    }
+}
+
 impl IQueue for QueueVk {
     type DeviceType = DeviceVk;
     type CommandBufferType = CommandBufferVk;
     type FenceType = FenceVk;
+    type SemaphoreType = SemaphoreVk;
     type SwapChainType = SwapChainVk;
 
     fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
@@ -163,12 +258,29 @@ impl IQueue for QueueVk {
         self.execute(command_buffer);
     }
 
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        self.execute_many(command_buffers);
+    }
+
     fn execute_with_fence(
         &mut self,
-        _command_buffer: &Self::CommandBufferType,
-        _fence: &mut Self::FenceType,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
     ) {
-        todo!()
+        self.execute_with_fence(command_buffer, fence);
+    }
+
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        self.execute_with_semaphores(command_buffer, wait_semaphores, signal_semaphores);
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.get_queue_type()
     }
 
     fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
@@ -0,0 +1,168 @@
+use std::sync::{Arc, Mutex};
+
+use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage, Subbuffer},
+    format::Format,
+    image::{AttachmentImage, ImageUsage},
+    memory::allocator::{AllocationCreateInfo, MemoryUsage, StandardMemoryAllocator},
+    DeviceSize,
+};
+
+// この回数だけ取得されるあいだ使われなかったリソースは解放する
+const RETAIN_ACQUIRE_COUNT: u64 = 64;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ImageDescriptor {
+    pub dimensions: [u32; 2],
+    pub format: Format,
+    pub usage: ImageUsage,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BufferDescriptor {
+    pub size: DeviceSize,
+    pub usage: BufferUsage,
+    pub memory_usage: MemoryUsage,
+}
+
+struct PoolEntry<TDescriptor, TResource> {
+    descriptor: TDescriptor,
+    resource: TResource,
+    last_acquired: u64,
+}
+
+struct Pool<TDescriptor, TResource> {
+    entries: Vec<PoolEntry<TDescriptor, TResource>>,
+    acquire_count: u64,
+}
+
+impl<TDescriptor: PartialEq, TResource: Clone> Pool<TDescriptor, TResource> {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            acquire_count: 0,
+        }
+    }
+
+    // is_idle はプール以外から参照されていないかを返す
+    fn acquire<TIsIdle, TCreate>(
+        &mut self,
+        descriptor: TDescriptor,
+        is_idle: TIsIdle,
+        create: TCreate,
+    ) -> TResource
+    where
+        TIsIdle: Fn(&TResource) -> bool,
+        TCreate: FnOnce(&TDescriptor) -> TResource,
+    {
+        self.acquire_count += 1;
+        let acquire_count = self.acquire_count;
+
+        // しばらく使われていないリソースを解放する
+        // ウィンドウのリサイズで古いサイズのレンダーターゲットが残り続けないようにする
+        self.entries.retain(|entry| {
+            !is_idle(&entry.resource) || acquire_count - entry.last_acquired <= RETAIN_ACQUIRE_COUNT
+        });
+
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.descriptor == descriptor && is_idle(&entry.resource))
+        {
+            entry.last_acquired = acquire_count;
+            return entry.resource.clone();
+        }
+
+        let resource = create(&descriptor);
+        self.entries.push(PoolEntry {
+            descriptor,
+            resource: resource.clone(),
+            last_acquired: acquire_count,
+        });
+        resource
+    }
+
+    fn trim<TIsIdle: Fn(&TResource) -> bool>(&mut self, is_idle: TIsIdle) {
+        self.entries.retain(|entry| !is_idle(&entry.resource));
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+// 一時的なレンダーターゲットとステージングバッファーを記述子ごとに使いまわす
+// 取得したリソースがどこからも参照されなくなったら再利用する
+pub(crate) struct ResourcePoolVk {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    images: Mutex<Pool<ImageDescriptor, Arc<AttachmentImage>>>,
+    buffers: Mutex<Pool<BufferDescriptor, Subbuffer<[u8]>>>,
+}
+
+impl ResourcePoolVk {
+    pub fn new(memory_allocator: Arc<StandardMemoryAllocator>) -> Self {
+        Self {
+            memory_allocator,
+            images: Mutex::new(Pool::new()),
+            buffers: Mutex::new(Pool::new()),
+        }
+    }
+
+    pub fn acquire_image(&self, descriptor: ImageDescriptor) -> Arc<AttachmentImage> {
+        self.images.lock().unwrap().acquire(
+            descriptor,
+            |image| Arc::strong_count(image) == 1,
+            |descriptor| {
+                AttachmentImage::with_usage(
+                    self.memory_allocator.as_ref(),
+                    descriptor.dimensions,
+                    descriptor.format,
+                    descriptor.usage,
+                )
+                .unwrap()
+            },
+        )
+    }
+
+    pub fn acquire_buffer(&self, descriptor: BufferDescriptor) -> Subbuffer<[u8]> {
+        self.buffers.lock().unwrap().acquire(
+            descriptor,
+            |buffer| Arc::strong_count(buffer.buffer()) == 1,
+            |descriptor| {
+                Buffer::new_slice::<u8>(
+                    self.memory_allocator.as_ref(),
+                    BufferCreateInfo {
+                        usage: descriptor.usage,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo {
+                        usage: descriptor.memory_usage,
+                        ..Default::default()
+                    },
+                    descriptor.size,
+                )
+                .unwrap()
+            },
+        )
+    }
+
+    // 使われていないリソースをすべて解放する
+    pub fn trim(&self) {
+        self.images
+            .lock()
+            .unwrap()
+            .trim(|image| Arc::strong_count(image) == 1);
+        self.buffers
+            .lock()
+            .unwrap()
+            .trim(|buffer| Arc::strong_count(buffer.buffer()) == 1);
+    }
+
+    pub fn get_image_count(&self) -> usize {
+        self.images.lock().unwrap().len()
+    }
+
+    pub fn get_buffer_count(&self) -> usize {
+        self.buffers.lock().unwrap().len()
+    }
+}
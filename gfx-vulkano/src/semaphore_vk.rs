@@ -1,13 +1,182 @@
-use sjgfx_interface::ISemaphore;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
-use crate::DeviceVk;
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+use vulkano::sync::FlushError;
 
-pub struct SemaphoreVk;
+use crate::{fence_vk::FenceSignalFutureVk, DeviceVk};
+
+struct TimelineState {
+    // GPU で完了した値
+    value: u64,
+
+    // サブミット済みで完了を待っているシグナル
+    pending_signals: Vec<(u64, FenceSignalFutureVk)>,
+
+    // キューに積まれているがまだ flush されていないシグナル
+    unflushed_signals: Vec<u64>,
+}
+
+// vulkano 0.33 はタイムラインセマフォに対応していないので、フェンスでタイムラインの値を管理する
+pub(crate) struct TimelineVk {
+    state: Mutex<TimelineState>,
+}
+
+impl TimelineVk {
+    fn new(initial_value: u64) -> Self {
+        Self {
+            state: Mutex::new(TimelineState {
+                value: initial_value,
+                pending_signals: Vec::new(),
+                unflushed_signals: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn get_value(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        Self::update(&mut state);
+        state.value
+    }
+
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        let start = Instant::now();
+        loop {
+            // 目標の値をシグナルするサブミットがあればそのフェンスを待つ
+            let future = {
+                let mut state = self.state.lock().unwrap();
+                Self::update(&mut state);
+                if state.value >= value {
+                    return true;
+                }
+
+                state
+                    .pending_signals
+                    .iter()
+                    .filter(|(signal_value, _)| *signal_value >= value)
+                    .min_by_key(|(signal_value, _)| *signal_value)
+                    .map(|(_, future)| future.clone())
+            };
+
+            let remaining = timeout.map(|x| x.saturating_sub(start.elapsed()));
+            if let Some(future) = future {
+                match future.wait(remaining) {
+                    Ok(_) => continue,
+                    Err(FlushError::Timeout) => return false,
+                    Err(e) => panic!("Failed to wait semaphore: {:?}", e),
+                }
+            }
+
+            // CPU からのシグナルを待つ
+            if remaining == Some(Duration::ZERO) {
+                return false;
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    pub fn signal(&self, value: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.value = state.value.max(value);
+    }
+
+    // キューに積んだシグナルを記録しておく。flush されるまでは GPU から待つことができない
+    pub fn reserve_signal(&self, value: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.unflushed_signals.push(value);
+    }
+
+    // value に達するには flush されていないシグナルを待つ必要がある
+    pub fn is_waiting_unflushed_signal(&self, value: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        Self::update(&mut state);
+        state.value < value
+            && !state
+                .pending_signals
+                .iter()
+                .any(|(signal_value, _)| *signal_value >= value)
+            && state.unflushed_signals.iter().any(|x| *x >= value)
+    }
+
+    pub fn push_signal_future(&self, value: u64, future: FenceSignalFutureVk) {
+        let mut state = self.state.lock().unwrap();
+        Self::remove_unflushed_signal(&mut state, value);
+        state.pending_signals.push((value, future));
+    }
+
+    // サブミットに失敗したシグナルはすぐに完了させる
+    pub fn signal_unflushed(&self, value: u64) {
+        let mut state = self.state.lock().unwrap();
+        Self::remove_unflushed_signal(&mut state, value);
+        state.value = state.value.max(value);
+    }
+
+    fn remove_unflushed_signal(state: &mut TimelineState, value: u64) {
+        if let Some(index) = state.unflushed_signals.iter().position(|x| *x == value) {
+            state.unflushed_signals.swap_remove(index);
+        }
+    }
+
+    fn update(state: &mut TimelineState) {
+        let mut value = state.value;
+        state.pending_signals.retain(|(signal_value, future)| {
+            if future.is_signaled().unwrap() {
+                value = value.max(*signal_value);
+                false
+            } else {
+                true
+            }
+        });
+        state.value = value;
+    }
+}
+
+pub struct SemaphoreVk {
+    timeline: Arc<TimelineVk>,
+}
+
+impl SemaphoreVk {
+    pub fn new(_device: &DeviceVk, info: &SemaphoreInfo) -> Self {
+        Self {
+            timeline: Arc::new(TimelineVk::new(info.get_initial_value())),
+        }
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.timeline.get_value()
+    }
+
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.timeline.wait(value, timeout)
+    }
+
+    pub fn signal(&self, value: u64) {
+        self.timeline.signal(value)
+    }
+
+    pub(crate) fn clone_timeline(&self) -> Arc<TimelineVk> {
+        self.timeline.clone()
+    }
+}
 
 impl ISemaphore for SemaphoreVk {
     type DeviceType = DeviceVk;
 
-    fn new(_device: &Self::DeviceType, _info: &sjgfx_interface::SemaphoreInfo) -> Self {
-        Self {}
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn get_value(&self) -> u64 {
+        self.get_value()
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.wait(value, timeout)
+    }
+
+    fn signal(&self, value: u64) {
+        self.signal(value)
     }
 }
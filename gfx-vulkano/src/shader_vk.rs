@@ -37,6 +37,13 @@ impl ShaderVk {
                     ShaderModule::from_bytes(device.clone_device(), pixel_shader_binary).unwrap()
                 });
 
+        for shader_module in [&compute_shader, &vertex_shader, &pixel_shader]
+            .into_iter()
+            .flatten()
+        {
+            device.set_debug_label(shader_module.as_ref(), info.get_debug_label());
+        }
+
         Self {
             compute_shader,
             vertex_shader,
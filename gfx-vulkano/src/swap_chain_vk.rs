@@ -1,79 +1,181 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
-use sjgfx_interface::{IDisplayEventListener, ISwapChain, SwapChainInfo};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    select_format, select_present_mode, CompositeAlphaMode, IDisplayEventListener, ISwapChain,
+    ImageData, ImageFormat, OutputTransform, PresentMode, SwapChainInfo, SwapChainStatus,
+};
 use vulkano::{
     image::{view::ImageView, ImageUsage, ImageViewAbstract, SwapchainImage},
     swapchain::{
-        self, AcquireError, CompositeAlpha, Swapchain, SwapchainAcquireFuture, SwapchainCreateInfo,
+        self, AcquireError, CompositeAlpha, Surface, Swapchain, SwapchainAcquireFuture,
+        SwapchainCreateInfo, SwapchainCreationError,
     },
 };
 
-use crate::{ColorTargetViewVk, DeviceVk, FenceVk, SemaphoreVk};
+use crate::{
+    resource_pool_vk::{ImageDescriptor, ResourcePoolVk},
+    transfer_vk::TransferVk,
+    util, ColorTargetViewVk, DeviceVk, FenceVk, SemaphoreVk,
+};
+
+// この時間内にイメージを取得できなければフレームを飛ばす
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(1);
 
 pub struct SwapChainVk {
+    transfer: TransferVk,
+    resource_pool: Arc<ResourcePoolVk>,
+    image_format: ImageFormat,
+    present_mode: PresentMode,
+    alpha_mode: CompositeAlphaMode,
+    frame_latency: u32,
+    width: u32,
+    height: u32,
+    status: SwapChainStatus,
+
+    // リサイズされたときや表示に失敗したときに立てて、次の取得の前にスワップチェインを作りなおす
+    needs_recreate: Arc<AtomicBool>,
+
     swap_chain: Arc<Swapchain>,
     images: Vec<Arc<SwapchainImage>>,
     swap_chain_acquire_future: Option<SwapchainAcquireFuture>,
     index: i32,
+
+    // 取得したスキャンバッファのビュー。参照を返すので保持しておく
+    scan_buffer_view: Option<ColorTargetViewVk>,
 }
 
 impl SwapChainVk {
-    pub fn new(device: &DeviceVk, _info: &SwapChainInfo) -> Self {
-        let surface = device.clone_surface();
+    pub fn new(device: &DeviceVk, info: &SwapChainInfo) -> Self {
+        Self::new_impl(device, device.clone_surface(), info)
+    }
+
+    // 既存のデバイスで別のウィンドウに表示する
+    pub fn new_with_handle<T>(device: &DeviceVk, info: &SwapChainInfo, handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let surface = device.create_surface(handle);
+        assert!(device
+            .get_physical_device()
+            .surface_support(device.get_queue().queue_family_index(), &surface)
+            .unwrap());
+        Self::new_impl(device, surface, info)
+    }
+
+    fn new_impl(device: &DeviceVk, surface: Arc<Surface>, info: &SwapChainInfo) -> Self {
+        Self::assert_render_scale(info.get_render_scale());
+        Self::assert_output_transform(info.get_output_transform());
         let physical_device = device.get_physical_device();
 
         let capabilities = physical_device
             .surface_capabilities(&surface, Default::default())
             .unwrap();
 
-        // MEMO: Swapchain がサポートしているかのチェックをした方がいいと思う
-        let _composite_alpha = capabilities.supported_composite_alpha;
-
-        let image_format = physical_device
+        // 要求された設定のうちサポートされていないものはサポートされているものに置き換える
+        let surface_formats = physical_device
             .surface_formats(&surface, Default::default())
-            .unwrap()[0]
-            .0;
+            .unwrap();
+        let supported_formats: Vec<ImageFormat> = surface_formats
+            .iter()
+            .filter_map(|(format, _color_space)| util::convert_from_format(*format))
+            .collect();
+        let image_format = select_format(info.get_preferred_formats(), &supported_formats)
+            .map(util::convert_format)
+            .unwrap_or(surface_formats[0].0);
+
+        let supported_present_modes: Vec<PresentMode> = physical_device
+            .surface_present_modes(&surface)
+            .unwrap()
+            .filter_map(util::convert_from_present_mode)
+            .collect();
+        let present_mode = select_present_mode(info.get_present_mode(), &supported_present_modes);
+
+        let supported_composite_alpha: Vec<CompositeAlpha> =
+            capabilities.supported_composite_alpha.into_iter().collect();
+        let composite_alpha = util::convert_composite_alpha_mode(info.get_alpha_mode())
+            .filter(|alpha| supported_composite_alpha.contains(alpha))
+            .unwrap_or(supported_composite_alpha[0]);
+
+        // 表示待ちのフレームに加えて描画中のイメージが 1 枚必要
+        let mut min_image_count = capabilities
+            .min_image_count
+            .max(info.get_frame_latency() + 1);
+        if let Some(max_image_count) = capabilities.max_image_count {
+            min_image_count = min_image_count.min(max_image_count);
+        }
+
+        // スクリーンショットを撮れるようにサポートされていれば読み戻しを有効にする
+        let image_usage = ImageUsage::COLOR_ATTACHMENT
+            | (capabilities.supported_usage_flags & ImageUsage::TRANSFER_SRC);
 
         let (swap_chain, images) = Swapchain::new(
             device.clone_device(),
             surface.clone(),
             SwapchainCreateInfo {
-                min_image_count: capabilities.min_image_count,
+                min_image_count,
                 image_format: Some(image_format),
-                // image_extent: surface.window().inner_size().into(),
-                image_extent: [1280, 960],
-                image_usage: ImageUsage::COLOR_ATTACHMENT,
-                composite_alpha: CompositeAlpha::Opaque,
+                image_extent: [info.get_width(), info.get_height()],
+                image_usage,
+                composite_alpha,
+                present_mode: util::convert_present_mode(present_mode),
                 ..Default::default()
             },
         )
         .unwrap();
 
         Self {
+            transfer: TransferVk::new(device),
+            resource_pool: device.clone_resource_pool(),
+            image_format: util::convert_from_format(image_format).unwrap(),
+            present_mode,
+            alpha_mode: util::convert_from_composite_alpha_mode(composite_alpha),
+            frame_latency: info.get_frame_latency(),
+            width: info.get_width(),
+            height: info.get_height(),
+            status: SwapChainStatus::Optimal,
+            needs_recreate: Arc::new(AtomicBool::new(false)),
             swap_chain,
             images,
             swap_chain_acquire_future: None,
             index: -1,
+            scan_buffer_view: None,
         }
     }
 
+    // スワップチェインのイメージの並び順で返す
     pub fn get_color_target_views(&self) -> Vec<ColorTargetViewVk> {
-        todo!()
+        self.images
+            .iter()
+            .map(|image| {
+                ColorTargetViewVk::new_from_swap_chain_image(
+                    self.transfer.clone(),
+                    image.clone(),
+                    self.swap_chain.image_format(),
+                )
+            })
+            .collect()
     }
 
     pub fn acquire_next_scan_buffer_index(
         &mut self,
         _semaphore: Option<&mut SemaphoreVk>,
-        _fence: Option<&mut FenceVk>,
+        fence: Option<&mut FenceVk>,
     ) -> i32 {
-        let (image_num, _suboptimal, acquire_future) =
-            match swapchain::acquire_next_image(self.swap_chain.clone(), None) {
-                Ok(r) => r,
-                Err(AcquireError::OutOfDate) => {
-                    return -1;
-                }
-                Err(e) => panic!("Failed to acquire next image: {:?}", e),
-            };
+        let Some((image_num, acquire_future)) = self.acquire_next_image() else {
+            return -1;
+        };
+
+        if let Some(fence) = fence {
+            acquire_future.wait(None).unwrap();
+            fence.signal();
+        }
 
         self.swap_chain_acquire_future = Some(acquire_future);
         self.index = image_num as i32;
@@ -83,25 +185,112 @@ impl SwapChainVk {
     pub fn acquire_next_scan_buffer_view(
         &mut self,
         _semaphore: Option<&mut SemaphoreVk>,
-        fence: Option<&mut FenceVk>,
-    ) -> ColorTargetViewVk {
-        if let Some(fence) = fence {
+        mut fence: Option<&mut FenceVk>,
+    ) -> &mut ColorTargetViewVk {
+        if let Some(fence) = fence.as_deref_mut() {
             fence.cleanup_finished();
         }
 
-        let (image_num, _suboptimal, acquire_future) =
-            match swapchain::acquire_next_image(self.swap_chain.clone(), None) {
-                Ok(r) => r,
+        let Some((image_num, acquire_future)) = self.acquire_next_image() else {
+            // 待っている側が止まらないようにフェンスはシグナルしておく
+            if let Some(fence) = fence {
+                fence.signal();
+            }
+            let fallback_view = self.create_fallback_view();
+            return self.scan_buffer_view.insert(fallback_view);
+        };
+
+        // イメージが取得できたらフェンスをシグナルする
+        if let Some(fence) = fence {
+            acquire_future.wait(None).unwrap();
+            fence.signal();
+        }
+
+        self.swap_chain_acquire_future = Some(acquire_future);
+        self.index = image_num as i32;
+
+        let scan_buffer_view = ColorTargetViewVk::new_from_swap_chain(self);
+        self.scan_buffer_view.insert(scan_buffer_view)
+    }
+
+    // スワップチェインが使えなくなっていたら作りなおして 1 回だけ取得しなおす
+    // それでも取得できなければフレームを飛ばす
+    fn acquire_next_image(&mut self) -> Option<(u32, SwapchainAcquireFuture)> {
+        // 最小化されているときはサイズが 0 で表示できない
+        if self.width == 0 || self.height == 0 {
+            self.status = SwapChainStatus::Skipped;
+            return None;
+        }
+
+        self.status = SwapChainStatus::Optimal;
+        if self.needs_recreate.swap(false, Ordering::Relaxed) {
+            if !self.recreate() {
+                self.status = SwapChainStatus::Skipped;
+                return None;
+            }
+            self.status = SwapChainStatus::Reconfigured;
+        }
+
+        for _ in 0..2 {
+            match swapchain::acquire_next_image(self.swap_chain.clone(), Some(ACQUIRE_TIMEOUT)) {
+                Ok((image_num, suboptimal, acquire_future)) => {
+                    // 最適でなくても表示はできるので次のフレームで作りなおす
+                    if suboptimal {
+                        self.needs_recreate.store(true, Ordering::Relaxed);
+                    }
+                    return Some((image_num, acquire_future));
+                }
                 Err(AcquireError::OutOfDate) => {
-                    todo!()
+                    if !self.recreate() {
+                        break;
+                    }
+                    self.status = SwapChainStatus::Reconfigured;
                 }
+                Err(AcquireError::SurfaceLost) => {
+                    if !self.recreate() {
+                        break;
+                    }
+                    self.status = SwapChainStatus::Recreated;
+                }
+                Err(AcquireError::Timeout) => break,
                 Err(e) => panic!("Failed to acquire next image: {:?}", e),
-            };
+            }
+        }
 
-        self.swap_chain_acquire_future = Some(acquire_future);
-        self.index = image_num as i32;
+        self.status = SwapChainStatus::Skipped;
+        None
+    }
 
-        ColorTargetViewVk::new_from_swap_chain(self)
+    fn recreate(&mut self) -> bool {
+        let result = self.swap_chain.recreate(SwapchainCreateInfo {
+            image_extent: [self.width, self.height],
+            ..self.swap_chain.create_info()
+        });
+        match result {
+            Ok((swap_chain, images)) => {
+                self.swap_chain = swap_chain;
+                self.images = images;
+                self.swap_chain_acquire_future = None;
+                self.index = -1;
+                self.scan_buffer_view = None;
+                true
+            }
+            // リサイズ中はサーフェスのサイズと合わないことがある
+            // サーフェスを失ったときは作りなおせないのでフレームを飛ばし続ける
+            Err(SwapchainCreationError::ImageExtentNotSupported { .. })
+            | Err(SwapchainCreationError::SurfaceLost) => false,
+            Err(e) => panic!("Failed to recreate swapchain: {:?}", e),
+        }
+    }
+
+    // 描画先がないときも描画コマンドが失敗しないように、表示されないカラーターゲットを返す
+    fn create_fallback_view(&self) -> ColorTargetViewVk {
+        let image = self.resource_pool.acquire_image(ImageDescriptor {
+            dimensions: [self.width.max(1), self.height.max(1)],
+            format: self.swap_chain.image_format(),
+            usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+        });
+        ColorTargetViewVk::new_from_attachment_image(self.transfer.clone(), image)
     }
 
     pub(crate) fn clone_current_image_view(&self) -> Arc<dyn ImageViewAbstract> {
@@ -115,6 +304,10 @@ impl SwapChainVk {
         temp.unwrap()
     }
 
+    pub(crate) fn clone_transfer(&self) -> TransferVk {
+        self.transfer.clone()
+    }
+
     pub fn get_current_index(&self) -> i32 {
         self.index
     }
@@ -132,6 +325,75 @@ impl SwapChainVk {
         std::mem::swap(&mut temp, &mut self.swap_chain_acquire_future);
         temp.unwrap()
     }
+
+    // イメージを取得できなかったフレームでは None
+    pub(crate) fn take_acquire_future(&mut self) -> Option<SwapchainAcquireFuture> {
+        self.swap_chain_acquire_future.take()
+    }
+
+    pub(crate) fn clone_needs_recreate(&self) -> Arc<AtomicBool> {
+        self.needs_recreate.clone()
+    }
+
+    pub fn get_status(&self) -> SwapChainStatus {
+        self.status
+    }
+
+    // 直接描画するので内部のカラーターゲットの設定は使われず、スワップチェインのイメージの大きさになる
+    pub fn get_render_width(&self) -> u32 {
+        self.swap_chain.image_extent()[0]
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        self.swap_chain.image_extent()[1]
+    }
+
+    // 内部のカラーターゲットを持たずにスワップチェーンのイメージへ直接描画するので、
+    // 解像度の変更と色の変換はできない。既定値以外が指定されたら気づけるように止める
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        Self::assert_render_scale(render_scale);
+    }
+
+    pub fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        Self::assert_output_transform(output_transform);
+    }
+
+    fn assert_render_scale(render_scale: f32) {
+        assert!(
+            render_scale == 1.0,
+            "vulkano backend doesn't support render scale {}",
+            render_scale
+        );
+    }
+
+    fn assert_output_transform(output_transform: &OutputTransform) {
+        assert!(
+            *output_transform == OutputTransform::new(),
+            "vulkano backend doesn't support output transform {:?}",
+            output_transform
+        );
+    }
+
+    pub fn get_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn get_format(&self) -> ImageFormat {
+        self.image_format.clone()
+    }
+
+    pub fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.alpha_mode
+    }
+
+    pub fn get_frame_latency(&self) -> u32 {
+        self.frame_latency
+    }
+
+    // スワップチェインのイメージに直接描画するので常に有効
+    pub fn is_direct_rendering_enabled(&self) -> bool {
+        true
+    }
 }
 
 impl ISwapChain for SwapChainVk {
@@ -144,16 +406,77 @@ impl ISwapChain for SwapChainVk {
         Self::new(device, info)
     }
 
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(device, info, raw_handle)
+    }
+
     fn acquire_next_scan_buffer_view(
         &mut self,
-        _semaphore: Option<&mut Self::SemaphoreType>,
-        _fence: Option<&mut Self::FenceType>,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
     ) -> &mut Self::ColorTargetViewType {
-        todo!()
-        // self.acquire_next_scan_buffer_view(semaphore, fence)
+        self.acquire_next_scan_buffer_view(semaphore, fence)
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.set_output_transform(output_transform);
+    }
+
+    // Vulkan のスワップチェーンは常にウィンドウのサーフェスに表示するので読み戻せない
+    // オフスクリーンで読み戻したいときは wgpu か ash バックエンドを使う
+    fn read_presented_image(&self) -> Option<ImageData> {
+        None
     }
 }
 
 impl IDisplayEventListener for SwapChainVk {
-    fn on_resized(&mut self, _width: u32, _height: u32) {}
+    // 使用中のイメージがあるかもしれないので、作りなおすのは次の取得のとき
+    fn on_resized(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.needs_recreate.store(true, Ordering::Relaxed);
+    }
 }
@@ -1,53 +1,250 @@
 use std::sync::Arc;
 
-use sjgfx_interface::{ITexture, TextureInfo};
+use sjgfx_interface::{
+    validate_texture_write, GpuAccess, ITexture, ImageData, ImageFormat, TextureCopyRegion,
+    TextureInfo,
+};
 use vulkano::{
-    image::{view::ImageView, AttachmentImage, ImageAccess, ImageUsage, ImmutableImage},
-    memory::allocator::StandardMemoryAllocator,
+    buffer::BufferUsage,
+    command_buffer::{
+        BufferImageCopy, ClearColorImageInfo, ClearDepthStencilImageInfo, CopyBufferToImageInfo,
+    },
+    image::{
+        view::ImageView, AttachmentImage, ImageAccess, ImageAspects, ImageCreateFlags,
+        ImageDimensions, ImageLayout, ImageSubresourceLayers, ImageUsage, ImmutableImage,
+        MipmapsCount,
+    },
+    memory::allocator::MemoryUsage,
 };
 
-use crate::{util, DeviceVk};
+use crate::{
+    interop, resource_pool_vk::ImageDescriptor, transfer_vk::TransferVk, util, DeviceVk, QueueVk,
+};
 
 pub struct TextureVk {
+    transfer: TransferVk,
     image: Arc<dyn ImageAccess>,
     image_view: Option<Arc<ImageView<AttachmentImage>>>,
-
-    #[allow(dead_code)]
-    memory_allocator: Arc<StandardMemoryAllocator>,
+    image_format: ImageFormat,
 
     _immutable_image_view: Option<Arc<ImageView<ImmutableImage>>>,
 }
 
 impl TextureVk {
     pub fn new(device: &DeviceVk, info: &TextureInfo) -> Self {
+        Self::new_impl(device, info, GpuAccess::empty())
+    }
+
+    pub fn new_with_data(device: &DeviceVk, info: &TextureInfo, data: &[u8]) -> Self {
+        // 初期データを書き込めるようにコピー先として作る
+        let texture = Self::new_impl(device, info, GpuAccess::WRITE);
+        let region = TextureCopyRegion::new()
+            .set_width(info.get_width())
+            .set_height(info.get_height());
+        let bytes_per_row =
+            info.get_width() as usize * info.get_image_format().get_bytes_per_pixel();
+        texture.write(&region, data, bytes_per_row, info.get_height() as usize);
+        texture
+    }
+
+    fn new_impl(device: &DeviceVk, info: &TextureInfo, additional_gpu_access: GpuAccess) -> Self {
+        let gpu_access = *info.get_gpu_access_flags() | additional_gpu_access;
+        if info.get_mip_count() > 1 || info.get_depth() > 1 {
+            return Self::new_mipmapped(device, info, gpu_access);
+        }
+
         let dimensions = [info.get_width() as u32, info.get_height() as u32];
         let format = util::convert_format(info.get_image_format().clone());
-        let memory_allocator =
-            Arc::new(StandardMemoryAllocator::new_default(device.clone_device()));
 
-        let image = AttachmentImage::with_usage(
-            &memory_allocator,
+        // カラーと深度のターゲットにしか使わないテクスチャは使い捨てのことが多いので、
+        // 同じ記述子のものが解放されていればプールから使いまわす
+        // サンプルしたり書き込んだりするテクスチャは長く生きるので、プールに入れずに直接作る
+        let usage = interop::convert_image_usage(&gpu_access);
+        let image = if Self::is_transient(&gpu_access) {
+            device.get_resource_pool().acquire_image(ImageDescriptor {
+                dimensions,
+                format,
+                usage,
+            })
+        } else {
+            AttachmentImage::with_usage(device.get_memory_allocator(), dimensions, format, usage)
+                .unwrap()
+        };
+        device.set_debug_label(image.inner().image.as_ref(), info.get_debug_label());
+        let image_view = ImageView::new_default(image.clone()).unwrap();
+
+        Self {
+            transfer: TransferVk::new(device),
+            image,
+            image_view: Some(image_view),
+            image_format: info.get_image_format().clone(),
+            _immutable_image_view: None,
+        }
+    }
+
+    // AttachmentImage はミップマップと配列を持てないので ImmutableImage で作る
+    // 転送とサンプルの両方で使えるように GENERAL レイアウトに置いておく
+    fn new_mipmapped(device: &DeviceVk, info: &TextureInfo, gpu_access: GpuAccess) -> Self {
+        let dimensions = ImageDimensions::Dim2d {
+            width: info.get_width() as u32,
+            height: info.get_height() as u32,
+            array_layers: info.get_depth() as u32,
+        };
+        let format = util::convert_format(info.get_image_format().clone());
+        let mut usage = interop::convert_image_usage(&gpu_access) | ImageUsage::TRANSFER_DST;
+        if gpu_access.contains(GpuAccess::COLOR_BUFFER) {
+            usage |= ImageUsage::COLOR_ATTACHMENT;
+        }
+        if gpu_access.contains(GpuAccess::DEPTH_STENCIL) {
+            usage |= ImageUsage::DEPTH_STENCIL_ATTACHMENT;
+        }
+        let (image, initialization) = ImmutableImage::uninitialized(
+            device.get_memory_allocator(),
             dimensions,
             format,
-            ImageUsage::default(),
+            MipmapsCount::Specific(info.get_mip_count() as u32),
+            usage,
+            ImageCreateFlags::empty(),
+            ImageLayout::General,
+            [device.get_queue().queue_family_index()],
         )
         .unwrap();
+        device.set_debug_label(image.inner().image.as_ref(), info.get_debug_label());
+
+        // wgpu とそろえて中身をゼロで初期化しておく
+        let transfer = TransferVk::new(device);
+        transfer.execute(|builder| {
+            if format.aspects().intersects(ImageAspects::DEPTH) {
+                builder
+                    .clear_depth_stencil_image(ClearDepthStencilImageInfo::image(initialization))
+                    .unwrap();
+            } else {
+                builder
+                    .clear_color_image(ClearColorImageInfo::image(initialization))
+                    .unwrap();
+            }
+        });
         let image_view = ImageView::new_default(image.clone()).unwrap();
 
         Self {
+            transfer,
             image,
-            memory_allocator,
-            image_view: Some(image_view),
-            _immutable_image_view: None,
+            image_view: None,
+            image_format: info.get_image_format().clone(),
+            _immutable_image_view: Some(image_view),
         }
     }
 
+    fn is_transient(gpu_access: &GpuAccess) -> bool {
+        !gpu_access.is_empty()
+            && (GpuAccess::COLOR_BUFFER | GpuAccess::DEPTH_STENCIL).contains(*gpu_access)
+    }
+
+    pub fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        assert!(
+            self.image.usage().intersects(ImageUsage::TRANSFER_DST),
+            "texture must be created with GpuAccess::WRITE to be written"
+        );
+        let required_size = validate_texture_write(
+            region,
+            &self.image_format,
+            data.len(),
+            bytes_per_row,
+            rows_per_image,
+        );
+        if required_size == 0 {
+            return;
+        }
+
+        let subresource = region.get_texture_subresource();
+        let mip_level = subresource.get_mip_level() as u32;
+        let base_array_layer = subresource.get_array_index() as u32;
+        let array_length = region.get_array_length() as u32;
+        let dimensions = self.image.dimensions();
+        assert!(
+            mip_level < self.image.mip_levels()
+                && base_array_layer + array_length <= dimensions.array_layers()
+                && (region.get_offset_u() + region.get_width()) as u32
+                    <= (dimensions.width() >> mip_level).max(1)
+                && (region.get_offset_v() + region.get_height()) as u32
+                    <= (dimensions.height() >> mip_level).max(1),
+            "texture region is out of mip level {}",
+            mip_level
+        );
+
+        // 詰め物を取り除いてステージングバッファーに詰める
+        let width = region.get_width() as usize;
+        let height = region.get_height() as usize;
+        let row_size = width * self.image_format.get_bytes_per_pixel();
+        let staging_buffer = self.transfer.acquire_staging_buffer(
+            row_size * height * array_length as usize,
+            BufferUsage::TRANSFER_SRC,
+            MemoryUsage::Upload,
+        );
+        {
+            let mut mapped_data = staging_buffer.write().unwrap();
+            for (index, dst) in mapped_data.chunks_exact_mut(row_size).enumerate() {
+                let (layer, row) = (index / height, index % height);
+                let start = bytes_per_row * (rows_per_image * layer + row);
+                dst.copy_from_slice(&data[start..(start + row_size)]);
+            }
+        }
+
+        let format = self.image.format();
+        let aspects = if format.aspects().intersects(ImageAspects::DEPTH) {
+            ImageAspects::DEPTH
+        } else {
+            ImageAspects::COLOR
+        };
+
+        // 書き込みが終わるまで待つのでデータはすぐに再利用できる
+        self.transfer.execute(|builder| {
+            builder
+                .copy_buffer_to_image(CopyBufferToImageInfo {
+                    regions: [BufferImageCopy {
+                        image_subresource: ImageSubresourceLayers {
+                            aspects,
+                            mip_level,
+                            array_layers: base_array_layer..(base_array_layer + array_length),
+                        },
+                        image_offset: [
+                            region.get_offset_u() as u32,
+                            region.get_offset_v() as u32,
+                            0,
+                        ],
+                        image_extent: [width as u32, height as u32, 1],
+                        ..Default::default()
+                    }]
+                    .into(),
+                    ..CopyBufferToImageInfo::buffer_image(staging_buffer, self.image.clone())
+                })
+                .unwrap();
+        });
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        self.transfer.read_image(self.image.clone())
+    }
+
+    // キューにためているコマンドの完了を待ってから読み戻す
+    pub fn read_image_with_queue(&self, queue: &mut QueueVk) -> ImageData {
+        queue.sync();
+        self.read_image()
+    }
+
     pub fn clone_image(&self) -> Arc<dyn ImageAccess> {
         self.image.clone()
     }
 
-    pub fn clone_attachment_image(&self) -> Arc<ImageView<AttachmentImage>> {
-        self.image_view.as_ref().unwrap().clone()
+    // ミップマップや配列を持つテクスチャは AttachmentImage ではないので None を返す
+    pub fn clone_attachment_image(&self) -> Option<Arc<ImageView<AttachmentImage>>> {
+        self.image_view.clone()
     }
 }
 
@@ -58,7 +255,21 @@ impl ITexture for TextureVk {
         Self::new(device, info)
     }
 
-    fn new_with_data(_device: &Self::DeviceType, _info: &TextureInfo, _data: &[u8]) -> Self {
-        todo!()
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        Self::new_with_data(device, info, data)
+    }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
     }
 }
@@ -0,0 +1,130 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use sjgfx_interface::ImageData;
+use vulkano::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, BufferImageCopy,
+        CommandBufferUsage, CopyImageToBufferInfo, PrimaryAutoCommandBuffer,
+    },
+    device::{Device, Queue},
+    image::{ImageAccess, ImageAspects, ImageSubresourceLayers, ImageUsage},
+    memory::allocator::MemoryUsage,
+    sync::{self, GpuFuture},
+    DeviceSize,
+};
+
+use crate::{
+    resource_pool_vk::{BufferDescriptor, ResourcePoolVk},
+    util, DeviceVk,
+};
+
+// CPU とのデータのやり取りのためにコマンドを積んで完了まで待つ
+#[derive(Clone)]
+pub(crate) struct TransferVk {
+    device: Arc<Device>,
+    queue: Arc<Queue>,
+    command_buffer_allocator: Arc<StandardCommandBufferAllocator>,
+    resource_pool: Arc<ResourcePoolVk>,
+    unflushed_command_count: Arc<AtomicUsize>,
+}
+
+impl TransferVk {
+    pub fn new(device: &DeviceVk) -> Self {
+        Self {
+            device: device.clone_device(),
+            queue: device.clone_queue(),
+            command_buffer_allocator: device.clone_command_buffer_allocator(),
+            resource_pool: device.clone_resource_pool(),
+            unflushed_command_count: device.clone_unflushed_command_count(),
+        }
+    }
+
+    // プールから取り出したバッファーは前の中身が残っているので、使う範囲をすべて上書きすること
+    pub fn acquire_staging_buffer(
+        &self,
+        size: usize,
+        usage: BufferUsage,
+        memory_usage: MemoryUsage,
+    ) -> Subbuffer<[u8]> {
+        self.resource_pool.acquire_buffer(BufferDescriptor {
+            size: size as DeviceSize,
+            usage,
+            memory_usage,
+        })
+    }
+
+    pub fn execute<F>(&self, func: F)
+    where
+        F: FnOnce(&mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>),
+    {
+        let mut builder = AutoCommandBufferBuilder::primary(
+            self.command_buffer_allocator.as_ref(),
+            self.queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        func(&mut builder);
+        let command_buffer = builder.build().unwrap();
+
+        sync::now(self.device.clone())
+            .then_execute(self.queue.clone(), command_buffer)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    // ミップレベル 0、配列要素 0 を読み戻して RGBA に変換する
+    // 読み戻しはキューとは別にサブミットするので、キューにためたコマンドの結果は読めない
+    // 見落とさないように flush していないコマンドがあれば止める
+    pub fn read_image(&self, image: Arc<dyn ImageAccess>) -> ImageData {
+        assert!(
+            image.usage().intersects(ImageUsage::TRANSFER_SRC),
+            "texture must be created with GpuAccess::READ to be read back"
+        );
+        assert_eq!(
+            self.unflushed_command_count.load(Ordering::Relaxed),
+            0,
+            "queued commands must be flushed before reading back. use read_image_with_queue or flush the queue"
+        );
+        let format = image.format();
+        let image_format = util::convert_from_format(format)
+            .unwrap_or_else(|| panic!("{:?} can't be read back", format));
+
+        // バッファーへのコピーは詰め物なしで行える
+        let [width, height] = image.dimensions().width_height();
+        let size = (width * height) as usize * format.block_size().unwrap() as usize;
+        let staging_buffer =
+            self.acquire_staging_buffer(size, BufferUsage::TRANSFER_DST, MemoryUsage::Download);
+        let aspects = if format.aspects().intersects(ImageAspects::DEPTH) {
+            ImageAspects::DEPTH
+        } else {
+            ImageAspects::COLOR
+        };
+        self.execute(|builder| {
+            builder
+                .copy_image_to_buffer(CopyImageToBufferInfo {
+                    regions: [BufferImageCopy {
+                        image_subresource: ImageSubresourceLayers {
+                            aspects,
+                            mip_level: 0,
+                            array_layers: 0..1,
+                        },
+                        image_extent: [width, height, 1],
+                        ..Default::default()
+                    }]
+                    .into(),
+                    ..CopyImageToBufferInfo::image_buffer(image.clone(), staging_buffer.clone())
+                })
+                .unwrap();
+        });
+
+        let texels = staging_buffer.read().unwrap();
+        ImageData::from_texels(width, height, image_format, &texels)
+    }
+}
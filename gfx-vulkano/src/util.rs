@@ -1,5 +1,15 @@
-use sjgfx_interface::ImageFormat;
-use vulkano::format::Format;
+use sjgfx_interface::{
+    AdapterInfo, AdapterType, BackendMask, CompositeAlphaMode, DeviceFeatures, ImageFormat,
+    ImageFormatUsage, PresentMode,
+};
+use vulkano::{
+    device::{
+        physical::{PhysicalDevice, PhysicalDeviceType},
+        Features,
+    },
+    format::{Format, FormatFeatures},
+    swapchain::{self, CompositeAlpha},
+};
 
 pub fn convert_format(image_format: ImageFormat) -> Format {
     match image_format {
@@ -9,10 +19,164 @@ pub fn convert_format(image_format: ImageFormat) -> Format {
         ImageFormat::R8Uint => Format::R8_UINT,
         ImageFormat::R32Sint => Format::R32_SINT,
         ImageFormat::R32Uint => Format::R32_UINT,
-        ImageFormat::R8G8B8A8Uint => Format::R8G8B8_UINT,
-        ImageFormat::R8G8B8A8Sint => Format::R8G8B8_SINT,
+        ImageFormat::R8G8B8A8Uint => Format::R8G8B8A8_UINT,
+        ImageFormat::R8G8B8A8Sint => Format::R8G8B8A8_SINT,
         ImageFormat::R8G8B8Unorm => Format::R8G8B8_UNORM,
         ImageFormat::R8G8B8A8Unorm => Format::R8G8B8A8_UNORM,
+        ImageFormat::R8G8B8A8UnormSrgb => Format::R8G8B8A8_SRGB,
+        ImageFormat::B8G8R8A8Unorm => Format::B8G8R8A8_UNORM,
+        ImageFormat::B8G8R8A8UnormSrgb => Format::B8G8R8A8_SRGB,
+        ImageFormat::R16G16B16A16Float => Format::R16G16B16A16_SFLOAT,
         ImageFormat::D32 => Format::D32_SFLOAT,
     }
 }
+
+pub fn convert_from_format(format: Format) -> Option<ImageFormat> {
+    match format {
+        Format::R8_UNORM => Some(ImageFormat::R8Unorm),
+        Format::R8_SNORM => Some(ImageFormat::R8Snorm),
+        Format::R8_SINT => Some(ImageFormat::R8Sint),
+        Format::R8_UINT => Some(ImageFormat::R8Uint),
+        Format::R32_SINT => Some(ImageFormat::R32Sint),
+        Format::R32_UINT => Some(ImageFormat::R32Uint),
+        Format::R8G8B8A8_UINT => Some(ImageFormat::R8G8B8A8Uint),
+        Format::R8G8B8A8_SINT => Some(ImageFormat::R8G8B8A8Sint),
+        Format::R8G8B8_UNORM => Some(ImageFormat::R8G8B8Unorm),
+        Format::R8G8B8A8_UNORM => Some(ImageFormat::R8G8B8A8Unorm),
+        Format::R8G8B8A8_SRGB => Some(ImageFormat::R8G8B8A8UnormSrgb),
+        Format::B8G8R8A8_UNORM => Some(ImageFormat::B8G8R8A8Unorm),
+        Format::B8G8R8A8_SRGB => Some(ImageFormat::B8G8R8A8UnormSrgb),
+        Format::R16G16B16A16_SFLOAT => Some(ImageFormat::R16G16B16A16Float),
+        Format::D32_SFLOAT => Some(ImageFormat::D32),
+        _ => None,
+    }
+}
+
+pub fn convert_present_mode(present_mode: PresentMode) -> swapchain::PresentMode {
+    match present_mode {
+        PresentMode::Fifo => swapchain::PresentMode::Fifo,
+        PresentMode::FifoRelaxed => swapchain::PresentMode::FifoRelaxed,
+        PresentMode::Mailbox => swapchain::PresentMode::Mailbox,
+        PresentMode::Immediate => swapchain::PresentMode::Immediate,
+    }
+}
+
+pub fn convert_from_present_mode(present_mode: swapchain::PresentMode) -> Option<PresentMode> {
+    match present_mode {
+        swapchain::PresentMode::Fifo => Some(PresentMode::Fifo),
+        swapchain::PresentMode::FifoRelaxed => Some(PresentMode::FifoRelaxed),
+        swapchain::PresentMode::Mailbox => Some(PresentMode::Mailbox),
+        swapchain::PresentMode::Immediate => Some(PresentMode::Immediate),
+        _ => None,
+    }
+}
+
+// Auto は Vulkan に対応するものがないので None
+pub fn convert_composite_alpha_mode(alpha_mode: CompositeAlphaMode) -> Option<CompositeAlpha> {
+    match alpha_mode {
+        CompositeAlphaMode::Auto => None,
+        CompositeAlphaMode::Opaque => Some(CompositeAlpha::Opaque),
+        CompositeAlphaMode::PreMultiplied => Some(CompositeAlpha::PreMultiplied),
+        CompositeAlphaMode::PostMultiplied => Some(CompositeAlpha::PostMultiplied),
+        CompositeAlphaMode::Inherit => Some(CompositeAlpha::Inherit),
+    }
+}
+
+pub fn convert_from_composite_alpha_mode(alpha_mode: CompositeAlpha) -> CompositeAlphaMode {
+    match alpha_mode {
+        CompositeAlpha::Opaque => CompositeAlphaMode::Opaque,
+        CompositeAlpha::PreMultiplied => CompositeAlphaMode::PreMultiplied,
+        CompositeAlpha::PostMultiplied => CompositeAlphaMode::PostMultiplied,
+        CompositeAlpha::Inherit => CompositeAlphaMode::Inherit,
+    }
+}
+
+pub fn convert_to_features(features: DeviceFeatures) -> Features {
+    // TIMESTAMP_QUERY は Vulkan では機能ではなくプロパティなので有効化は不要
+    Features {
+        pipeline_statistics_query: features.contains(DeviceFeatures::PIPELINE_STATISTICS_QUERY),
+        texture_compression_bc: features.contains(DeviceFeatures::TEXTURE_COMPRESSION_BC),
+        texture_compression_etc2: features.contains(DeviceFeatures::TEXTURE_COMPRESSION_ETC2),
+        texture_compression_astc_ldr: features.contains(DeviceFeatures::TEXTURE_COMPRESSION_ASTC),
+        draw_indirect_first_instance: features.contains(DeviceFeatures::INDIRECT_FIRST_INSTANCE),
+        multi_draw_indirect: features.contains(DeviceFeatures::MULTI_DRAW_INDIRECT),
+        shader_float64: features.contains(DeviceFeatures::SHADER_FLOAT64),
+        fill_mode_non_solid: features.contains(DeviceFeatures::POLYGON_MODE_LINE),
+        depth_clamp: features.contains(DeviceFeatures::DEPTH_CLIP_CONTROL),
+        ..Features::empty()
+    }
+}
+
+pub fn convert_from_features(features: &Features) -> DeviceFeatures {
+    let mut result = DeviceFeatures::empty();
+    result.set(
+        DeviceFeatures::PIPELINE_STATISTICS_QUERY,
+        features.pipeline_statistics_query,
+    );
+    result.set(
+        DeviceFeatures::TEXTURE_COMPRESSION_BC,
+        features.texture_compression_bc,
+    );
+    result.set(
+        DeviceFeatures::TEXTURE_COMPRESSION_ETC2,
+        features.texture_compression_etc2,
+    );
+    result.set(
+        DeviceFeatures::TEXTURE_COMPRESSION_ASTC,
+        features.texture_compression_astc_ldr,
+    );
+    result.set(
+        DeviceFeatures::INDIRECT_FIRST_INSTANCE,
+        features.draw_indirect_first_instance,
+    );
+    result.set(
+        DeviceFeatures::MULTI_DRAW_INDIRECT,
+        features.multi_draw_indirect,
+    );
+    result.set(DeviceFeatures::SHADER_FLOAT64, features.shader_float64);
+    result.set(
+        DeviceFeatures::POLYGON_MODE_LINE,
+        features.fill_mode_non_solid,
+    );
+    result.set(DeviceFeatures::DEPTH_CLIP_CONTROL, features.depth_clamp);
+    result
+}
+
+pub fn convert_from_format_features(features: FormatFeatures) -> ImageFormatUsage {
+    let mut result = ImageFormatUsage::empty();
+    if features.intersects(FormatFeatures::SAMPLED_IMAGE) {
+        result |= ImageFormatUsage::SAMPLED;
+    }
+    if features.intersects(FormatFeatures::STORAGE_IMAGE) {
+        result |= ImageFormatUsage::STORAGE;
+    }
+    if features
+        .intersects(FormatFeatures::COLOR_ATTACHMENT | FormatFeatures::DEPTH_STENCIL_ATTACHMENT)
+    {
+        result |= ImageFormatUsage::RENDER_TARGET;
+    }
+    if features.intersects(FormatFeatures::COLOR_ATTACHMENT_BLEND) {
+        result |= ImageFormatUsage::BLENDABLE;
+    }
+    result
+}
+
+pub fn convert_from_device_type(device_type: PhysicalDeviceType) -> AdapterType {
+    match device_type {
+        PhysicalDeviceType::DiscreteGpu => AdapterType::DiscreteGpu,
+        PhysicalDeviceType::IntegratedGpu => AdapterType::IntegratedGpu,
+        PhysicalDeviceType::VirtualGpu => AdapterType::VirtualGpu,
+        PhysicalDeviceType::Cpu => AdapterType::Cpu,
+        _ => AdapterType::Other,
+    }
+}
+
+pub fn convert_to_adapter_info(index: usize, physical_device: &PhysicalDevice) -> AdapterInfo {
+    let properties = physical_device.properties();
+    AdapterInfo::new()
+        .set_index(index)
+        .set_name(&properties.device_name)
+        .set_vendor(properties.vendor_id)
+        .set_adapter_type(convert_from_device_type(properties.device_type))
+        .set_backend(BackendMask::VULKAN)
+}
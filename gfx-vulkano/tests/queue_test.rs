@@ -1,11 +1,11 @@
 use sjgfx_interface::{
     AttributeFormat, BufferInfo, ColorTargetViewInfo, CommandBufferInfo, DeviceInfo, GpuAccess,
-    ImageFormat, PrimitiveTopology, QueueInfo, ShaderInfo, TextureInfo, VertexAttributeStateInfo,
-    VertexBufferStateInfo, VertexStateInfo,
+    ImageFormat, PrimitiveTopology, QueueInfo, QueueType, SemaphoreInfo, ShaderInfo, TextureInfo,
+    VertexAttributeStateInfo, VertexBufferStateInfo, VertexStateInfo,
 };
 use sjgfx_vulkano::CommandBufferVk;
 use sjgfx_vulkano::{
-    BufferVk, ColorTargetViewVk, DeviceVk, QueueVk, ShaderVk, TextureVk, VertexStateVk,
+    BufferVk, ColorTargetViewVk, DeviceVk, QueueVk, SemaphoreVk, ShaderVk, TextureVk, VertexStateVk,
 };
 
 #[test]
@@ -105,7 +105,7 @@ pub fn execute_array_test() {
         queue.sync();
     }
 
-    buffer.map_as_array(|x: &[u32]| {
+    buffer.map_as_slice(|x: &[u32]| {
         assert_eq!(x[0], 0);
         assert_eq!(x[1], 1);
         assert_eq!(x[2], 2);
@@ -207,3 +207,109 @@ pub fn execute_hello_triangle() {
         queue.sync();
     }
 }
+
+fn create_compute_shader(device: &DeviceVk) -> ShaderVk {
+    let shader_source = include_str!("../../resources/tests/simple_compute.glsl");
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let shader_binary = compiler
+        .compile_into_spirv(
+            shader_source,
+            shaderc::ShaderKind::Compute,
+            "test.glsl",
+            "main",
+            None,
+        )
+        .unwrap();
+    ShaderVk::new(
+        device,
+        &ShaderInfo::new().set_compute_shader_binary(shader_binary.as_binary_u8()),
+    )
+}
+
+// 専用のコンピュートキューがあればそのファミリー向けにコマンドバッファが作られる
+#[test]
+pub fn execute_on_compute_queue() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut queue = QueueVk::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Compute),
+    );
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+    let shader = create_compute_shader(&device);
+    let buffer = BufferVk::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(256)
+            .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER),
+    );
+
+    command_buffer.begin();
+    command_buffer.set_shader(&shader);
+    command_buffer.set_unordered_access_buffer(0, &buffer);
+    command_buffer.dispatch(1, 1, 1);
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.sync();
+
+    buffer.map_as_slice(|x: &[u32]| {
+        assert!(x
+            .iter()
+            .enumerate()
+            .all(|(index, value)| *value == index as u32));
+    });
+}
+
+#[test]
+pub fn execute_on_transfer_queue() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut queue = QueueVk::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Transfer),
+    );
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+
+    command_buffer.begin();
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.sync();
+}
+
+// グラフィックスキューのシグナルをコンピュートキューで待つ
+#[test]
+pub fn wait_semaphore_across_queues() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut graphics_queue = QueueVk::new(&device, &QueueInfo::new());
+    let mut compute_queue = QueueVk::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Compute),
+    );
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+    let semaphore = SemaphoreVk::new(&device, &SemaphoreInfo::new());
+
+    command_buffer.begin();
+    command_buffer.end();
+    graphics_queue.execute_with_semaphores(&command_buffer, &[], &[(&semaphore, 1)]);
+    graphics_queue.flush();
+    compute_queue.execute_with_semaphores(&command_buffer, &[(&semaphore, 1)], &[]);
+    compute_queue.sync();
+
+    assert_eq!(semaphore.get_value(), 1);
+}
+
+#[test]
+#[should_panic(expected = "is signaled by work that is not flushed on another queue")]
+pub fn wait_unflushed_semaphore_across_queues() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut graphics_queue = QueueVk::new(&device, &QueueInfo::new());
+    let mut compute_queue = QueueVk::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Compute),
+    );
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+    let semaphore = SemaphoreVk::new(&device, &SemaphoreInfo::new());
+
+    command_buffer.begin();
+    command_buffer.end();
+    graphics_queue.execute_with_semaphores(&command_buffer, &[], &[(&semaphore, 1)]);
+    compute_queue.execute_with_semaphores(&command_buffer, &[(&semaphore, 1)], &[]);
+}
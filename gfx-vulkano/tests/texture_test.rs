@@ -0,0 +1,224 @@
+use sjgfx_interface::{
+    CommandBufferInfo, DeviceInfo, GpuAccess, ImageFormat, QueueInfo, TextureCopyRegion,
+    TextureInfo,
+};
+use sjgfx_vulkano::{CommandBufferVk, DeviceVk, QueueVk, TextureVk};
+use vulkano::{
+    buffer::{Buffer, BufferCreateInfo, BufferUsage},
+    command_buffer::{
+        AutoCommandBufferBuilder, BufferImageCopy, CommandBufferUsage, CopyImageToBufferInfo,
+    },
+    image::{ImageAccess, ImageAspects, ImageSubresourceLayers},
+    memory::allocator::{AllocationCreateInfo, MemoryUsage},
+    sync::{self, GpuFuture},
+};
+
+// テクスチャーの 1 ミップレベルの 1 配列要素を詰めて読み戻す
+fn read_texture(
+    device: &DeviceVk,
+    texture: &TextureVk,
+    mip_level: u32,
+    array_index: u32,
+    bytes_per_pixel: u32,
+) -> Vec<u8> {
+    let image = texture.clone_image();
+    let dimensions = image.dimensions();
+    let width = (dimensions.width() >> mip_level).max(1);
+    let height = (dimensions.height() >> mip_level).max(1);
+    let buffer = Buffer::new_slice::<u8>(
+        device.get_memory_allocator(),
+        BufferCreateInfo {
+            usage: BufferUsage::TRANSFER_DST,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            usage: MemoryUsage::Download,
+            ..Default::default()
+        },
+        (width * height * bytes_per_pixel) as u64,
+    )
+    .unwrap();
+
+    let command_buffer_allocator = device.clone_command_buffer_allocator();
+    let mut builder = AutoCommandBufferBuilder::primary(
+        command_buffer_allocator.as_ref(),
+        device.get_queue().queue_family_index(),
+        CommandBufferUsage::OneTimeSubmit,
+    )
+    .unwrap();
+    builder
+        .copy_image_to_buffer(CopyImageToBufferInfo {
+            regions: [BufferImageCopy {
+                image_subresource: ImageSubresourceLayers {
+                    aspects: ImageAspects::COLOR,
+                    mip_level,
+                    array_layers: array_index..(array_index + 1),
+                },
+                image_extent: [width, height, 1],
+                ..Default::default()
+            }]
+            .into(),
+            ..CopyImageToBufferInfo::image_buffer(image, buffer.clone())
+        })
+        .unwrap();
+    let command_buffer = builder.build().unwrap();
+    sync::now(device.clone_device())
+        .then_execute(device.clone_queue(), command_buffer)
+        .unwrap()
+        .then_signal_fence_and_flush()
+        .unwrap()
+        .wait(None)
+        .unwrap();
+
+    let data = buffer.read().unwrap();
+    data.to_vec()
+}
+
+#[test]
+fn write_sub_region() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let texture = TextureVk::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(8)
+            .set_height(8)
+            .set_mip_count(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    // 1 行 3 ピクセルのデータを 5 バイト間隔で並べる
+    let data = [1, 2, 3, 0, 0, 4, 5, 6];
+    let region = TextureCopyRegion::new()
+        .set_offset_u(1)
+        .set_offset_v(2)
+        .set_width(3)
+        .set_height(2)
+        .edit_texture_subresource(|x| x.set_mip_level(1));
+    texture.write(&region, &data, 5, 2);
+
+    let result = read_texture(&device, &texture, 1, 0, 1);
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 0,
+        0, 0, 0, 0,
+        0, 1, 2, 3,
+        0, 4, 5, 6,
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn write_array_layers() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let texture = TextureVk::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_depth(3)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    // 配列要素 1 と 2 に 1 行ずつ書き込む
+    let region = TextureCopyRegion::new()
+        .set_width(2)
+        .set_height(1)
+        .set_array_length(2)
+        .edit_texture_subresource(|x| x.set_array_index(1));
+    texture.write(&region, &[1, 2, 3, 4], 2, 1);
+
+    assert_eq!(read_texture(&device, &texture, 0, 0, 1), [0, 0]);
+    assert_eq!(read_texture(&device, &texture, 0, 1, 1), [1, 2]);
+    assert_eq!(read_texture(&device, &texture, 0, 2, 1), [3, 4]);
+}
+
+#[test]
+fn read_image() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let texture = TextureVk::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_mip_count(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let region = TextureCopyRegion::new().set_width(2).set_height(2);
+    texture.write(&region, &[1, 2, 3, 4], 2, 2);
+
+    // ミップレベル 0 だけが読み戻される
+    let image = texture.read_image();
+    assert_eq!(image.get_width(), 2);
+    assert_eq!(image.get_height(), 2);
+    assert_eq!(image.get_pixel(1, 0), [2, 2, 2, 255]);
+    assert_eq!(image.get_pixel(0, 1), [3, 3, 3, 255]);
+}
+
+fn create_readable_texture(device: &DeviceVk) -> TextureVk {
+    let texture = TextureVk::new(
+        device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let region = TextureCopyRegion::new().set_width(2).set_height(1);
+    texture.write(&region, &[1, 2], 2, 1);
+    texture
+}
+
+#[test]
+fn read_image_with_queue() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut queue = QueueVk::new(&device, &QueueInfo::new());
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+    let texture = create_readable_texture(&device);
+
+    command_buffer.begin();
+    command_buffer.end();
+    queue.execute(&command_buffer);
+
+    // キューにためたコマンドを flush してから読み戻す
+    let image = texture.read_image_with_queue(&mut queue);
+    assert_eq!(image.get_pixel(1, 0), [2, 2, 2, 255]);
+}
+
+#[test]
+#[should_panic(expected = "queued commands must be flushed before reading back")]
+fn read_image_before_flush() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let mut queue = QueueVk::new(&device, &QueueInfo::new());
+    let mut command_buffer = CommandBufferVk::new(&device, &CommandBufferInfo::new());
+    let texture = create_readable_texture(&device);
+
+    command_buffer.begin();
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    texture.read_image();
+}
+
+#[test]
+#[should_panic(expected = "texture region is out of mip level 0")]
+fn write_out_of_array() {
+    let device = DeviceVk::new(&DeviceInfo::new());
+    let texture = TextureVk::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_array_index(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
@@ -19,7 +19,7 @@ use winit::{
 use winit::platform::web::WindowExtWebSys;
 
 #[repr(C)]
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
     #[allow(dead_code)]
     pub x: f32,
@@ -108,7 +108,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<Vertex>() * 6),
     );
-    vertex_buffer.map_as_slice_mut(6, |x| {
+    vertex_buffer.map_as_slice_mut(|x| {
         x[0] = Vertex {
             x: 0.0,
             y: 0.5,
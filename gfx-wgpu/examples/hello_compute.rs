@@ -1,6 +1,6 @@
 use sjgfx_interface::{
-    BufferCopyRegion, BufferInfo, CommandBufferInfo, DeviceInfo, GpuAccess, IDevice, QueueInfo,
-    ShaderInfo,
+    BufferCopyRegion, BufferInfo, CommandBufferInfo, CpuAccessMode, DeviceInfo, GpuAccess, IDevice,
+    QueueInfo, ShaderInfo,
 };
 use sjgfx_wgpu::{BufferWgpu, CommandBufferWgpu, DeviceWgpu, QueueWgpu, ShaderWgpu};
 
@@ -30,6 +30,7 @@ fn main() {
         &device,
         &BufferInfo::new()
             .set_gpu_access_flags(GpuAccess::WRITE)
+            .set_cpu_access_mode(CpuAccessMode::Readback)
             .set_size(buffer_size),
     );
 
@@ -37,20 +38,18 @@ fn main() {
     command_buffer.set_shader(&shader);
     command_buffer.set_unordered_access_buffer(0, &buffer);
     command_buffer.dispatch(1, 1, 1);
-    command_buffer.end();
-
-    queue.execute(&command_buffer);
-
     command_buffer.copy_buffer_to_buffer(
         &mut dst_buffer,
         &buffer,
         &BufferCopyRegion::default().set_copy_size(buffer_size),
     );
+    command_buffer.end();
 
+    queue.execute(&command_buffer);
     queue.flush();
     queue.sync();
 
-    dst_buffer.map_as_slice(64, |x: &[u32]| {
+    dst_buffer.map_as_slice(|x: &[u32]| {
         for value in x {
             println!("{}", value);
         }
@@ -17,7 +17,7 @@ use winit::{
 use winit::platform::web::WindowExtWebSys;
 
 #[repr(C)]
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
     #[allow(dead_code)]
     pub x: f32,
@@ -27,7 +27,7 @@ struct Vertex {
 }
 
 #[repr(C)]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct ConstantBuffer {
     pub red: f32,
     pub green: f32,
@@ -89,7 +89,7 @@ pub fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<Vertex>() * 3),
     );
-    vertex_buffer.map_as_slice_mut(3, |x| {
+    vertex_buffer.map_as_slice_mut(|x| {
         x[0] = Vertex { x: -0.5, y: -0.5 };
         x[1] = Vertex { x: 0.5, y: -0.5 };
         x[2] = Vertex { x: 0.0, y: 0.5 };
@@ -18,7 +18,7 @@ use winit::{
 use winit::platform::web::WindowExtWebSys;
 
 #[repr(C)]
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
     #[allow(dead_code)]
     pub x: f32,
@@ -84,7 +84,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<Vertex>() * 6),
     );
-    vertex_buffer.map_as_slice_mut(6, |buffer| {
+    vertex_buffer.map_as_slice_mut(|buffer| {
         buffer[0] = Vertex {
             x: -0.5,
             y: -0.5,
@@ -124,7 +124,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::INDEX_BUFFER)
             .set_size(std::mem::size_of::<u32>() * 6),
     );
-    index_buffer.map_as_slice_mut(6, |buffer| {
+    index_buffer.map_as_slice_mut(|buffer| {
         buffer[0] = 0;
         buffer[1] = 1;
         buffer[2] = 2;
@@ -16,7 +16,7 @@ use winit::{
 };
 
 #[repr(C)]
-#[derive(Default, Clone)]
+#[derive(Default, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
     #[allow(dead_code)]
     pub x: f32,
@@ -79,7 +79,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<Vertex>() * 4),
     );
-    vertex_buffer.map_as_slice_mut(4, |x: &mut [Vertex]| {
+    vertex_buffer.map_as_slice_mut(|x: &mut [Vertex]| {
         x[0] = Vertex { x: -0.5, y: 0.5 };
         x[1] = Vertex { x: -0.5, y: -0.5 };
         x[2] = Vertex { x: 0.5, y: -0.5 };
@@ -92,7 +92,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::INDEX_BUFFER)
             .set_size(std::mem::size_of::<u32>() * 6),
     );
-    index_buffer.map_as_slice_mut(6, |x| {
+    index_buffer.map_as_slice_mut(|x| {
         x[0] = 0;
         x[1] = 1;
         x[2] = 2;
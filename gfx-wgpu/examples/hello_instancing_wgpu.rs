@@ -18,8 +18,9 @@ use winit::{
 };
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct TransformData {
-    positions: [glm::Vec4; 9],
+    positions: [[f32; 4]; 9],
 }
 
 fn main() {
@@ -75,17 +76,17 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::CONSTANT_BUFFER),
     );
     positions_buffer.map_mut(|x: &mut TransformData| {
-        x.positions[0] = glm::vec4(-0.75, -0.75, 0.0, 0.0);
-        x.positions[1] = glm::vec4(0.0, -0.75, 0.0, 0.0);
-        x.positions[2] = glm::vec4(0.75, -0.75, 0.0, 0.0);
+        x.positions[0] = glm::vec4(-0.75, -0.75, 0.0, 0.0).into();
+        x.positions[1] = glm::vec4(0.0, -0.75, 0.0, 0.0).into();
+        x.positions[2] = glm::vec4(0.75, -0.75, 0.0, 0.0).into();
 
-        x.positions[3] = glm::vec4(-0.75, 0.0, 0.0, 0.0);
-        x.positions[4] = glm::vec4(0.0, 0.0, 0.0, 0.0);
-        x.positions[5] = glm::vec4(0.75, 0.0, 0.0, 0.0);
+        x.positions[3] = glm::vec4(-0.75, 0.0, 0.0, 0.0).into();
+        x.positions[4] = glm::vec4(0.0, 0.0, 0.0, 0.0).into();
+        x.positions[5] = glm::vec4(0.75, 0.0, 0.0, 0.0).into();
 
-        x.positions[6] = glm::vec4(-0.75, 0.75, 0.0, 0.0);
-        x.positions[7] = glm::vec4(0.0, 0.75, 0.0, 0.0);
-        x.positions[8] = glm::vec4(0.75, 0.75, 0.0, 0.0);
+        x.positions[6] = glm::vec4(-0.75, 0.75, 0.0, 0.0).into();
+        x.positions[7] = glm::vec4(0.0, 0.75, 0.0, 0.0).into();
+        x.positions[8] = glm::vec4(0.75, 0.75, 0.0, 0.0).into();
     });
 
     // 頂点バッファ
@@ -95,10 +96,10 @@ fn main() {
             .set_size(std::mem::size_of::<glm::Vec2>() * 3)
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER),
     );
-    vertex_buffer.map_as_slice_mut(3, |x: &mut [glm::Vec2]| {
-        x[0] = glm::vec2(-0.15, -0.15);
-        x[1] = glm::vec2(0.15, -0.15);
-        x[2] = glm::vec2(0.0, 0.15);
+    vertex_buffer.map_as_slice_mut(|x: &mut [[f32; 2]]| {
+        x[0] = glm::vec2(-0.15, -0.15).into();
+        x[1] = glm::vec2(0.15, -0.15).into();
+        x[2] = glm::vec2(0.0, 0.15).into();
     });
 
     event_loop.run_return(|event, _, control_flow| {
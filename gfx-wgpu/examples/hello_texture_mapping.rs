@@ -15,6 +15,7 @@ use winit::{
 };
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 struct Vertex {
     #[allow(dead_code)]
     pub x: f32,
@@ -99,7 +100,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
             .set_size(std::mem::size_of::<f32>() * 16),
     );
-    vertex_buffer.map_as_slice_mut(16, |x: &mut [f32]| {
+    vertex_buffer.map_as_slice_mut(|x: &mut [f32]| {
         x[0] = -1.0;
         x[1] = 1.0;
         x[2] = 0.0;
@@ -127,7 +128,7 @@ fn main() {
             .set_gpu_access_flags(GpuAccess::INDEX_BUFFER)
             .set_size(std::mem::size_of::<u32>() * 6),
     );
-    index_buffer.map_as_slice_mut(6, |x| {
+    index_buffer.map_as_slice_mut(|x| {
         x[0] = 0;
         x[1] = 1;
         x[2] = 2;
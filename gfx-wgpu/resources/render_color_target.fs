@@ -6,7 +6,79 @@ layout(location = 0) in vec2 v_Uv;
 
 layout(binding = 0) uniform texture2D u_Texture;
 layout(binding = 1) uniform sampler u_Sampler;
+layout(binding = 2) uniform OutputTransform {
+  float u_Exposure;
+  // 周囲との差をどれだけ強調するか。0 なら輪郭を強調しない
+  float u_Sharpness;
+  // 0: なし, 1: Reinhard, 2: ACES
+  uint u_Tonemap;
+  // 0: 線形, 1: ガンマ, 2: sRGB
+  uint u_Encoding;
+  float u_Gamma;
+  uint u_IsDitheringEnabled;
+};
+
+vec4 fetch(vec2 uv) {
+  return texture(sampler2D(u_Texture, u_Sampler), uv);
+}
+
+vec4 sharpen(vec2 uv) {
+  vec4 center = fetch(uv);
+  if (u_Sharpness <= 0.0) {
+    return center;
+  }
+
+  vec2 texelSize = 1.0 / vec2(textureSize(sampler2D(u_Texture, u_Sampler), 0));
+  vec4 left = fetch(uv - vec2(texelSize.x, 0.0));
+  vec4 right = fetch(uv + vec2(texelSize.x, 0.0));
+  vec4 top = fetch(uv - vec2(0.0, texelSize.y));
+  vec4 bottom = fetch(uv + vec2(0.0, texelSize.y));
+  vec4 edge = 4.0 * center - left - right - top - bottom;
+  return max(center + u_Sharpness * edge, 0.0);
+}
+
+// Narkowicz による ACES の近似
+vec3 tonemapAces(vec3 color) {
+  const float a = 2.51;
+  const float b = 0.03;
+  const float c = 2.43;
+  const float d = 0.59;
+  const float e = 0.14;
+  return (color * (a * color + b)) / (color * (c * color + d) + e);
+}
+
+vec3 tonemap(vec3 color) {
+  if (u_Tonemap == 1) {
+    return color / (1.0 + color);
+  } else if (u_Tonemap == 2) {
+    return tonemapAces(color);
+  }
+  return color;
+}
+
+vec3 encodeSrgb(vec3 color) {
+  vec3 low = color * 12.92;
+  vec3 high = 1.055 * pow(color, vec3(1.0 / 2.4)) - 0.055;
+  return mix(high, low, lessThanEqual(color, vec3(0.0031308)));
+}
+
+// 8 ビットの 1 段階分の揺らぎ
+float dither(vec2 position) {
+  float noise = fract(52.9829189 * fract(dot(position, vec2(0.06711056, 0.00583715))));
+  return (noise - 0.5) / 255.0;
+}
 
 void main() {
-  o_Color = texture(sampler2D(u_Texture, u_Sampler), v_Uv);
-}
\ No newline at end of file
+  vec4 color = sharpen(v_Uv);
+  vec3 rgb = tonemap(color.rgb * u_Exposure);
+  rgb = clamp(rgb, 0.0, 1.0);
+  if (u_Encoding == 1) {
+    rgb = pow(rgb, vec3(1.0 / u_Gamma));
+  } else if (u_Encoding == 2) {
+    rgb = encodeSrgb(rgb);
+  }
+  if (u_IsDitheringEnabled != 0) {
+    rgb += dither(gl_FragCoord.xy);
+  }
+  o_Color = vec4(rgb, clamp(color.a, 0.0, 1.0));
+}
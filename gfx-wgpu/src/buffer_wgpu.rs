@@ -1,6 +1,9 @@
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
-use sjgfx_interface::{BufferInfo, GpuAccess, IBuffer};
+use bytemuck::Pod;
+use sjgfx_interface::{
+    get_whole_map_range, validate_map_range, BufferInfo, CpuAccessMode, IBuffer,
+};
 use uuid::Uuid;
 use wgpu::util::DeviceExt;
 
@@ -11,6 +14,7 @@ pub struct BufferWgpu {
     queue: Arc<wgpu::Queue>,
     buffer: Arc<wgpu::Buffer>,
     size: usize,
+    cpu_access_mode: CpuAccessMode,
     id: Uuid,
 }
 
@@ -27,10 +31,11 @@ impl BufferWgpu {
         let queue = device.clone_queue();
         let device = device.close_device();
 
+        // キュー経由で末尾まで書き込めるようにサイズを COPY_BUFFER_ALIGNMENT の倍数にそろえる
         let buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: None,
-            size: info.get_size() as u64,
-            usage: Self::convert(&info.get_gpu_access_flags()),
+            label: info.get_debug_label(),
+            size: (info.get_size() as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT),
+            usage: Self::convert(info),
             mapped_at_creation: false,
         });
 
@@ -39,6 +44,7 @@ impl BufferWgpu {
             queue,
             buffer: Arc::new(buffer),
             size: info.get_size(),
+            cpu_access_mode: info.get_cpu_access_mode(),
             id: Uuid::new_v4(),
         }
     }
@@ -47,9 +53,9 @@ impl BufferWgpu {
         let queue = device.clone_queue();
         let device = device.close_device();
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: None,
+            label: info.get_debug_label(),
             contents: data,
-            usage: Self::convert(&info.get_gpu_access_flags()),
+            usage: Self::convert(info),
         });
 
         Self {
@@ -57,6 +63,7 @@ impl BufferWgpu {
             queue,
             buffer: Arc::new(buffer),
             size: info.get_size(),
+            cpu_access_mode: info.get_cpu_access_mode(),
             id: Uuid::new_v4(),
         }
     }
@@ -69,88 +76,246 @@ impl BufferWgpu {
         BufferView {
             buffer: self.buffer.clone(),
             id: self.id,
+            offset: 0,
+            size: None,
+        }
+    }
+
+    pub fn view_range(&self, offset: usize, size: usize) -> BufferView {
+        assert!(
+            offset + size <= self.size,
+            "buffer range {}..{} is out of buffer size {}",
+            offset,
+            offset + size,
+            self.size
+        );
+        BufferView {
+            buffer: self.buffer.clone(),
+            id: self.id,
+            offset: offset as u64,
+            size: wgpu::BufferSize::new(size as u64),
         }
     }
 
-    pub fn map<T, F: Fn(&T)>(&self, func: F) {
-        let (sender, _receiver) = futures_intrusive::channel::shared::oneshot_channel();
-        let _result = self
-            .buffer
-            .slice(..)
-            .map_async(wgpu::MapMode::Write, move |v| sender.send(v).unwrap());
+    pub fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.map_range(0..std::mem::size_of::<T>(), |x: &[T]| func(&x[0]));
+    }
 
-        self.device.poll(wgpu::Maintain::Wait);
+    pub fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_range(get_whole_map_range::<T>(self.size), func);
+    }
+
+    pub fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        if range.is_empty() {
+            func(&[]);
+            return;
+        }
+
+        // wgpu のマップは範囲のアライメントに制約があるので、範囲を含むようにマップして切り出す
+        let aligned_range = self.align_map_range(&range);
+        let local_range = (range.start - aligned_range.start as usize)
+            ..(range.end - aligned_range.start as usize);
+
+        if self.buffer.usage().contains(wgpu::BufferUsages::MAP_READ) {
+            // キュー経由の書き込みが残っているかもしれないので先にサブミットしておく
+            self.queue.submit(None);
+            self.read_mapped_range(&self.buffer, aligned_range, |data| {
+                Self::cast_slice(&data[local_range], func)
+            });
+            return;
+        }
+
+        // マップできないバッファーは読み戻し用のバッファーにコピーしてから読む
+        assert!(
+            self.buffer.usage().contains(wgpu::BufferUsages::COPY_SRC),
+            "buffer must have MAP_READ or COPY_SRC usage to be read back"
+        );
+        let size = aligned_range.end - aligned_range.start;
+        let staging_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut command_encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        command_encoder.copy_buffer_to_buffer(
+            &self.buffer,
+            aligned_range.start,
+            &staging_buffer,
+            0,
+            size,
+        );
+        self.queue.submit(Some(command_encoder.finish()));
+        self.read_mapped_range(&staging_buffer, 0..size, |data| {
+            Self::cast_slice(&data[local_range], func)
+        });
+    }
+
+    pub fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_range_mut(0..std::mem::size_of::<T>(), |x: &mut [T]| func(&mut x[0]));
+    }
+
+    pub fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_range_mut(get_whole_map_range::<T>(self.size), func);
+    }
+
+    pub fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        if range.is_empty() {
+            func(&mut []);
+            return;
+        }
 
-        let ptr = self.buffer.slice(..).get_mapped_range().as_ptr();
-        let casted = unsafe { (ptr as *const T).as_ref().unwrap() };
-        func(casted);
-        self.buffer.unmap();
+        if self.buffer.usage().contains(wgpu::BufferUsages::MAP_WRITE) {
+            let aligned_range = self.align_map_range(&range);
+            let local_range = (range.start - aligned_range.start as usize)
+                ..(range.end - aligned_range.start as usize);
+            let slice = self.buffer.slice(aligned_range);
+            slice.map_async(wgpu::MapMode::Write, |result| result.unwrap());
+            self.device.poll(wgpu::Maintain::Wait);
+            {
+                let mut data = slice.get_mapped_range_mut();
+                Self::cast_slice_mut(&mut data[local_range], func);
+            }
+            self.buffer.unmap();
+            return;
+        }
+
+        // マップできないバッファーはキュー経由で書き込む
+        let aligned_range = Self::align_write_range(&range);
+        if aligned_range != range {
+            // キュー経由の書き込みは COPY_BUFFER_ALIGNMENT の倍数でないといけないので、
+            // 前後のバイトを読み戻して範囲を広げてから書き込む
+            let local_range =
+                (range.start - aligned_range.start)..(range.end - aligned_range.start);
+            // 確保したサイズを超える部分は読めないのでゼロで埋める
+            let mut data = Vec::new();
+            let read_range = aligned_range.start..aligned_range.end.min(self.size);
+            self.map_range(read_range, |x: &[u8]| data.extend_from_slice(x));
+            data.resize(aligned_range.end - aligned_range.start, 0);
+            Self::cast_slice_mut(&mut data[local_range], func);
+            self.queue
+                .write_buffer(&self.buffer, aligned_range.start as u64, &data);
+            return;
+        }
+
+        // wgpu のステージング用のメモリーに直接書き込む。中身は読めないのでゼロで埋めてから渡す
+        let size = wgpu::BufferSize::new((range.end - range.start) as u64).unwrap();
+        let mut data = self
+            .queue
+            .write_buffer_with(&self.buffer, range.start as u64, size)
+            .unwrap();
+        data.fill(0);
+        Self::cast_slice_mut(&mut data, func);
     }
 
-    pub fn map_mut<T, F: Fn(&mut T)>(&self, func: F)
-    where
-        T: Default,
-    {
-        let mut temp_buffer = Default::default();
-        func(&mut temp_buffer);
-        let data = unsafe {
-            std::slice::from_raw_parts(
-                (&temp_buffer as *const T) as *const u8,
-                std::mem::size_of::<T>(),
-            )
-        };
+    // 確保するときにサイズをそろえてあるので末尾を広げてもはみ出さない
+    fn align_write_range(range: &Range<usize>) -> Range<usize> {
+        let alignment = wgpu::COPY_BUFFER_ALIGNMENT as usize;
+        let start = range.start / alignment * alignment;
+        let end = range.end.next_multiple_of(alignment);
+        start..end
+    }
 
-        self.queue.write_buffer(&self.buffer, 0, &data);
+    fn validate_map_range<T: Pod>(&self, range: &Range<usize>) {
+        assert!(
+            self.cpu_access_mode.is_mappable(),
+            "buffer created with CpuAccessMode::None can't be mapped"
+        );
+        validate_map_range::<T>(self.size, range);
     }
 
-    pub fn map_as_slice<T, F: Fn(&[T])>(&self, size: usize, func: F) {
-        let (sender, _receiver) = futures_intrusive::channel::shared::oneshot_channel();
-        let _result = self
-            .buffer
-            .slice(..)
-            .map_async(wgpu::MapMode::Read, move |v| sender.send(v).unwrap());
+    // マップの開始位置は MAP_ALIGNMENT、サイズは COPY_BUFFER_ALIGNMENT の倍数でないといけない
+    fn align_map_range(&self, range: &Range<usize>) -> Range<u64> {
+        let start = range.start as u64 / wgpu::MAP_ALIGNMENT * wgpu::MAP_ALIGNMENT;
+        let end = (range.end as u64).next_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT);
+        start..end.min(self.buffer.size())
+    }
 
+    fn read_mapped_range<F: FnOnce(&[u8])>(
+        &self,
+        buffer: &wgpu::Buffer,
+        range: Range<u64>,
+        func: F,
+    ) {
+        let slice = buffer.slice(range);
+        slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
         self.device.poll(wgpu::Maintain::Wait);
+        {
+            let data = slice.get_mapped_range();
+            func(&data);
+        }
+        buffer.unmap();
+    }
+
+    // マップしたメモリーが T のアライメントを満たしていなければコピーしてから渡す
+    fn cast_slice<T: Pod, F: FnOnce(&[T])>(data: &[u8], func: F) {
+        match bytemuck::try_cast_slice(data) {
+            Ok(slice) => func(slice),
+            Err(_) => func(&Self::copy_to_vec(data)),
+        }
+    }
+
+    fn cast_slice_mut<T: Pod, F: FnOnce(&mut [T])>(data: &mut [u8], func: F) {
+        match bytemuck::try_cast_slice_mut(data) {
+            Ok(slice) => func(slice),
+            Err(_) => {
+                let mut temp = Self::copy_to_vec::<T>(data);
+                func(&mut temp);
+                data.copy_from_slice(bytemuck::cast_slice(&temp));
+            }
+        }
+    }
 
-        let ptr = self
-            .buffer
-            .slice(..)
-            .get_mapped_range()
-            .as_ptr()
-            .cast::<T>();
-        let slice = unsafe { std::slice::from_raw_parts::<T>(ptr, size) };
-        func(slice);
-        self.buffer.unmap();
-    }
-
-    pub fn map_as_slice_mut<T, F: Fn(&mut [T])>(&self, size: usize, func: F)
-    where
-        T: Default + Clone,
-    {
-        let mut buffer = vec![Default::default(); size];
-        func(&mut buffer);
-        let data = unsafe {
-            std::slice::from_raw_parts(
-                (buffer.as_mut_ptr() as *const T) as *const u8,
-                size * std::mem::size_of::<T>(),
-            )
-        };
-
-        self.queue.write_buffer(&self.buffer, 0, &data);
+    fn copy_to_vec<T: Pod>(data: &[u8]) -> Vec<T> {
+        let mut result = vec![T::zeroed(); data.len() / std::mem::size_of::<T>()];
+        bytemuck::cast_slice_mut(&mut result).copy_from_slice(data);
+        result
     }
 
     pub fn get_gpu_address(&self) -> GpuAddressWgpu {
         GpuAddressWgpu::new(self)
     }
 
-    fn convert(gpu_access: &GpuAccess) -> wgpu::BufferUsages {
-        crate::util::convert_to_buffer_usage(*gpu_access)
+    fn convert(info: &BufferInfo) -> wgpu::BufferUsages {
+        crate::util::convert_to_buffer_usage(
+            info.get_gpu_access_flags(),
+            info.get_cpu_access_mode(),
+        )
     }
 }
 
 pub struct BufferView {
     pub buffer: Arc<wgpu::Buffer>,
     pub id: Uuid,
+
+    // size が None のときは offset から末尾まで
+    pub offset: u64,
+    pub size: Option<wgpu::BufferSize>,
+}
+
+impl BufferView {
+    pub fn is_same(&self, other: &BufferView) -> bool {
+        self.id == other.id && self.offset == other.offset && self.size == other.size
+    }
+
+    pub fn as_binding(&self) -> wgpu::BindingResource<'_> {
+        wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+            buffer: &self.buffer,
+            offset: self.offset,
+            size: self.size,
+        })
+    }
+
+    pub fn slice(&self) -> wgpu::BufferSlice<'_> {
+        match self.size {
+            Some(size) => self.buffer.slice(self.offset..(self.offset + size.get())),
+            None => self.buffer.slice(self.offset..),
+        }
+    }
 }
 
 impl IBuffer for BufferWgpu {
@@ -160,21 +325,28 @@ impl IBuffer for BufferWgpu {
         Self::new(device, info)
     }
 
-    fn map<T, F: Fn(&T)>(&self, func: F) {
+    fn map<T: Pod, F: FnOnce(&T)>(&self, func: F) {
         self.map(func);
     }
 
-    fn map_mut<T, F: Fn(&mut T)>(&self, _func: F) {
-        todo!()
+    fn map_as_slice<T: Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.map_as_slice(func);
+    }
+
+    fn map_range<T: Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.map_range(range, func);
+    }
+
+    fn map_mut<T: Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.map_mut(func);
     }
 
-    fn map_as_slice<T, F: Fn(&[T])>(&self, func: F) {
-        let size = self.size / std::mem::size_of::<T>();
-        self.map_as_slice(size + 1, func);
+    fn map_as_slice_mut<T: Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.map_as_slice_mut(func);
     }
 
-    fn map_as_slice_mut<T, F: Fn(&mut [T])>(&self, _func: F) {
-        todo!()
+    fn map_range_mut<T: Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.map_range_mut(range, func);
     }
 
     fn flush_mapped_range(&self, _offset: isize, _size: usize) {}
@@ -1,40 +1,83 @@
 use std::sync::{Arc, Mutex};
 
-use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView};
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData};
 use wgpu::{TextureFormat, TextureViewDescriptor};
 
-use crate::{util, DeviceWgpu, TextureWgpu};
+use crate::{detail::PendingCommandBuffers, util, DeviceWgpu, TextureWgpu};
 
 #[derive(Debug, Clone)]
 pub struct ColorTargetViewWgpu {
-    _surface_texture: Option<Arc<Mutex<Option<wgpu::SurfaceTexture>>>>,
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pending_command_buffers: PendingCommandBuffers,
+    // スキャンバッファに直接描画するときはサーフェスのテクスチャを参照する
+    surface_texture: Option<Arc<Mutex<Option<wgpu::SurfaceTexture>>>>,
+    texture: Option<Arc<wgpu::Texture>>,
     texture_view: Option<Arc<wgpu::TextureView>>,
     texture_format: TextureFormat,
 }
 
 impl ColorTargetViewWgpu {
-    pub fn new(_device: &DeviceWgpu, info: &ColorTargetViewInfo, texture: &TextureWgpu) -> Self {
-        let view = texture
-            .get_texture()
-            .create_view(&TextureViewDescriptor::default());
+    pub fn new(device: &DeviceWgpu, info: &ColorTargetViewInfo, texture: &TextureWgpu) -> Self {
+        let view = texture.get_texture().create_view(&TextureViewDescriptor {
+            label: info.get_debug_label(),
+            ..Default::default()
+        });
         Self {
-            _surface_texture: None,
+            device: device.close_device(),
+            queue: device.clone_queue(),
+            pending_command_buffers: device.clone_pending_command_buffers(),
+            surface_texture: None,
+            texture: Some(texture.close_texture()),
             texture_view: Some(Arc::new(view)),
             texture_format: util::convert_format(info.get_image_format()),
         }
     }
 
     pub(crate) fn new_direct(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        pending_command_buffers: PendingCommandBuffers,
+        texture: Arc<wgpu::Texture>,
         texture_view: Arc<wgpu::TextureView>,
         texture_format: TextureFormat,
     ) -> Self {
         Self {
-            _surface_texture: None,
+            device,
+            queue,
+            pending_command_buffers,
+            surface_texture: None,
+            texture: Some(texture),
             texture_view: Some(texture_view),
             texture_format,
         }
     }
 
+    pub(crate) fn new_from_surface_texture(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        pending_command_buffers: PendingCommandBuffers,
+        surface_texture: Arc<Mutex<Option<wgpu::SurfaceTexture>>>,
+        texture_format: TextureFormat,
+    ) -> Self {
+        let texture_view = surface_texture
+            .lock()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .texture
+            .create_view(&TextureViewDescriptor::default());
+        Self {
+            device,
+            queue,
+            pending_command_buffers,
+            surface_texture: Some(surface_texture),
+            texture: None,
+            texture_view: Some(Arc::new(texture_view)),
+            texture_format,
+        }
+    }
+
     pub fn get_texture_view(&self) -> &wgpu::TextureView {
         self.texture_view.as_ref().unwrap()
     }
@@ -42,6 +85,32 @@ impl ColorTargetViewWgpu {
     pub fn get_texture_format(&self) -> wgpu::TextureFormat {
         self.texture_format
     }
+
+    pub fn read_image(&self) -> ImageData {
+        if let Some(texture) = &self.texture {
+            return self.read_image_impl(texture);
+        }
+
+        // 表示済みのスキャンバッファは読み戻せない
+        let surface_texture = self.surface_texture.as_ref().unwrap().lock().unwrap();
+        let surface_texture = surface_texture
+            .as_ref()
+            .expect("scan buffer has already been presented");
+        self.read_image_impl(&surface_texture.texture)
+    }
+
+    fn read_image_impl(&self, texture: &wgpu::Texture) -> ImageData {
+        let format = texture.format();
+        let image_format = util::convert_from_format(format)
+            .unwrap_or_else(|| panic!("{:?} can't be read back", format));
+        TextureWgpu::read_image_impl(
+            &self.device,
+            &self.queue,
+            &self.pending_command_buffers,
+            texture,
+            image_format,
+        )
+    }
 }
 
 impl IColorTargetView for ColorTargetViewWgpu {
@@ -55,4 +124,8 @@ impl IColorTargetView for ColorTargetViewWgpu {
     ) -> Self {
         Self::new(device, info, texture)
     }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
 }
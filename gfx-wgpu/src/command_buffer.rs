@@ -35,9 +35,33 @@ enum DrawCommand {
     DrawIndexed(DrawIndexedInfo),
 }
 
+// 描画やディスパッチ以外のコマンド。積まれた順にコマンドエンコーダーに記録する
+enum EncoderCommand {
+    PushDebugGroup(String),
+    PopDebugGroup,
+    InsertDebugMarker(String),
+    ClearColor {
+        color_target_view: ColorTargetViewWgpu,
+        color: wgpu::Color,
+    },
+    CopyImageToBuffer {
+        buffer: Arc<wgpu::Buffer>,
+        texture: Arc<wgpu::Texture>,
+        offset: u64,
+        copy_size: Extent3d,
+    },
+    CopyBufferToBuffer {
+        dst_buffer: Arc<wgpu::Buffer>,
+        src_buffer: Arc<wgpu::Buffer>,
+        dst_offset: BufferAddress,
+        src_offset: BufferAddress,
+        copy_size: BufferAddress,
+    },
+}
+
 pub struct CommandBufferWgpu {
     device: Arc<wgpu::Device>,
-    queue: Arc<wgpu::Queue>,
+    label: Option<String>,
 
     // レンダーターゲット
     color_target_view: [Option<ColorTargetViewWgpu>; 8],
@@ -64,13 +88,17 @@ pub struct CommandBufferWgpu {
 
     render_pipeline: Option<wgpu::RenderPipeline>,
     is_render_pipeliine_dirty: bool,
+
+    // 描画コマンドの前後で積まれたものを分けて保持する
+    encoder_commands_before_draw: Vec<EncoderCommand>,
+    encoder_commands_after_draw: Vec<EncoderCommand>,
 }
 
 impl CommandBufferWgpu {
-    pub fn new(device: &DeviceWgpu, _info: &CommandBufferInfo) -> Self {
+    pub fn new(device: &DeviceWgpu, info: &CommandBufferInfo) -> Self {
         Self {
             device: device.close_device(),
-            queue: device.clone_queue(),
+            label: info.get_debug_label().map(|x| x.to_string()),
 
             color_target_view: Default::default(),
             depth_stencil_view: None,
@@ -97,10 +125,19 @@ impl CommandBufferWgpu {
             // 演算シェーダの可能性もあるので false を初期値に設定
             is_render_pipeliine_dirty: false,
             render_pipeline: None,
+
+            encoder_commands_before_draw: Vec::new(),
+            encoder_commands_after_draw: Vec::new(),
         }
     }
 
-    pub fn begin(&self) {}
+    pub fn begin(&mut self) {
+        // 描画とディスパッチは記録ごとに積みなおすので、前回の記録のものを残さない
+        self.encoder_commands_before_draw.clear();
+        self.encoder_commands_after_draw.clear();
+        self.draw_command = None;
+        self.dispatch_count = None;
+    }
 
     pub fn end(&mut self) {
         if self.is_render_pipeliine_dirty {
@@ -146,7 +183,7 @@ impl CommandBufferWgpu {
             let render_pipeline =
                 self.device
                     .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                        label: None,
+                        label: self.label.as_deref(),
                         layout: None,
                         vertex: wgpu::VertexState {
                             module: &vertex_shader_module,
@@ -186,6 +223,18 @@ impl CommandBufferWgpu {
         }
     }
 
+    pub fn push_debug_group(&mut self, label: &str) {
+        self.push_encoder_command(EncoderCommand::PushDebugGroup(label.to_string()));
+    }
+
+    pub fn pop_debug_group(&mut self) {
+        self.push_encoder_command(EncoderCommand::PopDebugGroup);
+    }
+
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        self.push_encoder_command(EncoderCommand::InsertDebugMarker(label.to_string()));
+    }
+
     pub fn clear_color(
         &mut self,
         color_target_view: &mut ColorTargetViewWgpu,
@@ -195,29 +244,15 @@ impl CommandBufferWgpu {
         alpha: f32,
         _texture_array_range: TextureArrayRange,
     ) {
-        let mut command_encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-        {
-            let _ = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: color_target_view.get_texture_view(),
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: red as f64,
-                            g: green as f64,
-                            b: blue as f64,
-                            a: alpha as f64,
-                        }),
-                        store: true,
-                    },
-                })],
-                depth_stencil_attachment: None,
-            });
-        }
-        self.queue.submit(Some(command_encoder.finish()));
+        self.push_encoder_command(EncoderCommand::ClearColor {
+            color_target_view: color_target_view.clone(),
+            color: wgpu::Color {
+                r: red as f64,
+                g: green as f64,
+                b: blue as f64,
+                a: alpha as f64,
+            },
+        });
     }
 
     pub fn set_render_targets(
@@ -266,21 +301,27 @@ impl CommandBufferWgpu {
     }
 
     pub fn set_constant_buffer(&mut self, index: i32, buffer: &BufferWgpu) {
-        let current_buffer = &mut self.constant_buffers[index as usize];
-        if current_buffer.is_some() && current_buffer.as_ref().unwrap().id == *buffer.get_id() {
-            // 変更がないので更新しない
-        } else {
-            std::mem::swap(current_buffer, &mut Some(buffer.view()));
-        }
+        Self::update_buffer_view(&mut self.constant_buffers[index as usize], buffer.view());
+    }
+
+    pub fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &BufferWgpu,
+        offset: usize,
+        size: usize,
+    ) {
+        Self::update_buffer_view(
+            &mut self.constant_buffers[index as usize],
+            buffer.view_range(offset, size),
+        );
     }
 
     pub fn set_unordered_access_buffer(&mut self, index: i32, buffer: &BufferWgpu) {
-        let current_buffer = &mut self.unordered_access_buffer[index as usize];
-        if current_buffer.is_some() && current_buffer.as_ref().unwrap().id == *buffer.get_id() {
-            // 変更がないので更新しない
-        } else {
-            std::mem::swap(current_buffer, &mut Some(buffer.view()));
-        }
+        Self::update_buffer_view(
+            &mut self.unordered_access_buffer[index as usize],
+            buffer.view(),
+        );
     }
 
     pub fn set_texture_direct(&mut self, index: i32, texture: &TextureWgpu) {
@@ -305,11 +346,27 @@ impl CommandBufferWgpu {
     }
 
     pub fn set_vertex_buffer(&mut self, index: i32, buffer: &BufferWgpu) {
-        let current_buffer = &mut self.vertex_buffer[index as usize];
-        if current_buffer.is_some() && current_buffer.as_ref().unwrap().id == *buffer.get_id() {
+        Self::update_buffer_view(&mut self.vertex_buffer[index as usize], buffer.view());
+    }
+
+    pub fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &BufferWgpu,
+        offset: usize,
+        size: usize,
+    ) {
+        Self::update_buffer_view(
+            &mut self.vertex_buffer[index as usize],
+            buffer.view_range(offset, size),
+        );
+    }
+
+    fn update_buffer_view(current_buffer: &mut Option<BufferView>, buffer_view: BufferView) {
+        if current_buffer.is_some() && current_buffer.as_ref().unwrap().is_same(&buffer_view) {
             // 変更がないので更新しない
         } else {
-            std::mem::swap(current_buffer, &mut Some(buffer.view()));
+            *current_buffer = Some(buffer_view);
         }
     }
 
@@ -417,182 +474,244 @@ impl CommandBufferWgpu {
         texture: &TextureWgpu,
         copy_region: BufferTextureCopyRegion,
     ) {
-        let mut command_encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-
-        let copy_size = Extent3d {
-            width: copy_region.get_image_width() as u32,
-            height: copy_region.get_image_height() as u32,
-            depth_or_array_layers: 0,
-        };
-        let image = texture.close_texture();
-        let image_copy = image.as_image_copy();
-        let image_copy_buffer = wgpu::ImageCopyBuffer {
-            buffer: buffer.get_buffer(),
-            layout: wgpu::ImageDataLayout {
-                offset: copy_region.get_offset() as u64,
-                bytes_per_row: None,
-                rows_per_image: None,
+        self.push_encoder_command(EncoderCommand::CopyImageToBuffer {
+            buffer: buffer.view().buffer,
+            texture: texture.close_texture(),
+            offset: copy_region.get_offset() as u64,
+            copy_size: Extent3d {
+                width: copy_region.get_image_width() as u32,
+                height: copy_region.get_image_height() as u32,
+                depth_or_array_layers: 0,
             },
-        };
-        command_encoder.copy_texture_to_buffer(image_copy, image_copy_buffer, copy_size);
-        self.queue.submit(Some(command_encoder.finish()));
+        });
     }
 
     pub fn copy_buffer_to_buffer(
-        &self,
+        &mut self,
         dst_buffer: &mut BufferWgpu,
         src_buffer: &BufferWgpu,
         region: &BufferCopyRegion,
     ) {
-        let source = src_buffer.get_buffer();
-        let source_offset = region.get_src_offset();
-        let destination = dst_buffer.get_buffer();
-        let destination_offset = region.get_dst_offset();
-        let copy_size = region.get_copy_size();
-        let mut command_encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
-        command_encoder.copy_buffer_to_buffer(
-            source,
-            source_offset as BufferAddress,
-            destination,
-            destination_offset as BufferAddress,
-            copy_size as BufferAddress,
-        );
-        self.queue.submit(Some(command_encoder.finish()));
+        self.push_encoder_command(EncoderCommand::CopyBufferToBuffer {
+            dst_buffer: dst_buffer.view().buffer,
+            src_buffer: src_buffer.view().buffer,
+            dst_offset: region.get_dst_offset() as BufferAddress,
+            src_offset: region.get_src_offset() as BufferAddress,
+            copy_size: region.get_copy_size() as BufferAddress,
+        });
     }
 
     pub(crate) fn build_command(&self) -> Option<wgpu::CommandBuffer> {
+        if self.shader.is_none()
+            && self.encoder_commands_before_draw.is_empty()
+            && self.encoder_commands_after_draw.is_empty()
+        {
+            return None;
+        }
+
+        let mut command_encoder =
+            self.device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: self.label.as_deref(),
+                });
+        self.encode_commands(&mut command_encoder, &self.encoder_commands_before_draw);
         if let Some(shader) = &self.shader {
+            // コピーだけを積んだときはディスパッチしない
             if shader.is_compute() {
-                return Some(self.build_compute_command());
+                if self.dispatch_count.is_some() {
+                    self.encode_compute_pass(&mut command_encoder);
+                }
             } else {
-                return Some(self.build_graphics_command());
+                self.encode_render_pass(&mut command_encoder);
             }
-        } else {
-            return None;
         }
+        self.encode_commands(&mut command_encoder, &self.encoder_commands_after_draw);
+
+        Some(command_encoder.finish())
     }
 
-    fn build_compute_command(&self) -> wgpu::CommandBuffer {
+    fn encode_compute_pass(&self, command_encoder: &mut wgpu::CommandEncoder) {
         let bind_group = self.create_bind_group();
-        let mut command_encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-        {
-            let mut compute_pass =
-                command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: None });
-
-            let compute_pipeline = self.shader.as_ref().unwrap().get_compute_pipeline();
-            compute_pass.set_pipeline(&compute_pipeline);
+        let mut compute_pass = command_encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: self.label.as_deref(),
+        });
 
-            compute_pass.set_bind_group(0, &bind_group, &[]);
+        let compute_pipeline = self.shader.as_ref().unwrap().get_compute_pipeline();
+        compute_pass.set_pipeline(&compute_pipeline);
 
-            let (dispatch_count_x, dispatch_cout_y, dispatch_count_z) =
-                *self.dispatch_count.as_ref().unwrap();
-            compute_pass.dispatch_workgroups(dispatch_count_x, dispatch_cout_y, dispatch_count_z);
-        }
+        compute_pass.set_bind_group(0, &bind_group, &[]);
 
-        command_encoder.finish()
+        let (dispatch_count_x, dispatch_cout_y, dispatch_count_z) =
+            *self.dispatch_count.as_ref().unwrap();
+        compute_pass.dispatch_workgroups(dispatch_count_x, dispatch_cout_y, dispatch_count_z);
     }
 
-    fn build_graphics_command(&self) -> wgpu::CommandBuffer {
+    fn encode_render_pass(&self, command_encoder: &mut wgpu::CommandEncoder) {
         // レンダーパイプライン。CommandBufferWgpu::End() で更新済み
         let render_pipeline = self.render_pipeline.as_ref().unwrap();
 
         let bind_group = self.create_bind_group();
-        let mut command_encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-        {
-            let color_attachments = self
-                .color_target_view
-                .iter()
-                .map(|x| {
-                    if let Some(view) = x {
-                        Some(wgpu::RenderPassColorAttachment {
-                            view: view.get_texture_view(),
-                            resolve_target: None,
-                            ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Load,
-                                store: true,
-                            },
-                        })
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>();
-            let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: None,
-                color_attachments: &color_attachments,
-                depth_stencil_attachment: self.create_render_pass_depth_stencil_attachment(),
-            });
-
-            // パイプライン
-            render_pass.set_pipeline(&render_pipeline);
-
-            // デスクリプタたち
-            render_pass.set_bind_group(0, &bind_group, &[]);
-
-            // ビューポート
-            if let Some(viewport_state_info) = &self.viewport_state_info {
-                render_pass.set_viewport(
-                    viewport_state_info.get_origin_x(),
-                    viewport_state_info.get_origin_y(),
-                    viewport_state_info.get_width(),
-                    viewport_state_info.get_height(),
-                    -1.0, /*min_depth*/
-                    1.0,  /*max_depth*/
-                )
-            }
+        let color_attachments = self
+            .color_target_view
+            .iter()
+            .map(|x| {
+                if let Some(view) = x {
+                    Some(wgpu::RenderPassColorAttachment {
+                        view: view.get_texture_view(),
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: true,
+                        },
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: self.label.as_deref(),
+            color_attachments: &color_attachments,
+            depth_stencil_attachment: self.create_render_pass_depth_stencil_attachment(),
+        });
+
+        // パイプライン
+        render_pass.set_pipeline(&render_pipeline);
+
+        // デスクリプタたち
+        render_pass.set_bind_group(0, &bind_group, &[]);
+
+        // ビューポート
+        if let Some(viewport_state_info) = &self.viewport_state_info {
+            render_pass.set_viewport(
+                viewport_state_info.get_origin_x(),
+                viewport_state_info.get_origin_y(),
+                viewport_state_info.get_width(),
+                viewport_state_info.get_height(),
+                -1.0, /*min_depth*/
+                1.0,  /*max_depth*/
+            )
+        }
 
-            // シザリング
-            if let Some(scissor_state_info) = &self.scissor_state_info {
-                render_pass.set_scissor_rect(
-                    scissor_state_info.get_origin_x() as u32,
-                    scissor_state_info.get_origin_y() as u32,
-                    scissor_state_info.get_width() as u32,
-                    scissor_state_info.get_height() as u32,
-                );
+        // シザリング
+        if let Some(scissor_state_info) = &self.scissor_state_info {
+            render_pass.set_scissor_rect(
+                scissor_state_info.get_origin_x() as u32,
+                scissor_state_info.get_origin_y() as u32,
+                scissor_state_info.get_width() as u32,
+                scissor_state_info.get_height() as u32,
+            );
+        }
+
+        // 頂点バッファ
+        for (index, vertex_buffer_opt) in self.vertex_buffer.iter().enumerate() {
+            if let Some(vertex_buffer) = vertex_buffer_opt {
+                let index = index as u32;
+                render_pass.set_vertex_buffer(index, vertex_buffer.slice());
             }
+        }
+
+        if let Some(vertex_buffer_view) = &self.vertex_buffer[0] {
+            render_pass.set_vertex_buffer(0, vertex_buffer_view.slice());
+        }
 
-            // 頂点バッファ
-            for (index, vertex_buffer_opt) in self.vertex_buffer.iter().enumerate() {
-                if let Some(vertex_buffer) = vertex_buffer_opt {
-                    let index = index as u32;
-                    render_pass.set_vertex_buffer(index, vertex_buffer.buffer.slice(..));
+        // 描画
+        if let Some(draw_command) = &self.draw_command {
+            match draw_command {
+                DrawCommand::Draw(ref draw_info) => {
+                    render_pass.draw(
+                        0..draw_info.vertex_count,
+                        draw_info.base_instance..draw_info.instance_count,
+                    );
+                }
+                DrawCommand::DrawIndexed(ref draw_indexed_info) => {
+                    let buffer_slice = draw_indexed_info.index_buffer.buffer.slice(..);
+                    render_pass.set_index_buffer(buffer_slice, draw_indexed_info.index_format);
+                    render_pass.draw_indexed(
+                        0..draw_indexed_info.index_count,
+                        0,
+                        draw_indexed_info.base_instance..draw_indexed_info.instance_count,
+                    );
                 }
             }
+        }
+    }
 
-            if let Some(vertex_buffer_view) = &self.vertex_buffer[0] {
-                render_pass.set_vertex_buffer(0, vertex_buffer_view.buffer.slice(..));
-            }
+    fn push_encoder_command(&mut self, encoder_command: EncoderCommand) {
+        // 描画コマンドはステートとして保持しているので、描画の前後どちらで積まれたかだけを記録する
+        if self.draw_command.is_some() || self.dispatch_count.is_some() {
+            self.encoder_commands_after_draw.push(encoder_command);
+        } else {
+            self.encoder_commands_before_draw.push(encoder_command);
+        }
+    }
 
-            // 描画
-            if let Some(draw_command) = &self.draw_command {
-                match draw_command {
-                    DrawCommand::Draw(ref draw_info) => {
-                        render_pass.draw(
-                            0..draw_info.vertex_count,
-                            draw_info.base_instance..draw_info.instance_count,
-                        );
-                    }
-                    DrawCommand::DrawIndexed(ref draw_indexed_info) => {
-                        let buffer_slice = draw_indexed_info.index_buffer.buffer.slice(..);
-                        render_pass.set_index_buffer(buffer_slice, draw_indexed_info.index_format);
-                        render_pass.draw_indexed(
-                            0..draw_indexed_info.index_count,
-                            0,
-                            draw_indexed_info.base_instance..draw_indexed_info.instance_count,
-                        );
-                    }
+    fn encode_commands(
+        &self,
+        command_encoder: &mut wgpu::CommandEncoder,
+        encoder_commands: &[EncoderCommand],
+    ) {
+        for encoder_command in encoder_commands {
+            match encoder_command {
+                EncoderCommand::PushDebugGroup(label) => command_encoder.push_debug_group(label),
+                EncoderCommand::PopDebugGroup => command_encoder.pop_debug_group(),
+                EncoderCommand::InsertDebugMarker(label) => {
+                    command_encoder.insert_debug_marker(label)
+                }
+                EncoderCommand::ClearColor {
+                    color_target_view,
+                    color,
+                } => {
+                    let _ = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: self.label.as_deref(),
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: color_target_view.get_texture_view(),
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(*color),
+                                store: true,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                    });
+                }
+                EncoderCommand::CopyImageToBuffer {
+                    buffer,
+                    texture,
+                    offset,
+                    copy_size,
+                } => {
+                    let image_copy_buffer = wgpu::ImageCopyBuffer {
+                        buffer,
+                        layout: wgpu::ImageDataLayout {
+                            offset: *offset,
+                            bytes_per_row: None,
+                            rows_per_image: None,
+                        },
+                    };
+                    command_encoder.copy_texture_to_buffer(
+                        texture.as_image_copy(),
+                        image_copy_buffer,
+                        *copy_size,
+                    );
+                }
+                EncoderCommand::CopyBufferToBuffer {
+                    dst_buffer,
+                    src_buffer,
+                    dst_offset,
+                    src_offset,
+                    copy_size,
+                } => {
+                    command_encoder.copy_buffer_to_buffer(
+                        src_buffer,
+                        *src_offset,
+                        dst_buffer,
+                        *dst_offset,
+                        *copy_size,
+                    );
                 }
             }
         }
-        command_encoder.finish()
     }
 
     fn create_bind_group(&self) -> wgpu::BindGroup {
@@ -603,7 +722,7 @@ impl CommandBufferWgpu {
             if let Some(unordered_access_buffer) = &self.unordered_access_buffer[index] {
                 entries.push(wgpu::BindGroupEntry {
                     binding: index as u32,
-                    resource: unordered_access_buffer.buffer.as_entire_binding(),
+                    resource: unordered_access_buffer.as_binding(),
                 });
             }
         }
@@ -613,7 +732,7 @@ impl CommandBufferWgpu {
             if let Some(constant_buffer) = &self.constant_buffers[index] {
                 entries.push(wgpu::BindGroupEntry {
                     binding: index as u32,
-                    resource: constant_buffer.buffer.as_entire_binding(),
+                    resource: constant_buffer.as_binding(),
                 });
             }
         }
@@ -649,7 +768,7 @@ impl CommandBufferWgpu {
         }
 
         self.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: None,
+            label: self.label.as_deref(),
             layout: self.shader.as_ref().unwrap().get_bind_group_layout(),
             entries: &entries,
         })
@@ -707,13 +826,25 @@ impl ICommandBuffer for CommandBufferWgpu {
     }
 
     fn begin(&mut self) {
-        CommandBufferWgpu::begin(&self);
+        CommandBufferWgpu::begin(self);
     }
 
     fn end(&mut self) {
         CommandBufferWgpu::end(self);
     }
 
+    fn push_debug_group(&mut self, label: &str) {
+        CommandBufferWgpu::push_debug_group(self, label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        CommandBufferWgpu::pop_debug_group(self);
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        CommandBufferWgpu::insert_debug_marker(self, label);
+    }
+
     fn clear_color(
         &mut self,
         color_target_view: &mut Self::ColorTargetViewType,
@@ -761,6 +892,16 @@ impl ICommandBuffer for CommandBufferWgpu {
         self.set_constant_buffer(index, buffer);
     }
 
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_constant_buffer_range(index, buffer, offset, size);
+    }
+
     fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
         self.set_unordered_access_buffer(index, buffer);
     }
@@ -769,6 +910,16 @@ impl ICommandBuffer for CommandBufferWgpu {
         self.set_vertex_buffer(index, buffer);
     }
 
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.set_vertex_buffer_range(index, buffer, offset, size);
+    }
+
     fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
         self.set_vertex_state(vertex_state);
     }
@@ -1,2 +1,6 @@
+mod offscreen_scan_buffers;
+mod pending_command_buffers;
 mod swap_chain_pipeline;
+pub use offscreen_scan_buffers::OffscreenScanBuffers;
+pub use pending_command_buffers::PendingCommandBuffers;
 pub use swap_chain_pipeline::SwapChainPipeline;
@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use sjgfx_interface::ImageData;
+
+use crate::{detail::PendingCommandBuffers, ColorTargetViewWgpu};
+
+// サーフェスがないときにスキャンバッファの代わりに使うテクスチャのリング
+// 表示されたフレームは次に取得されるまで読み戻せる
+pub struct OffscreenScanBuffers {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pending_command_buffers: PendingCommandBuffers,
+    texture_format: wgpu::TextureFormat,
+    count: usize,
+    views: Vec<ColorTargetViewWgpu>,
+    next_index: usize,
+    presented_index: Option<usize>,
+}
+
+impl OffscreenScanBuffers {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        pending_command_buffers: PendingCommandBuffers,
+        texture_format: wgpu::TextureFormat,
+        count: usize,
+    ) -> Self {
+        assert!(count > 0);
+        Self {
+            device,
+            queue,
+            pending_command_buffers,
+            texture_format,
+            count,
+            views: Vec::new(),
+            next_index: 0,
+            presented_index: None,
+        }
+    }
+
+    // 大きさが変わると中身は失われる
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.views = (0..self.count)
+            .map(|_| self.create_view(width, height))
+            .collect();
+        self.next_index = 0;
+        self.presented_index = None;
+    }
+
+    fn create_view(&self, width: u32, height: u32) -> ColorTargetViewWgpu {
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.texture_format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let texture = Arc::new(texture);
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        ColorTargetViewWgpu::new_direct(
+            self.device.clone(),
+            self.queue.clone(),
+            self.pending_command_buffers.clone(),
+            texture,
+            Arc::new(texture_view),
+            self.texture_format,
+        )
+    }
+
+    pub fn acquire(&mut self) -> usize {
+        assert!(
+            !self.views.is_empty(),
+            "offscreen scan buffers are not sized"
+        );
+        let index = self.next_index;
+        self.next_index = (self.next_index + 1) % self.views.len();
+        index
+    }
+
+    pub fn get_view(&self, index: usize) -> &ColorTargetViewWgpu {
+        &self.views[index]
+    }
+
+    pub fn present(&mut self, index: usize) {
+        self.presented_index = Some(index);
+    }
+
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        let index = self.presented_index?;
+        Some(self.views[index].read_image())
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::{Arc, Mutex};
+
+// キューにためてまだサブミットしていないコマンド
+// wgpu のキューは 1 つなので、同じデバイスから作ったキューと読み戻しで共有して順番を守る
+#[derive(Clone, Debug, Default)]
+pub struct PendingCommandBuffers {
+    command_buffers: Arc<Mutex<Vec<wgpu::CommandBuffer>>>,
+}
+
+impl PendingCommandBuffers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, command_buffer: wgpu::CommandBuffer) {
+        self.command_buffers.lock().unwrap().push(command_buffer);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.command_buffers.lock().unwrap().is_empty()
+    }
+
+    // ためているコマンドに command_buffers を続けてサブミットする
+    pub fn submit<I>(&self, queue: &wgpu::Queue, command_buffers: I) -> wgpu::SubmissionIndex
+    where
+        I: IntoIterator<Item = wgpu::CommandBuffer>,
+    {
+        let mut pending_command_buffers = self.command_buffers.lock().unwrap();
+        queue.submit(pending_command_buffers.drain(..).chain(command_buffers))
+    }
+}
@@ -1,28 +1,55 @@
 use std::sync::Arc;
 
+use sjgfx_interface::{
+    compute_scaled_rect, OutputEncoding, OutputTransform, ScalingMode, SwapChainInfo, Tonemap,
+    UpscaleFilter,
+};
 use wgpu::util::DeviceExt;
 
-use crate::ColorTargetViewWgpu;
+use crate::{detail::PendingCommandBuffers, util, ColorTargetViewWgpu};
+
+// UpscaleFilter::Sharpen のときに周囲との差をどれだけ強調するか
+const SHARPNESS: f32 = 0.25;
 
 pub struct SwapChainPipeline {
     device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pending_command_buffers: PendingCommandBuffers,
     render_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
+    bind_group_layout: wgpu::BindGroupLayout,
     bind_group: wgpu::BindGroup,
-    #[allow(dead_code)]
     sampler: wgpu::Sampler,
-    #[allow(dead_code)]
-    texture: wgpu::Texture,
+    output_transform_buffer: wgpu::Buffer,
+    scaling_mode: ScalingMode,
+    sharpness: f32,
+
+    // sRGB のスキャンバッファにはハードウェアが符号化するのでシェーダーで符号化しない
+    is_srgb_target: bool,
 
+    // 内部のカラーターゲットのフォーマット
+    render_format: wgpu::TextureFormat,
+
+    // スキャンバッファの大きさ
     width: u32,
     height: u32,
 
+    // 内部のカラーターゲットの大きさ
+    render_width: u32,
+    render_height: u32,
+
     color_target_view: ColorTargetViewWgpu,
 }
 
 impl SwapChainPipeline {
-    pub fn new(device: Arc<wgpu::Device>, texture_format: wgpu::TextureFormat) -> Self {
+    pub fn new(
+        device: Arc<wgpu::Device>,
+        queue: Arc<wgpu::Queue>,
+        pending_command_buffers: PendingCommandBuffers,
+        texture_format: wgpu::TextureFormat,
+        info: &SwapChainInfo,
+    ) -> Self {
         let vertex_shader_source = include_str!("../../resources/render_color_target.vs");
         let pixel_shader_source = include_str!("../../resources/render_color_target.fs");
         let mut compiler = sjgfx_util::ShaderCompiler::new();
@@ -56,32 +83,6 @@ impl SwapChainPipeline {
             ],
         }];
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: None,
-            layout: None,
-            vertex: wgpu::VertexState {
-                module: &vertex_module,
-                entry_point: "main",
-                buffers,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &pixel_module,
-                entry_point: "main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: texture_format,
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                ..Default::default()
-            },
-            depth_stencil: None,
-            multisample: Default::default(),
-            multiview: None,
-        });
-
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: None,
             contents: bytemuck::cast_slice(&[
@@ -96,22 +97,15 @@ impl SwapChainPipeline {
             usage: wgpu::BufferUsages::INDEX,
         });
 
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: None,
-            size: wgpu::Extent3d {
-                width: 1280,
-                height: 960,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
-            view_formats: &vec![],
+        let filter_mode = match info.get_upscale_filter() {
+            UpscaleFilter::Nearest => wgpu::FilterMode::Nearest,
+            UpscaleFilter::Linear | UpscaleFilter::Sharpen => wgpu::FilterMode::Linear,
+        };
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: filter_mode,
+            min_filter: filter_mode,
+            ..Default::default()
         });
-        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: None,
@@ -132,41 +126,215 @@ impl SwapChainPipeline {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
-        let bind_group_entries = [
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&texture_view),
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &vertex_module,
+                entry_point: "main",
+                buffers,
             },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::Sampler(&sampler),
+            fragment: Some(wgpu::FragmentState {
+                module: &pixel_module,
+                entry_point: "main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: texture_format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
             },
-        ];
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: None,
-            layout: &bind_group_layout,
-            entries: &bind_group_entries,
+            depth_stencil: None,
+            multisample: Default::default(),
+            multiview: None,
         });
 
+        let sharpness = match info.get_upscale_filter() {
+            UpscaleFilter::Nearest | UpscaleFilter::Linear => 0.0,
+            UpscaleFilter::Sharpen => SHARPNESS,
+        };
+        let is_srgb_target = texture_format.is_srgb();
+        let output_transform_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&Self::create_output_transform_data(
+                    info.get_output_transform(),
+                    sharpness,
+                    is_srgb_target,
+                )),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+
+        let render_format = util::convert_format(info.get_render_format().clone());
+        let color_target_view = Self::create_render_target(
+            &device,
+            &queue,
+            &pending_command_buffers,
+            render_format,
+            Self::DEFAULT_RENDER_WIDTH,
+            Self::DEFAULT_RENDER_HEIGHT,
+        );
+        let bind_group = Self::create_bind_group(
+            &device,
+            &bind_group_layout,
+            &color_target_view,
+            &sampler,
+            &output_transform_buffer,
+        );
+
         Self {
             device,
+            queue,
+            pending_command_buffers,
             render_pipeline,
             vertex_buffer,
             index_buffer,
-            sampler,
-            texture,
-            color_target_view: ColorTargetViewWgpu::new_direct(
-                Arc::new(texture_view),
-                wgpu::TextureFormat::Rgba8Unorm,
-            ),
+            bind_group_layout,
             bind_group,
-            width: 1280,
-            height: 960,
+            sampler,
+            output_transform_buffer,
+            scaling_mode: info.get_scaling_mode(),
+            sharpness,
+            is_srgb_target,
+            render_format,
+            width: Self::DEFAULT_RENDER_WIDTH,
+            height: Self::DEFAULT_RENDER_HEIGHT,
+            render_width: Self::DEFAULT_RENDER_WIDTH,
+            render_height: Self::DEFAULT_RENDER_HEIGHT,
+            color_target_view,
         }
     }
 
+    const DEFAULT_RENDER_WIDTH: u32 = 1280;
+    const DEFAULT_RENDER_HEIGHT: u32 = 960;
+
+    fn create_render_target(
+        device: &Arc<wgpu::Device>,
+        queue: &Arc<wgpu::Queue>,
+        pending_command_buffers: &PendingCommandBuffers,
+        render_format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> ColorTargetViewWgpu {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: render_format,
+            // スクリーンショットを撮れるように読み戻せるようにしておく
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let texture = Arc::new(texture);
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        ColorTargetViewWgpu::new_direct(
+            device.clone(),
+            queue.clone(),
+            pending_command_buffers.clone(),
+            texture,
+            Arc::new(texture_view),
+            render_format,
+        )
+    }
+
+    fn create_bind_group(
+        device: &wgpu::Device,
+        bind_group_layout: &wgpu::BindGroupLayout,
+        color_target_view: &ColorTargetViewWgpu,
+        sampler: &wgpu::Sampler,
+        output_transform_buffer: &wgpu::Buffer,
+    ) -> wgpu::BindGroup {
+        let bind_group_entries = [
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(color_target_view.get_texture_view()),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: output_transform_buffer.as_entire_binding(),
+            },
+        ];
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: bind_group_layout,
+            entries: &bind_group_entries,
+        })
+    }
+
+    // シェーダーの OutputTransform ブロックと同じ並び
+    fn create_output_transform_data(
+        output_transform: &OutputTransform,
+        sharpness: f32,
+        is_srgb_target: bool,
+    ) -> [u32; 8] {
+        let tonemap = match output_transform.get_tonemap() {
+            Tonemap::None => 0,
+            Tonemap::Reinhard => 1,
+            Tonemap::Aces => 2,
+        };
+        let (encoding, gamma) = match output_transform.get_encoding() {
+            OutputEncoding::Linear => (0, 1.0),
+            OutputEncoding::Gamma(gamma) => (1, gamma),
+            OutputEncoding::Srgb if is_srgb_target => (0, 1.0),
+            OutputEncoding::Srgb => (2, 1.0),
+        };
+        [
+            output_transform.get_exposure().to_bits(),
+            sharpness.to_bits(),
+            tonemap,
+            encoding,
+            f32::to_bits(gamma),
+            output_transform.is_dithering_enabled() as u32,
+            0,
+            0,
+        ]
+    }
+
+    pub fn set_output_transform(&self, output_transform: &OutputTransform) {
+        let data = Self::create_output_transform_data(
+            output_transform,
+            self.sharpness,
+            self.is_srgb_target,
+        );
+        self.queue.write_buffer(
+            &self.output_transform_buffer,
+            0,
+            bytemuck::cast_slice(&data),
+        );
+    }
+
     pub fn build_command(&self, texture_view: &wgpu::TextureView) -> wgpu::CommandBuffer {
         let mut command_encoder = self
             .device
@@ -174,8 +342,9 @@ impl SwapChainPipeline {
         let color_attachment = wgpu::RenderPassColorAttachment {
             view: texture_view,
             resolve_target: None,
+            // レターボックスの余白は黒にする
             ops: wgpu::Operations {
-                load: wgpu::LoadOp::Load,
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
                 store: true,
             },
         };
@@ -185,6 +354,12 @@ impl SwapChainPipeline {
                 color_attachments: &[Some(color_attachment)],
                 depth_stencil_attachment: None,
             });
+            let (x, y, width, height) = compute_scaled_rect(
+                (self.render_width, self.render_height),
+                (self.width, self.height),
+                self.scaling_mode,
+            );
+            render_pass.set_viewport(x, y, width, height, 0.0, 1.0);
             render_pass.set_pipeline(&self.render_pipeline);
             render_pass.set_bind_group(0, &self.bind_group, &[]);
             render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
@@ -210,4 +385,37 @@ impl SwapChainPipeline {
         self.width = width;
         self.height = height;
     }
+
+    pub fn get_render_width(&self) -> u32 {
+        self.render_width
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        self.render_height
+    }
+
+    // 大きさが変わったときだけ内部のカラーターゲットを作りなおす
+    pub fn set_render_size(&mut self, width: u32, height: u32) {
+        if self.render_width == width && self.render_height == height {
+            return;
+        }
+
+        self.color_target_view = Self::create_render_target(
+            &self.device,
+            &self.queue,
+            &self.pending_command_buffers,
+            self.render_format,
+            width,
+            height,
+        );
+        self.bind_group = Self::create_bind_group(
+            &self.device,
+            &self.bind_group_layout,
+            &self.color_target_view,
+            &self.sampler,
+            &self.output_transform_buffer,
+        );
+        self.render_width = width;
+        self.render_height = height;
+    }
 }
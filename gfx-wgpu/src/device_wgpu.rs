@@ -2,75 +2,90 @@ use std::sync::Arc;
 
 use futures::executor;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
-use sjgfx_interface::{DeviceInfo, IDevice};
+use sjgfx_interface::{
+    AdapterInfo, BackendMask, DeviceCapabilities, DeviceInfo, IDevice, ImageFormat,
+    ImageFormatCapability,
+};
 use wgpu::{Adapter, Surface};
 
+use crate::detail::PendingCommandBuffers;
+
 pub struct DeviceWgpu {
     device: Arc<wgpu::Device>,
     queue_impl: Arc<wgpu::Queue>,
 
-    #[allow(dead_code)]
+    // このデバイスから作ったキューがためているコマンド
+    pending_command_buffers: PendingCommandBuffers,
+
+    // 後から別のウィンドウのサーフェスを作るときに使う
+    instance: wgpu::Instance,
     adapter: Adapter,
+    adapter_index: usize,
 
-    #[allow(dead_code)]
     surface_opt: Option<Arc<Surface>>,
 }
 
 impl DeviceWgpu {
-    pub fn new_as_graphics<W>(_info: &DeviceInfo, window: &W) -> Self
+    pub fn new_as_graphics<W>(info: &DeviceInfo, window: &W) -> Self
     where
         W: HasRawWindowHandle + HasRawDisplayHandle,
     {
-        let backend = Self::get_primary_backend_type();
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: backend,
-            dx12_shader_compiler: Default::default(),
-        });
-        let surface = unsafe { instance.create_surface(window) }.unwrap();
-        let adapter = executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            force_fallback_adapter: false,
-            compatible_surface: Some(&surface),
-        }))
-        .unwrap();
+        Self::try_new_as_graphics(info, window).expect("no wgpu adapter is available")
+    }
 
-        // Device の limits はウェブ版で分岐が必要
-        let optional_features = wgpu::Features::empty();
-        let required_features = wgpu::Features::empty();
-        let adapter_features = adapter.features();
-        let (device, queue) = executor::block_on(adapter.request_device(
-            &wgpu::DeviceDescriptor {
-                limits: if cfg!(target_arch = "wasm32") {
-                    wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits())
-                } else {
-                    wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits())
-                },
-                features: (optional_features & adapter_features) | required_features,
-                label: None,
-            },
-            None,
-        ))
-        .unwrap();
-
-        let swapchain_capabilities = surface.get_capabilities(&adapter);
-        let swapchain_format = swapchain_capabilities.formats[0];
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: swapchain_format,
-            width: 1600,
-            height: 1200,
-            present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: swapchain_capabilities.alpha_modes[0],
-            view_formats: vec![],
-        };
-        surface.configure(&device, &config);
+    // 使えるアダプターがなければ None を返す
+    pub fn try_new(info: &DeviceInfo) -> Option<Self> {
+        let backends = Self::get_backends(info);
+        let instance = Self::create_instance(backends);
+        let (adapter, adapter_index) = Self::select_adapter(&instance, backends, info, None)?;
+
+        let (device, queue) = Self::request_device(&adapter, info)?;
 
-        DeviceWgpu {
+        Some(DeviceWgpu {
             device: Arc::new(device),
             queue_impl: Arc::new(queue),
+            pending_command_buffers: PendingCommandBuffers::new(),
+            instance,
             adapter,
+            adapter_index,
+            surface_opt: None,
+        })
+    }
+
+    pub fn try_new_as_graphics<W>(info: &DeviceInfo, window: &W) -> Option<Self>
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let backends = Self::get_surface_backends(info);
+        let instance = Self::create_instance(backends);
+        let surface = unsafe { instance.create_surface(window) }.ok()?;
+        let (adapter, adapter_index) =
+            Self::select_adapter(&instance, backends, info, Some(&surface))?;
+
+        let (device, queue) = Self::request_device(&adapter, info)?;
+
+        // サーフェスの設定はスワップチェインを作るときに行う
+
+        Some(DeviceWgpu {
+            device: Arc::new(device),
+            queue_impl: Arc::new(queue),
+            pending_command_buffers: PendingCommandBuffers::new(),
+            instance,
+            adapter,
+            adapter_index,
             surface_opt: Some(Arc::new(surface)),
-        }
+        })
+    }
+
+    // デバイスを作ったときとは別のウィンドウのサーフェスを作る
+    // アダプターが表示できないウィンドウだったら失敗する
+    pub fn create_surface<W>(&self, window: &W) -> Arc<wgpu::Surface>
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let surface = unsafe { self.instance.create_surface(window) }.unwrap();
+        assert!(self.adapter.is_surface_supported(&surface));
+        Arc::new(surface)
     }
 
     pub fn get_device(&self) -> &wgpu::Device {
@@ -89,6 +104,10 @@ impl DeviceWgpu {
         self.queue_impl.clone()
     }
 
+    pub(crate) fn clone_pending_command_buffers(&self) -> PendingCommandBuffers {
+        self.pending_command_buffers.clone()
+    }
+
     pub fn get_adapter(&self) -> &wgpu::Adapter {
         &self.adapter
     }
@@ -101,68 +120,217 @@ impl DeviceWgpu {
         self.surface_opt.as_ref().unwrap().clone()
     }
 
-    pub fn update_surface_size(&mut self, width: u32, height: u32) {
-        let swapchain_capabilities = self.get_surface().get_capabilities(&self.adapter);
-        let swapchain_format = swapchain_capabilities.formats[0];
-
-        if let Some(surface) = &self.surface_opt {
-            let config = wgpu::SurfaceConfiguration {
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-                format: swapchain_format,
-                width,
-                height,
-                present_mode: wgpu::PresentMode::Fifo,
-                alpha_mode: swapchain_capabilities.alpha_modes[0],
-                view_formats: vec![],
-            };
-            surface.configure(&self.device, &config);
-        }
+    // DeviceWgpu::new で作ったデバイスにはサーフェスがない
+    pub fn clone_surface_opt(&self) -> Option<Arc<wgpu::Surface>> {
+        self.surface_opt.clone()
     }
 
-    fn get_primary_backend_type() -> wgpu::Backends {
-        if cfg!(target_os = "windows") {
-            wgpu::Backends::DX12
-        } else {
-            wgpu::Backends::all()
+    pub fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        let backends = Self::get_backends(info);
+        let instance = Self::create_instance(backends);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            instance
+                .enumerate_adapters(backends)
+                .enumerate()
+                .map(|(index, adapter)| {
+                    crate::util::convert_from_adapter_info(index, &adapter.get_info())
+                })
+                .collect()
+        }
+
+        // ウェブ版はアダプターを列挙できないので既定のアダプターだけを返す
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::select_adapter(&instance, backends, info, None)
+                .map(|(adapter, adapter_index)| {
+                    crate::util::convert_from_adapter_info(adapter_index, &adapter.get_info())
+                })
+                .into_iter()
+                .collect()
         }
     }
-}
 
-impl IDevice for DeviceWgpu {
-    fn new(_: &DeviceInfo) -> Self {
-        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::all(),
-            dx12_shader_compiler: Default::default(),
+    pub fn get_adapter_info(&self) -> AdapterInfo {
+        crate::util::convert_from_adapter_info(self.adapter_index, &self.adapter.get_info())
+    }
+
+    pub fn get_capabilities(&self) -> DeviceCapabilities {
+        let limits = self.device.limits();
+        let features = self.device.features();
+        let image_format_capabilities = ImageFormat::ALL.iter().map(|format| {
+            let wgpu_format = crate::util::convert_format(format.clone());
+
+            // アダプター固有の情報が取れないときは仕様で保証されている範囲を返す
+            let format_features =
+                if features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+                    self.adapter.get_texture_format_features(wgpu_format)
+                } else {
+                    wgpu_format.guaranteed_format_features(features)
+                };
+            ImageFormatCapability::new(
+                format.clone(),
+                crate::util::convert_from_texture_format_features(&format_features),
+            )
         });
+
+        DeviceCapabilities::new()
+            .set_max_texture_size(limits.max_texture_dimension_2d)
+            .set_max_texture_array_layers(limits.max_texture_array_layers)
+            .set_max_constant_buffers_per_stage(limits.max_uniform_buffers_per_shader_stage)
+            .set_max_unordered_access_buffers_per_stage(limits.max_storage_buffers_per_shader_stage)
+            .set_max_textures_per_stage(limits.max_sampled_textures_per_shader_stage)
+            .set_max_images_per_stage(limits.max_storage_textures_per_shader_stage)
+            .set_max_samplers_per_stage(limits.max_samplers_per_shader_stage)
+            .set_max_compute_workgroup_size([
+                limits.max_compute_workgroup_size_x,
+                limits.max_compute_workgroup_size_y,
+                limits.max_compute_workgroup_size_z,
+            ])
+            .set_max_compute_invocations_per_workgroup(limits.max_compute_invocations_per_workgroup)
+            .set_max_compute_workgroups_per_dimension(limits.max_compute_workgroups_per_dimension)
+            .set_constant_buffer_offset_alignment(limits.min_uniform_buffer_offset_alignment)
+            .set_unordered_access_buffer_offset_alignment(
+                limits.min_storage_buffer_offset_alignment,
+            )
+            .set_image_format_capabilities(image_format_capabilities)
+            .set_features(crate::util::convert_from_features(features))
+    }
+
+    fn create_instance(backends: wgpu::Backends) -> wgpu::Instance {
+        wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends,
+            dx12_shader_compiler: Default::default(),
+        })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn select_adapter(
+        instance: &wgpu::Instance,
+        backends: wgpu::Backends,
+        info: &DeviceInfo,
+        surface: Option<&Surface>,
+    ) -> Option<(Adapter, usize)> {
+        let mut adapters: Vec<Adapter> = instance.enumerate_adapters(backends).collect();
+
+        // 範囲外のインデックスは無視して電力設定から選びなおす
+        if let Some(adapter_index) = info.get_adapter_index() {
+            if adapter_index < adapters.len() {
+                let adapter = adapters.swap_remove(adapter_index);
+                return Some((adapter, adapter_index));
+            }
+        }
+
+        adapters
+            .into_iter()
+            .enumerate()
+            .filter(|(_, adapter)| {
+                !info.is_fallback_adapter_only()
+                    || adapter.get_info().device_type == wgpu::DeviceType::Cpu
+            })
+            .filter(|(_, adapter)| match surface {
+                Some(surface) => adapter.is_surface_supported(surface),
+                None => true,
+            })
+            .min_by_key(|(_, adapter)| {
+                let adapter_type =
+                    crate::util::convert_from_device_type(adapter.get_info().device_type);
+                info.get_power_preference()
+                    .get_adapter_priority(&adapter_type)
+            })
+            .map(|(index, adapter)| (adapter, index))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn select_adapter(
+        instance: &wgpu::Instance,
+        _backends: wgpu::Backends,
+        info: &DeviceInfo,
+        surface: Option<&Surface>,
+    ) -> Option<(Adapter, usize)> {
+        let power_preference = match info.get_power_preference() {
+            sjgfx_interface::PowerPreference::Default => wgpu::PowerPreference::default(),
+            sjgfx_interface::PowerPreference::LowPower => wgpu::PowerPreference::LowPower,
+            sjgfx_interface::PowerPreference::HighPerformance => {
+                wgpu::PowerPreference::HighPerformance
+            }
+        };
         let adapter = executor::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            force_fallback_adapter: false,
-            compatible_surface: None,
-        }))
-        .unwrap();
+            power_preference,
+            force_fallback_adapter: info.is_fallback_adapter_only(),
+            compatible_surface: surface,
+        }))?;
+        Some((adapter, 0))
+    }
+
+    fn request_device(adapter: &Adapter, info: &DeviceInfo) -> Option<(wgpu::Device, wgpu::Queue)> {
+        // 要求された機能のうちアダプターがサポートしているものだけを有効にする
+        let optional_features = crate::util::convert_to_features(info.get_optional_features());
+        let required_features = wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
+        let adapter_features = adapter.features();
 
-        let (device, queue) = executor::block_on(adapter.request_device(
+        // Device の limits はウェブ版で分岐が必要
+        let limits = if cfg!(target_arch = "wasm32") {
+            wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits())
+        } else {
+            adapter.limits()
+        };
+        executor::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
-                limits: wgpu::Limits::downlevel_defaults().using_resolution(adapter.limits()),
-                features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
-                label: None,
+                limits,
+                features: (optional_features | required_features) & adapter_features,
+                label: info.get_debug_label(),
             },
             None,
         ))
-        .unwrap();
+        .ok()
+    }
 
-        DeviceWgpu {
-            device: Arc::new(device),
-            queue_impl: Arc::new(queue),
-            adapter,
-            surface_opt: None, //surface_opt,
+    fn get_backends(info: &DeviceInfo) -> wgpu::Backends {
+        crate::util::convert_to_backends(info.get_backends())
+    }
+
+    // 表示用のデバイスは指定がなければプラットフォームごとの既定のバックエンドを使う
+    // アダプターのインデックスが指定されていたら enumerate_adapters と同じ並びにそろえる
+    fn get_surface_backends(info: &DeviceInfo) -> wgpu::Backends {
+        if info.get_backends() == BackendMask::all() && info.get_adapter_index().is_none() {
+            Self::get_primary_backend_type()
+        } else {
+            Self::get_backends(info)
         }
     }
 
+    fn get_primary_backend_type() -> wgpu::Backends {
+        if cfg!(target_os = "windows") {
+            wgpu::Backends::DX12
+        } else {
+            wgpu::Backends::all()
+        }
+    }
+}
+
+impl IDevice for DeviceWgpu {
+    fn new(info: &DeviceInfo) -> Self {
+        Self::try_new(info).expect("no wgpu adapter is available")
+    }
+
     fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
     where
         T: HasRawWindowHandle + HasRawDisplayHandle,
     {
         DeviceWgpu::new_as_graphics(info, raw_handle)
     }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        Self::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.get_adapter_info()
+    }
 }
@@ -1,13 +1,106 @@
-use sjgfx_interface::IFence;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use sjgfx_interface::{FenceInfo, IFence};
 
 use crate::DeviceWgpu;
 
-pub struct FenceWgpu {}
+pub struct FenceWgpu {
+    device: Arc<wgpu::Device>,
+
+    // キューのコールバックからシグナルされるので共有しておく
+    is_signaled: Arc<AtomicBool>,
+    submission_index: Option<wgpu::SubmissionIndex>,
+}
+
+impl FenceWgpu {
+    pub fn new(device: &DeviceWgpu, info: &FenceInfo) -> Self {
+        Self {
+            device: device.close_device(),
+            is_signaled: Arc::new(AtomicBool::new(info.is_signaled())),
+            submission_index: None,
+        }
+    }
+
+    pub fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        if self.is_signaled.load(Ordering::Acquire) {
+            return true;
+        }
+
+        // 一度もサブミットされていないフェンスはシグナルされない
+        let submission_index = match &self.submission_index {
+            Some(submission_index) => submission_index.clone(),
+            None => return false,
+        };
+
+        if let Some(timeout) = timeout {
+            let start = Instant::now();
+            loop {
+                self.device.poll(wgpu::Maintain::Poll);
+                if self.is_signaled.load(Ordering::Acquire) {
+                    return true;
+                }
+                if start.elapsed() >= timeout {
+                    return false;
+                }
+                std::thread::yield_now();
+            }
+        } else {
+            self.device
+                .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+            self.device.poll(wgpu::Maintain::Poll);
+            self.is_signaled.load(Ordering::Acquire)
+        }
+    }
+
+    pub fn is_signaled(&self) -> bool {
+        if !self.is_signaled.load(Ordering::Acquire) && self.submission_index.is_some() {
+            self.device.poll(wgpu::Maintain::Poll);
+        }
+        self.is_signaled.load(Ordering::Acquire)
+    }
+
+    // 前のサブミットのコールバックがあとから呼ばれてもシグナルされないようにフラグごと作りなおす
+    pub fn reset(&mut self) {
+        self.is_signaled = Arc::new(AtomicBool::new(false));
+        self.submission_index = None;
+    }
+
+    // これまでにキューに積まれた処理がすべて終わったらシグナルする
+    pub(crate) fn signal_on_submitted_work_done(
+        &mut self,
+        queue: &wgpu::Queue,
+        submission_index: wgpu::SubmissionIndex,
+    ) {
+        self.is_signaled = Arc::new(AtomicBool::new(false));
+        self.submission_index = Some(submission_index);
+
+        let is_signaled = self.is_signaled.clone();
+        queue.on_submitted_work_done(move || is_signaled.store(true, Ordering::Release));
+    }
+}
 
 impl IFence for FenceWgpu {
     type DeviceType = DeviceWgpu;
 
-    fn new(_device: &Self::DeviceType, _info: &sjgfx_interface::FenceInfo) -> Self {
-        Self {}
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        self.wait(timeout)
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.reset()
     }
 }
@@ -1,53 +1,131 @@
 use std::sync::Arc;
 
-use sjgfx_interface::{IQueue, QueueInfo};
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
 
-use crate::{CommandBufferWgpu, DeviceWgpu, FenceWgpu, SwapChainWgpu};
+use crate::{
+    detail::PendingCommandBuffers, CommandBufferWgpu, DeviceWgpu, FenceWgpu, SemaphoreWgpu,
+    SwapChainWgpu,
+};
 
 pub struct QueueWgpu {
     device: Arc<wgpu::Device>,
     queue: Arc<wgpu::Queue>,
+
+    // wgpu はキューが 1 つなのでどの種類でも同じキューにサブミットする
+    queue_type: QueueType,
+
+    // flush されるまでサブミットせずにためておくコマンド
+    // テクスチャーの読み戻しが先にサブミットできるようにデバイスで共有している
+    pending_command_buffers: PendingCommandBuffers,
+    last_submission_index: Option<wgpu::SubmissionIndex>,
 }
 
 impl QueueWgpu {
-    pub fn new(device: &DeviceWgpu, _info: &QueueInfo) -> Self {
+    pub fn new(device: &DeviceWgpu, info: &QueueInfo) -> Self {
         Self {
             device: device.close_device(),
             queue: device.clone_queue(),
+            queue_type: info.get_queue_type().clone(),
+            pending_command_buffers: device.clone_pending_command_buffers(),
+            last_submission_index: None,
         }
     }
 
     pub fn execute(&mut self, command_buffer: &CommandBufferWgpu) {
-        let build_command = command_buffer.build_command();
-        self.queue.submit(build_command);
-        self.device.poll(wgpu::Maintain::Wait);
+        if let Some(build_command) = command_buffer.build_command() {
+            self.pending_command_buffers.push(build_command);
+        }
     }
 
-    pub fn submit_command_buffer_direct(&self, command_buffer: wgpu::CommandBuffer) {
-        self.queue.submit(Some(command_buffer));
+    // ためているコマンドとあわせて一度にサブミットする
+    pub fn execute_many(&mut self, command_buffers: &[&CommandBufferWgpu]) {
+        for command_buffer in command_buffers {
+            self.execute(command_buffer);
+        }
+        self.flush();
+    }
+
+    pub fn submit_command_buffer_direct(
+        &mut self,
+        command_buffer: wgpu::CommandBuffer,
+    ) -> wgpu::SubmissionIndex {
+        let submission_index = self.queue.submit(Some(command_buffer));
+        self.last_submission_index = Some(submission_index.clone());
+        submission_index
     }
 
     pub fn execute_with_fence(
         &mut self,
-        _command_buffer: &CommandBufferWgpu,
-        _fence: &mut FenceWgpu,
+        command_buffer: &CommandBufferWgpu,
+        fence: &mut FenceWgpu,
     ) {
-        todo!()
+        self.execute(command_buffer);
+        let submission_index = self.submit_pending_command_buffers();
+        fence.signal_on_submitted_work_done(&self.queue, submission_index);
+    }
+
+    pub fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &CommandBufferWgpu,
+        wait_semaphores: &[(&SemaphoreWgpu, u64)],
+        signal_semaphores: &[(&SemaphoreWgpu, u64)],
+    ) {
+        // サブミット済みのシグナルはキューの順番で処理されるので待たなくていい
+        // まだサブミットされていない値は CPU からのシグナルを待つ
+        for (semaphore, value) in wait_semaphores {
+            if !semaphore.is_submitted(*value) {
+                semaphore.wait(*value, None);
+            }
+        }
+
+        self.execute(command_buffer);
+        self.submit_pending_command_buffers();
+
+        for (semaphore, value) in signal_semaphores {
+            semaphore.signal_on_submitted_work_done(&self.queue, *value);
+        }
+    }
+
+    pub fn get_queue_type(&self) -> &QueueType {
+        &self.queue_type
     }
 
     pub fn present(&mut self, swap_chain: &mut SwapChainWgpu) {
+        // スキャンバッファへのコピーより先に描画コマンドをサブミットしておく
+        self.flush();
         swap_chain.present(self);
     }
 
-    pub fn flush(&self) {}
+    pub fn flush(&mut self) {
+        if !self.pending_command_buffers.is_empty() {
+            self.submit_pending_command_buffers();
+        }
+
+        // 完了したサブミットのコールバックを処理する
+        self.device.poll(wgpu::Maintain::Poll);
+    }
 
-    pub fn sync(&self) {}
+    pub fn sync(&mut self) {
+        self.flush();
+
+        if let Some(submission_index) = self.last_submission_index.take() {
+            self.device
+                .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        }
+    }
+
+    fn submit_pending_command_buffers(&mut self) -> wgpu::SubmissionIndex {
+        let submission_index = self.pending_command_buffers.submit(&self.queue, None);
+        self.last_submission_index = Some(submission_index.clone());
+        submission_index
+    }
 }
 
 impl IQueue for QueueWgpu {
     type DeviceType = DeviceWgpu;
     type CommandBufferType = CommandBufferWgpu;
     type FenceType = FenceWgpu;
+    type SemaphoreType = SemaphoreWgpu;
     type SwapChainType = SwapChainWgpu;
 
     fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
@@ -58,6 +136,10 @@ impl IQueue for QueueWgpu {
         self.execute(command_buffer)
     }
 
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        self.execute_many(command_buffers)
+    }
+
     fn execute_with_fence(
         &mut self,
         command_buffer: &Self::CommandBufferType,
@@ -66,15 +148,28 @@ impl IQueue for QueueWgpu {
         self.execute_with_fence(command_buffer, fence)
     }
 
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        self.execute_with_semaphores(command_buffer, wait_semaphores, signal_semaphores)
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.get_queue_type()
+    }
+
     fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
         QueueWgpu::present(self, swap_chain);
     }
 
     fn flush(&mut self) {
-        QueueWgpu::flush(&self);
+        QueueWgpu::flush(self);
     }
 
     fn sync(&mut self) {
-        QueueWgpu::sync(&self);
+        QueueWgpu::sync(self);
     }
 }
@@ -9,10 +9,13 @@ pub struct SamplerWgpu {
 }
 
 impl SamplerWgpu {
-    pub fn new(device: &DeviceWgpu, _info: &SamplerInfo) -> Self {
+    pub fn new(device: &DeviceWgpu, info: &SamplerInfo) -> Self {
         let sampler = device
             .get_device()
-            .create_sampler(&wgpu::SamplerDescriptor::default());
+            .create_sampler(&wgpu::SamplerDescriptor {
+                label: info.get_debug_label(),
+                ..Default::default()
+            });
         Self {
             sampler: Arc::new(sampler),
         }
@@ -1,13 +1,92 @@
-use sjgfx_interface::ISemaphore;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
 
 use crate::DeviceWgpu;
 
-pub struct SemaphoreWgpu {}
+// wgpu にはセマフォがないのでタイムラインの値を CPU 側で管理する
+pub struct SemaphoreWgpu {
+    device: Arc<wgpu::Device>,
+
+    // GPU で完了した値
+    value: Arc<AtomicU64>,
+
+    // キューにサブミット済みのシグナルの値
+    // wgpu のキューは 1 つしかなく順番に処理されるので、ここまでの値は GPU 側で待たなくていい
+    submitted_value: AtomicU64,
+}
+
+impl SemaphoreWgpu {
+    pub fn new(device: &DeviceWgpu, info: &SemaphoreInfo) -> Self {
+        Self {
+            device: device.close_device(),
+            value: Arc::new(AtomicU64::new(info.get_initial_value())),
+            submitted_value: AtomicU64::new(info.get_initial_value()),
+        }
+    }
+
+    pub fn get_value(&self) -> u64 {
+        self.device.poll(wgpu::Maintain::Poll);
+        self.value.load(Ordering::Acquire)
+    }
+
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        let start = Instant::now();
+        loop {
+            if self.get_value() >= value {
+                return true;
+            }
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    return false;
+                }
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    pub fn signal(&self, value: u64) {
+        self.submitted_value.fetch_max(value, Ordering::AcqRel);
+        self.value.fetch_max(value, Ordering::AcqRel);
+    }
+
+    pub(crate) fn is_submitted(&self, value: u64) -> bool {
+        self.submitted_value.load(Ordering::Acquire) >= value
+    }
+
+    // これまでにキューに積まれた処理がすべて終わったら value をシグナルする
+    pub(crate) fn signal_on_submitted_work_done(&self, queue: &wgpu::Queue, value: u64) {
+        self.submitted_value.fetch_max(value, Ordering::AcqRel);
+
+        let current_value = self.value.clone();
+        queue.on_submitted_work_done(move || {
+            current_value.fetch_max(value, Ordering::AcqRel);
+        });
+    }
+}
 
 impl ISemaphore for SemaphoreWgpu {
     type DeviceType = DeviceWgpu;
 
-    fn new(_device: &Self::DeviceType, _info: &sjgfx_interface::SemaphoreInfo) -> Self {
-        Self {}
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn get_value(&self) -> u64 {
+        self.get_value()
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.wait(value, timeout)
+    }
+
+    fn signal(&self, value: u64) {
+        self.signal(value)
     }
 }
@@ -14,12 +14,13 @@ pub struct ShaderWgpu {
 impl ShaderWgpu {
     pub fn new(device: &DeviceWgpu, info: &ShaderInfo) -> Self {
         if let Some(compute_shader_binary) = info.get_compute_shader_binary() {
-            return Self::new_as_compute(device, &compute_shader_binary);
+            return Self::new_as_compute(device, &compute_shader_binary, info.get_debug_label());
         } else {
             return Self::new_as_graphics(
                 device,
                 info.get_vertex_shader_binary().as_ref().unwrap(),
                 info.get_pixel_shader_binary().as_ref().unwrap(),
+                info.get_debug_label(),
             );
         }
     }
@@ -36,9 +37,9 @@ impl ShaderWgpu {
         self.shader_data.clone()
     }
 
-    fn new_as_compute(device: &DeviceWgpu, shader_binary: &[u8]) -> Self {
+    fn new_as_compute(device: &DeviceWgpu, shader_binary: &[u8], label: Option<&str>) -> Self {
         let compute_shader =
-            Self::create_shader_module(device.get_device(), &Some(shader_binary)).unwrap();
+            Self::create_shader_module(device.get_device(), &Some(shader_binary), label).unwrap();
         let shader_reflection = ShaderReflection::new_from_biinary(&shader_binary);
         let entries = Self::create_bind_group_layout_entries(
             shader_binary,
@@ -49,14 +50,14 @@ impl ShaderWgpu {
             device
                 .get_device()
                 .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                    label: None,
+                    label,
                     entries: &entries,
                 });
         let pipeline_layout =
             device
                 .get_device()
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: None,
+                    label,
                     bind_group_layouts: &[&bind_group_layout],
                     push_constant_ranges: &[],
                 });
@@ -65,7 +66,7 @@ impl ShaderWgpu {
             device
                 .get_device()
                 .create_compute_pipeline(&ComputePipelineDescriptor {
-                    label: None,
+                    label,
                     layout: Some(&pipeline_layout),
                     module: &compute_shader,
                     entry_point: "main",
@@ -88,11 +89,12 @@ impl ShaderWgpu {
         device: &DeviceWgpu,
         vertex_shader_binary: &[u8],
         pixel_shader_binary: &[u8],
+        label: Option<&str>,
     ) -> Self {
         let vertex_shader =
-            Self::create_shader_module(device.get_device(), &Some(vertex_shader_binary));
+            Self::create_shader_module(device.get_device(), &Some(vertex_shader_binary), label);
         let pixel_shader =
-            Self::create_shader_module(device.get_device(), &Some(pixel_shader_binary));
+            Self::create_shader_module(device.get_device(), &Some(pixel_shader_binary), label);
 
         let bind_group_layout = crate::util::create_bind_group_layout(
             device.get_device(),
@@ -104,7 +106,7 @@ impl ShaderWgpu {
             device
                 .get_device()
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-                    label: None,
+                    label,
                     bind_group_layouts: &[&bind_group_layout],
                     push_constant_ranges: &[],
                 });
@@ -125,10 +127,11 @@ impl ShaderWgpu {
     fn create_shader_module(
         device: &wgpu::Device,
         sprv_binary_opt: &Option<&[u8]>,
+        label: Option<&str>,
     ) -> Option<wgpu::ShaderModule> {
         match sprv_binary_opt {
             Some(sprv_binary) => Some(device.create_shader_module(wgpu::ShaderModuleDescriptor {
-                label: None,
+                label,
                 source: wgpu::util::make_spirv(sprv_binary),
             })),
             None => None,
@@ -1,33 +1,138 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
 
-use sjgfx_interface::{IDisplayEventListener, ISwapChain, SwapChainInfo};
-use wgpu::{CompositeAlphaMode, SurfaceTexture, TextureFormat};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    select_format, select_present_mode, CompositeAlphaMode, IDisplayEventListener, ISwapChain,
+    ImageData, ImageFormat, OutputTransform, PresentMode, SwapChainInfo, SwapChainStatus,
+};
+use wgpu::{SurfaceTexture, TextureFormat};
 
 use crate::{
-    detail::SwapChainPipeline, ColorTargetViewWgpu, DeviceWgpu, FenceWgpu, QueueWgpu, SemaphoreWgpu,
+    detail::{OffscreenScanBuffers, PendingCommandBuffers, SwapChainPipeline},
+    util, ColorTargetViewWgpu, DeviceWgpu, FenceWgpu, QueueWgpu, SemaphoreWgpu,
 };
 
+// 取得したスキャンバッファ
+enum ScanBuffer {
+    Surface(SurfaceTexture),
+    Offscreen(usize),
+}
+
 pub struct SwapChainWgpu {
     device: Arc<wgpu::Device>,
-    surface: Arc<wgpu::Surface>,
+    queue: Arc<wgpu::Queue>,
+    pending_command_buffers: PendingCommandBuffers,
+
+    // サーフェスがないデバイスではオフスクリーンのスキャンバッファに表示する
+    surface: Option<Arc<wgpu::Surface>>,
+    offscreen_scan_buffers: Option<OffscreenScanBuffers>,
     texture_format: wgpu::TextureFormat,
+    present_mode: PresentMode,
+    alpha_mode: wgpu::CompositeAlphaMode,
+    frame_latency: u32,
+    surface_usage: wgpu::TextureUsages,
+    width: u32,
+    height: u32,
+    render_scale: f32,
+    render_size: Option<(u32, u32)>,
+    status: SwapChainStatus,
     next_surface_texture: Option<Arc<Mutex<Option<SurfaceTexture>>>>,
+    next_offscreen_index: Option<usize>,
+
+    // 直接描画するときのスキャンバッファのビュー
+    scan_buffer_view: Option<ColorTargetViewWgpu>,
+
+    // 直接描画するときにスキャンバッファを取得できなかったら代わりに描画させる
+    fallback_view: Option<ColorTargetViewWgpu>,
 
-    swap_chain_pipeline: SwapChainPipeline,
+    // 表示したフレームのサブミット。frame_latency を超えたら古いものの完了を待つ
+    submission_indices: VecDeque<wgpu::SubmissionIndex>,
+
+    // 直接描画しないときだけ使う
+    swap_chain_pipeline: Option<SwapChainPipeline>,
 }
 
 impl SwapChainWgpu {
     pub fn new(device: &mut DeviceWgpu, info: &SwapChainInfo) -> Self {
-        let adapter = device.get_adapter();
-        let swapchain_capabilities = device.get_surface().get_capabilities(&adapter);
-        let texture_format = swapchain_capabilities.formats[0];
-        let swap_chain_pipeline = SwapChainPipeline::new(device.close_device(), texture_format);
+        let surface = device.clone_surface_opt();
+        Self::new_impl(device, surface, info)
+    }
+
+    // 既存のデバイスで別のウィンドウに表示する
+    // リソースはデバイスを共有するほかのスワップチェーンと同じものを使える
+    pub fn new_with_handle<W>(device: &mut DeviceWgpu, info: &SwapChainInfo, window: &W) -> Self
+    where
+        W: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let surface = device.create_surface(window);
+        Self::new_impl(device, Some(surface), info)
+    }
+
+    fn new_impl(
+        device: &DeviceWgpu,
+        surface: Option<Arc<wgpu::Surface>>,
+        info: &SwapChainInfo,
+    ) -> Self {
+        let (texture_format, present_mode, alpha_mode, supported_usages) = match &surface {
+            Some(surface) => Self::select_surface_settings(device, surface, info),
+            None => Self::select_offscreen_settings(info),
+        };
+
+        // 直接描画するときはスクリーンショットを撮れるようにサポートされていれば読み戻しを有効にする
+        let (surface_usage, swap_chain_pipeline) = if info.is_direct_rendering_enabled() {
+            let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+                | (supported_usages & wgpu::TextureUsages::COPY_SRC);
+            (usage, None)
+        } else {
+            let swap_chain_pipeline = SwapChainPipeline::new(
+                device.close_device(),
+                device.clone_queue(),
+                device.clone_pending_command_buffers(),
+                texture_format,
+                info,
+            );
+            (
+                wgpu::TextureUsages::RENDER_ATTACHMENT,
+                Some(swap_chain_pipeline),
+            )
+        };
+
+        // 表示待ちのフレームに加えて描画中のものが 1 枚必要
+        let offscreen_scan_buffers = match surface {
+            Some(_) => None,
+            None => Some(OffscreenScanBuffers::new(
+                device.close_device(),
+                device.clone_queue(),
+                device.clone_pending_command_buffers(),
+                texture_format,
+                info.get_frame_latency() as usize + 1,
+            )),
+        };
 
         let mut result = Self {
             device: device.close_device(),
-            surface: device.clone_surface(),
+            queue: device.clone_queue(),
+            pending_command_buffers: device.clone_pending_command_buffers(),
+            surface,
+            offscreen_scan_buffers,
             texture_format,
+            present_mode,
+            alpha_mode,
+            frame_latency: info.get_frame_latency(),
+            surface_usage,
+            width: 0,
+            height: 0,
+            render_scale: info.get_render_scale(),
+            render_size: info.get_render_size(),
+            status: SwapChainStatus::Optimal,
             next_surface_texture: None,
+            next_offscreen_index: None,
+            scan_buffer_view: None,
+            fallback_view: None,
+            submission_indices: VecDeque::new(),
             swap_chain_pipeline,
         };
 
@@ -35,33 +140,382 @@ impl SwapChainWgpu {
         result
     }
 
+    // 要求された設定のうちサポートされていないものはサポートされているものに置き換える
+    fn select_surface_settings(
+        device: &DeviceWgpu,
+        surface: &wgpu::Surface,
+        info: &SwapChainInfo,
+    ) -> (
+        wgpu::TextureFormat,
+        PresentMode,
+        wgpu::CompositeAlphaMode,
+        wgpu::TextureUsages,
+    ) {
+        let swapchain_capabilities = surface.get_capabilities(device.get_adapter());
+        let supported_formats: Vec<ImageFormat> = swapchain_capabilities
+            .formats
+            .iter()
+            .filter_map(|format| util::convert_from_format(*format))
+            .collect();
+        let texture_format = select_format(info.get_preferred_formats(), &supported_formats)
+            .map(util::convert_format)
+            .unwrap_or(swapchain_capabilities.formats[0]);
+        let supported_present_modes: Vec<PresentMode> = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+        ]
+        .into_iter()
+        .filter(|present_mode| {
+            swapchain_capabilities
+                .present_modes
+                .contains(&util::convert_present_mode(*present_mode))
+        })
+        .collect();
+        let present_mode = select_present_mode(info.get_present_mode(), &supported_present_modes);
+        let alpha_mode = Some(util::convert_composite_alpha_mode(info.get_alpha_mode()))
+            .filter(|alpha_mode| swapchain_capabilities.alpha_modes.contains(alpha_mode))
+            .unwrap_or(swapchain_capabilities.alpha_modes[0]);
+        (
+            texture_format,
+            present_mode,
+            alpha_mode,
+            swapchain_capabilities.usages,
+        )
+    }
+
+    // 表示先がないので表示方法とアルファは要求されたものをそのまま使う
+    fn select_offscreen_settings(
+        info: &SwapChainInfo,
+    ) -> (
+        wgpu::TextureFormat,
+        PresentMode,
+        wgpu::CompositeAlphaMode,
+        wgpu::TextureUsages,
+    ) {
+        let supported_formats = [
+            ImageFormat::R8G8B8A8Unorm,
+            ImageFormat::R8G8B8A8UnormSrgb,
+            ImageFormat::B8G8R8A8Unorm,
+            ImageFormat::B8G8R8A8UnormSrgb,
+        ];
+        let texture_format =
+            select_format(info.get_preferred_formats(), &supported_formats).unwrap();
+        let alpha_mode = match info.get_alpha_mode() {
+            CompositeAlphaMode::Auto => wgpu::CompositeAlphaMode::Opaque,
+            alpha_mode => util::convert_composite_alpha_mode(alpha_mode),
+        };
+        (
+            util::convert_format(texture_format),
+            info.get_present_mode(),
+            alpha_mode,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        )
+    }
+
     pub fn acquire_next_scan_buffer_view(
         &mut self,
         _semaphore: Option<&mut SemaphoreWgpu>,
-        _fence: Option<&mut FenceWgpu>,
+        fence: Option<&mut FenceWgpu>,
     ) -> &mut ColorTargetViewWgpu {
-        self.swap_chain_pipeline.get_color_target_view_mut()
+        // 前のフレームのスキャンバッファへのコピーが終わったらカラーターゲットを再利用できる
+        if let Some(fence) = fence {
+            let submission_index = self.queue.submit(None);
+            fence.signal_on_submitted_work_done(&self.queue, submission_index);
+        }
+
+        if self.is_direct_rendering_enabled() {
+            self.acquire_surface_texture_view()
+        } else {
+            self.swap_chain_pipeline
+                .as_mut()
+                .unwrap()
+                .get_color_target_view_mut()
+        }
+    }
+
+    fn acquire_surface_texture_view(&mut self) -> &mut ColorTargetViewWgpu {
+        // 表示する前に再度呼ばれたときは同じスキャンバッファを返す
+        if self.scan_buffer_view.is_none() {
+            match self.acquire_scan_buffer() {
+                Some(ScanBuffer::Surface(surface_texture)) => {
+                    let surface_texture = Arc::new(Mutex::new(Some(surface_texture)));
+                    self.scan_buffer_view = Some(ColorTargetViewWgpu::new_from_surface_texture(
+                        self.device.clone(),
+                        self.queue.clone(),
+                        self.pending_command_buffers.clone(),
+                        surface_texture.clone(),
+                        self.texture_format,
+                    ));
+                    self.next_surface_texture = Some(surface_texture);
+                }
+                Some(ScanBuffer::Offscreen(index)) => {
+                    let offscreen_scan_buffers = self.offscreen_scan_buffers.as_ref().unwrap();
+                    self.scan_buffer_view = Some(offscreen_scan_buffers.get_view(index).clone());
+                    self.next_offscreen_index = Some(index);
+                }
+                None => return self.acquire_fallback_view(),
+            }
+        }
+
+        self.scan_buffer_view.as_mut().unwrap()
+    }
+
+    // 描画先がないときも描画コマンドが失敗しないように、表示されないカラーターゲットを返す
+    fn acquire_fallback_view(&mut self) -> &mut ColorTargetViewWgpu {
+        if self.fallback_view.is_none() {
+            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: self.width.max(1),
+                    height: self.height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.texture_format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let texture = Arc::new(texture);
+            let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            self.fallback_view = Some(ColorTargetViewWgpu::new_direct(
+                self.device.clone(),
+                self.queue.clone(),
+                self.pending_command_buffers.clone(),
+                texture,
+                Arc::new(texture_view),
+                self.texture_format,
+            ));
+        }
+
+        self.fallback_view.as_mut().unwrap()
+    }
+
+    fn acquire_scan_buffer(&mut self) -> Option<ScanBuffer> {
+        // 最小化されているときはサイズが 0 で表示できない
+        if self.width == 0 || self.height == 0 {
+            self.status = SwapChainStatus::Skipped;
+            return None;
+        }
+
+        self.status = SwapChainStatus::Optimal;
+        match &mut self.offscreen_scan_buffers {
+            Some(offscreen_scan_buffers) => {
+                Some(ScanBuffer::Offscreen(offscreen_scan_buffers.acquire()))
+            }
+            None => self.acquire_surface_texture().map(ScanBuffer::Surface),
+        }
+    }
+
+    fn present_scan_buffer(&mut self, scan_buffer: ScanBuffer) {
+        match scan_buffer {
+            ScanBuffer::Surface(surface_texture) => surface_texture.present(),
+            ScanBuffer::Offscreen(index) => {
+                self.offscreen_scan_buffers.as_mut().unwrap().present(index)
+            }
+        }
+    }
+
+    // サーフェスが使えなくなっていたら設定しなおして 1 回だけ取得しなおす
+    // それでも取得できなければフレームを飛ばす
+    fn acquire_surface_texture(&mut self) -> Option<SurfaceTexture> {
+        let surface = self.surface.clone().unwrap();
+        for _ in 0..2 {
+            match surface.get_current_texture() {
+                Ok(surface_texture)
+                    if surface_texture.suboptimal && self.status == SwapChainStatus::Optimal =>
+                {
+                    // 取得したテクスチャを手放さないと設定しなおせない
+                    drop(surface_texture);
+                    self.configure();
+                    self.status = SwapChainStatus::Reconfigured;
+                }
+                Ok(surface_texture) => return Some(surface_texture),
+                Err(wgpu::SurfaceError::Outdated) => {
+                    self.configure();
+                    self.status = SwapChainStatus::Reconfigured;
+                }
+                Err(wgpu::SurfaceError::Lost) => {
+                    self.configure();
+                    self.status = SwapChainStatus::Recreated;
+                }
+                Err(wgpu::SurfaceError::Timeout) => break,
+                Err(wgpu::SurfaceError::OutOfMemory) => {
+                    panic!("out of memory while acquiring a surface texture")
+                }
+            }
+        }
+
+        self.status = SwapChainStatus::Skipped;
+        None
+    }
+
+    fn configure(&self) {
+        let Some(surface) = &self.surface else {
+            return;
+        };
+        let config = wgpu::SurfaceConfiguration {
+            usage: self.surface_usage,
+            format: self.texture_format,
+            width: self.width,
+            height: self.height,
+            present_mode: util::convert_present_mode(self.present_mode),
+            alpha_mode: self.alpha_mode,
+            view_formats: vec![],
+        };
+        surface.configure(&self.device, &config);
     }
 
     pub fn present(&mut self, queue: &mut QueueWgpu) {
-        // スキャンバッファのビューを作成
-        let surface_texture = self.surface.get_current_texture().unwrap();
-        let texture_view = surface_texture
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        let submission_index = if !self.is_direct_rendering_enabled() {
+            let Some(scan_buffer) = self.acquire_scan_buffer() else {
+                return;
+            };
+
+            // スキャンバッファのビューを作成
+            let surface_texture_view;
+            let texture_view = match &scan_buffer {
+                ScanBuffer::Surface(surface_texture) => {
+                    surface_texture_view = surface_texture
+                        .texture
+                        .create_view(&wgpu::TextureViewDescriptor::default());
+                    &surface_texture_view
+                }
+                ScanBuffer::Offscreen(index) => self
+                    .offscreen_scan_buffers
+                    .as_ref()
+                    .unwrap()
+                    .get_view(*index)
+                    .get_texture_view(),
+            };
 
-        // カラーターゲットの内容をスキャンバッファにコピー
-        let command_buffer = self.swap_chain_pipeline.build_command(&texture_view);
-        queue.submit_command_buffer_direct(command_buffer);
-        surface_texture.present();
+            // カラーターゲットの内容をスキャンバッファにコピー
+            let command_buffer = self
+                .swap_chain_pipeline
+                .as_ref()
+                .unwrap()
+                .build_command(texture_view);
+            let submission_index = queue.submit_command_buffer_direct(command_buffer);
+            self.present_scan_buffer(scan_buffer);
+            submission_index
+        } else {
+            // 描画済みのスキャンバッファをそのまま表示する
+            // 取得できずに代わりのカラーターゲットに描画したフレームは表示しない
+            self.scan_buffer_view = None;
+            let scan_buffer = match self.next_offscreen_index.take() {
+                Some(index) => Some(ScanBuffer::Offscreen(index)),
+                None => self
+                    .next_surface_texture
+                    .take()
+                    .and_then(|surface_texture| surface_texture.lock().unwrap().take())
+                    .map(ScanBuffer::Surface),
+            };
+            let Some(scan_buffer) = scan_buffer else {
+                return;
+            };
+            let submission_index = self.queue.submit(None);
+            self.present_scan_buffer(scan_buffer);
+            submission_index
+        };
+
+        // CPU が先行しすぎないように古いフレームの完了を待つ
+        self.submission_indices.push_back(submission_index);
+        while self.submission_indices.len() > self.frame_latency as usize {
+            let submission_index = self.submission_indices.pop_front().unwrap();
+            self.device
+                .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        }
+    }
+
+    pub fn get_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    pub fn get_format(&self) -> ImageFormat {
+        util::convert_from_format(self.texture_format).unwrap()
+    }
+
+    pub fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        util::convert_from_composite_alpha_mode(self.alpha_mode)
+    }
+
+    pub fn get_frame_latency(&self) -> u32 {
+        self.frame_latency
+    }
+
+    pub fn is_direct_rendering_enabled(&self) -> bool {
+        self.swap_chain_pipeline.is_none()
+    }
+
+    pub fn get_status(&self) -> SwapChainStatus {
+        self.status
+    }
+
+    // オフスクリーンのときだけ直前に表示したフレームを読み戻せる
+    pub fn read_presented_image(&self) -> Option<ImageData> {
+        self.offscreen_scan_buffers
+            .as_ref()
+            .and_then(|offscreen_scan_buffers| offscreen_scan_buffers.read_presented_image())
+    }
+
+    pub fn get_render_width(&self) -> u32 {
+        match &self.swap_chain_pipeline {
+            Some(swap_chain_pipeline) => swap_chain_pipeline.get_render_width(),
+            None => self.width,
+        }
+    }
+
+    pub fn get_render_height(&self) -> u32 {
+        match &self.swap_chain_pipeline {
+            Some(swap_chain_pipeline) => swap_chain_pipeline.get_render_height(),
+            None => self.height,
+        }
+    }
+
+    pub fn set_render_scale(&mut self, render_scale: f32) {
+        assert!(render_scale > 0.0);
+        self.render_scale = render_scale;
+        self.update_render_size();
+    }
+
+    pub fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        if let Some(swap_chain_pipeline) = &self.swap_chain_pipeline {
+            swap_chain_pipeline.set_output_transform(output_transform);
+        }
+    }
+
+    fn update_render_size(&mut self) {
+        let Some(swap_chain_pipeline) = &mut self.swap_chain_pipeline else {
+            return;
+        };
+
+        // 最小化されているときは元に戻るまで作りなおさない
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        let (width, height) = self.render_size.unwrap_or_else(|| {
+            let scale = |size: u32| ((size as f32 * self.render_scale).round() as u32).max(1);
+            (scale(self.width), scale(self.height))
+        });
+        swap_chain_pipeline.set_render_size(width, height);
     }
 
     pub fn get_scan_buffer_view(&self) -> &ColorTargetViewWgpu {
-        self.swap_chain_pipeline.get_color_target_view()
+        match &self.swap_chain_pipeline {
+            Some(swap_chain_pipeline) => swap_chain_pipeline.get_color_target_view(),
+            None => self.scan_buffer_view.as_ref().unwrap(),
+        }
     }
 
     pub fn get_scan_buffer_view_mut(&mut self) -> &mut ColorTargetViewWgpu {
-        self.swap_chain_pipeline.get_color_target_view_mut()
+        match &mut self.swap_chain_pipeline {
+            Some(swap_chain_pipeline) => swap_chain_pipeline.get_color_target_view_mut(),
+            None => self.scan_buffer_view.as_mut().unwrap(),
+        }
     }
 
     pub fn get_texture_format(&self) -> TextureFormat {
@@ -83,6 +537,17 @@ impl ISwapChain for SwapChainWgpu {
         Self::new(device, info)
     }
 
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(device, info, raw_handle)
+    }
+
     fn acquire_next_scan_buffer_view(
         &mut self,
         semaphore: Option<&mut Self::SemaphoreType>,
@@ -90,20 +555,76 @@ impl ISwapChain for SwapChainWgpu {
     ) -> &mut Self::ColorTargetViewType {
         self.acquire_next_scan_buffer_view(semaphore, fence)
     }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.set_output_transform(output_transform);
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        self.read_presented_image()
+    }
 }
 
 impl IDisplayEventListener for SwapChainWgpu {
     fn on_resized(&mut self, width: u32, height: u32) {
-        let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format: self.texture_format,
-            width,
-            height,
-            present_mode: wgpu::PresentMode::Fifo,
-            alpha_mode: CompositeAlphaMode::Auto,
-            view_formats: vec![],
-        };
-        self.surface.configure(&self.device, &config);
-        self.swap_chain_pipeline.set_size(width, height);
+        // 取得済みのスキャンバッファは古いサイズなので捨てる
+        // ビューが複製されていても手放すように中身を取り出す
+        self.scan_buffer_view = None;
+        if let Some(surface_texture) = self.next_surface_texture.take() {
+            surface_texture.lock().unwrap().take();
+        }
+        self.next_offscreen_index = None;
+        self.fallback_view = None;
+
+        // 最小化されるとサイズが 0 になるので、元に戻るまで設定しない
+        self.width = width;
+        self.height = height;
+        if width > 0 && height > 0 {
+            self.configure();
+            if let Some(offscreen_scan_buffers) = &mut self.offscreen_scan_buffers {
+                offscreen_scan_buffers.resize(width, height);
+            }
+        }
+
+        if let Some(swap_chain_pipeline) = &mut self.swap_chain_pipeline {
+            swap_chain_pipeline.set_size(width, height);
+        }
+        self.update_render_size();
     }
 }
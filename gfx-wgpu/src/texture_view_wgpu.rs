@@ -13,6 +13,7 @@ impl TextureViewWgpu {
         let texture_view = texture
             .get_texture()
             .create_view(&wgpu::TextureViewDescriptor {
+                label: info.get_debug_label(),
                 format: Some(util::convert_format(info.get_format())),
                 ..Default::default()
             });
@@ -1,12 +1,19 @@
 use std::sync::Arc;
 
-use sjgfx_interface::{GpuAccess, ITexture, TextureInfo};
+use sjgfx_interface::{
+    validate_texture_write, GpuAccess, ITexture, ImageData, ImageFormat, TextureCopyRegion,
+    TextureInfo,
+};
 use wgpu::util::DeviceExt;
 
-use crate::{util, DeviceWgpu};
+use crate::{detail::PendingCommandBuffers, util, DeviceWgpu};
 
 pub struct TextureWgpu {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    pending_command_buffers: PendingCommandBuffers,
     texture: Arc<wgpu::Texture>,
+    image_format: ImageFormat,
 }
 
 impl TextureWgpu {
@@ -15,7 +22,11 @@ impl TextureWgpu {
         let texture = device.get_device().create_texture(&texture_descriptor);
 
         Self {
+            device: device.close_device(),
+            queue: device.clone_queue(),
+            pending_command_buffers: device.clone_pending_command_buffers(),
             texture: Arc::new(texture),
+            image_format: info.get_image_format().clone(),
         }
     }
 
@@ -28,10 +39,192 @@ impl TextureWgpu {
                 .create_texture_with_data(queue, &texture_descriptor, data);
 
         Self {
+            device: device.close_device(),
+            queue: device.clone_queue(),
+            pending_command_buffers: device.clone_pending_command_buffers(),
             texture: Arc::new(texture),
+            image_format: info.get_image_format().clone(),
         }
     }
 
+    pub fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        assert!(
+            self.texture.usage().contains(wgpu::TextureUsages::COPY_DST),
+            "texture must be created with GpuAccess::WRITE to be written"
+        );
+        let required_size = validate_texture_write(
+            region,
+            &self.image_format,
+            data.len(),
+            bytes_per_row,
+            rows_per_image,
+        );
+        if required_size == 0 {
+            return;
+        }
+
+        let subresource = region.get_texture_subresource();
+        let mip_level = subresource.get_mip_level() as u32;
+        let mip_size = self
+            .texture
+            .size()
+            .mip_level_size(mip_level, self.texture.dimension());
+        assert!(
+            mip_level < self.texture.mip_level_count()
+                && region.get_offset_u() + region.get_width() <= mip_size.width as i32
+                && region.get_offset_v() + region.get_height() <= mip_size.height as i32,
+            "texture region is out of mip level {}",
+            mip_level
+        );
+        let base_array_layer = subresource.get_array_index() as u32;
+        let array_length = region.get_array_length() as u32;
+        assert!(
+            base_array_layer + array_length <= mip_size.depth_or_array_layers,
+            "texture layers {}..{} are out of {} array layers",
+            base_array_layer,
+            base_array_layer + array_length,
+            mip_size.depth_or_array_layers
+        );
+
+        let image_copy_texture = wgpu::ImageCopyTexture {
+            texture: &self.texture,
+            mip_level,
+            origin: wgpu::Origin3d {
+                x: region.get_offset_u() as u32,
+                y: region.get_offset_v() as u32,
+                z: base_array_layer,
+            },
+            aspect: wgpu::TextureAspect::All,
+        };
+        let size = wgpu::Extent3d {
+            width: region.get_width() as u32,
+            height: region.get_height() as u32,
+            depth_or_array_layers: array_length,
+        };
+
+        if self.image_format != ImageFormat::R8G8B8Unorm {
+            // write_texture は行のアライメントを内部で吸収してくれる
+            self.queue.write_texture(
+                image_copy_texture,
+                &data[..required_size],
+                wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row as u32),
+                    rows_per_image: Some(rows_per_image as u32),
+                },
+                size,
+            );
+            return;
+        }
+
+        // RGB は RGBA のテクスチャーで扱っているのでアルファを補って詰めなおす
+        let width = region.get_width() as usize;
+        let height = region.get_height() as usize;
+        let mut rgba_data =
+            Vec::with_capacity(width * height * size.depth_or_array_layers as usize * 4);
+        for layer in 0..size.depth_or_array_layers as usize {
+            for row in 0..height {
+                let start = bytes_per_row * (rows_per_image * layer + row);
+                for pixel in data[start..(start + width * 3)].chunks_exact(3) {
+                    rgba_data.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 255]);
+                }
+            }
+        }
+        self.queue.write_texture(
+            image_copy_texture,
+            &rgba_data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width as u32 * 4),
+                rows_per_image: Some(height as u32),
+            },
+            size,
+        );
+    }
+
+    pub fn read_image(&self) -> ImageData {
+        // RGB は RGBA のテクスチャーで扱っている
+        let image_format = match self.image_format {
+            ImageFormat::R8G8B8Unorm => ImageFormat::R8G8B8A8Unorm,
+            _ => self.image_format.clone(),
+        };
+        Self::read_image_impl(
+            &self.device,
+            &self.queue,
+            &self.pending_command_buffers,
+            &self.texture,
+            image_format,
+        )
+    }
+
+    // ミップレベル 0、配列要素 0 を読み戻して RGBA に変換する
+    // image_format はテクスチャーのテクセルの並びと一致していること
+    // キューにたまっているコマンドを先にサブミットして、その結果を読み戻す
+    pub(crate) fn read_image_impl(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        pending_command_buffers: &PendingCommandBuffers,
+        texture: &wgpu::Texture,
+        image_format: ImageFormat,
+    ) -> ImageData {
+        assert!(
+            texture.usage().contains(wgpu::TextureUsages::COPY_SRC),
+            "texture must be created with GpuAccess::READ to be read back"
+        );
+
+        // バッファーへのコピーは行のサイズを COPY_BYTES_PER_ROW_ALIGNMENT に揃える必要がある
+        let width = texture.width();
+        let height = texture.height();
+        let bytes_per_pixel = texture.format().block_size(None).unwrap();
+        let row_size = width * bytes_per_pixel;
+        let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (padded_row_size * height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut command_encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        command_encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        pending_command_buffers.submit(queue, Some(command_encoder.finish()));
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+        device.poll(wgpu::Maintain::Wait);
+        let mut texels = Vec::with_capacity((row_size * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks_exact(padded_row_size as usize) {
+                texels.extend_from_slice(&row[..row_size as usize]);
+            }
+        }
+        buffer.unmap();
+
+        ImageData::from_texels(width, height, image_format, &texels)
+    }
+
     pub fn get_texture(&self) -> &wgpu::Texture {
         &self.texture
     }
@@ -47,9 +240,9 @@ impl TextureWgpu {
             depth_or_array_layers: info.get_depth() as u32,
         };
         wgpu::TextureDescriptor {
-            label: None,
+            label: info.get_debug_label(),
             size: texture_size,
-            mip_level_count: 1,
+            mip_level_count: info.get_mip_count() as u32,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: util::convert_format(info.get_image_format().clone()),
@@ -93,6 +286,20 @@ impl ITexture for TextureWgpu {
     fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
         Self::new_with_data(device, info, data)
     }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.read_image()
+    }
 }
 
 #[cfg(test)]
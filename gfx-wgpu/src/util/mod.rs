@@ -4,36 +4,60 @@ pub use convert_pipeline_layout::{
     create_bind_group_layout, create_bind_group_layout_entries, create_pipeline_layout,
     create_vertex_attributes,
 };
-use sjgfx_interface::{AttributeFormat, GpuAccess, ImageFormat, ShaderStage};
+use sjgfx_interface::{
+    AdapterInfo, AdapterType, AttributeFormat, BackendMask, CompositeAlphaMode, CpuAccessMode,
+    DeviceFeatures, GpuAccess, ImageFormat, ImageFormatUsage, PresentMode, ShaderStage,
+};
 
-pub fn convert_to_buffer_usage(gpu_access: GpuAccess) -> wgpu::BufferUsages {
+pub fn convert_to_buffer_usage(
+    gpu_access: GpuAccess,
+    cpu_access_mode: CpuAccessMode,
+) -> wgpu::BufferUsages {
     let mut result = wgpu::BufferUsages::empty();
     if gpu_access.contains(GpuAccess::VERTEX_BUFFER) {
         result |= wgpu::BufferUsages::VERTEX;
-        result |= wgpu::BufferUsages::COPY_DST;
     }
     if gpu_access.contains(GpuAccess::INDEX_BUFFER) {
         result |= wgpu::BufferUsages::INDEX;
-        result |= wgpu::BufferUsages::COPY_DST;
     }
     if gpu_access.contains(GpuAccess::UNORDERED_ACCESS_BUFFER) {
-        result = wgpu::BufferUsages::STORAGE;
+        result |= wgpu::BufferUsages::STORAGE;
     }
     if gpu_access.contains(GpuAccess::CONSTANT_BUFFER) {
         result |= wgpu::BufferUsages::UNIFORM;
-        result |= wgpu::BufferUsages::COPY_DST;
     }
-
+    if gpu_access.contains(GpuAccess::INDIRECT_BUFFER) {
+        result |= wgpu::BufferUsages::INDIRECT;
+    }
     if gpu_access.contains(GpuAccess::READ) {
         result |= wgpu::BufferUsages::COPY_SRC;
-        result |= wgpu::BufferUsages::COPY_DST;
     }
-
     if gpu_access.contains(GpuAccess::WRITE) {
-        result |= wgpu::BufferUsages::MAP_READ;
         result |= wgpu::BufferUsages::COPY_DST;
     }
 
+    // MAP_READ と MAP_WRITE はコピー以外の用途と組み合わせられないので、
+    // それ以外はキュー経由の書き込みと読み戻し用バッファーへのコピーで CPU からアクセスする
+    let is_copy_only =
+        (result - wgpu::BufferUsages::COPY_SRC - wgpu::BufferUsages::COPY_DST).is_empty();
+    match cpu_access_mode {
+        CpuAccessMode::None => {}
+        // 4 バイト単位でない書き込みは前後を読み戻して広げるので COPY_SRC もつける
+        CpuAccessMode::Upload => {
+            result |= wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST
+        }
+        CpuAccessMode::Readback => {
+            if is_copy_only && !result.contains(wgpu::BufferUsages::COPY_SRC) {
+                result |= wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST;
+            } else {
+                result |= wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST;
+            }
+        }
+        CpuAccessMode::Persistent => {
+            result |= wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST
+        }
+    }
+
     result
 }
 
@@ -49,10 +73,217 @@ pub fn convert_format(format: ImageFormat) -> wgpu::TextureFormat {
         ImageFormat::R8G8B8A8Sint => wgpu::TextureFormat::Rgba8Sint,
         ImageFormat::R8G8B8Unorm => wgpu::TextureFormat::Rgba8Unorm,
         ImageFormat::R8G8B8A8Unorm => wgpu::TextureFormat::Rgba8Unorm,
+        ImageFormat::R8G8B8A8UnormSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
+        ImageFormat::B8G8R8A8Unorm => wgpu::TextureFormat::Bgra8Unorm,
+        ImageFormat::B8G8R8A8UnormSrgb => wgpu::TextureFormat::Bgra8UnormSrgb,
+        ImageFormat::R16G16B16A16Float => wgpu::TextureFormat::Rgba16Float,
         ImageFormat::D32 => wgpu::TextureFormat::Depth32Float,
     }
 }
 
+pub fn convert_from_format(format: wgpu::TextureFormat) -> Option<ImageFormat> {
+    match format {
+        wgpu::TextureFormat::R8Unorm => Some(ImageFormat::R8Unorm),
+        wgpu::TextureFormat::R8Snorm => Some(ImageFormat::R8Snorm),
+        wgpu::TextureFormat::R8Uint => Some(ImageFormat::R8Uint),
+        wgpu::TextureFormat::R8Sint => Some(ImageFormat::R8Sint),
+        wgpu::TextureFormat::R32Uint => Some(ImageFormat::R32Uint),
+        wgpu::TextureFormat::R32Sint => Some(ImageFormat::R32Sint),
+        wgpu::TextureFormat::Rgba8Uint => Some(ImageFormat::R8G8B8A8Uint),
+        wgpu::TextureFormat::Rgba8Sint => Some(ImageFormat::R8G8B8A8Sint),
+        wgpu::TextureFormat::Rgba8Unorm => Some(ImageFormat::R8G8B8A8Unorm),
+        wgpu::TextureFormat::Rgba8UnormSrgb => Some(ImageFormat::R8G8B8A8UnormSrgb),
+        wgpu::TextureFormat::Bgra8Unorm => Some(ImageFormat::B8G8R8A8Unorm),
+        wgpu::TextureFormat::Bgra8UnormSrgb => Some(ImageFormat::B8G8R8A8UnormSrgb),
+        wgpu::TextureFormat::Rgba16Float => Some(ImageFormat::R16G16B16A16Float),
+        wgpu::TextureFormat::Depth32Float => Some(ImageFormat::D32),
+        _ => None,
+    }
+}
+
+pub fn convert_present_mode(present_mode: PresentMode) -> wgpu::PresentMode {
+    match present_mode {
+        PresentMode::Fifo => wgpu::PresentMode::Fifo,
+        PresentMode::FifoRelaxed => wgpu::PresentMode::FifoRelaxed,
+        PresentMode::Mailbox => wgpu::PresentMode::Mailbox,
+        PresentMode::Immediate => wgpu::PresentMode::Immediate,
+    }
+}
+
+pub fn convert_composite_alpha_mode(alpha_mode: CompositeAlphaMode) -> wgpu::CompositeAlphaMode {
+    match alpha_mode {
+        CompositeAlphaMode::Auto => wgpu::CompositeAlphaMode::Auto,
+        CompositeAlphaMode::Opaque => wgpu::CompositeAlphaMode::Opaque,
+        CompositeAlphaMode::PreMultiplied => wgpu::CompositeAlphaMode::PreMultiplied,
+        CompositeAlphaMode::PostMultiplied => wgpu::CompositeAlphaMode::PostMultiplied,
+        CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
+    }
+}
+
+pub fn convert_from_composite_alpha_mode(
+    alpha_mode: wgpu::CompositeAlphaMode,
+) -> CompositeAlphaMode {
+    match alpha_mode {
+        wgpu::CompositeAlphaMode::Auto => CompositeAlphaMode::Auto,
+        wgpu::CompositeAlphaMode::Opaque => CompositeAlphaMode::Opaque,
+        wgpu::CompositeAlphaMode::PreMultiplied => CompositeAlphaMode::PreMultiplied,
+        wgpu::CompositeAlphaMode::PostMultiplied => CompositeAlphaMode::PostMultiplied,
+        wgpu::CompositeAlphaMode::Inherit => CompositeAlphaMode::Inherit,
+    }
+}
+
+const FEATURE_TABLE: [(DeviceFeatures, wgpu::Features); 10] = [
+    (
+        DeviceFeatures::TIMESTAMP_QUERY,
+        wgpu::Features::TIMESTAMP_QUERY,
+    ),
+    (
+        DeviceFeatures::PIPELINE_STATISTICS_QUERY,
+        wgpu::Features::PIPELINE_STATISTICS_QUERY,
+    ),
+    (
+        DeviceFeatures::TEXTURE_COMPRESSION_BC,
+        wgpu::Features::TEXTURE_COMPRESSION_BC,
+    ),
+    (
+        DeviceFeatures::TEXTURE_COMPRESSION_ETC2,
+        wgpu::Features::TEXTURE_COMPRESSION_ETC2,
+    ),
+    (
+        DeviceFeatures::TEXTURE_COMPRESSION_ASTC,
+        wgpu::Features::TEXTURE_COMPRESSION_ASTC,
+    ),
+    (
+        DeviceFeatures::INDIRECT_FIRST_INSTANCE,
+        wgpu::Features::INDIRECT_FIRST_INSTANCE,
+    ),
+    (
+        DeviceFeatures::MULTI_DRAW_INDIRECT,
+        wgpu::Features::MULTI_DRAW_INDIRECT,
+    ),
+    (DeviceFeatures::SHADER_FLOAT64, wgpu::Features::SHADER_F64),
+    (
+        DeviceFeatures::POLYGON_MODE_LINE,
+        wgpu::Features::POLYGON_MODE_LINE,
+    ),
+    (
+        DeviceFeatures::DEPTH_CLIP_CONTROL,
+        wgpu::Features::DEPTH_CLIP_CONTROL,
+    ),
+];
+
+pub fn convert_to_features(features: DeviceFeatures) -> wgpu::Features {
+    FEATURE_TABLE
+        .iter()
+        .filter(|(device_features, _)| features.contains(*device_features))
+        .fold(wgpu::Features::empty(), |result, (_, wgpu_features)| {
+            result | *wgpu_features
+        })
+}
+
+pub fn convert_from_features(features: wgpu::Features) -> DeviceFeatures {
+    FEATURE_TABLE
+        .iter()
+        .filter(|(_, wgpu_features)| features.contains(*wgpu_features))
+        .fold(DeviceFeatures::empty(), |result, (device_features, _)| {
+            result | *device_features
+        })
+}
+
+pub fn convert_from_texture_format_features(
+    features: &wgpu::TextureFormatFeatures,
+) -> ImageFormatUsage {
+    let mut result = ImageFormatUsage::empty();
+    if features
+        .allowed_usages
+        .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+    {
+        result |= ImageFormatUsage::SAMPLED;
+    }
+    if features
+        .allowed_usages
+        .contains(wgpu::TextureUsages::STORAGE_BINDING)
+    {
+        result |= ImageFormatUsage::STORAGE;
+    }
+    if features
+        .allowed_usages
+        .contains(wgpu::TextureUsages::RENDER_ATTACHMENT)
+    {
+        result |= ImageFormatUsage::RENDER_TARGET;
+    }
+    if features
+        .flags
+        .contains(wgpu::TextureFormatFeatureFlags::BLENDABLE)
+    {
+        result |= ImageFormatUsage::BLENDABLE;
+    }
+    if features.flags.intersects(
+        wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X2
+            | wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X4
+            | wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X8
+            | wgpu::TextureFormatFeatureFlags::MULTISAMPLE_X16,
+    ) {
+        result |= ImageFormatUsage::MULTISAMPLE;
+    }
+
+    result
+}
+
+pub fn convert_to_backends(backends: BackendMask) -> wgpu::Backends {
+    let mut result = wgpu::Backends::empty();
+    if backends.contains(BackendMask::VULKAN) {
+        result |= wgpu::Backends::VULKAN;
+    }
+    if backends.contains(BackendMask::METAL) {
+        result |= wgpu::Backends::METAL;
+    }
+    if backends.contains(BackendMask::DX12) {
+        result |= wgpu::Backends::DX12;
+    }
+    if backends.contains(BackendMask::DX11) {
+        result |= wgpu::Backends::DX11;
+    }
+    if backends.contains(BackendMask::GL) {
+        result |= wgpu::Backends::GL;
+    }
+    if backends.contains(BackendMask::BROWSER_WEBGPU) {
+        result |= wgpu::Backends::BROWSER_WEBGPU;
+    }
+    result
+}
+
+pub fn convert_from_backend(backend: wgpu::Backend) -> BackendMask {
+    match backend {
+        wgpu::Backend::Empty => BackendMask::empty(),
+        wgpu::Backend::Vulkan => BackendMask::VULKAN,
+        wgpu::Backend::Metal => BackendMask::METAL,
+        wgpu::Backend::Dx12 => BackendMask::DX12,
+        wgpu::Backend::Dx11 => BackendMask::DX11,
+        wgpu::Backend::Gl => BackendMask::GL,
+        wgpu::Backend::BrowserWebGpu => BackendMask::BROWSER_WEBGPU,
+    }
+}
+
+pub fn convert_from_device_type(device_type: wgpu::DeviceType) -> AdapterType {
+    match device_type {
+        wgpu::DeviceType::DiscreteGpu => AdapterType::DiscreteGpu,
+        wgpu::DeviceType::IntegratedGpu => AdapterType::IntegratedGpu,
+        wgpu::DeviceType::VirtualGpu => AdapterType::VirtualGpu,
+        wgpu::DeviceType::Cpu => AdapterType::Cpu,
+        wgpu::DeviceType::Other => AdapterType::Other,
+    }
+}
+
+pub fn convert_from_adapter_info(index: usize, info: &wgpu::AdapterInfo) -> AdapterInfo {
+    AdapterInfo::new()
+        .set_index(index)
+        .set_name(&info.name)
+        .set_vendor(info.vendor)
+        .set_adapter_type(convert_from_device_type(info.device_type))
+        .set_backend(convert_from_backend(info.backend))
+}
+
 pub fn convert_attribute_format(format: AttributeFormat) -> wgpu::VertexFormat {
     match format {
         AttributeFormat::Uint32 => wgpu::VertexFormat::Uint32,
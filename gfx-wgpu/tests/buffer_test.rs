@@ -0,0 +1,122 @@
+use sjgfx_interface::{BufferInfo, CpuAccessMode, DeviceInfo, GpuAccess, IDevice};
+use sjgfx_wgpu::{BufferWgpu, DeviceWgpu};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct Data {
+    x: f32,
+    y: f32,
+    z: u32,
+    w: u32,
+}
+
+#[test]
+fn map_mut() {
+    map_mut_impl(GpuAccess::empty(), CpuAccessMode::Readback);
+    map_mut_impl(GpuAccess::READ, CpuAccessMode::Readback);
+    map_mut_impl(GpuAccess::CONSTANT_BUFFER, CpuAccessMode::Persistent);
+    map_mut_impl(
+        GpuAccess::VERTEX_BUFFER | GpuAccess::UNORDERED_ACCESS_BUFFER,
+        CpuAccessMode::Readback,
+    );
+}
+
+fn map_mut_impl(gpu_access: GpuAccess, cpu_access_mode: CpuAccessMode) {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(std::mem::size_of::<Data>())
+            .set_gpu_access_flags(gpu_access)
+            .set_cpu_access_mode(cpu_access_mode),
+    );
+
+    let data = Data {
+        x: 1.0,
+        y: 2.0,
+        z: 3,
+        w: 4,
+    };
+    buffer.map_mut(|x: &mut Data| *x = data);
+    buffer.map(|x: &Data| assert_eq!(*x, data));
+}
+
+#[test]
+fn map_range() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(std::mem::size_of::<u32>() * 8)
+            .set_cpu_access_mode(CpuAccessMode::Readback),
+    );
+
+    buffer.map_as_slice_mut(|x: &mut [u32]| {
+        for (index, value) in x.iter_mut().enumerate() {
+            *value = index as u32;
+        }
+    });
+    buffer.map_range_mut(8..16, |x: &mut [u32]| x.copy_from_slice(&[20, 30]));
+
+    buffer.map_range(4..20, |x: &[u32]| assert_eq!(x, [1, 20, 30, 4]));
+    buffer.map_range(12..16, |x: &[u32]| assert_eq!(x, [30]));
+    buffer.map_range(4..6, |x: &[u8]| assert_eq!(x, [1, 0]));
+    buffer.map_as_slice(|x: &[u32]| assert_eq!(x, [0, 1, 20, 30, 4, 5, 6, 7]));
+}
+
+#[test]
+fn map_range_mut_unaligned() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(6)
+            .set_gpu_access_flags(GpuAccess::VERTEX_BUFFER)
+            .set_cpu_access_mode(CpuAccessMode::Persistent),
+    );
+
+    // 4 バイト単位でない範囲に書き込んでも前後のバイトは書き換わらない
+    buffer.map_as_slice_mut(|x: &mut [u8]| x.copy_from_slice(&[1, 2, 3, 4, 5, 6]));
+    buffer.map_range_mut(3..5, |x: &mut [u8]| x.copy_from_slice(&[30, 40]));
+    buffer.map_as_slice(|x: &[u8]| assert_eq!(x, [1, 2, 3, 30, 40, 6]));
+}
+
+#[test]
+#[should_panic]
+fn map_range_out_of_buffer() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(16)
+            .set_cpu_access_mode(CpuAccessMode::Readback),
+    );
+    buffer.map_range(8..24, |_: &[u32]| {});
+}
+
+#[test]
+#[should_panic]
+fn map_larger_than_buffer() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(8)
+            .set_cpu_access_mode(CpuAccessMode::Readback),
+    );
+    buffer.map(|_: &Data| {});
+}
+
+#[test]
+#[should_panic]
+fn map_device_only() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(16)
+            .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER)
+            .set_cpu_access_mode(CpuAccessMode::None),
+    );
+    buffer.map_mut(|_: &mut u32| {});
+}
@@ -1,5 +1,8 @@
-use sjgfx_interface::{BufferInfo, CommandBufferInfo, DeviceInfo, GpuAccess, IDevice, ShaderInfo};
-use sjgfx_wgpu::{BufferWgpu, CommandBufferWgpu, DeviceWgpu, ShaderWgpu};
+use sjgfx_interface::{
+    BufferCopyRegion, BufferInfo, CommandBufferInfo, DeviceInfo, GpuAccess, IDevice, QueueInfo,
+    ShaderInfo,
+};
+use sjgfx_wgpu::{BufferWgpu, CommandBufferWgpu, DeviceWgpu, QueueWgpu, ShaderWgpu};
 
 #[test]
 fn new() {
@@ -59,3 +62,131 @@ fn simple_graphics_command() {
     command_buffer.begin();
     command_buffer.end();
 }
+
+#[test]
+fn debug_label_compute_command() {
+    let device = DeviceWgpu::new(&DeviceInfo::new().set_debug_label("TestDevice"));
+
+    let shader_source = include_str!("../../resources/tests/simple_compute.glsl");
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let shader_binary = compiler
+        .compile_into_spirv(
+            &shader_source,
+            shaderc::ShaderKind::Compute,
+            "test.glsl",
+            "main",
+            None,
+        )
+        .unwrap();
+    let shader = ShaderWgpu::new(
+        &device,
+        &ShaderInfo::new()
+            .set_compute_shader_binary(shader_binary.as_binary_u8())
+            .set_debug_label("TestShader"),
+    );
+
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_gpu_access_flags(GpuAccess::UNORDERED_ACCESS_BUFFER)
+            .set_size(1024)
+            .set_debug_label("TestBuffer"),
+    );
+    let mut command_buffer = CommandBufferWgpu::new(
+        &device,
+        &CommandBufferInfo::new().set_debug_label("TestCommandBuffer"),
+    );
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+
+    command_buffer.begin();
+    command_buffer.push_debug_group("Compute");
+    command_buffer.insert_debug_marker("Dispatch");
+    command_buffer.set_shader(&shader);
+    command_buffer.set_unordered_access_buffer(0, &buffer);
+    command_buffer.dispatch(1, 1, 1);
+    command_buffer.pop_debug_group();
+    command_buffer.end();
+
+    queue.execute(&command_buffer);
+    queue.sync();
+}
+
+// 前回の記録のディスパッチは次の記録に持ち越さない
+#[test]
+fn record_twice() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+
+    let shader_source = include_str!("../../resources/tests/simple_compute.glsl");
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let shader_binary = compiler
+        .compile_into_spirv(
+            &shader_source,
+            shaderc::ShaderKind::Compute,
+            "test.glsl",
+            "main",
+            None,
+        )
+        .unwrap();
+    let shader = ShaderWgpu::new(
+        &device,
+        &ShaderInfo::new().set_compute_shader_binary(shader_binary.as_binary_u8()),
+    );
+
+    let buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_gpu_access_flags(
+                GpuAccess::UNORDERED_ACCESS_BUFFER | GpuAccess::READ | GpuAccess::WRITE,
+            )
+            .set_size(256),
+    );
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+
+    command_buffer.begin();
+    command_buffer.set_shader(&shader);
+    command_buffer.set_unordered_access_buffer(0, &buffer);
+    command_buffer.dispatch(1, 1, 1);
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.sync();
+    buffer.map_as_slice(|x: &[u32]| assert_eq!(x[1], 1));
+
+    // 2 回目はディスパッチしないのでバッファーは書き換わらない
+    buffer.map_as_slice_mut(|x: &mut [u32]| x.fill(0));
+    command_buffer.begin();
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.sync();
+    buffer.map_as_slice(|x: &[u32]| assert!(x.iter().all(|x| *x == 0)));
+}
+
+// コピーは実行したときに積んだ順番で処理される
+#[test]
+fn copy_buffer_to_buffer_in_order() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let buffer_info = BufferInfo::new()
+        .set_gpu_access_flags(GpuAccess::READ | GpuAccess::WRITE)
+        .set_size(64);
+    let src_buffer = BufferWgpu::new(&device, &buffer_info);
+    let mut intermediate_buffer = BufferWgpu::new(&device, &buffer_info);
+    let mut dst_buffer = BufferWgpu::new(&device, &buffer_info);
+    src_buffer.map_as_slice_mut(|x: &mut [u32]| x.fill(1));
+    intermediate_buffer.map_as_slice_mut(|x: &mut [u32]| x.fill(2));
+
+    let region = BufferCopyRegion::default().set_copy_size(64);
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.copy_buffer_to_buffer(&mut intermediate_buffer, &src_buffer, &region);
+    command_buffer.copy_buffer_to_buffer(&mut dst_buffer, &intermediate_buffer, &region);
+    command_buffer.end();
+
+    // 記録しただけではサブミットされない
+    queue.sync();
+    intermediate_buffer.map_as_slice(|x: &[u32]| assert!(x.iter().all(|x| *x == 2)));
+
+    queue.execute(&command_buffer);
+    queue.sync();
+    dst_buffer.map_as_slice(|x: &[u32]| assert!(x.iter().all(|x| *x == 1)));
+}
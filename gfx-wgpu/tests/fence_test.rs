@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use sjgfx_interface::{
+    BufferCopyRegion, BufferInfo, CommandBufferInfo, DeviceInfo, FenceInfo, GpuAccess, IDevice,
+    QueueInfo,
+};
+use sjgfx_wgpu::{BufferWgpu, CommandBufferWgpu, DeviceWgpu, FenceWgpu, QueueWgpu};
+
+#[test]
+fn new() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let fence = FenceWgpu::new(&device, &FenceInfo::new());
+    assert!(!fence.is_signaled());
+}
+
+#[test]
+fn new_signaled() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut fence = FenceWgpu::new(&device, &FenceInfo::new().set_signaled(true));
+    assert!(fence.is_signaled());
+    assert!(fence.wait(None));
+
+    fence.reset();
+    assert!(!fence.is_signaled());
+    assert!(!fence.wait(Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn execute_with_fence() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut fence = FenceWgpu::new(&device, &FenceInfo::new());
+
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.end();
+
+    for _ in 0..2 {
+        queue.execute_with_fence(&command_buffer, &mut fence);
+        assert!(fence.wait(None));
+        assert!(fence.is_signaled());
+
+        fence.reset();
+        assert!(!fence.is_signaled());
+    }
+}
+
+// 完了前にリセットしたフェンスは前のサブミットの完了でシグナルされない
+#[test]
+fn reset_before_completion() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut fence = FenceWgpu::new(&device, &FenceInfo::new());
+    let mut other_fence = FenceWgpu::new(&device, &FenceInfo::new());
+
+    // リセットするまでに完了しないように大きなコピーを積む
+    let size = 64 * 1024 * 1024;
+    let src_buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(size)
+            .set_gpu_access_flags(GpuAccess::READ),
+    );
+    let mut dst_buffer = BufferWgpu::new(
+        &device,
+        &BufferInfo::new()
+            .set_size(size)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    for _ in 0..8 {
+        command_buffer.copy_buffer_to_buffer(
+            &mut dst_buffer,
+            &src_buffer,
+            &BufferCopyRegion::default().set_copy_size(size),
+        );
+    }
+    command_buffer.end();
+
+    queue.execute_with_fence(&command_buffer, &mut fence);
+    fence.reset();
+
+    // 前のサブミットのコールバックが呼ばれるまで待つ
+    let mut empty_command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    empty_command_buffer.begin();
+    empty_command_buffer.end();
+    queue.execute_with_fence(&empty_command_buffer, &mut other_fence);
+    assert!(other_fence.wait(None));
+
+    assert!(!fence.is_signaled());
+}
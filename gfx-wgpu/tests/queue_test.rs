@@ -15,14 +15,32 @@ fn new() {
 #[test]
 fn flush_empty() {
     let device = DeviceWgpu::new(&DeviceInfo::new());
-    let queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
     queue.flush();
 }
 
 #[test]
 fn flush_sync() {
     let device = DeviceWgpu::new(&DeviceInfo::new());
-    let queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+    queue.sync();
+}
+
+#[test]
+fn execute_many_empty() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+
+    let mut command_buffers = Vec::new();
+    for _ in 0..3 {
+        let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+        command_buffer.begin();
+        command_buffer.end();
+        command_buffers.push(command_buffer);
+    }
+
+    let command_buffer_refs = command_buffers.iter().collect::<Vec<&CommandBufferWgpu>>();
+    queue.execute_many(&command_buffer_refs);
     queue.sync();
 }
 
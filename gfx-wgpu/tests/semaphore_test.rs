@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use sjgfx_interface::{
+    CommandBufferInfo, DeviceInfo, IDevice, QueueInfo, QueueType, SemaphoreInfo,
+};
+use sjgfx_wgpu::{CommandBufferWgpu, DeviceWgpu, QueueWgpu, SemaphoreWgpu};
+
+#[test]
+fn new() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let semaphore = SemaphoreWgpu::new(&device, &SemaphoreInfo::new().set_initial_value(3));
+    assert_eq!(semaphore.get_value(), 3);
+    assert!(semaphore.wait(3, None));
+    assert!(!semaphore.wait(4, Some(Duration::from_millis(1))));
+}
+
+#[test]
+fn signal() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let semaphore = SemaphoreWgpu::new(&device, &SemaphoreInfo::new());
+    semaphore.signal(2);
+    assert_eq!(semaphore.get_value(), 2);
+
+    // 値は単調増加
+    semaphore.signal(1);
+    assert_eq!(semaphore.get_value(), 2);
+}
+
+#[test]
+fn execute_with_semaphores() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut graphics_queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut compute_queue = QueueWgpu::new(
+        &device,
+        &QueueInfo::new().set_queue_type(QueueType::Compute),
+    );
+    assert_eq!(compute_queue.get_queue_type(), &QueueType::Compute);
+    let semaphore = SemaphoreWgpu::new(&device, &SemaphoreInfo::new());
+
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    command_buffer.begin();
+    command_buffer.end();
+
+    for value in 1..3 {
+        compute_queue.execute_with_semaphores(&command_buffer, &[], &[(&semaphore, value * 2 - 1)]);
+        graphics_queue.execute_with_semaphores(
+            &command_buffer,
+            &[(&semaphore, value * 2 - 1)],
+            &[(&semaphore, value * 2)],
+        );
+        assert!(semaphore.wait(value * 2, None));
+    }
+    assert_eq!(semaphore.get_value(), 4);
+}
@@ -0,0 +1,242 @@
+use sjgfx_interface::{
+    ColorTargetViewInfo, CommandBufferInfo, DeviceInfo, GpuAccess, IDevice, ImageFormat, QueueInfo,
+    TextureArrayRange, TextureCopyRegion, TextureInfo,
+};
+use sjgfx_wgpu::{ColorTargetViewWgpu, CommandBufferWgpu, DeviceWgpu, QueueWgpu, TextureWgpu};
+
+// テクスチャーの 1 ミップレベルを詰めて読み戻す
+fn read_texture(
+    device: &DeviceWgpu,
+    texture: &TextureWgpu,
+    mip_level: u32,
+    bytes_per_pixel: u32,
+) -> Vec<u8> {
+    let size = texture
+        .get_texture()
+        .size()
+        .mip_level_size(mip_level, wgpu::TextureDimension::D2);
+    let row_size = size.width * bytes_per_pixel;
+    let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+    let buffer = device.get_device().create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (padded_row_size * size.height) as u64,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut command_encoder = device
+        .get_device()
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    command_encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            texture: texture.get_texture(),
+            mip_level,
+            origin: wgpu::Origin3d::ZERO,
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_size),
+                rows_per_image: Some(size.height),
+            },
+        },
+        wgpu::Extent3d {
+            width: size.width,
+            height: size.height,
+            depth_or_array_layers: 1,
+        },
+    );
+    device.get_queue().submit(Some(command_encoder.finish()));
+
+    let slice = buffer.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+    device.get_device().poll(wgpu::Maintain::Wait);
+    let data = slice.get_mapped_range();
+    data.chunks(padded_row_size as usize)
+        .flat_map(|row| row[..row_size as usize].to_vec())
+        .collect()
+}
+
+#[test]
+fn write_sub_region() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(8)
+            .set_height(8)
+            .set_mip_count(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    // 1 行 3 ピクセルのデータを 5 バイト間隔で並べる
+    let data = [1, 2, 3, 0, 0, 4, 5, 6];
+    let region = TextureCopyRegion::new()
+        .set_offset_u(1)
+        .set_offset_v(2)
+        .set_width(3)
+        .set_height(2)
+        .edit_texture_subresource(|x| x.set_mip_level(1));
+    texture.write(&region, &data, 5, 2);
+
+    let result = read_texture(&device, &texture, 1, 1);
+    #[rustfmt::skip]
+    let expected = [
+        0, 0, 0, 0,
+        0, 0, 0, 0,
+        0, 1, 2, 3,
+        0, 4, 5, 6,
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn write_rgb() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(1)
+            .set_image_format(ImageFormat::R8G8B8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+
+    let region = TextureCopyRegion::new().set_width(2).set_height(1);
+    texture.write(&region, &[10, 20, 30, 40, 50, 60], 6, 1);
+
+    let image = texture.read_image();
+    assert_eq!(image.get_data(), [10, 20, 30, 255, 40, 50, 60, 255]);
+}
+
+#[test]
+fn read_image() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let region = TextureCopyRegion::new().set_width(2).set_height(2);
+    texture.write(&region, &[1, 2, 3, 4], 2, 2);
+
+    // 1 チャンネルはグレースケールになる
+    let image = texture.read_image();
+    assert_eq!(image.get_width(), 2);
+    assert_eq!(image.get_height(), 2);
+    assert_eq!(image.get_format(), &ImageFormat::R8Unorm);
+    assert_eq!(image.get_pixel(1, 0), [2, 2, 2, 255]);
+    assert_eq!(image.get_pixel(0, 1), [3, 3, 3, 255]);
+}
+
+#[test]
+fn read_color_target_view() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+
+    // 行のサイズが COPY_BYTES_PER_ROW_ALIGNMENT に揃わない幅にしておく
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(3)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::COLOR_BUFFER | GpuAccess::WRITE | GpuAccess::READ),
+    );
+    let data: Vec<u8> = (0..24).collect();
+    let region = TextureCopyRegion::new().set_width(3).set_height(2);
+    texture.write(&region, &data, 12, 2);
+
+    let color_target_view = ColorTargetViewWgpu::new(
+        &device,
+        &ColorTargetViewInfo::new().set_image_format(ImageFormat::R8G8B8A8Unorm),
+        &texture,
+    );
+    let image = color_target_view.read_image();
+    assert_eq!(image.get_data(), data);
+}
+
+#[test]
+fn read_color_target_view_before_flush() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let mut queue = QueueWgpu::new(&device, &QueueInfo::new());
+    let mut command_buffer = CommandBufferWgpu::new(&device, &CommandBufferInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(2)
+            .set_height(2)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::COLOR_BUFFER | GpuAccess::READ),
+    );
+    let mut color_target_view = ColorTargetViewWgpu::new(
+        &device,
+        &ColorTargetViewInfo::new().set_image_format(ImageFormat::R8G8B8A8Unorm),
+        &texture,
+    );
+
+    // キューにためたままのクリアも読み戻しに反映される
+    command_buffer.begin();
+    command_buffer.clear_color(
+        &mut color_target_view,
+        0.0,
+        1.0,
+        0.0,
+        1.0,
+        TextureArrayRange::new(),
+    );
+    command_buffer.end();
+    queue.execute(&command_buffer);
+
+    let image = color_target_view.read_image();
+    assert!(image.get_data().chunks(4).all(|x| x == [0, 255, 0, 255]));
+    assert_eq!(texture.read_image().get_data(), image.get_data());
+}
+
+#[test]
+#[should_panic]
+fn write_out_of_mip() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_mip_level(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
+
+#[test]
+#[should_panic(expected = "texture layers 1..2 are out of 1 array layers")]
+fn write_out_of_array() {
+    let device = DeviceWgpu::new(&DeviceInfo::new());
+    let texture = TextureWgpu::new(
+        &device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8Unorm)
+            .set_gpu_access_flags(GpuAccess::WRITE),
+    );
+
+    let region = TextureCopyRegion::new().set_width(4).set_height(4);
+    texture.write(
+        &region.edit_texture_subresource(|x| x.set_array_index(1)),
+        &[0; 16],
+        4,
+        4,
+    );
+}
@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use sjgfx_interface::{BufferInfo, IBuffer};
+use sjgfx_vulkano::BufferVk;
+use sjgfx_wgpu::BufferWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum BufferAny {
+    Wgpu(BufferWgpu),
+    Vulkano(BufferVk),
+}
+
+impl BufferAny {
+    pub(crate) fn as_wgpu(&self) -> &BufferWgpu {
+        match self {
+            Self::Wgpu(buffer) => buffer,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &BufferVk {
+        match self {
+            Self::Vulkano(buffer) => buffer,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IBuffer for BufferAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(IBuffer::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(IBuffer::new(device, info)),
+        }
+    }
+
+    fn map<T: bytemuck::Pod, F: FnOnce(&T)>(&self, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map(buffer, func),
+            Self::Vulkano(buffer) => IBuffer::map(buffer, func),
+        }
+    }
+
+    fn map_as_slice<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map_as_slice(buffer, func),
+            Self::Vulkano(buffer) => IBuffer::map_as_slice(buffer, func),
+        }
+    }
+
+    fn map_range<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map_range(buffer, range, func),
+            Self::Vulkano(buffer) => IBuffer::map_range(buffer, range, func),
+        }
+    }
+
+    fn map_mut<T: bytemuck::Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map_mut(buffer, func),
+            Self::Vulkano(buffer) => IBuffer::map_mut(buffer, func),
+        }
+    }
+
+    fn map_as_slice_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map_as_slice_mut(buffer, func),
+            Self::Vulkano(buffer) => IBuffer::map_as_slice_mut(buffer, func),
+        }
+    }
+
+    fn map_range_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::map_range_mut(buffer, range, func),
+            Self::Vulkano(buffer) => IBuffer::map_range_mut(buffer, range, func),
+        }
+    }
+
+    fn flush_mapped_range(&self, offset: isize, size: usize) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::flush_mapped_range(buffer, offset, size),
+            Self::Vulkano(buffer) => IBuffer::flush_mapped_range(buffer, offset, size),
+        }
+    }
+
+    fn invalidate_mapped_range(&self, offset: isize, size: usize) {
+        match self {
+            Self::Wgpu(buffer) => IBuffer::invalidate_mapped_range(buffer, offset, size),
+            Self::Vulkano(buffer) => IBuffer::invalidate_mapped_range(buffer, offset, size),
+        }
+    }
+}
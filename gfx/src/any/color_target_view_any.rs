@@ -0,0 +1,69 @@
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData};
+use sjgfx_vulkano::ColorTargetViewVk;
+use sjgfx_wgpu::ColorTargetViewWgpu;
+
+use crate::any::{DeviceAny, TextureAny};
+
+// スワップチェーンが返すビューを包めるように複製できる
+#[derive(Clone)]
+pub enum ColorTargetViewAny {
+    Wgpu(ColorTargetViewWgpu),
+    Vulkano(ColorTargetViewVk),
+}
+
+impl ColorTargetViewAny {
+    pub(crate) fn as_wgpu(&self) -> &ColorTargetViewWgpu {
+        match self {
+            Self::Wgpu(color_target_view) => color_target_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &ColorTargetViewVk {
+        match self {
+            Self::Vulkano(color_target_view) => color_target_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_wgpu_mut(&mut self) -> &mut ColorTargetViewWgpu {
+        match self {
+            Self::Wgpu(color_target_view) => color_target_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano_mut(&mut self) -> &mut ColorTargetViewVk {
+        match self {
+            Self::Vulkano(color_target_view) => color_target_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IColorTargetView for ColorTargetViewAny {
+    type DeviceType = DeviceAny;
+    type TextureType = TextureAny;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &ColorTargetViewInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => {
+                Self::Wgpu(IColorTargetView::new(device, info, texture.as_wgpu()))
+            }
+            DeviceAny::Vulkano(device) => {
+                Self::Vulkano(IColorTargetView::new(device, info, texture.as_vulkano()))
+            }
+        }
+    }
+
+    fn read_image(&self) -> ImageData {
+        match self {
+            Self::Wgpu(color_target_view) => IColorTargetView::read_image(color_target_view),
+            Self::Vulkano(color_target_view) => IColorTargetView::read_image(color_target_view),
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use sjgfx_interface::{
+    CommandBufferInfo, ICommandBuffer, IndexFormat, PrimitiveTopology, ScissorStateInfo,
+    TextureArrayRange,
+};
+use sjgfx_vulkano::CommandBufferVk;
+use sjgfx_wgpu::CommandBufferWgpu;
+
+use crate::any::{
+    BufferAny, ColorTargetViewAny, DepthStencilViewAny, DeviceAny, SamplerAny, ShaderAny,
+    TextureAny, TextureViewAny, VertexStateAny,
+};
+
+pub enum CommandBufferAny {
+    Wgpu(CommandBufferWgpu),
+    Vulkano(CommandBufferVk),
+}
+
+impl CommandBufferAny {
+    pub(crate) fn as_wgpu(&self) -> &CommandBufferWgpu {
+        match self {
+            Self::Wgpu(command_buffer) => command_buffer,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &CommandBufferVk {
+        match self {
+            Self::Vulkano(command_buffer) => command_buffer,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl ICommandBuffer for CommandBufferAny {
+    type DeviceType = DeviceAny;
+    type BufferType = BufferAny;
+    type ColorTargetViewType = ColorTargetViewAny;
+    type DepthStencilViewType = DepthStencilViewAny;
+    type SamplerType = SamplerAny;
+    type ShaderType = ShaderAny;
+    type TextureType = TextureAny;
+    type TextureViewType = TextureViewAny;
+    type VertexStateType = VertexStateAny;
+
+    fn new(device: &Self::DeviceType, info: &CommandBufferInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(ICommandBuffer::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(ICommandBuffer::new(device, info)),
+        }
+    }
+
+    fn begin(&mut self) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::begin(command_buffer),
+            Self::Vulkano(command_buffer) => ICommandBuffer::begin(command_buffer),
+        }
+    }
+
+    fn end(&mut self) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::end(command_buffer),
+            Self::Vulkano(command_buffer) => ICommandBuffer::end(command_buffer),
+        }
+    }
+
+    fn push_debug_group(&mut self, label: &str) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::push_debug_group(command_buffer, label),
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::push_debug_group(command_buffer, label)
+            }
+        }
+    }
+
+    fn pop_debug_group(&mut self) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::pop_debug_group(command_buffer),
+            Self::Vulkano(command_buffer) => ICommandBuffer::pop_debug_group(command_buffer),
+        }
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::insert_debug_marker(command_buffer, label)
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::insert_debug_marker(command_buffer, label)
+            }
+        }
+    }
+
+    fn clear_color(
+        &mut self,
+        color_target_view: &mut Self::ColorTargetViewType,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::clear_color(
+                command_buffer,
+                color_target_view.as_wgpu_mut(),
+                red,
+                green,
+                blue,
+                alpha,
+                texture_array_range,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::clear_color(
+                command_buffer,
+                color_target_view.as_vulkano_mut(),
+                red,
+                green,
+                blue,
+                alpha,
+                texture_array_range,
+            ),
+        }
+    }
+
+    fn set_render_targets(
+        &mut self,
+        color_target_views: &[&Self::ColorTargetViewType],
+        depth_stencil_view: Option<&Self::DepthStencilViewType>,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                let color_target_views: Vec<_> = color_target_views
+                    .iter()
+                    .map(|color_target_view| color_target_view.as_wgpu())
+                    .collect();
+                ICommandBuffer::set_render_targets(
+                    command_buffer,
+                    &color_target_views,
+                    depth_stencil_view.map(DepthStencilViewAny::as_wgpu),
+                )
+            }
+            Self::Vulkano(command_buffer) => {
+                let color_target_views: Vec<_> = color_target_views
+                    .iter()
+                    .map(|color_target_view| color_target_view.as_vulkano())
+                    .collect();
+                ICommandBuffer::set_render_targets(
+                    command_buffer,
+                    &color_target_views,
+                    depth_stencil_view.map(DepthStencilViewAny::as_vulkano),
+                )
+            }
+        }
+    }
+
+    fn set_shader(&mut self, shader: &Self::ShaderType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_shader(command_buffer, shader.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_shader(command_buffer, shader.as_vulkano())
+            }
+        }
+    }
+
+    fn set_sampler(&mut self, index: i32, sampler: &Self::SamplerType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_sampler(command_buffer, index, sampler.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_sampler(command_buffer, index, sampler.as_vulkano())
+            }
+        }
+    }
+
+    fn set_texture(&mut self, index: i32, texture_view: &Self::TextureViewType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_texture(command_buffer, index, texture_view.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_texture(command_buffer, index, texture_view.as_vulkano())
+            }
+        }
+    }
+
+    fn set_image(&mut self, index: i32, texture: &Self::TextureViewType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_image(command_buffer, index, texture.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_image(command_buffer, index, texture.as_vulkano())
+            }
+        }
+    }
+
+    fn set_constant_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_constant_buffer(command_buffer, index, buffer.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_constant_buffer(command_buffer, index, buffer.as_vulkano())
+            }
+        }
+    }
+
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::set_constant_buffer_range(
+                command_buffer,
+                index,
+                buffer.as_wgpu(),
+                offset,
+                size,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::set_constant_buffer_range(
+                command_buffer,
+                index,
+                buffer.as_vulkano(),
+                offset,
+                size,
+            ),
+        }
+    }
+
+    fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_unordered_access_buffer(command_buffer, index, buffer.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => ICommandBuffer::set_unordered_access_buffer(
+                command_buffer,
+                index,
+                buffer.as_vulkano(),
+            ),
+        }
+    }
+
+    fn set_vertex_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_vertex_buffer(command_buffer, index, buffer.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_vertex_buffer(command_buffer, index, buffer.as_vulkano())
+            }
+        }
+    }
+
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::set_vertex_buffer_range(
+                command_buffer,
+                index,
+                buffer.as_wgpu(),
+                offset,
+                size,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::set_vertex_buffer_range(
+                command_buffer,
+                index,
+                buffer.as_vulkano(),
+                offset,
+                size,
+            ),
+        }
+    }
+
+    fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_vertex_state(command_buffer, vertex_state.as_wgpu())
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_vertex_state(command_buffer, vertex_state.as_vulkano())
+            }
+        }
+    }
+
+    fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::set_scissor(command_buffer, scissor_state_info)
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::set_scissor(command_buffer, scissor_state_info)
+            }
+        }
+    }
+
+    fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        match self {
+            Self::Wgpu(command_buffer) => {
+                ICommandBuffer::dispatch(command_buffer, count_x, count_y, count_z)
+            }
+            Self::Vulkano(command_buffer) => {
+                ICommandBuffer::dispatch(command_buffer, count_x, count_y, count_z)
+            }
+        }
+    }
+
+    fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::draw(
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::draw(
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+            ),
+        }
+    }
+
+    fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::draw_instanced(
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+                instance_count,
+                base_instance,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::draw_instanced(
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+                instance_count,
+                base_instance,
+            ),
+        }
+    }
+
+    fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::draw_indexed(
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer.as_wgpu(),
+                index_count,
+                base_vertex,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::draw_indexed(
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer.as_vulkano(),
+                index_count,
+                base_vertex,
+            ),
+        }
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        match self {
+            Self::Wgpu(command_buffer) => ICommandBuffer::draw_indexed_instanced(
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer.as_wgpu(),
+                index_count,
+                base_vertex,
+                instance_count,
+                base_instance,
+            ),
+            Self::Vulkano(command_buffer) => ICommandBuffer::draw_indexed_instanced(
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer.as_vulkano(),
+                index_count,
+                base_vertex,
+                instance_count,
+                base_instance,
+            ),
+        }
+    }
+}
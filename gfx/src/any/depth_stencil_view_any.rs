@@ -0,0 +1,46 @@
+use sjgfx_interface::{DepthStencilStateInfo, IDepthStencilView};
+use sjgfx_vulkano::DepthStencilViewVk;
+use sjgfx_wgpu::DepthStencilViewWgpu;
+
+use crate::any::{DeviceAny, TextureAny};
+
+pub enum DepthStencilViewAny {
+    Wgpu(DepthStencilViewWgpu),
+    Vulkano(DepthStencilViewVk),
+}
+
+impl DepthStencilViewAny {
+    pub(crate) fn as_wgpu(&self) -> &DepthStencilViewWgpu {
+        match self {
+            Self::Wgpu(depth_stencil_view) => depth_stencil_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &DepthStencilViewVk {
+        match self {
+            Self::Vulkano(depth_stencil_view) => depth_stencil_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IDepthStencilView for DepthStencilViewAny {
+    type DeviceType = DeviceAny;
+    type TextureType = TextureAny;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &DepthStencilStateInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => {
+                Self::Wgpu(IDepthStencilView::new(device, info, texture.as_wgpu()))
+            }
+            DeviceAny::Vulkano(device) => {
+                Self::Vulkano(IDepthStencilView::new(device, info, texture.as_vulkano()))
+            }
+        }
+    }
+}
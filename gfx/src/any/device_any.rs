@@ -0,0 +1,97 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{AdapterInfo, ApiType, DeviceCapabilities, DeviceInfo, IDevice};
+use sjgfx_vulkano::DeviceVk;
+use sjgfx_wgpu::DeviceWgpu;
+
+pub enum DeviceAny {
+    Wgpu(DeviceWgpu),
+    Vulkano(DeviceVk),
+}
+
+impl DeviceAny {
+    pub fn new(info: &DeviceInfo) -> Self {
+        Self::new_impl(info, |api_type| match api_type {
+            ApiType::Wgpu => DeviceWgpu::try_new(info).map(Self::Wgpu),
+            ApiType::Vulkano => DeviceVk::try_new(info).map(Self::Vulkano),
+        })
+    }
+
+    pub fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_impl(info, |api_type| match api_type {
+            ApiType::Wgpu => DeviceWgpu::try_new_as_graphics(info, raw_handle).map(Self::Wgpu),
+            ApiType::Vulkano => DeviceVk::try_new_from_handle(info, raw_handle).map(Self::Vulkano),
+        })
+    }
+
+    // 初期化に失敗したバックエンドは飛ばして次の候補を試す
+    fn new_impl<F>(info: &DeviceInfo, create: F) -> Self
+    where
+        F: Fn(ApiType) -> Option<Self>,
+    {
+        super::get_api_type_candidates(info)
+            .into_iter()
+            .find_map(create)
+            .expect("no backend could be initialized")
+    }
+
+    pub fn get_api_type(&self) -> ApiType {
+        match self {
+            Self::Wgpu(_) => ApiType::Wgpu,
+            Self::Vulkano(_) => ApiType::Vulkano,
+        }
+    }
+
+    pub fn get_capabilities(&self) -> DeviceCapabilities {
+        match self {
+            Self::Wgpu(device) => IDevice::get_capabilities(device),
+            Self::Vulkano(device) => IDevice::get_capabilities(device),
+        }
+    }
+
+    // 最初に使えるバックエンドのアダプターを列挙する
+    pub fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        super::get_api_type_candidates(info)
+            .into_iter()
+            .map(|api_type| match api_type {
+                ApiType::Wgpu => DeviceWgpu::enumerate_adapters(info),
+                ApiType::Vulkano => DeviceVk::enumerate_adapters(info),
+            })
+            .find(|adapters| !adapters.is_empty())
+            .unwrap_or_default()
+    }
+
+    pub fn get_adapter_info(&self) -> AdapterInfo {
+        match self {
+            Self::Wgpu(device) => IDevice::get_adapter_info(device),
+            Self::Vulkano(device) => IDevice::get_adapter_info(device),
+        }
+    }
+}
+
+impl IDevice for DeviceAny {
+    fn new(info: &DeviceInfo) -> Self {
+        Self::new(info)
+    }
+
+    fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(info, raw_handle)
+    }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        Self::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.get_adapter_info()
+    }
+}
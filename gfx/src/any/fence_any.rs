@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use sjgfx_interface::{FenceInfo, IFence};
+use sjgfx_vulkano::FenceVk;
+use sjgfx_wgpu::FenceWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum FenceAny {
+    Wgpu(FenceWgpu),
+    Vulkano(FenceVk),
+}
+
+impl FenceAny {
+    pub(crate) fn as_wgpu_mut(&mut self) -> &mut FenceWgpu {
+        match self {
+            Self::Wgpu(fence) => fence,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano_mut(&mut self) -> &mut FenceVk {
+        match self {
+            Self::Vulkano(fence) => fence,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IFence for FenceAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(IFence::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(IFence::new(device, info)),
+        }
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        match self {
+            Self::Wgpu(fence) => IFence::wait(fence, timeout),
+            Self::Vulkano(fence) => IFence::wait(fence, timeout),
+        }
+    }
+
+    fn is_signaled(&self) -> bool {
+        match self {
+            Self::Wgpu(fence) => IFence::is_signaled(fence),
+            Self::Vulkano(fence) => IFence::is_signaled(fence),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::Wgpu(fence) => IFence::reset(fence),
+            Self::Vulkano(fence) => IFence::reset(fence),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+mod buffer_any;
+mod color_target_view_any;
+mod command_buffer_any;
+mod depth_stencil_view_any;
+mod device_any;
+mod fence_any;
+mod queue_any;
+mod sampler_any;
+mod semaphore_any;
+mod shader_any;
+mod swap_chain_any;
+mod texture_any;
+mod texture_view_any;
+mod vertex_state_any;
+
+pub use buffer_any::BufferAny;
+pub use color_target_view_any::ColorTargetViewAny;
+pub use command_buffer_any::CommandBufferAny;
+pub use depth_stencil_view_any::DepthStencilViewAny;
+pub use device_any::DeviceAny;
+pub use fence_any::FenceAny;
+pub use queue_any::QueueAny;
+pub use sampler_any::SamplerAny;
+pub use semaphore_any::SemaphoreAny;
+pub use shader_any::ShaderAny;
+pub use swap_chain_any::SwapChainAny;
+pub use texture_any::TextureAny;
+pub use texture_view_any::TextureViewAny;
+pub use vertex_state_any::VertexStateAny;
+
+use sjgfx_interface::{ApiType, DeviceInfo};
+
+// 配布するバイナリでバックエンドを切り替えるための環境変数
+pub const API_TYPE_ENV: &str = "SJGFX_API";
+
+// 指定がないときに試す順番
+const API_TYPES: [ApiType; 2] = [ApiType::Wgpu, ApiType::Vulkano];
+
+pub fn parse_api_type(name: &str) -> Option<ApiType> {
+    match name.to_ascii_lowercase().as_str() {
+        "wgpu" => Some(ApiType::Wgpu),
+        "vulkano" | "vulkan" => Some(ApiType::Vulkano),
+        _ => None,
+    }
+}
+
+// 環境変数、DeviceInfo の順に優先して、残りはフォールバック先として既定の順に並べる
+pub(crate) fn get_api_type_candidates(info: &DeviceInfo) -> Vec<ApiType> {
+    let preferred_api_type = std::env::var(API_TYPE_ENV)
+        .ok()
+        .and_then(|name| parse_api_type(&name))
+        .or(info.get_api_type());
+
+    let mut api_types: Vec<ApiType> = preferred_api_type.into_iter().collect();
+    api_types.extend(
+        API_TYPES
+            .iter()
+            .copied()
+            .filter(|api_type| Some(*api_type) != preferred_api_type),
+    );
+    api_types
+}
+
+// 別のバックエンドで作ったオブジェクトどうしは組み合わせられない
+pub(crate) fn panic_api_mismatch() -> ! {
+    panic!("objects created by different backends cannot be mixed")
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::{ApiType, DeviceInfo};
+
+    #[test]
+    fn parse_api_type() {
+        assert_eq!(super::parse_api_type("wgpu"), Some(ApiType::Wgpu));
+        assert_eq!(super::parse_api_type("Vulkan"), Some(ApiType::Vulkano));
+        assert_eq!(super::parse_api_type("VULKANO"), Some(ApiType::Vulkano));
+        assert_eq!(super::parse_api_type("metal"), None);
+    }
+
+    #[test]
+    fn get_api_type_candidates() {
+        // 環境変数はほかのテストと共有されるので、設定されていない場合だけ確かめる
+        if std::env::var(super::API_TYPE_ENV).is_ok() {
+            return;
+        }
+
+        assert_eq!(
+            super::get_api_type_candidates(&DeviceInfo::new()),
+            vec![ApiType::Wgpu, ApiType::Vulkano]
+        );
+        assert_eq!(
+            super::get_api_type_candidates(&DeviceInfo::new().set_api_type(ApiType::Vulkano)),
+            vec![ApiType::Vulkano, ApiType::Wgpu]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+use sjgfx_vulkano::QueueVk;
+use sjgfx_wgpu::QueueWgpu;
+
+use crate::any::{CommandBufferAny, DeviceAny, FenceAny, SemaphoreAny, SwapChainAny};
+
+pub enum QueueAny {
+    Wgpu(QueueWgpu),
+    Vulkano(QueueVk),
+}
+
+impl IQueue for QueueAny {
+    type DeviceType = DeviceAny;
+    type CommandBufferType = CommandBufferAny;
+    type FenceType = FenceAny;
+    type SemaphoreType = SemaphoreAny;
+    type SwapChainType = SwapChainAny;
+
+    fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(IQueue::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(IQueue::new(device, info)),
+        }
+    }
+
+    fn execute(&mut self, command_buffer: &Self::CommandBufferType) {
+        match self {
+            Self::Wgpu(queue) => IQueue::execute(queue, command_buffer.as_wgpu()),
+            Self::Vulkano(queue) => IQueue::execute(queue, command_buffer.as_vulkano()),
+        }
+    }
+
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        match self {
+            Self::Wgpu(queue) => {
+                let command_buffers: Vec<_> = command_buffers
+                    .iter()
+                    .map(|command_buffer| command_buffer.as_wgpu())
+                    .collect();
+                IQueue::execute_many(queue, &command_buffers)
+            }
+            Self::Vulkano(queue) => {
+                let command_buffers: Vec<_> = command_buffers
+                    .iter()
+                    .map(|command_buffer| command_buffer.as_vulkano())
+                    .collect();
+                IQueue::execute_many(queue, &command_buffers)
+            }
+        }
+    }
+
+    fn execute_with_fence(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
+    ) {
+        match self {
+            Self::Wgpu(queue) => {
+                IQueue::execute_with_fence(queue, command_buffer.as_wgpu(), fence.as_wgpu_mut())
+            }
+            Self::Vulkano(queue) => IQueue::execute_with_fence(
+                queue,
+                command_buffer.as_vulkano(),
+                fence.as_vulkano_mut(),
+            ),
+        }
+    }
+
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        match self {
+            Self::Wgpu(queue) => {
+                let wait_semaphores: Vec<_> = wait_semaphores
+                    .iter()
+                    .map(|(semaphore, value)| (semaphore.as_wgpu(), *value))
+                    .collect();
+                let signal_semaphores: Vec<_> = signal_semaphores
+                    .iter()
+                    .map(|(semaphore, value)| (semaphore.as_wgpu(), *value))
+                    .collect();
+                IQueue::execute_with_semaphores(
+                    queue,
+                    command_buffer.as_wgpu(),
+                    &wait_semaphores,
+                    &signal_semaphores,
+                )
+            }
+            Self::Vulkano(queue) => {
+                let wait_semaphores: Vec<_> = wait_semaphores
+                    .iter()
+                    .map(|(semaphore, value)| (semaphore.as_vulkano(), *value))
+                    .collect();
+                let signal_semaphores: Vec<_> = signal_semaphores
+                    .iter()
+                    .map(|(semaphore, value)| (semaphore.as_vulkano(), *value))
+                    .collect();
+                IQueue::execute_with_semaphores(
+                    queue,
+                    command_buffer.as_vulkano(),
+                    &wait_semaphores,
+                    &signal_semaphores,
+                )
+            }
+        }
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        match self {
+            Self::Wgpu(queue) => IQueue::get_queue_type(queue),
+            Self::Vulkano(queue) => IQueue::get_queue_type(queue),
+        }
+    }
+
+    fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
+        match self {
+            Self::Wgpu(queue) => IQueue::present(queue, swap_chain.as_wgpu_mut()),
+            Self::Vulkano(queue) => IQueue::present(queue, swap_chain.as_vulkano_mut()),
+        }
+    }
+
+    fn flush(&mut self) {
+        match self {
+            Self::Wgpu(queue) => IQueue::flush(queue),
+            Self::Vulkano(queue) => IQueue::flush(queue),
+        }
+    }
+
+    fn sync(&mut self) {
+        match self {
+            Self::Wgpu(queue) => IQueue::sync(queue),
+            Self::Vulkano(queue) => IQueue::sync(queue),
+        }
+    }
+}
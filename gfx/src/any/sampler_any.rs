@@ -0,0 +1,37 @@
+use sjgfx_interface::{ISampler, SamplerInfo};
+use sjgfx_vulkano::SamplerVk;
+use sjgfx_wgpu::SamplerWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum SamplerAny {
+    Wgpu(SamplerWgpu),
+    Vulkano(SamplerVk),
+}
+
+impl SamplerAny {
+    pub(crate) fn as_wgpu(&self) -> &SamplerWgpu {
+        match self {
+            Self::Wgpu(sampler) => sampler,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &SamplerVk {
+        match self {
+            Self::Vulkano(sampler) => sampler,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl ISampler for SamplerAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &mut Self::DeviceType, info: &SamplerInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(ISampler::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(ISampler::new(device, info)),
+        }
+    }
+}
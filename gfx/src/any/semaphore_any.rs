@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+use sjgfx_vulkano::SemaphoreVk;
+use sjgfx_wgpu::SemaphoreWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum SemaphoreAny {
+    Wgpu(SemaphoreWgpu),
+    Vulkano(SemaphoreVk),
+}
+
+impl SemaphoreAny {
+    pub(crate) fn as_wgpu(&self) -> &SemaphoreWgpu {
+        match self {
+            Self::Wgpu(semaphore) => semaphore,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &SemaphoreVk {
+        match self {
+            Self::Vulkano(semaphore) => semaphore,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_wgpu_mut(&mut self) -> &mut SemaphoreWgpu {
+        match self {
+            Self::Wgpu(semaphore) => semaphore,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano_mut(&mut self) -> &mut SemaphoreVk {
+        match self {
+            Self::Vulkano(semaphore) => semaphore,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl ISemaphore for SemaphoreAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(ISemaphore::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(ISemaphore::new(device, info)),
+        }
+    }
+
+    fn get_value(&self) -> u64 {
+        match self {
+            Self::Wgpu(semaphore) => ISemaphore::get_value(semaphore),
+            Self::Vulkano(semaphore) => ISemaphore::get_value(semaphore),
+        }
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        match self {
+            Self::Wgpu(semaphore) => ISemaphore::wait(semaphore, value, timeout),
+            Self::Vulkano(semaphore) => ISemaphore::wait(semaphore, value, timeout),
+        }
+    }
+
+    fn signal(&self, value: u64) {
+        match self {
+            Self::Wgpu(semaphore) => ISemaphore::signal(semaphore, value),
+            Self::Vulkano(semaphore) => ISemaphore::signal(semaphore, value),
+        }
+    }
+}
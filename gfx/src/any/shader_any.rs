@@ -0,0 +1,37 @@
+use sjgfx_interface::{IShader, ShaderInfo};
+use sjgfx_vulkano::ShaderVk;
+use sjgfx_wgpu::ShaderWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum ShaderAny {
+    Wgpu(ShaderWgpu),
+    Vulkano(ShaderVk),
+}
+
+impl ShaderAny {
+    pub(crate) fn as_wgpu(&self) -> &ShaderWgpu {
+        match self {
+            Self::Wgpu(shader) => shader,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &ShaderVk {
+        match self {
+            Self::Vulkano(shader) => shader,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IShader for ShaderAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &mut Self::DeviceType, info: &ShaderInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(IShader::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(IShader::new(device, info)),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    CompositeAlphaMode, IDisplayEventListener, ISwapChain, ImageData, ImageFormat, OutputTransform,
+    PresentMode, SwapChainInfo, SwapChainStatus,
+};
+use sjgfx_vulkano::SwapChainVk;
+use sjgfx_wgpu::SwapChainWgpu;
+
+use crate::any::{ColorTargetViewAny, DeviceAny, FenceAny, SemaphoreAny};
+
+enum SwapChainImpl {
+    Wgpu(SwapChainWgpu),
+    Vulkano(SwapChainVk),
+}
+
+pub struct SwapChainAny {
+    swap_chain: SwapChainImpl,
+
+    // 各バックエンドが返すビューは参照なので、複製して包んだものを返す
+    scan_buffer_view: Option<ColorTargetViewAny>,
+}
+
+impl SwapChainAny {
+    pub fn new(device: &mut DeviceAny, info: &SwapChainInfo) -> Self {
+        let swap_chain = match device {
+            DeviceAny::Wgpu(device) => SwapChainImpl::Wgpu(ISwapChain::new(device, info)),
+            DeviceAny::Vulkano(device) => SwapChainImpl::Vulkano(ISwapChain::new(device, info)),
+        };
+        Self::new_impl(swap_chain)
+    }
+
+    pub fn new_with_handle<T>(device: &mut DeviceAny, info: &SwapChainInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let swap_chain = match device {
+            DeviceAny::Wgpu(device) => {
+                SwapChainImpl::Wgpu(ISwapChain::new_with_handle(device, info, raw_handle))
+            }
+            DeviceAny::Vulkano(device) => {
+                SwapChainImpl::Vulkano(ISwapChain::new_with_handle(device, info, raw_handle))
+            }
+        };
+        Self::new_impl(swap_chain)
+    }
+
+    fn new_impl(swap_chain: SwapChainImpl) -> Self {
+        Self {
+            swap_chain,
+            scan_buffer_view: None,
+        }
+    }
+
+    // 表示したスキャンバッファのビューを持ち続けないように、表示の前に手放す
+    pub(crate) fn as_wgpu_mut(&mut self) -> &mut SwapChainWgpu {
+        self.scan_buffer_view = None;
+        match &mut self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => swap_chain,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano_mut(&mut self) -> &mut SwapChainVk {
+        self.scan_buffer_view = None;
+        match &mut self.swap_chain {
+            SwapChainImpl::Vulkano(swap_chain) => swap_chain,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut SemaphoreAny>,
+        fence: Option<&mut FenceAny>,
+    ) -> &mut ColorTargetViewAny {
+        let scan_buffer_view = match &mut self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ColorTargetViewAny::Wgpu(
+                ISwapChain::acquire_next_scan_buffer_view(
+                    swap_chain,
+                    semaphore.map(SemaphoreAny::as_wgpu_mut),
+                    fence.map(FenceAny::as_wgpu_mut),
+                )
+                .clone(),
+            ),
+            SwapChainImpl::Vulkano(swap_chain) => ColorTargetViewAny::Vulkano(
+                ISwapChain::acquire_next_scan_buffer_view(
+                    swap_chain,
+                    semaphore.map(SemaphoreAny::as_vulkano_mut),
+                    fence.map(FenceAny::as_vulkano_mut),
+                )
+                .clone(),
+            ),
+        };
+        self.scan_buffer_view.insert(scan_buffer_view)
+    }
+}
+
+impl ISwapChain for SwapChainAny {
+    type ColorTargetViewType = ColorTargetViewAny;
+    type DeviceType = DeviceAny;
+    type SemaphoreType = SemaphoreAny;
+    type FenceType = FenceAny;
+
+    fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self {
+        Self::new(device, info)
+    }
+
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_with_handle(device, info, raw_handle)
+    }
+
+    fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
+    ) -> &mut Self::ColorTargetViewType {
+        self.acquire_next_scan_buffer_view(semaphore, fence)
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_present_mode(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_present_mode(swap_chain),
+        }
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_format(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_format(swap_chain),
+        }
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_alpha_mode(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_alpha_mode(swap_chain),
+        }
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_frame_latency(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_frame_latency(swap_chain),
+        }
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::is_direct_rendering_enabled(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => {
+                ISwapChain::is_direct_rendering_enabled(swap_chain)
+            }
+        }
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_status(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_status(swap_chain),
+        }
+    }
+
+    fn get_render_width(&self) -> u32 {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_render_width(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_render_width(swap_chain),
+        }
+    }
+
+    fn get_render_height(&self) -> u32 {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::get_render_height(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::get_render_height(swap_chain),
+        }
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        match &mut self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => {
+                ISwapChain::set_render_scale(swap_chain, render_scale)
+            }
+            SwapChainImpl::Vulkano(swap_chain) => {
+                ISwapChain::set_render_scale(swap_chain, render_scale)
+            }
+        }
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        match &mut self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => {
+                ISwapChain::set_output_transform(swap_chain, output_transform)
+            }
+            SwapChainImpl::Vulkano(swap_chain) => {
+                ISwapChain::set_output_transform(swap_chain, output_transform)
+            }
+        }
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        match &self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => ISwapChain::read_presented_image(swap_chain),
+            SwapChainImpl::Vulkano(swap_chain) => ISwapChain::read_presented_image(swap_chain),
+        }
+    }
+}
+
+impl IDisplayEventListener for SwapChainAny {
+    fn on_resized(&mut self, width: u32, height: u32) {
+        self.scan_buffer_view = None;
+        match &mut self.swap_chain {
+            SwapChainImpl::Wgpu(swap_chain) => {
+                IDisplayEventListener::on_resized(swap_chain, width, height)
+            }
+            SwapChainImpl::Vulkano(swap_chain) => {
+                IDisplayEventListener::on_resized(swap_chain, width, height)
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use sjgfx_interface::{ITexture, ImageData, TextureCopyRegion, TextureInfo};
+use sjgfx_vulkano::TextureVk;
+use sjgfx_wgpu::TextureWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum TextureAny {
+    Wgpu(TextureWgpu),
+    Vulkano(TextureVk),
+}
+
+impl TextureAny {
+    pub(crate) fn as_wgpu(&self) -> &TextureWgpu {
+        match self {
+            Self::Wgpu(texture) => texture,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &TextureVk {
+        match self {
+            Self::Vulkano(texture) => texture,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl ITexture for TextureAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(ITexture::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(ITexture::new(device, info)),
+        }
+    }
+
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(ITexture::new_with_data(device, info, data)),
+            DeviceAny::Vulkano(device) => {
+                Self::Vulkano(ITexture::new_with_data(device, info, data))
+            }
+        }
+    }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        match self {
+            Self::Wgpu(texture) => {
+                ITexture::write(texture, region, data, bytes_per_row, rows_per_image)
+            }
+            Self::Vulkano(texture) => {
+                ITexture::write(texture, region, data, bytes_per_row, rows_per_image)
+            }
+        }
+    }
+
+    fn read_image(&self) -> ImageData {
+        match self {
+            Self::Wgpu(texture) => ITexture::read_image(texture),
+            Self::Vulkano(texture) => ITexture::read_image(texture),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use sjgfx_interface::{ITextureView, TextureViewInfo};
+use sjgfx_vulkano::TextureViewVk;
+use sjgfx_wgpu::TextureViewWgpu;
+
+use crate::any::{DeviceAny, TextureAny};
+
+pub enum TextureViewAny {
+    Wgpu(TextureViewWgpu),
+    Vulkano(TextureViewVk),
+}
+
+impl TextureViewAny {
+    pub(crate) fn as_wgpu(&self) -> &TextureViewWgpu {
+        match self {
+            Self::Wgpu(texture_view) => texture_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &TextureViewVk {
+        match self {
+            Self::Vulkano(texture_view) => texture_view,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl ITextureView for TextureViewAny {
+    type DeviceType = DeviceAny;
+    type TextureType = TextureAny;
+
+    fn new(device: &Self::DeviceType, info: &TextureViewInfo, texture: &Self::TextureType) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => {
+                Self::Wgpu(ITextureView::new(device, info, texture.as_wgpu()))
+            }
+            DeviceAny::Vulkano(device) => {
+                Self::Vulkano(ITextureView::new(device, info, texture.as_vulkano()))
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use sjgfx_interface::{IVertexState, VertexStateInfo};
+use sjgfx_vulkano::VertexStateVk;
+use sjgfx_wgpu::VertexStateWgpu;
+
+use crate::any::DeviceAny;
+
+pub enum VertexStateAny {
+    Wgpu(VertexStateWgpu),
+    Vulkano(VertexStateVk),
+}
+
+impl VertexStateAny {
+    pub(crate) fn as_wgpu(&self) -> &VertexStateWgpu {
+        match self {
+            Self::Wgpu(vertex_state) => vertex_state,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+
+    pub(crate) fn as_vulkano(&self) -> &VertexStateVk {
+        match self {
+            Self::Vulkano(vertex_state) => vertex_state,
+            _ => super::panic_api_mismatch(),
+        }
+    }
+}
+
+impl IVertexState for VertexStateAny {
+    type DeviceType = DeviceAny;
+
+    fn new(device: &Self::DeviceType, info: &VertexStateInfo) -> Self {
+        match device {
+            DeviceAny::Wgpu(device) => Self::Wgpu(IVertexState::new(device, info)),
+            DeviceAny::Vulkano(device) => Self::Vulkano(IVertexState::new(device, info)),
+        }
+    }
+}
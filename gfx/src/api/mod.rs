@@ -1,7 +1,19 @@
+use std::marker::PhantomData;
+
+use sjgfx_ash::{
+    BufferAsh, ColorTargetViewAsh, CommandBufferAsh, DepthStencilViewAsh, DeviceAsh, FenceAsh,
+    QueueAsh, SamplerAsh, SemaphoreAsh, ShaderAsh, SwapChainAsh, TextureAsh, TextureViewAsh,
+    VertexStateAsh,
+};
 use sjgfx_interface::{
     IBuffer, IColorTargetView, ICommandBuffer, IDepthStencilView, IDevice, IFence, IQueue,
     ISampler, ISemaphore, IShader, ISwapChain, ITexture, ITextureView, IVertexState,
 };
+use sjgfx_null::{
+    BufferNull, ColorTargetViewNull, CommandBufferNull, DepthStencilViewNull, DeviceNull,
+    FenceNull, QueueNull, SamplerNull, SemaphoreNull, ShaderNull, SwapChainNull, TextureNull,
+    TextureViewNull, VertexStateNull,
+};
 use sjgfx_vulkano::{
     BufferVk, ColorTargetViewVk, CommandBufferVk, DepthStencilViewVk, DeviceVk, FenceVk, QueueVk,
     SamplerVk, SemaphoreVk, ShaderVk, SwapChainVk, TextureViewVk, TextureVk, VertexStateVk,
@@ -12,6 +24,25 @@ use sjgfx_wgpu::{
     TextureWgpu, VertexStateWgpu,
 };
 
+use crate::{
+    any::{
+        BufferAny, ColorTargetViewAny, CommandBufferAny, DepthStencilViewAny, DeviceAny, FenceAny,
+        QueueAny, SamplerAny, SemaphoreAny, ShaderAny, SwapChainAny, TextureAny, TextureViewAny,
+        VertexStateAny,
+    },
+    capture::{
+        BufferCapture, ColorTargetViewCapture, CommandBufferCapture, DepthStencilViewCapture,
+        DeviceCapture, FenceCapture, QueueCapture, SamplerCapture, SemaphoreCapture, ShaderCapture,
+        SwapChainCapture, TextureCapture, TextureViewCapture, VertexStateCapture,
+    },
+    validation::{
+        BufferValidation, ColorTargetViewValidation, CommandBufferValidation,
+        DepthStencilViewValidation, DeviceValidation, FenceValidation, QueueValidation,
+        SamplerValidation, SemaphoreValidation, ShaderValidation, SwapChainValidation,
+        TextureValidation, TextureViewValidation, VertexStateValidation,
+    },
+};
+
 pub trait IApi {
     type Buffer: IBuffer<DeviceType = Self::Device>;
     type ColorTargetView: IColorTargetView<DeviceType = Self::Device, TextureType = Self::Texture>;
@@ -20,6 +51,8 @@ pub trait IApi {
     type Queue: IQueue<
         DeviceType = Self::Device,
         CommandBufferType = Self::CommandBuffer,
+        FenceType = Self::Fence,
+        SemaphoreType = Self::Semaphore,
         SwapChainType = Self::SwapChain,
     >;
     type CommandBuffer: ICommandBuffer<
@@ -42,6 +75,7 @@ pub trait IApi {
     type SwapChain: ISwapChain<
         DeviceType = Self::Device,
         SemaphoreType = Self::Semaphore,
+        FenceType = Self::Fence,
         ColorTargetViewType = Self::ColorTargetView,
     >;
     type VertexState: IVertexState<DeviceType = Self::Device>;
@@ -65,6 +99,43 @@ impl IApi for Wgpu {
     type VertexState = VertexStateWgpu;
 }
 
+pub struct Ash;
+impl IApi for Ash {
+    type Buffer = BufferAsh;
+    type ColorTargetView = ColorTargetViewAsh;
+    type DepthStencilView = DepthStencilViewAsh;
+    type Device = DeviceAsh;
+    type Queue = QueueAsh;
+    type CommandBuffer = CommandBufferAsh;
+    type Fence = FenceAsh;
+    type Sampler = SamplerAsh;
+    type Shader = ShaderAsh;
+    type Semaphore = SemaphoreAsh;
+    type SwapChain = SwapChainAsh;
+    type Texture = TextureAsh;
+    type TextureView = TextureViewAsh;
+    type VertexState = VertexStateAsh;
+}
+
+// GPU を使わずに CPU だけで動く。描画とディスパッチは記録するだけで実行しない
+pub struct Null;
+impl IApi for Null {
+    type Buffer = BufferNull;
+    type ColorTargetView = ColorTargetViewNull;
+    type DepthStencilView = DepthStencilViewNull;
+    type Device = DeviceNull;
+    type Queue = QueueNull;
+    type CommandBuffer = CommandBufferNull;
+    type Fence = FenceNull;
+    type Sampler = SamplerNull;
+    type Shader = ShaderNull;
+    type Semaphore = SemaphoreNull;
+    type SwapChain = SwapChainNull;
+    type Texture = TextureNull;
+    type TextureView = TextureViewNull;
+    type VertexState = VertexStateNull;
+}
+
 pub struct Vulkano;
 impl IApi for Vulkano {
     type Buffer = BufferVk;
@@ -82,3 +153,68 @@ impl IApi for Vulkano {
     type SwapChain = SwapChainVk;
     type VertexState = VertexStateVk;
 }
+
+// 実行時に DeviceInfo か環境変数でバックエンドを選ぶ
+pub struct AnyApi;
+impl IApi for AnyApi {
+    type Buffer = BufferAny;
+    type ColorTargetView = ColorTargetViewAny;
+    type DepthStencilView = DepthStencilViewAny;
+    type Device = DeviceAny;
+    type Queue = QueueAny;
+    type CommandBuffer = CommandBufferAny;
+    type Fence = FenceAny;
+    type Sampler = SamplerAny;
+    type Shader = ShaderAny;
+    type Semaphore = SemaphoreAny;
+    type SwapChain = SwapChainAny;
+    type Texture = TextureAny;
+    type TextureView = TextureViewAny;
+    type VertexState = VertexStateAny;
+}
+
+// 任意のバックエンドを包んで使い方の誤りを検証する
+// 誤りは DeviceInfo の DebugMode が FullAssertion なら panic し、Full なら標準エラーに出す
+pub struct ValidationApi<TApi>(PhantomData<TApi>);
+impl<TApi: IApi> IApi for ValidationApi<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type Buffer = BufferValidation<TApi>;
+    type ColorTargetView = ColorTargetViewValidation<TApi>;
+    type DepthStencilView = DepthStencilViewValidation<TApi>;
+    type Device = DeviceValidation<TApi>;
+    type Queue = QueueValidation<TApi>;
+    type CommandBuffer = CommandBufferValidation<TApi>;
+    type Fence = FenceValidation<TApi>;
+    type Sampler = SamplerValidation<TApi>;
+    type Shader = ShaderValidation<TApi>;
+    type Semaphore = SemaphoreValidation<TApi>;
+    type SwapChain = SwapChainValidation<TApi>;
+    type Texture = TextureValidation<TApi>;
+    type TextureView = TextureViewValidation<TApi>;
+    type VertexState = VertexStateValidation<TApi>;
+}
+
+// 任意のバックエンドを包んで、オブジェクトの生成とコマンドをキャプチャーとして記録する
+// 記録したものは DeviceCapture::save_capture で保存して TCaptureReplayer で再生できる
+pub struct CaptureApi<TApi>(PhantomData<TApi>);
+impl<TApi: IApi> IApi for CaptureApi<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type Buffer = BufferCapture<TApi>;
+    type ColorTargetView = ColorTargetViewCapture<TApi>;
+    type DepthStencilView = DepthStencilViewCapture<TApi>;
+    type Device = DeviceCapture<TApi>;
+    type Queue = QueueCapture<TApi>;
+    type CommandBuffer = CommandBufferCapture<TApi>;
+    type Fence = FenceCapture<TApi>;
+    type Sampler = SamplerCapture<TApi>;
+    type Shader = ShaderCapture<TApi>;
+    type Semaphore = SemaphoreCapture<TApi>;
+    type SwapChain = SwapChainCapture<TApi>;
+    type Texture = TextureCapture<TApi>;
+    type TextureView = TextureViewCapture<TApi>;
+    type VertexState = VertexStateCapture<TApi>;
+}
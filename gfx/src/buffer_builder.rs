@@ -1,4 +1,4 @@
-use sjgfx_interface::{BufferInfo, GpuAccess, IBuffer};
+use sjgfx_interface::{BufferInfo, CpuAccessMode, GpuAccess, IBuffer};
 
 use crate::api::IApi;
 
@@ -15,6 +15,7 @@ impl<T: IApi> TBufferBuilder<T> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut T::Device) -> T::Buffer {
         T::Buffer::new(device, &self.info)
     }
@@ -26,6 +27,20 @@ impl<T: IApi> TBufferBuilder<T> {
         }
     }
 
+    pub fn with_debug_label(self, debug_label: &str) -> Self {
+        Self {
+            info: self.info.set_debug_label(debug_label),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_cpu_access_mode(self, cpu_access_mode: CpuAccessMode) -> Self {
+        Self {
+            info: self.info.set_cpu_access_mode(cpu_access_mode),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
     pub fn enable_vertex_buffer(self) -> Self {
         self.enable_flag(GpuAccess::VERTEX_BUFFER)
     }
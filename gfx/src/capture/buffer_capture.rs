@@ -0,0 +1,110 @@
+use std::ops::Range;
+
+use sjgfx_interface::{BufferInfo, IBuffer};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, CaptureData, DeviceCapture, ObjectId, Recorder},
+};
+
+pub struct BufferCapture<TApi: IApi> {
+    buffer: TApi::Buffer,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> BufferCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Buffer {
+        &self.buffer
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+
+    // CPU から書き込んだ範囲を書き込み終わった内容で記録する
+    fn record_write(&self, offset: usize, data: Vec<u8>) {
+        self.recorder.record(CaptureCommand::WriteBuffer {
+            buffer: self.id,
+            offset: offset as u64,
+            data: CaptureData::new(data),
+        });
+    }
+}
+
+impl<TApi: IApi> IBuffer for BufferCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateBuffer {
+            id,
+            size: info.get_size() as u64,
+            gpu_access: info.get_gpu_access_flags(),
+            cpu_access_mode: info.get_cpu_access_mode(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            buffer: TApi::Buffer::new(device.as_inner_mut(), info),
+            id,
+            recorder,
+        }
+    }
+
+    fn map<T: bytemuck::Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.buffer.map(func);
+    }
+
+    fn map_as_slice<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.buffer.map_as_slice(func);
+    }
+
+    fn map_range<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.buffer.map_range(range, func);
+    }
+
+    fn map_mut<T: bytemuck::Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        let mut data = Vec::new();
+        self.buffer.map_mut(|x: &mut T| {
+            func(x);
+            data = bytemuck::bytes_of(x).to_vec();
+        });
+        self.record_write(0, data);
+    }
+
+    fn map_as_slice_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        let mut data = Vec::new();
+        self.buffer.map_as_slice_mut(|x: &mut [T]| {
+            func(x);
+            data = bytemuck::cast_slice(x).to_vec();
+        });
+        self.record_write(0, data);
+    }
+
+    fn map_range_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        let offset = range.start;
+        let mut data = Vec::new();
+        self.buffer.map_range_mut(range, |x: &mut [T]| {
+            func(x);
+            data = bytemuck::cast_slice(x).to_vec();
+        });
+        self.record_write(offset, data);
+    }
+
+    fn flush_mapped_range(&self, offset: isize, size: usize) {
+        self.buffer.flush_mapped_range(offset, size);
+    }
+
+    fn invalidate_mapped_range(&self, offset: isize, size: usize) {
+        self.buffer.invalidate_mapped_range(offset, size);
+    }
+}
+
+impl<TApi: IApi> Drop for BufferCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,441 @@
+use std::fmt;
+
+use sjgfx_interface::{
+    AttributeFormat, CompositeAlphaMode, CpuAccessMode, DebugMode, GpuAccess, ImageFormat,
+    IndexFormat, OutputTransform, PresentMode, PrimitiveTopology, QueueType, ScalingMode,
+    ScissorStateInfo, TextureCopyRegion, TextureSubresource, UpscaleFilter,
+    VertexAttributeStateInfo, VertexBufferStateInfo,
+};
+
+// キャプチャーの中でオブジェクトを識別する番号。作られた順に振られる
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId(u32);
+
+impl ObjectId {
+    pub fn new(value: u32) -> Self {
+        Self(value)
+    }
+
+    pub fn get_value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Debug for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// バッファーやシェーダーの中身。テキストに書き出すときは大きさだけ出す
+#[derive(Clone, PartialEq)]
+pub struct CaptureData(Vec<u8>);
+
+impl CaptureData {
+    pub fn new(data: Vec<u8>) -> Self {
+        Self(data)
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for CaptureData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes>", self.0.len())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptureTextureRegion {
+    pub offset_u: i32,
+    pub offset_v: i32,
+    pub offset_w: i32,
+    pub width: i32,
+    pub height: i32,
+    pub depth: i32,
+    pub array_length: i32,
+    pub mip_level: i32,
+    pub array_index: i32,
+}
+
+impl CaptureTextureRegion {
+    pub fn from_region(region: &TextureCopyRegion) -> Self {
+        Self {
+            offset_u: region.get_offset_u(),
+            offset_v: region.get_offset_v(),
+            offset_w: region.get_offset_w(),
+            width: region.get_width(),
+            height: region.get_height(),
+            depth: region.get_depth(),
+            array_length: region.get_array_length(),
+            mip_level: region.get_texture_subresource().get_mip_level(),
+            array_index: region.get_texture_subresource().get_array_index(),
+        }
+    }
+
+    pub fn to_region(&self) -> TextureCopyRegion {
+        TextureCopyRegion::new()
+            .set_offset_u(self.offset_u)
+            .set_offset_v(self.offset_v)
+            .set_offset_w(self.offset_w)
+            .set_width(self.width)
+            .set_height(self.height)
+            .set_depth(self.depth)
+            .set_array_length(self.array_length)
+            .set_texture_subresource(
+                TextureSubresource::new()
+                    .set_mip_level(self.mip_level)
+                    .set_array_index(self.array_index),
+            )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptureVertexAttribute {
+    pub format: AttributeFormat,
+    pub slot: i32,
+    pub buffer_index: i32,
+    pub offset: i64,
+}
+
+impl CaptureVertexAttribute {
+    pub fn from_info(info: &VertexAttributeStateInfo) -> Self {
+        Self {
+            format: info.get_format().clone(),
+            slot: info.get_slot(),
+            buffer_index: info.get_buffer_index(),
+            offset: info.get_offset(),
+        }
+    }
+
+    pub fn to_info(&self) -> VertexAttributeStateInfo {
+        VertexAttributeStateInfo::new()
+            .set_format(self.format.clone())
+            .set_slot(self.slot)
+            .set_buffer_index(self.buffer_index)
+            .set_offset(self.offset)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptureVertexBuffer {
+    pub stride: i64,
+    pub divisor: i64,
+}
+
+impl CaptureVertexBuffer {
+    pub fn from_info(info: &VertexBufferStateInfo) -> Self {
+        Self {
+            stride: info.get_stride(),
+            divisor: info.get_divisor(),
+        }
+    }
+
+    pub fn to_info(&self) -> VertexBufferStateInfo {
+        VertexBufferStateInfo::new()
+            .set_stride(self.stride)
+            .set_divisor(self.divisor)
+    }
+}
+
+// 記録した API の呼び出し。キャプチャーファイルにはこれが呼ばれた順に並ぶ
+// 読み戻しのように GPU の状態を変えない呼び出しは記録しない
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaptureCommand {
+    // 生成
+    CreateDevice {
+        debug_mode: DebugMode,
+    },
+    CreateQueue {
+        id: ObjectId,
+        queue_type: QueueType,
+    },
+    CreateCommandBuffer {
+        id: ObjectId,
+        debug_label: Option<String>,
+    },
+    CreateBuffer {
+        id: ObjectId,
+        size: u64,
+        gpu_access: GpuAccess,
+        cpu_access_mode: CpuAccessMode,
+        debug_label: Option<String>,
+    },
+    CreateTexture {
+        id: ObjectId,
+        width: i32,
+        height: i32,
+        depth: i32,
+        mip_count: i32,
+        gpu_access: GpuAccess,
+        image_format: ImageFormat,
+        debug_label: Option<String>,
+        data: Option<CaptureData>,
+    },
+    CreateTextureView {
+        id: ObjectId,
+        texture: ObjectId,
+        image_format: ImageFormat,
+        debug_label: Option<String>,
+    },
+    CreateColorTargetView {
+        id: ObjectId,
+        texture: ObjectId,
+        image_format: ImageFormat,
+        debug_label: Option<String>,
+    },
+    CreateDepthStencilView {
+        id: ObjectId,
+        texture: ObjectId,
+        is_depth_test_enabled: bool,
+        is_depth_write_enabled: bool,
+    },
+    CreateSampler {
+        id: ObjectId,
+        debug_label: Option<String>,
+    },
+    CreateShader {
+        id: ObjectId,
+        compute_shader_binary: Option<CaptureData>,
+        vertex_shader_binary: Option<CaptureData>,
+        pixel_shader_binary: Option<CaptureData>,
+        vertex_shader_source: Option<String>,
+        pixel_shader_source: Option<String>,
+        debug_label: Option<String>,
+    },
+    CreateVertexState {
+        id: ObjectId,
+        attributes: Vec<CaptureVertexAttribute>,
+        buffers: Vec<CaptureVertexBuffer>,
+        debug_label: Option<String>,
+    },
+    CreateFence {
+        id: ObjectId,
+        is_signaled: bool,
+        debug_label: Option<String>,
+    },
+    CreateSemaphore {
+        id: ObjectId,
+        initial_value: u64,
+        debug_label: Option<String>,
+    },
+    // ウィンドウに表示していたものも再生するときはオフスクリーンで作る
+    CreateSwapChain {
+        id: ObjectId,
+        width: u32,
+        height: u32,
+        present_mode: PresentMode,
+        preferred_formats: Vec<ImageFormat>,
+        alpha_mode: CompositeAlphaMode,
+        frame_latency: u32,
+        is_direct_rendering_enabled: bool,
+        render_scale: f32,
+        render_size: Option<(u32, u32)>,
+        scaling_mode: ScalingMode,
+        upscale_filter: UpscaleFilter,
+        render_format: ImageFormat,
+        output_transform: OutputTransform,
+    },
+
+    // CPU からの書き込み
+    WriteBuffer {
+        buffer: ObjectId,
+        offset: u64,
+        data: CaptureData,
+    },
+    WriteTexture {
+        texture: ObjectId,
+        region: CaptureTextureRegion,
+        data: CaptureData,
+        bytes_per_row: u64,
+        rows_per_image: u64,
+    },
+
+    // コマンドの記録
+    Begin {
+        command_buffer: ObjectId,
+    },
+    End {
+        command_buffer: ObjectId,
+    },
+    PushDebugGroup {
+        command_buffer: ObjectId,
+        label: String,
+    },
+    PopDebugGroup {
+        command_buffer: ObjectId,
+    },
+    InsertDebugMarker {
+        command_buffer: ObjectId,
+        label: String,
+    },
+    ClearColor {
+        command_buffer: ObjectId,
+        color_target_view: ObjectId,
+        color: [f32; 4],
+        base_index: i32,
+        length: i32,
+    },
+    SetRenderTargets {
+        command_buffer: ObjectId,
+        color_target_views: Vec<ObjectId>,
+        depth_stencil_view: Option<ObjectId>,
+    },
+    SetShader {
+        command_buffer: ObjectId,
+        shader: ObjectId,
+    },
+    SetSampler {
+        command_buffer: ObjectId,
+        index: i32,
+        sampler: ObjectId,
+    },
+    SetTexture {
+        command_buffer: ObjectId,
+        index: i32,
+        texture_view: ObjectId,
+    },
+    SetImage {
+        command_buffer: ObjectId,
+        index: i32,
+        texture_view: ObjectId,
+    },
+    // range は (offset, size)。None のときはバッファー全体
+    SetConstantBuffer {
+        command_buffer: ObjectId,
+        index: i32,
+        buffer: ObjectId,
+        range: Option<(u64, u64)>,
+    },
+    SetUnorderedAccessBuffer {
+        command_buffer: ObjectId,
+        index: i32,
+        buffer: ObjectId,
+    },
+    SetVertexBuffer {
+        command_buffer: ObjectId,
+        index: i32,
+        buffer: ObjectId,
+        range: Option<(u64, u64)>,
+    },
+    SetVertexState {
+        command_buffer: ObjectId,
+        vertex_state: ObjectId,
+    },
+    SetScissor {
+        command_buffer: ObjectId,
+        scissor: ScissorStateInfo,
+    },
+    Dispatch {
+        command_buffer: ObjectId,
+        count: [i32; 3],
+    },
+    Draw {
+        command_buffer: ObjectId,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    },
+    DrawInstanced {
+        command_buffer: ObjectId,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    },
+    DrawIndexed {
+        command_buffer: ObjectId,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: ObjectId,
+        index_count: i32,
+        base_vertex: i32,
+    },
+    DrawIndexedInstanced {
+        command_buffer: ObjectId,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: ObjectId,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    },
+
+    // サブミット
+    Execute {
+        queue: ObjectId,
+        command_buffer: ObjectId,
+    },
+    ExecuteMany {
+        queue: ObjectId,
+        command_buffers: Vec<ObjectId>,
+    },
+    ExecuteWithFence {
+        queue: ObjectId,
+        command_buffer: ObjectId,
+        fence: ObjectId,
+    },
+    ExecuteWithSemaphores {
+        queue: ObjectId,
+        command_buffer: ObjectId,
+        wait_semaphores: Vec<(ObjectId, u64)>,
+        signal_semaphores: Vec<(ObjectId, u64)>,
+    },
+    Present {
+        queue: ObjectId,
+        swap_chain: ObjectId,
+    },
+    Flush {
+        queue: ObjectId,
+    },
+    Sync {
+        queue: ObjectId,
+    },
+
+    // スワップチェーン。取得したスキャンバッファには新しい番号を振る
+    AcquireNextScanBufferView {
+        swap_chain: ObjectId,
+        color_target_view: ObjectId,
+        semaphore: Option<ObjectId>,
+        fence: Option<ObjectId>,
+    },
+    SetRenderScale {
+        swap_chain: ObjectId,
+        render_scale: f32,
+    },
+    SetOutputTransform {
+        swap_chain: ObjectId,
+        output_transform: OutputTransform,
+    },
+    Resize {
+        swap_chain: ObjectId,
+        width: u32,
+        height: u32,
+    },
+
+    // CPU での同期。timeout はナノ秒
+    WaitFence {
+        fence: ObjectId,
+        timeout: Option<u64>,
+    },
+    ResetFence {
+        fence: ObjectId,
+    },
+    WaitSemaphore {
+        semaphore: ObjectId,
+        value: u64,
+        timeout: Option<u64>,
+    },
+    SignalSemaphore {
+        semaphore: ObjectId,
+        value: u64,
+    },
+    // 破棄。再生するときはここでオブジェクトを手放す
+    Destroy {
+        id: ObjectId,
+    },
+}
@@ -0,0 +1,1468 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use sjgfx_interface::{
+    AttributeFormat, CompositeAlphaMode, CpuAccessMode, DebugMode, GpuAccess, ImageFormat,
+    IndexFormat, OutputEncoding, OutputTransform, PresentMode, PrimitiveTopology, QueueType,
+    ScalingMode, ScissorStateInfo, Tonemap, UpscaleFilter,
+};
+
+use crate::capture::{
+    CaptureCommand, CaptureData, CaptureTextureRegion, CaptureVertexAttribute, CaptureVertexBuffer,
+    ObjectId,
+};
+
+// 互換性のない変更をしたら上げる。読み込むときはバージョンが一致しないものを拒否する
+pub const CAPTURE_VERSION: u32 = 1;
+
+const CAPTURE_MAGIC: &[u8; 8] = b"SJGFXCAP";
+
+// キャプチャーファイルの中身
+// ファイルはマジック、バージョン、コマンドの数、コマンドの順に並ぶ。数値はすべてリトルエンディアン
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capture {
+    commands: Vec<CaptureCommand>,
+}
+
+impl Capture {
+    pub fn new(commands: Vec<CaptureCommand>) -> Self {
+        Self { commands }
+    }
+
+    pub fn get_commands(&self) -> &[CaptureCommand] {
+        &self.commands
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut capture_writer = CaptureWriter::new();
+        capture_writer.write_bytes(CAPTURE_MAGIC);
+        capture_writer.write(&CAPTURE_VERSION);
+        capture_writer.write(&(self.commands.len() as u64));
+        for command in &self.commands {
+            capture_writer.write(command);
+        }
+        writer.write_all(&capture_writer.data)
+    }
+
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut capture_reader = CaptureReader::new(&data);
+        if capture_reader.read_bytes(CAPTURE_MAGIC.len())? != CAPTURE_MAGIC {
+            return Err(invalid_data("not a capture file".to_string()));
+        }
+        let version: u32 = capture_reader.read()?;
+        if version != CAPTURE_VERSION {
+            return Err(invalid_data(format!(
+                "capture version {} is not supported (expected {})",
+                version, CAPTURE_VERSION
+            )));
+        }
+        let command_count: u64 = capture_reader.read()?;
+        let mut commands = Vec::new();
+        for _ in 0..command_count {
+            commands.push(capture_reader.read()?);
+        }
+        if !capture_reader.is_empty() {
+            return Err(invalid_data(
+                "trailing data after the last command".to_string(),
+            ));
+        }
+        Ok(Self { commands })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    // 1 行に 1 コマンドずつ書き出す。バッファーなどの中身は大きさだけ出す
+    pub fn write_text<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "capture version {}", CAPTURE_VERSION)?;
+        for (index, command) in self.commands.iter().enumerate() {
+            writeln!(writer, "{:6} {:?}", index, command)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+struct CaptureWriter {
+    data: Vec<u8>,
+}
+
+impl CaptureWriter {
+    fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    fn write<T: CaptureValue>(&mut self, value: &T) {
+        value.write(self);
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+}
+
+struct CaptureReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> CaptureReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn read<T: CaptureValue>(&mut self) -> io::Result<T> {
+        T::read(self)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if self.data.len() < length {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "capture file is truncated",
+            ));
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    // 壊れたファイルで巨大な確保をしないように、残りのデータに収まらない長さは拒否する
+    fn read_length(&mut self) -> io::Result<usize> {
+        let length: u64 = self.read()?;
+        if length > self.data.len() as u64 {
+            return Err(invalid_data(format!("length {} is too large", length)));
+        }
+        Ok(length as usize)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+trait CaptureValue: Sized {
+    fn write(&self, writer: &mut CaptureWriter);
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self>;
+}
+
+impl CaptureValue for u8 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&[*self]);
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(reader.read_array::<1>()?[0])
+    }
+}
+
+impl CaptureValue for bool {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&(*self as u8));
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        match reader.read::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(invalid_data(format!("invalid bool {}", value))),
+        }
+    }
+}
+
+impl CaptureValue for u16 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for u32 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for i32 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for u64 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for i64 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for f32 {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write_bytes(&self.to_le_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self::from_le_bytes(reader.read_array()?))
+    }
+}
+
+impl CaptureValue for String {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&(self.len() as u64));
+        writer.write_bytes(self.as_bytes());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let length = reader.read_length()?;
+        String::from_utf8(reader.read_bytes(length)?.to_vec())
+            .map_err(|error| invalid_data(error.to_string()))
+    }
+}
+
+impl CaptureValue for CaptureData {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&(self.get_data().len() as u64));
+        writer.write_bytes(self.get_data());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let length = reader.read_length()?;
+        Ok(CaptureData::new(reader.read_bytes(length)?.to_vec()))
+    }
+}
+
+impl CaptureValue for ObjectId {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.get_value());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(ObjectId::new(reader.read()?))
+    }
+}
+
+impl<T: CaptureValue> CaptureValue for Option<T> {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.is_some());
+        if let Some(value) = self {
+            writer.write(value);
+        }
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        if reader.read::<bool>()? {
+            Ok(Some(reader.read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: CaptureValue> CaptureValue for Vec<T> {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&(self.len() as u64));
+        for value in self {
+            writer.write(value);
+        }
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let length = reader.read_length()?;
+        (0..length).map(|_| reader.read()).collect()
+    }
+}
+
+impl<T0: CaptureValue, T1: CaptureValue> CaptureValue for (T0, T1) {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.0);
+        writer.write(&self.1);
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok((reader.read()?, reader.read()?))
+    }
+}
+
+impl<T: CaptureValue + Copy + Default, const N: usize> CaptureValue for [T; N] {
+    fn write(&self, writer: &mut CaptureWriter) {
+        for value in self {
+            writer.write(value);
+        }
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let mut values = [T::default(); N];
+        for value in &mut values {
+            *value = reader.read()?;
+        }
+        Ok(values)
+    }
+}
+
+impl CaptureValue for GpuAccess {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.bits());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let bits = reader.read()?;
+        GpuAccess::from_bits(bits)
+            .ok_or_else(|| invalid_data(format!("invalid GpuAccess {}", bits)))
+    }
+}
+
+// 列挙型は並び順が変わってもファイルが読めるように番号を明示する
+macro_rules! impl_capture_value_for_enum {
+    ($type:ident { $($value:literal => $variant:ident),* $(,)? }) => {
+        impl CaptureValue for $type {
+            fn write(&self, writer: &mut CaptureWriter) {
+                let value: u8 = match self {
+                    $($type::$variant => $value,)*
+                };
+                writer.write(&value);
+            }
+
+            fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+                match reader.read::<u8>()? {
+                    $($value => Ok($type::$variant),)*
+                    value => Err(invalid_data(format!(
+                        concat!("invalid ", stringify!($type), " {}"),
+                        value
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+impl_capture_value_for_enum!(DebugMode {
+    0 => Full,
+    1 => FullAssertion,
+});
+
+impl_capture_value_for_enum!(QueueType {
+    0 => Graphics,
+    1 => Compute,
+    2 => Transfer,
+});
+
+impl_capture_value_for_enum!(CpuAccessMode {
+    0 => None,
+    1 => Upload,
+    2 => Readback,
+    3 => Persistent,
+});
+
+impl_capture_value_for_enum!(ImageFormat {
+    0 => R8Unorm,
+    1 => R8Snorm,
+    2 => R8Uint,
+    3 => R8Sint,
+    4 => R32Uint,
+    5 => R32Sint,
+    6 => R8G8B8A8Sint,
+    7 => R8G8B8A8Uint,
+    8 => R8G8B8A8Unorm,
+    9 => R8G8B8A8UnormSrgb,
+    10 => B8G8R8A8Unorm,
+    11 => B8G8R8A8UnormSrgb,
+    12 => R8G8B8Unorm,
+    13 => R16G16B16A16Float,
+    14 => D32,
+});
+
+impl_capture_value_for_enum!(AttributeFormat {
+    0 => Uint32,
+    1 => Float32_32,
+    2 => Float32_32_32,
+    3 => Float32_32_32_32,
+});
+
+impl_capture_value_for_enum!(PrimitiveTopology {
+    0 => PointList,
+    1 => TriangleList,
+});
+
+impl_capture_value_for_enum!(IndexFormat {
+    0 => Uint32,
+});
+
+impl_capture_value_for_enum!(PresentMode {
+    0 => Fifo,
+    1 => FifoRelaxed,
+    2 => Mailbox,
+    3 => Immediate,
+});
+
+impl_capture_value_for_enum!(CompositeAlphaMode {
+    0 => Auto,
+    1 => Opaque,
+    2 => PreMultiplied,
+    3 => PostMultiplied,
+    4 => Inherit,
+});
+
+impl_capture_value_for_enum!(ScalingMode {
+    0 => Stretch,
+    1 => Letterbox,
+    2 => IntegerLetterbox,
+});
+
+impl_capture_value_for_enum!(UpscaleFilter {
+    0 => Nearest,
+    1 => Linear,
+    2 => Sharpen,
+});
+
+impl_capture_value_for_enum!(Tonemap {
+    0 => None,
+    1 => Reinhard,
+    2 => Aces,
+});
+
+impl CaptureValue for OutputEncoding {
+    fn write(&self, writer: &mut CaptureWriter) {
+        match self {
+            OutputEncoding::Linear => writer.write(&0u8),
+            OutputEncoding::Srgb => writer.write(&1u8),
+            OutputEncoding::Gamma(gamma) => {
+                writer.write(&2u8);
+                writer.write(gamma);
+            }
+        }
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        match reader.read::<u8>()? {
+            0 => Ok(OutputEncoding::Linear),
+            1 => Ok(OutputEncoding::Srgb),
+            2 => Ok(OutputEncoding::Gamma(reader.read()?)),
+            value => Err(invalid_data(format!("invalid OutputEncoding {}", value))),
+        }
+    }
+}
+
+impl CaptureValue for OutputTransform {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.get_exposure());
+        writer.write(&self.get_tonemap());
+        writer.write(&self.get_encoding());
+        writer.write(&self.is_dithering_enabled());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(OutputTransform::new()
+            .set_exposure(reader.read()?)
+            .set_tonemap(reader.read()?)
+            .set_encoding(reader.read()?)
+            .set_dithering_enabled(reader.read()?))
+    }
+}
+
+impl CaptureValue for ScissorStateInfo {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.get_origin_x());
+        writer.write(&self.get_origin_y());
+        writer.write(&self.get_width());
+        writer.write(&self.get_height());
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(ScissorStateInfo::new()
+            .set_origin_x(reader.read()?)
+            .set_origin_y(reader.read()?)
+            .set_width(reader.read()?)
+            .set_height(reader.read()?))
+    }
+}
+
+impl CaptureValue for CaptureTextureRegion {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.offset_u);
+        writer.write(&self.offset_v);
+        writer.write(&self.offset_w);
+        writer.write(&self.width);
+        writer.write(&self.height);
+        writer.write(&self.depth);
+        writer.write(&self.array_length);
+        writer.write(&self.mip_level);
+        writer.write(&self.array_index);
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self {
+            offset_u: reader.read()?,
+            offset_v: reader.read()?,
+            offset_w: reader.read()?,
+            width: reader.read()?,
+            height: reader.read()?,
+            depth: reader.read()?,
+            array_length: reader.read()?,
+            mip_level: reader.read()?,
+            array_index: reader.read()?,
+        })
+    }
+}
+
+impl CaptureValue for CaptureVertexAttribute {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.format);
+        writer.write(&self.slot);
+        writer.write(&self.buffer_index);
+        writer.write(&self.offset);
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self {
+            format: reader.read()?,
+            slot: reader.read()?,
+            buffer_index: reader.read()?,
+            offset: reader.read()?,
+        })
+    }
+}
+
+impl CaptureValue for CaptureVertexBuffer {
+    fn write(&self, writer: &mut CaptureWriter) {
+        writer.write(&self.stride);
+        writer.write(&self.divisor);
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        Ok(Self {
+            stride: reader.read()?,
+            divisor: reader.read()?,
+        })
+    }
+}
+
+// コマンドは番号のあとにフィールドを宣言した順に並べる
+// 番号はファイルの互換性のために変えず、コマンドを増やすときは末尾に足す
+impl CaptureValue for CaptureCommand {
+    fn write(&self, writer: &mut CaptureWriter) {
+        match self {
+            CaptureCommand::CreateDevice { debug_mode } => {
+                writer.write(&0u16);
+                writer.write(debug_mode);
+            }
+            CaptureCommand::CreateQueue { id, queue_type } => {
+                writer.write(&1u16);
+                writer.write(id);
+                writer.write(queue_type);
+            }
+            CaptureCommand::CreateCommandBuffer { id, debug_label } => {
+                writer.write(&2u16);
+                writer.write(id);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateBuffer {
+                id,
+                size,
+                gpu_access,
+                cpu_access_mode,
+                debug_label,
+            } => {
+                writer.write(&3u16);
+                writer.write(id);
+                writer.write(size);
+                writer.write(gpu_access);
+                writer.write(cpu_access_mode);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateTexture {
+                id,
+                width,
+                height,
+                depth,
+                mip_count,
+                gpu_access,
+                image_format,
+                debug_label,
+                data,
+            } => {
+                writer.write(&4u16);
+                writer.write(id);
+                writer.write(width);
+                writer.write(height);
+                writer.write(depth);
+                writer.write(mip_count);
+                writer.write(gpu_access);
+                writer.write(image_format);
+                writer.write(debug_label);
+                writer.write(data);
+            }
+            CaptureCommand::CreateTextureView {
+                id,
+                texture,
+                image_format,
+                debug_label,
+            } => {
+                writer.write(&5u16);
+                writer.write(id);
+                writer.write(texture);
+                writer.write(image_format);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateColorTargetView {
+                id,
+                texture,
+                image_format,
+                debug_label,
+            } => {
+                writer.write(&6u16);
+                writer.write(id);
+                writer.write(texture);
+                writer.write(image_format);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateDepthStencilView {
+                id,
+                texture,
+                is_depth_test_enabled,
+                is_depth_write_enabled,
+            } => {
+                writer.write(&7u16);
+                writer.write(id);
+                writer.write(texture);
+                writer.write(is_depth_test_enabled);
+                writer.write(is_depth_write_enabled);
+            }
+            CaptureCommand::CreateSampler { id, debug_label } => {
+                writer.write(&8u16);
+                writer.write(id);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateShader {
+                id,
+                compute_shader_binary,
+                vertex_shader_binary,
+                pixel_shader_binary,
+                vertex_shader_source,
+                pixel_shader_source,
+                debug_label,
+            } => {
+                writer.write(&9u16);
+                writer.write(id);
+                writer.write(compute_shader_binary);
+                writer.write(vertex_shader_binary);
+                writer.write(pixel_shader_binary);
+                writer.write(vertex_shader_source);
+                writer.write(pixel_shader_source);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateVertexState {
+                id,
+                attributes,
+                buffers,
+                debug_label,
+            } => {
+                writer.write(&10u16);
+                writer.write(id);
+                writer.write(attributes);
+                writer.write(buffers);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateFence {
+                id,
+                is_signaled,
+                debug_label,
+            } => {
+                writer.write(&11u16);
+                writer.write(id);
+                writer.write(is_signaled);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateSemaphore {
+                id,
+                initial_value,
+                debug_label,
+            } => {
+                writer.write(&12u16);
+                writer.write(id);
+                writer.write(initial_value);
+                writer.write(debug_label);
+            }
+            CaptureCommand::CreateSwapChain {
+                id,
+                width,
+                height,
+                present_mode,
+                preferred_formats,
+                alpha_mode,
+                frame_latency,
+                is_direct_rendering_enabled,
+                render_scale,
+                render_size,
+                scaling_mode,
+                upscale_filter,
+                render_format,
+                output_transform,
+            } => {
+                writer.write(&13u16);
+                writer.write(id);
+                writer.write(width);
+                writer.write(height);
+                writer.write(present_mode);
+                writer.write(preferred_formats);
+                writer.write(alpha_mode);
+                writer.write(frame_latency);
+                writer.write(is_direct_rendering_enabled);
+                writer.write(render_scale);
+                writer.write(render_size);
+                writer.write(scaling_mode);
+                writer.write(upscale_filter);
+                writer.write(render_format);
+                writer.write(output_transform);
+            }
+            CaptureCommand::WriteBuffer {
+                buffer,
+                offset,
+                data,
+            } => {
+                writer.write(&14u16);
+                writer.write(buffer);
+                writer.write(offset);
+                writer.write(data);
+            }
+            CaptureCommand::WriteTexture {
+                texture,
+                region,
+                data,
+                bytes_per_row,
+                rows_per_image,
+            } => {
+                writer.write(&15u16);
+                writer.write(texture);
+                writer.write(region);
+                writer.write(data);
+                writer.write(bytes_per_row);
+                writer.write(rows_per_image);
+            }
+            CaptureCommand::Begin { command_buffer } => {
+                writer.write(&16u16);
+                writer.write(command_buffer);
+            }
+            CaptureCommand::End { command_buffer } => {
+                writer.write(&17u16);
+                writer.write(command_buffer);
+            }
+            CaptureCommand::PushDebugGroup {
+                command_buffer,
+                label,
+            } => {
+                writer.write(&18u16);
+                writer.write(command_buffer);
+                writer.write(label);
+            }
+            CaptureCommand::PopDebugGroup { command_buffer } => {
+                writer.write(&19u16);
+                writer.write(command_buffer);
+            }
+            CaptureCommand::InsertDebugMarker {
+                command_buffer,
+                label,
+            } => {
+                writer.write(&20u16);
+                writer.write(command_buffer);
+                writer.write(label);
+            }
+            CaptureCommand::ClearColor {
+                command_buffer,
+                color_target_view,
+                color,
+                base_index,
+                length,
+            } => {
+                writer.write(&21u16);
+                writer.write(command_buffer);
+                writer.write(color_target_view);
+                writer.write(color);
+                writer.write(base_index);
+                writer.write(length);
+            }
+            CaptureCommand::SetRenderTargets {
+                command_buffer,
+                color_target_views,
+                depth_stencil_view,
+            } => {
+                writer.write(&22u16);
+                writer.write(command_buffer);
+                writer.write(color_target_views);
+                writer.write(depth_stencil_view);
+            }
+            CaptureCommand::SetShader {
+                command_buffer,
+                shader,
+            } => {
+                writer.write(&23u16);
+                writer.write(command_buffer);
+                writer.write(shader);
+            }
+            CaptureCommand::SetSampler {
+                command_buffer,
+                index,
+                sampler,
+            } => {
+                writer.write(&24u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(sampler);
+            }
+            CaptureCommand::SetTexture {
+                command_buffer,
+                index,
+                texture_view,
+            } => {
+                writer.write(&25u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(texture_view);
+            }
+            CaptureCommand::SetImage {
+                command_buffer,
+                index,
+                texture_view,
+            } => {
+                writer.write(&26u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(texture_view);
+            }
+            CaptureCommand::SetConstantBuffer {
+                command_buffer,
+                index,
+                buffer,
+                range,
+            } => {
+                writer.write(&27u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(buffer);
+                writer.write(range);
+            }
+            CaptureCommand::SetUnorderedAccessBuffer {
+                command_buffer,
+                index,
+                buffer,
+            } => {
+                writer.write(&28u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(buffer);
+            }
+            CaptureCommand::SetVertexBuffer {
+                command_buffer,
+                index,
+                buffer,
+                range,
+            } => {
+                writer.write(&29u16);
+                writer.write(command_buffer);
+                writer.write(index);
+                writer.write(buffer);
+                writer.write(range);
+            }
+            CaptureCommand::SetVertexState {
+                command_buffer,
+                vertex_state,
+            } => {
+                writer.write(&30u16);
+                writer.write(command_buffer);
+                writer.write(vertex_state);
+            }
+            CaptureCommand::SetScissor {
+                command_buffer,
+                scissor,
+            } => {
+                writer.write(&31u16);
+                writer.write(command_buffer);
+                writer.write(scissor);
+            }
+            CaptureCommand::Dispatch {
+                command_buffer,
+                count,
+            } => {
+                writer.write(&32u16);
+                writer.write(command_buffer);
+                writer.write(count);
+            }
+            CaptureCommand::Draw {
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+            } => {
+                writer.write(&33u16);
+                writer.write(command_buffer);
+                writer.write(primitive_topology);
+                writer.write(vertex_count);
+                writer.write(vertex_offset);
+            }
+            CaptureCommand::DrawInstanced {
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+                instance_count,
+                base_instance,
+            } => {
+                writer.write(&34u16);
+                writer.write(command_buffer);
+                writer.write(primitive_topology);
+                writer.write(vertex_count);
+                writer.write(vertex_offset);
+                writer.write(instance_count);
+                writer.write(base_instance);
+            }
+            CaptureCommand::DrawIndexed {
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer,
+                index_count,
+                base_vertex,
+            } => {
+                writer.write(&35u16);
+                writer.write(command_buffer);
+                writer.write(primitive_topology);
+                writer.write(index_format);
+                writer.write(index_buffer);
+                writer.write(index_count);
+                writer.write(base_vertex);
+            }
+            CaptureCommand::DrawIndexedInstanced {
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer,
+                index_count,
+                base_vertex,
+                instance_count,
+                base_instance,
+            } => {
+                writer.write(&36u16);
+                writer.write(command_buffer);
+                writer.write(primitive_topology);
+                writer.write(index_format);
+                writer.write(index_buffer);
+                writer.write(index_count);
+                writer.write(base_vertex);
+                writer.write(instance_count);
+                writer.write(base_instance);
+            }
+            CaptureCommand::Execute {
+                queue,
+                command_buffer,
+            } => {
+                writer.write(&37u16);
+                writer.write(queue);
+                writer.write(command_buffer);
+            }
+            CaptureCommand::ExecuteMany {
+                queue,
+                command_buffers,
+            } => {
+                writer.write(&38u16);
+                writer.write(queue);
+                writer.write(command_buffers);
+            }
+            CaptureCommand::ExecuteWithFence {
+                queue,
+                command_buffer,
+                fence,
+            } => {
+                writer.write(&39u16);
+                writer.write(queue);
+                writer.write(command_buffer);
+                writer.write(fence);
+            }
+            CaptureCommand::ExecuteWithSemaphores {
+                queue,
+                command_buffer,
+                wait_semaphores,
+                signal_semaphores,
+            } => {
+                writer.write(&40u16);
+                writer.write(queue);
+                writer.write(command_buffer);
+                writer.write(wait_semaphores);
+                writer.write(signal_semaphores);
+            }
+            CaptureCommand::Present { queue, swap_chain } => {
+                writer.write(&41u16);
+                writer.write(queue);
+                writer.write(swap_chain);
+            }
+            CaptureCommand::Flush { queue } => {
+                writer.write(&42u16);
+                writer.write(queue);
+            }
+            CaptureCommand::Sync { queue } => {
+                writer.write(&43u16);
+                writer.write(queue);
+            }
+            CaptureCommand::AcquireNextScanBufferView {
+                swap_chain,
+                color_target_view,
+                semaphore,
+                fence,
+            } => {
+                writer.write(&44u16);
+                writer.write(swap_chain);
+                writer.write(color_target_view);
+                writer.write(semaphore);
+                writer.write(fence);
+            }
+            CaptureCommand::SetRenderScale {
+                swap_chain,
+                render_scale,
+            } => {
+                writer.write(&45u16);
+                writer.write(swap_chain);
+                writer.write(render_scale);
+            }
+            CaptureCommand::SetOutputTransform {
+                swap_chain,
+                output_transform,
+            } => {
+                writer.write(&46u16);
+                writer.write(swap_chain);
+                writer.write(output_transform);
+            }
+            CaptureCommand::Resize {
+                swap_chain,
+                width,
+                height,
+            } => {
+                writer.write(&47u16);
+                writer.write(swap_chain);
+                writer.write(width);
+                writer.write(height);
+            }
+            CaptureCommand::WaitFence { fence, timeout } => {
+                writer.write(&48u16);
+                writer.write(fence);
+                writer.write(timeout);
+            }
+            CaptureCommand::ResetFence { fence } => {
+                writer.write(&49u16);
+                writer.write(fence);
+            }
+            CaptureCommand::WaitSemaphore {
+                semaphore,
+                value,
+                timeout,
+            } => {
+                writer.write(&50u16);
+                writer.write(semaphore);
+                writer.write(value);
+                writer.write(timeout);
+            }
+            CaptureCommand::SignalSemaphore { semaphore, value } => {
+                writer.write(&51u16);
+                writer.write(semaphore);
+                writer.write(value);
+            }
+            CaptureCommand::Destroy { id } => {
+                writer.write(&52u16);
+                writer.write(id);
+            }
+        }
+    }
+
+    fn read(reader: &mut CaptureReader) -> io::Result<Self> {
+        let command = match reader.read::<u16>()? {
+            0 => CaptureCommand::CreateDevice {
+                debug_mode: reader.read()?,
+            },
+            1 => CaptureCommand::CreateQueue {
+                id: reader.read()?,
+                queue_type: reader.read()?,
+            },
+            2 => CaptureCommand::CreateCommandBuffer {
+                id: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            3 => CaptureCommand::CreateBuffer {
+                id: reader.read()?,
+                size: reader.read()?,
+                gpu_access: reader.read()?,
+                cpu_access_mode: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            4 => CaptureCommand::CreateTexture {
+                id: reader.read()?,
+                width: reader.read()?,
+                height: reader.read()?,
+                depth: reader.read()?,
+                mip_count: reader.read()?,
+                gpu_access: reader.read()?,
+                image_format: reader.read()?,
+                debug_label: reader.read()?,
+                data: reader.read()?,
+            },
+            5 => CaptureCommand::CreateTextureView {
+                id: reader.read()?,
+                texture: reader.read()?,
+                image_format: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            6 => CaptureCommand::CreateColorTargetView {
+                id: reader.read()?,
+                texture: reader.read()?,
+                image_format: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            7 => CaptureCommand::CreateDepthStencilView {
+                id: reader.read()?,
+                texture: reader.read()?,
+                is_depth_test_enabled: reader.read()?,
+                is_depth_write_enabled: reader.read()?,
+            },
+            8 => CaptureCommand::CreateSampler {
+                id: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            9 => CaptureCommand::CreateShader {
+                id: reader.read()?,
+                compute_shader_binary: reader.read()?,
+                vertex_shader_binary: reader.read()?,
+                pixel_shader_binary: reader.read()?,
+                vertex_shader_source: reader.read()?,
+                pixel_shader_source: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            10 => CaptureCommand::CreateVertexState {
+                id: reader.read()?,
+                attributes: reader.read()?,
+                buffers: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            11 => CaptureCommand::CreateFence {
+                id: reader.read()?,
+                is_signaled: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            12 => CaptureCommand::CreateSemaphore {
+                id: reader.read()?,
+                initial_value: reader.read()?,
+                debug_label: reader.read()?,
+            },
+            13 => CaptureCommand::CreateSwapChain {
+                id: reader.read()?,
+                width: reader.read()?,
+                height: reader.read()?,
+                present_mode: reader.read()?,
+                preferred_formats: reader.read()?,
+                alpha_mode: reader.read()?,
+                frame_latency: reader.read()?,
+                is_direct_rendering_enabled: reader.read()?,
+                render_scale: reader.read()?,
+                render_size: reader.read()?,
+                scaling_mode: reader.read()?,
+                upscale_filter: reader.read()?,
+                render_format: reader.read()?,
+                output_transform: reader.read()?,
+            },
+            14 => CaptureCommand::WriteBuffer {
+                buffer: reader.read()?,
+                offset: reader.read()?,
+                data: reader.read()?,
+            },
+            15 => CaptureCommand::WriteTexture {
+                texture: reader.read()?,
+                region: reader.read()?,
+                data: reader.read()?,
+                bytes_per_row: reader.read()?,
+                rows_per_image: reader.read()?,
+            },
+            16 => CaptureCommand::Begin {
+                command_buffer: reader.read()?,
+            },
+            17 => CaptureCommand::End {
+                command_buffer: reader.read()?,
+            },
+            18 => CaptureCommand::PushDebugGroup {
+                command_buffer: reader.read()?,
+                label: reader.read()?,
+            },
+            19 => CaptureCommand::PopDebugGroup {
+                command_buffer: reader.read()?,
+            },
+            20 => CaptureCommand::InsertDebugMarker {
+                command_buffer: reader.read()?,
+                label: reader.read()?,
+            },
+            21 => CaptureCommand::ClearColor {
+                command_buffer: reader.read()?,
+                color_target_view: reader.read()?,
+                color: reader.read()?,
+                base_index: reader.read()?,
+                length: reader.read()?,
+            },
+            22 => CaptureCommand::SetRenderTargets {
+                command_buffer: reader.read()?,
+                color_target_views: reader.read()?,
+                depth_stencil_view: reader.read()?,
+            },
+            23 => CaptureCommand::SetShader {
+                command_buffer: reader.read()?,
+                shader: reader.read()?,
+            },
+            24 => CaptureCommand::SetSampler {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                sampler: reader.read()?,
+            },
+            25 => CaptureCommand::SetTexture {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                texture_view: reader.read()?,
+            },
+            26 => CaptureCommand::SetImage {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                texture_view: reader.read()?,
+            },
+            27 => CaptureCommand::SetConstantBuffer {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                buffer: reader.read()?,
+                range: reader.read()?,
+            },
+            28 => CaptureCommand::SetUnorderedAccessBuffer {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                buffer: reader.read()?,
+            },
+            29 => CaptureCommand::SetVertexBuffer {
+                command_buffer: reader.read()?,
+                index: reader.read()?,
+                buffer: reader.read()?,
+                range: reader.read()?,
+            },
+            30 => CaptureCommand::SetVertexState {
+                command_buffer: reader.read()?,
+                vertex_state: reader.read()?,
+            },
+            31 => CaptureCommand::SetScissor {
+                command_buffer: reader.read()?,
+                scissor: reader.read()?,
+            },
+            32 => CaptureCommand::Dispatch {
+                command_buffer: reader.read()?,
+                count: reader.read()?,
+            },
+            33 => CaptureCommand::Draw {
+                command_buffer: reader.read()?,
+                primitive_topology: reader.read()?,
+                vertex_count: reader.read()?,
+                vertex_offset: reader.read()?,
+            },
+            34 => CaptureCommand::DrawInstanced {
+                command_buffer: reader.read()?,
+                primitive_topology: reader.read()?,
+                vertex_count: reader.read()?,
+                vertex_offset: reader.read()?,
+                instance_count: reader.read()?,
+                base_instance: reader.read()?,
+            },
+            35 => CaptureCommand::DrawIndexed {
+                command_buffer: reader.read()?,
+                primitive_topology: reader.read()?,
+                index_format: reader.read()?,
+                index_buffer: reader.read()?,
+                index_count: reader.read()?,
+                base_vertex: reader.read()?,
+            },
+            36 => CaptureCommand::DrawIndexedInstanced {
+                command_buffer: reader.read()?,
+                primitive_topology: reader.read()?,
+                index_format: reader.read()?,
+                index_buffer: reader.read()?,
+                index_count: reader.read()?,
+                base_vertex: reader.read()?,
+                instance_count: reader.read()?,
+                base_instance: reader.read()?,
+            },
+            37 => CaptureCommand::Execute {
+                queue: reader.read()?,
+                command_buffer: reader.read()?,
+            },
+            38 => CaptureCommand::ExecuteMany {
+                queue: reader.read()?,
+                command_buffers: reader.read()?,
+            },
+            39 => CaptureCommand::ExecuteWithFence {
+                queue: reader.read()?,
+                command_buffer: reader.read()?,
+                fence: reader.read()?,
+            },
+            40 => CaptureCommand::ExecuteWithSemaphores {
+                queue: reader.read()?,
+                command_buffer: reader.read()?,
+                wait_semaphores: reader.read()?,
+                signal_semaphores: reader.read()?,
+            },
+            41 => CaptureCommand::Present {
+                queue: reader.read()?,
+                swap_chain: reader.read()?,
+            },
+            42 => CaptureCommand::Flush {
+                queue: reader.read()?,
+            },
+            43 => CaptureCommand::Sync {
+                queue: reader.read()?,
+            },
+            44 => CaptureCommand::AcquireNextScanBufferView {
+                swap_chain: reader.read()?,
+                color_target_view: reader.read()?,
+                semaphore: reader.read()?,
+                fence: reader.read()?,
+            },
+            45 => CaptureCommand::SetRenderScale {
+                swap_chain: reader.read()?,
+                render_scale: reader.read()?,
+            },
+            46 => CaptureCommand::SetOutputTransform {
+                swap_chain: reader.read()?,
+                output_transform: reader.read()?,
+            },
+            47 => CaptureCommand::Resize {
+                swap_chain: reader.read()?,
+                width: reader.read()?,
+                height: reader.read()?,
+            },
+            48 => CaptureCommand::WaitFence {
+                fence: reader.read()?,
+                timeout: reader.read()?,
+            },
+            49 => CaptureCommand::ResetFence {
+                fence: reader.read()?,
+            },
+            50 => CaptureCommand::WaitSemaphore {
+                semaphore: reader.read()?,
+                value: reader.read()?,
+                timeout: reader.read()?,
+            },
+            51 => CaptureCommand::SignalSemaphore {
+                semaphore: reader.read()?,
+                value: reader.read()?,
+            },
+            52 => CaptureCommand::Destroy { id: reader.read()? },
+            tag => return Err(invalid_data(format!("unknown command {}", tag))),
+        };
+        Ok(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::{DebugMode, GpuAccess, ImageFormat, PrimitiveTopology, QueueType};
+
+    use crate::capture::{Capture, CaptureCommand, CaptureData, ObjectId};
+
+    fn create_capture() -> Capture {
+        Capture::new(vec![
+            CaptureCommand::CreateDevice {
+                debug_mode: DebugMode::Full,
+            },
+            CaptureCommand::CreateQueue {
+                id: ObjectId::new(0),
+                queue_type: QueueType::Graphics,
+            },
+            CaptureCommand::CreateTexture {
+                id: ObjectId::new(1),
+                width: 2,
+                height: 2,
+                depth: 1,
+                mip_count: 1,
+                gpu_access: GpuAccess::TEXTURE | GpuAccess::WRITE,
+                image_format: ImageFormat::R8G8B8A8Unorm,
+                debug_label: Some("texture".to_string()),
+                data: Some(CaptureData::new(vec![255; 16])),
+            },
+            CaptureCommand::Draw {
+                command_buffer: ObjectId::new(2),
+                primitive_topology: PrimitiveTopology::TriangleList,
+                vertex_count: 3,
+                vertex_offset: 0,
+            },
+            CaptureCommand::WaitFence {
+                fence: ObjectId::new(3),
+                timeout: None,
+            },
+        ])
+    }
+
+    #[test]
+    fn round_trip() {
+        let capture = create_capture();
+        let mut data = Vec::new();
+        capture.write(&mut data).unwrap();
+
+        assert_eq!(Capture::read(data.as_slice()).unwrap(), capture);
+    }
+
+    #[test]
+    fn reject_unsupported_version() {
+        let mut data = Vec::new();
+        create_capture().write(&mut data).unwrap();
+        data[8] = data[8].wrapping_add(1);
+
+        assert!(Capture::read(data.as_slice()).is_err());
+    }
+
+    #[test]
+    fn reject_truncated_data() {
+        let mut data = Vec::new();
+        create_capture().write(&mut data).unwrap();
+        data.pop();
+
+        assert!(Capture::read(data.as_slice()).is_err());
+    }
+}
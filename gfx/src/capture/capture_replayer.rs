@@ -0,0 +1,708 @@
+use std::{collections::HashMap, fmt, time::Duration};
+
+use sjgfx_interface::{
+    BufferInfo, ColorTargetViewInfo, CommandBufferInfo, DepthStencilStateInfo, DeviceInfo,
+    FenceInfo, IBuffer, IColorTargetView, ICommandBuffer, IDepthStencilView, IDevice,
+    IDisplayEventListener, IFence, IQueue, ISampler, ISemaphore, IShader, ISwapChain, ITexture,
+    ITextureView, IVertexState, ImageData, QueueInfo, SamplerInfo, SemaphoreInfo, ShaderInfo,
+    SwapChainInfo, TextureArrayRange, TextureInfo, TextureViewInfo, VertexStateInfo,
+};
+
+use crate::{
+    api::IApi,
+    capture::{Capture, CaptureCommand, ObjectId},
+};
+
+// 再生できないキャプチャーを渡されたときのエラー
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayError {
+    // 作られる前か破棄されたあとのオブジェクトを使った
+    ObjectNotFound(ObjectId),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::ObjectNotFound(id) => write!(
+                f,
+                "object {:?} is used before it is created or after it is destroyed",
+                id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// キャプチャーを任意のバックエンドで作りなおして実行する
+// スワップチェーンはウィンドウを作らずにオフスクリーンで作り、表示した画像を読み戻して返す
+pub struct TCaptureReplayer<TApi: IApi> {
+    device: TApi::Device,
+    queues: HashMap<ObjectId, TApi::Queue>,
+    command_buffers: HashMap<ObjectId, TApi::CommandBuffer>,
+    buffers: HashMap<ObjectId, TApi::Buffer>,
+    textures: HashMap<ObjectId, TApi::Texture>,
+    texture_views: HashMap<ObjectId, TApi::TextureView>,
+    color_target_views: HashMap<ObjectId, TApi::ColorTargetView>,
+    depth_stencil_views: HashMap<ObjectId, TApi::DepthStencilView>,
+    samplers: HashMap<ObjectId, TApi::Sampler>,
+    shaders: HashMap<ObjectId, TApi::Shader>,
+    vertex_states: HashMap<ObjectId, TApi::VertexState>,
+    fences: HashMap<ObjectId, TApi::Fence>,
+    semaphores: HashMap<ObjectId, TApi::Semaphore>,
+    swap_chains: HashMap<ObjectId, TApi::SwapChain>,
+}
+
+impl<TApi: IApi> TCaptureReplayer<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    // デバイスは再生する側で作る。キャプチャーの CreateDevice は使わない
+    pub fn new(info: &DeviceInfo) -> Self {
+        Self {
+            device: TApi::Device::new(info),
+            queues: HashMap::new(),
+            command_buffers: HashMap::new(),
+            buffers: HashMap::new(),
+            textures: HashMap::new(),
+            texture_views: HashMap::new(),
+            color_target_views: HashMap::new(),
+            depth_stencil_views: HashMap::new(),
+            samplers: HashMap::new(),
+            shaders: HashMap::new(),
+            vertex_states: HashMap::new(),
+            fences: HashMap::new(),
+            semaphores: HashMap::new(),
+            swap_chains: HashMap::new(),
+        }
+    }
+
+    pub fn get_device(&self) -> &TApi::Device {
+        &self.device
+    }
+
+    // すべてのコマンドを実行して、表示するたびに表示した画像を渡す
+    // 壊れたキャプチャーはそこまでのコマンドを実行したところでエラーを返す
+    pub fn replay<F: FnMut(ImageData)>(
+        &mut self,
+        capture: &Capture,
+        mut on_presented: F,
+    ) -> Result<(), ReplayError> {
+        for command in capture.get_commands() {
+            if let Some(image) = self.replay_command(command)? {
+                on_presented(image);
+            }
+        }
+        Ok(())
+    }
+
+    // コマンドを 1 つ実行する。表示したときは表示した画像を返す
+    pub fn replay_command(
+        &mut self,
+        command: &CaptureCommand,
+    ) -> Result<Option<ImageData>, ReplayError> {
+        match command {
+            CaptureCommand::CreateDevice { .. } => {}
+            CaptureCommand::CreateQueue { id, queue_type } => {
+                let info = QueueInfo::new().set_queue_type(queue_type.clone());
+                let queue = TApi::Queue::new(&mut self.device, &info);
+                self.queues.insert(*id, queue);
+            }
+            CaptureCommand::CreateCommandBuffer { id, debug_label } => {
+                let mut info = CommandBufferInfo::new();
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let command_buffer = TApi::CommandBuffer::new(&self.device, &info);
+                self.command_buffers.insert(*id, command_buffer);
+            }
+            CaptureCommand::CreateBuffer {
+                id,
+                size,
+                gpu_access,
+                cpu_access_mode,
+                debug_label,
+            } => {
+                let mut info = BufferInfo::new()
+                    .set_size(*size as usize)
+                    .set_gpu_access_flags(*gpu_access)
+                    .set_cpu_access_mode(*cpu_access_mode);
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let buffer = TApi::Buffer::new(&mut self.device, &info);
+                self.buffers.insert(*id, buffer);
+            }
+            CaptureCommand::CreateTexture {
+                id,
+                width,
+                height,
+                depth,
+                mip_count,
+                gpu_access,
+                image_format,
+                debug_label,
+                data,
+            } => {
+                let mut info = TextureInfo::new()
+                    .set_width(*width)
+                    .set_height(*height)
+                    .set_depth(*depth)
+                    .set_mip_count(*mip_count)
+                    .set_gpu_access_flags(*gpu_access)
+                    .set_image_format(image_format.clone());
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let texture = match data {
+                    Some(data) => {
+                        TApi::Texture::new_with_data(&self.device, &info, data.get_data())
+                    }
+                    None => TApi::Texture::new(&mut self.device, &info),
+                };
+                self.textures.insert(*id, texture);
+            }
+            CaptureCommand::CreateTextureView {
+                id,
+                texture,
+                image_format,
+                debug_label,
+            } => {
+                let mut info = TextureViewInfo::new().set_format(image_format.clone());
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let texture_view =
+                    TApi::TextureView::new(&self.device, &info, get(&self.textures, texture)?);
+                self.texture_views.insert(*id, texture_view);
+            }
+            CaptureCommand::CreateColorTargetView {
+                id,
+                texture,
+                image_format,
+                debug_label,
+            } => {
+                let mut info = ColorTargetViewInfo::new().set_image_format(image_format.clone());
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let color_target_view =
+                    TApi::ColorTargetView::new(&self.device, &info, get(&self.textures, texture)?);
+                self.color_target_views.insert(*id, color_target_view);
+            }
+            CaptureCommand::CreateDepthStencilView {
+                id,
+                texture,
+                is_depth_test_enabled,
+                is_depth_write_enabled,
+            } => {
+                let info = DepthStencilStateInfo::new()
+                    .set_depth_test_enabled(*is_depth_test_enabled)
+                    .set_depth_write_enabled(*is_depth_write_enabled);
+                let depth_stencil_view =
+                    TApi::DepthStencilView::new(&self.device, &info, get(&self.textures, texture)?);
+                self.depth_stencil_views.insert(*id, depth_stencil_view);
+            }
+            CaptureCommand::CreateSampler { id, debug_label } => {
+                let mut info = SamplerInfo::new();
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let sampler = TApi::Sampler::new(&mut self.device, &info);
+                self.samplers.insert(*id, sampler);
+            }
+            CaptureCommand::CreateShader {
+                id,
+                compute_shader_binary,
+                vertex_shader_binary,
+                pixel_shader_binary,
+                vertex_shader_source,
+                pixel_shader_source,
+                debug_label,
+            } => {
+                let mut info = ShaderInfo::new();
+                if let Some(binary) = compute_shader_binary {
+                    info = info.set_compute_shader_binary(binary.get_data());
+                }
+                if let Some(binary) = vertex_shader_binary {
+                    info = info.set_vertex_shader_binary(binary.get_data());
+                }
+                if let Some(binary) = pixel_shader_binary {
+                    info = info.set_pixel_shader_binary(binary.get_data());
+                }
+                if let Some(source) = vertex_shader_source {
+                    info = info.set_vertex_shader_source(source);
+                }
+                if let Some(source) = pixel_shader_source {
+                    info = info.set_pixel_shader_source(source);
+                }
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let shader = TApi::Shader::new(&mut self.device, &info);
+                self.shaders.insert(*id, shader);
+            }
+            CaptureCommand::CreateVertexState {
+                id,
+                attributes,
+                buffers,
+                debug_label,
+            } => {
+                let mut info = VertexStateInfo::new()
+                    .set_attribute_state_info_array(attributes.iter().map(|x| x.to_info()))
+                    .set_buffer_state_info_array(buffers.iter().map(|x| x.to_info()));
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let vertex_state = TApi::VertexState::new(&self.device, &info);
+                self.vertex_states.insert(*id, vertex_state);
+            }
+            CaptureCommand::CreateFence {
+                id,
+                is_signaled,
+                debug_label,
+            } => {
+                let mut info = FenceInfo::new().set_signaled(*is_signaled);
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let fence = TApi::Fence::new(&self.device, &info);
+                self.fences.insert(*id, fence);
+            }
+            CaptureCommand::CreateSemaphore {
+                id,
+                initial_value,
+                debug_label,
+            } => {
+                let mut info = SemaphoreInfo::new().set_initial_value(*initial_value);
+                if let Some(debug_label) = debug_label {
+                    info = info.set_debug_label(debug_label);
+                }
+                let semaphore = TApi::Semaphore::new(&self.device, &info);
+                self.semaphores.insert(*id, semaphore);
+            }
+            CaptureCommand::CreateSwapChain {
+                id,
+                width,
+                height,
+                present_mode,
+                preferred_formats,
+                alpha_mode,
+                frame_latency,
+                is_direct_rendering_enabled,
+                render_scale,
+                render_size,
+                scaling_mode,
+                upscale_filter,
+                render_format,
+                output_transform,
+            } => {
+                let mut info = SwapChainInfo::new()
+                    .with_width(*width)
+                    .with_height(*height)
+                    .with_present_mode(*present_mode)
+                    .with_preferred_formats(preferred_formats)
+                    .with_alpha_mode(*alpha_mode)
+                    .with_frame_latency(*frame_latency)
+                    .with_direct_rendering_enabled(*is_direct_rendering_enabled)
+                    .with_render_scale(*render_scale)
+                    .with_scaling_mode(*scaling_mode)
+                    .with_upscale_filter(*upscale_filter)
+                    .with_render_format(render_format.clone())
+                    .with_output_transform(output_transform.clone());
+                if let Some((width, height)) = render_size {
+                    info = info.with_render_size(*width, *height);
+                }
+                let swap_chain = TApi::SwapChain::new(&mut self.device, &info);
+                self.swap_chains.insert(*id, swap_chain);
+            }
+            CaptureCommand::WriteBuffer {
+                buffer,
+                offset,
+                data,
+            } => {
+                let offset = *offset as usize;
+                let range = offset..(offset + data.get_data().len());
+                get(&self.buffers, buffer)?
+                    .map_range_mut(range, |x: &mut [u8]| x.copy_from_slice(data.get_data()));
+            }
+            CaptureCommand::WriteTexture {
+                texture,
+                region,
+                data,
+                bytes_per_row,
+                rows_per_image,
+            } => {
+                get(&self.textures, texture)?.write(
+                    &region.to_region(),
+                    data.get_data(),
+                    *bytes_per_row as usize,
+                    *rows_per_image as usize,
+                );
+            }
+            CaptureCommand::Begin { command_buffer } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.begin();
+            }
+            CaptureCommand::End { command_buffer } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.end();
+            }
+            CaptureCommand::PushDebugGroup {
+                command_buffer,
+                label,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.push_debug_group(label);
+            }
+            CaptureCommand::PopDebugGroup { command_buffer } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.pop_debug_group();
+            }
+            CaptureCommand::InsertDebugMarker {
+                command_buffer,
+                label,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.insert_debug_marker(label);
+            }
+            CaptureCommand::ClearColor {
+                command_buffer,
+                color_target_view,
+                color,
+                base_index,
+                length,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.clear_color(
+                    get_mut(&mut self.color_target_views, color_target_view)?,
+                    color[0],
+                    color[1],
+                    color[2],
+                    color[3],
+                    TextureArrayRange::new()
+                        .set_base_index(*base_index)
+                        .set_length(*length),
+                );
+            }
+            CaptureCommand::SetRenderTargets {
+                command_buffer,
+                color_target_views,
+                depth_stencil_view,
+            } => {
+                let color_target_views = color_target_views
+                    .iter()
+                    .map(|id| get(&self.color_target_views, id))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let depth_stencil_view = depth_stencil_view
+                    .as_ref()
+                    .map(|id| get(&self.depth_stencil_views, id))
+                    .transpose()?;
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_render_targets(&color_target_views, depth_stencil_view);
+            }
+            CaptureCommand::SetShader {
+                command_buffer,
+                shader,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_shader(get(&self.shaders, shader)?);
+            }
+            CaptureCommand::SetSampler {
+                command_buffer,
+                index,
+                sampler,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_sampler(*index, get(&self.samplers, sampler)?);
+            }
+            CaptureCommand::SetTexture {
+                command_buffer,
+                index,
+                texture_view,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_texture(*index, get(&self.texture_views, texture_view)?);
+            }
+            CaptureCommand::SetImage {
+                command_buffer,
+                index,
+                texture_view,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_image(*index, get(&self.texture_views, texture_view)?);
+            }
+            CaptureCommand::SetConstantBuffer {
+                command_buffer,
+                index,
+                buffer,
+                range,
+            } => {
+                let command_buffer = get_mut(&mut self.command_buffers, command_buffer)?;
+                let buffer = get(&self.buffers, buffer)?;
+                match range {
+                    Some((offset, size)) => command_buffer.set_constant_buffer_range(
+                        *index,
+                        buffer,
+                        *offset as usize,
+                        *size as usize,
+                    ),
+                    None => command_buffer.set_constant_buffer(*index, buffer),
+                }
+            }
+            CaptureCommand::SetUnorderedAccessBuffer {
+                command_buffer,
+                index,
+                buffer,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_unordered_access_buffer(*index, get(&self.buffers, buffer)?);
+            }
+            CaptureCommand::SetVertexBuffer {
+                command_buffer,
+                index,
+                buffer,
+                range,
+            } => {
+                let command_buffer = get_mut(&mut self.command_buffers, command_buffer)?;
+                let buffer = get(&self.buffers, buffer)?;
+                match range {
+                    Some((offset, size)) => command_buffer.set_vertex_buffer_range(
+                        *index,
+                        buffer,
+                        *offset as usize,
+                        *size as usize,
+                    ),
+                    None => command_buffer.set_vertex_buffer(*index, buffer),
+                }
+            }
+            CaptureCommand::SetVertexState {
+                command_buffer,
+                vertex_state,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .set_vertex_state(get(&self.vertex_states, vertex_state)?);
+            }
+            CaptureCommand::SetScissor {
+                command_buffer,
+                scissor,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.set_scissor(scissor);
+            }
+            CaptureCommand::Dispatch {
+                command_buffer,
+                count,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?
+                    .dispatch(count[0], count[1], count[2]);
+            }
+            CaptureCommand::Draw {
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.draw(
+                    primitive_topology.clone(),
+                    *vertex_count,
+                    *vertex_offset,
+                );
+            }
+            CaptureCommand::DrawInstanced {
+                command_buffer,
+                primitive_topology,
+                vertex_count,
+                vertex_offset,
+                instance_count,
+                base_instance,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.draw_instanced(
+                    primitive_topology.clone(),
+                    *vertex_count,
+                    *vertex_offset,
+                    *instance_count,
+                    *base_instance,
+                );
+            }
+            CaptureCommand::DrawIndexed {
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer,
+                index_count,
+                base_vertex,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.draw_indexed(
+                    primitive_topology.clone(),
+                    index_format.clone(),
+                    get(&self.buffers, index_buffer)?,
+                    *index_count,
+                    *base_vertex,
+                );
+            }
+            CaptureCommand::DrawIndexedInstanced {
+                command_buffer,
+                primitive_topology,
+                index_format,
+                index_buffer,
+                index_count,
+                base_vertex,
+                instance_count,
+                base_instance,
+            } => {
+                get_mut(&mut self.command_buffers, command_buffer)?.draw_indexed_instanced(
+                    primitive_topology.clone(),
+                    index_format.clone(),
+                    get(&self.buffers, index_buffer)?,
+                    *index_count,
+                    *base_vertex,
+                    *instance_count,
+                    *base_instance,
+                );
+            }
+            CaptureCommand::Execute {
+                queue,
+                command_buffer,
+            } => {
+                get_mut(&mut self.queues, queue)?
+                    .execute(get(&self.command_buffers, command_buffer)?);
+            }
+            CaptureCommand::ExecuteMany {
+                queue,
+                command_buffers,
+            } => {
+                let command_buffers = command_buffers
+                    .iter()
+                    .map(|id| get(&self.command_buffers, id))
+                    .collect::<Result<Vec<_>, _>>()?;
+                get_mut(&mut self.queues, queue)?.execute_many(&command_buffers);
+            }
+            CaptureCommand::ExecuteWithFence {
+                queue,
+                command_buffer,
+                fence,
+            } => {
+                get_mut(&mut self.queues, queue)?.execute_with_fence(
+                    get(&self.command_buffers, command_buffer)?,
+                    get_mut(&mut self.fences, fence)?,
+                );
+            }
+            CaptureCommand::ExecuteWithSemaphores {
+                queue,
+                command_buffer,
+                wait_semaphores,
+                signal_semaphores,
+            } => {
+                let convert = |semaphores: &[(ObjectId, u64)]| {
+                    semaphores
+                        .iter()
+                        .map(|(id, value)| Ok((get(&self.semaphores, id)?, *value)))
+                        .collect::<Result<Vec<_>, _>>()
+                };
+                let wait_semaphores = convert(wait_semaphores)?;
+                let signal_semaphores = convert(signal_semaphores)?;
+                get_mut(&mut self.queues, queue)?.execute_with_semaphores(
+                    get(&self.command_buffers, command_buffer)?,
+                    &wait_semaphores,
+                    &signal_semaphores,
+                );
+            }
+            CaptureCommand::Present { queue, swap_chain } => {
+                let swap_chain = get_mut(&mut self.swap_chains, swap_chain)?;
+                get_mut(&mut self.queues, queue)?.present(swap_chain);
+                return Ok(swap_chain.read_presented_image());
+            }
+            CaptureCommand::Flush { queue } => {
+                get_mut(&mut self.queues, queue)?.flush();
+            }
+            CaptureCommand::Sync { queue } => {
+                get_mut(&mut self.queues, queue)?.sync();
+            }
+            CaptureCommand::AcquireNextScanBufferView {
+                swap_chain,
+                color_target_view,
+                semaphore,
+                fence,
+            } => {
+                let semaphore = semaphore
+                    .as_ref()
+                    .map(|id| get_mut(&mut self.semaphores, id))
+                    .transpose()?;
+                let fence = fence
+                    .as_ref()
+                    .map(|id| get_mut(&mut self.fences, id))
+                    .transpose()?;
+                let scan_buffer_view = get_mut(&mut self.swap_chains, swap_chain)?
+                    .acquire_next_scan_buffer_view(semaphore, fence)
+                    .clone();
+                self.color_target_views
+                    .insert(*color_target_view, scan_buffer_view);
+            }
+            CaptureCommand::SetRenderScale {
+                swap_chain,
+                render_scale,
+            } => {
+                get_mut(&mut self.swap_chains, swap_chain)?.set_render_scale(*render_scale);
+            }
+            CaptureCommand::SetOutputTransform {
+                swap_chain,
+                output_transform,
+            } => {
+                get_mut(&mut self.swap_chains, swap_chain)?.set_output_transform(output_transform);
+            }
+            CaptureCommand::Resize {
+                swap_chain,
+                width,
+                height,
+            } => {
+                get_mut(&mut self.swap_chains, swap_chain)?.on_resized(*width, *height);
+            }
+            CaptureCommand::WaitFence { fence, timeout } => {
+                get_mut(&mut self.fences, fence)?.wait(timeout.map(Duration::from_nanos));
+            }
+            CaptureCommand::ResetFence { fence } => {
+                get_mut(&mut self.fences, fence)?.reset();
+            }
+            CaptureCommand::WaitSemaphore {
+                semaphore,
+                value,
+                timeout,
+            } => {
+                get(&self.semaphores, semaphore)?.wait(*value, timeout.map(Duration::from_nanos));
+            }
+            CaptureCommand::SignalSemaphore { semaphore, value } => {
+                get(&self.semaphores, semaphore)?.signal(*value);
+            }
+            CaptureCommand::Destroy { id } => self.destroy(id)?,
+        }
+
+        Ok(None)
+    }
+
+    // 番号はオブジェクトの種類をまたいで振られているので、持っているものを探して手放す
+    fn destroy(&mut self, id: &ObjectId) -> Result<(), ReplayError> {
+        let is_destroyed = self.queues.remove(id).is_some()
+            || self.command_buffers.remove(id).is_some()
+            || self.buffers.remove(id).is_some()
+            || self.textures.remove(id).is_some()
+            || self.texture_views.remove(id).is_some()
+            || self.color_target_views.remove(id).is_some()
+            || self.depth_stencil_views.remove(id).is_some()
+            || self.samplers.remove(id).is_some()
+            || self.shaders.remove(id).is_some()
+            || self.vertex_states.remove(id).is_some()
+            || self.fences.remove(id).is_some()
+            || self.semaphores.remove(id).is_some()
+            || self.swap_chains.remove(id).is_some();
+        if is_destroyed {
+            Ok(())
+        } else {
+            Err(ReplayError::ObjectNotFound(*id))
+        }
+    }
+}
+
+fn get<'a, T>(objects: &'a HashMap<ObjectId, T>, id: &ObjectId) -> Result<&'a T, ReplayError> {
+    objects.get(id).ok_or(ReplayError::ObjectNotFound(*id))
+}
+
+fn get_mut<'a, T>(
+    objects: &'a mut HashMap<ObjectId, T>,
+    id: &ObjectId,
+) -> Result<&'a mut T, ReplayError> {
+    objects.get_mut(id).ok_or(ReplayError::ObjectNotFound(*id))
+}
@@ -0,0 +1,80 @@
+use sjgfx_interface::{ColorTargetViewInfo, IColorTargetView, ImageData};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder, TextureCapture},
+};
+
+pub struct ColorTargetViewCapture<TApi: IApi> {
+    color_target_view: TApi::ColorTargetView,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> ColorTargetViewCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::ColorTargetView {
+        &self.color_target_view
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::ColorTargetView {
+        &mut self.color_target_view
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+
+    // スワップチェーンから取得したスキャンバッファ。番号は取得するたびに振る
+    pub(crate) fn new_scan_buffer(
+        color_target_view: TApi::ColorTargetView,
+        id: ObjectId,
+        recorder: Recorder,
+    ) -> Self {
+        Self {
+            color_target_view,
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> IColorTargetView for ColorTargetViewCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+    type TextureType = TextureCapture<TApi>;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &ColorTargetViewInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateColorTargetView {
+            id,
+            texture: texture.get_id(),
+            image_format: info.get_image_format(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            color_target_view: TApi::ColorTargetView::new(
+                device.as_inner(),
+                info,
+                texture.as_inner(),
+            ),
+            id,
+            recorder,
+        }
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.color_target_view.read_image()
+    }
+}
+
+impl<TApi: IApi> Drop for ColorTargetViewCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
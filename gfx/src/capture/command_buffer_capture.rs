@@ -0,0 +1,375 @@
+use sjgfx_interface::{
+    CommandBufferInfo, ICommandBuffer, IndexFormat, PrimitiveTopology, ScissorStateInfo,
+    TextureArrayRange,
+};
+
+use crate::{
+    api::IApi,
+    capture::{
+        BufferCapture, CaptureCommand, ColorTargetViewCapture, DepthStencilViewCapture,
+        DeviceCapture, ObjectId, Recorder, SamplerCapture, ShaderCapture, TextureCapture,
+        TextureViewCapture, VertexStateCapture,
+    },
+};
+
+pub struct CommandBufferCapture<TApi: IApi> {
+    command_buffer: TApi::CommandBuffer,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> CommandBufferCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::CommandBuffer {
+        &self.command_buffer
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> ICommandBuffer for CommandBufferCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+    type BufferType = BufferCapture<TApi>;
+    type ColorTargetViewType = ColorTargetViewCapture<TApi>;
+    type DepthStencilViewType = DepthStencilViewCapture<TApi>;
+    type SamplerType = SamplerCapture<TApi>;
+    type ShaderType = ShaderCapture<TApi>;
+    type TextureType = TextureCapture<TApi>;
+    type TextureViewType = TextureViewCapture<TApi>;
+    type VertexStateType = VertexStateCapture<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &CommandBufferInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateCommandBuffer {
+            id,
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            command_buffer: TApi::CommandBuffer::new(device.as_inner(), info),
+            id,
+            recorder,
+        }
+    }
+
+    fn begin(&mut self) {
+        self.recorder.record(CaptureCommand::Begin {
+            command_buffer: self.id,
+        });
+        self.command_buffer.begin();
+    }
+
+    fn end(&mut self) {
+        self.recorder.record(CaptureCommand::End {
+            command_buffer: self.id,
+        });
+        self.command_buffer.end();
+    }
+
+    fn push_debug_group(&mut self, label: &str) {
+        self.recorder.record(CaptureCommand::PushDebugGroup {
+            command_buffer: self.id,
+            label: label.to_string(),
+        });
+        self.command_buffer.push_debug_group(label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.recorder.record(CaptureCommand::PopDebugGroup {
+            command_buffer: self.id,
+        });
+        self.command_buffer.pop_debug_group();
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.recorder.record(CaptureCommand::InsertDebugMarker {
+            command_buffer: self.id,
+            label: label.to_string(),
+        });
+        self.command_buffer.insert_debug_marker(label);
+    }
+
+    fn clear_color(
+        &mut self,
+        color_target_view: &mut Self::ColorTargetViewType,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        self.recorder.record(CaptureCommand::ClearColor {
+            command_buffer: self.id,
+            color_target_view: color_target_view.get_id(),
+            color: [red, green, blue, alpha],
+            base_index: texture_array_range.get_base_index(),
+            length: texture_array_range.get_length(),
+        });
+        self.command_buffer.clear_color(
+            color_target_view.as_inner_mut(),
+            red,
+            green,
+            blue,
+            alpha,
+            texture_array_range,
+        );
+    }
+
+    fn set_render_targets(
+        &mut self,
+        color_target_views: &[&Self::ColorTargetViewType],
+        depth_stencil_view: Option<&Self::DepthStencilViewType>,
+    ) {
+        self.recorder.record(CaptureCommand::SetRenderTargets {
+            command_buffer: self.id,
+            color_target_views: color_target_views
+                .iter()
+                .map(|view| view.get_id())
+                .collect(),
+            depth_stencil_view: depth_stencil_view.map(DepthStencilViewCapture::get_id),
+        });
+
+        let color_target_views = color_target_views
+            .iter()
+            .map(|view| view.as_inner())
+            .collect::<Vec<_>>();
+        self.command_buffer.set_render_targets(
+            &color_target_views,
+            depth_stencil_view.map(DepthStencilViewCapture::as_inner),
+        );
+    }
+
+    fn set_shader(&mut self, shader: &Self::ShaderType) {
+        self.recorder.record(CaptureCommand::SetShader {
+            command_buffer: self.id,
+            shader: shader.get_id(),
+        });
+        self.command_buffer.set_shader(shader.as_inner());
+    }
+
+    fn set_sampler(&mut self, index: i32, sampler: &Self::SamplerType) {
+        self.recorder.record(CaptureCommand::SetSampler {
+            command_buffer: self.id,
+            index,
+            sampler: sampler.get_id(),
+        });
+        self.command_buffer.set_sampler(index, sampler.as_inner());
+    }
+
+    fn set_texture(&mut self, index: i32, texture_view: &Self::TextureViewType) {
+        self.recorder.record(CaptureCommand::SetTexture {
+            command_buffer: self.id,
+            index,
+            texture_view: texture_view.get_id(),
+        });
+        self.command_buffer
+            .set_texture(index, texture_view.as_inner());
+    }
+
+    fn set_image(&mut self, index: i32, texture: &Self::TextureViewType) {
+        self.recorder.record(CaptureCommand::SetImage {
+            command_buffer: self.id,
+            index,
+            texture_view: texture.get_id(),
+        });
+        self.command_buffer.set_image(index, texture.as_inner());
+    }
+
+    fn set_constant_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.recorder.record(CaptureCommand::SetConstantBuffer {
+            command_buffer: self.id,
+            index,
+            buffer: buffer.get_id(),
+            range: None,
+        });
+        self.command_buffer
+            .set_constant_buffer(index, buffer.as_inner());
+    }
+
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.recorder.record(CaptureCommand::SetConstantBuffer {
+            command_buffer: self.id,
+            index,
+            buffer: buffer.get_id(),
+            range: Some((offset as u64, size as u64)),
+        });
+        self.command_buffer
+            .set_constant_buffer_range(index, buffer.as_inner(), offset, size);
+    }
+
+    fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.recorder
+            .record(CaptureCommand::SetUnorderedAccessBuffer {
+                command_buffer: self.id,
+                index,
+                buffer: buffer.get_id(),
+            });
+        self.command_buffer
+            .set_unordered_access_buffer(index, buffer.as_inner());
+    }
+
+    fn set_vertex_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        self.recorder.record(CaptureCommand::SetVertexBuffer {
+            command_buffer: self.id,
+            index,
+            buffer: buffer.get_id(),
+            range: None,
+        });
+        self.command_buffer
+            .set_vertex_buffer(index, buffer.as_inner());
+    }
+
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        self.recorder.record(CaptureCommand::SetVertexBuffer {
+            command_buffer: self.id,
+            index,
+            buffer: buffer.get_id(),
+            range: Some((offset as u64, size as u64)),
+        });
+        self.command_buffer
+            .set_vertex_buffer_range(index, buffer.as_inner(), offset, size);
+    }
+
+    fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
+        self.recorder.record(CaptureCommand::SetVertexState {
+            command_buffer: self.id,
+            vertex_state: vertex_state.get_id(),
+        });
+        self.command_buffer
+            .set_vertex_state(vertex_state.as_inner());
+    }
+
+    fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo) {
+        self.recorder.record(CaptureCommand::SetScissor {
+            command_buffer: self.id,
+            scissor: *scissor_state_info,
+        });
+        self.command_buffer.set_scissor(scissor_state_info);
+    }
+
+    fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        self.recorder.record(CaptureCommand::Dispatch {
+            command_buffer: self.id,
+            count: [count_x, count_y, count_z],
+        });
+        self.command_buffer.dispatch(count_x, count_y, count_z);
+    }
+
+    fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        self.recorder.record(CaptureCommand::Draw {
+            command_buffer: self.id,
+            primitive_topology: primitive_topology.clone(),
+            vertex_count,
+            vertex_offset,
+        });
+        self.command_buffer
+            .draw(primitive_topology, vertex_count, vertex_offset);
+    }
+
+    fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.recorder.record(CaptureCommand::DrawInstanced {
+            command_buffer: self.id,
+            primitive_topology: primitive_topology.clone(),
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        });
+        self.command_buffer.draw_instanced(
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        );
+    }
+
+    fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        self.recorder.record(CaptureCommand::DrawIndexed {
+            command_buffer: self.id,
+            primitive_topology: primitive_topology.clone(),
+            index_format: index_format.clone(),
+            index_buffer: index_buffer.get_id(),
+            index_count,
+            base_vertex,
+        });
+        self.command_buffer.draw_indexed(
+            primitive_topology,
+            index_format,
+            index_buffer.as_inner(),
+            index_count,
+            base_vertex,
+        );
+    }
+
+    fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.recorder.record(CaptureCommand::DrawIndexedInstanced {
+            command_buffer: self.id,
+            primitive_topology: primitive_topology.clone(),
+            index_format: index_format.clone(),
+            index_buffer: index_buffer.get_id(),
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        });
+        self.command_buffer.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer.as_inner(),
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        );
+    }
+}
+
+impl<TApi: IApi> Drop for CommandBufferCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
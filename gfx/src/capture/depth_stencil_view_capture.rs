@@ -0,0 +1,59 @@
+use sjgfx_interface::{DepthStencilStateInfo, IDepthStencilView};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder, TextureCapture},
+};
+
+pub struct DepthStencilViewCapture<TApi: IApi> {
+    depth_stencil_view: TApi::DepthStencilView,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> DepthStencilViewCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::DepthStencilView {
+        &self.depth_stencil_view
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> IDepthStencilView for DepthStencilViewCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+    type TextureType = TextureCapture<TApi>;
+
+    fn new(
+        device: &Self::DeviceType,
+        info: &DepthStencilStateInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateDepthStencilView {
+            id,
+            texture: texture.get_id(),
+            is_depth_test_enabled: info.is_depth_test_enabled(),
+            is_depth_write_enabled: info.is_depth_write_enabled(),
+        });
+
+        Self {
+            depth_stencil_view: TApi::DepthStencilView::new(
+                device.as_inner(),
+                info,
+                texture.as_inner(),
+            ),
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> Drop for DepthStencilViewCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,70 @@
+use std::{io, path::Path};
+
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{AdapterInfo, DeviceCapabilities, DeviceInfo, IDevice};
+
+use crate::{
+    api::IApi,
+    capture::{Capture, CaptureCommand, Recorder},
+};
+
+pub struct DeviceCapture<TApi: IApi> {
+    device: TApi::Device,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> DeviceCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Device {
+        &self.device
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Device {
+        &mut self.device
+    }
+
+    pub(crate) fn clone_recorder(&self) -> Recorder {
+        self.recorder.clone()
+    }
+
+    // ここまでに記録したコマンド
+    pub fn get_capture(&self) -> Capture {
+        self.recorder.create_capture()
+    }
+
+    pub fn save_capture<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.get_capture().save(path)
+    }
+
+    fn new_impl(device: TApi::Device, info: &DeviceInfo) -> Self {
+        let recorder = Recorder::new();
+        recorder.record(CaptureCommand::CreateDevice {
+            debug_mode: info.get_debug_mode(),
+        });
+        Self { device, recorder }
+    }
+}
+
+impl<TApi: IApi> IDevice for DeviceCapture<TApi> {
+    fn new(info: &DeviceInfo) -> Self {
+        Self::new_impl(TApi::Device::new(info), info)
+    }
+
+    fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self::new_impl(TApi::Device::new_with_handle(info, raw_handle), info)
+    }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.device.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        TApi::Device::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.device.get_adapter_info()
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use sjgfx_interface::{FenceInfo, IFence};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder},
+};
+
+pub struct FenceCapture<TApi: IApi> {
+    fence: TApi::Fence,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> FenceCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Fence {
+        &self.fence
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Fence {
+        &mut self.fence
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> IFence for FenceCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateFence {
+            id,
+            is_signaled: info.is_signaled(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            fence: TApi::Fence::new(device.as_inner(), info),
+            id,
+            recorder,
+        }
+    }
+
+    // 別のスレッドのサブミットを待つこともあるので、待ち終わってから記録する
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        let is_signaled = self.fence.wait(timeout);
+        self.recorder.record(CaptureCommand::WaitFence {
+            fence: self.id,
+            timeout: timeout.map(|timeout| timeout.as_nanos() as u64),
+        });
+        is_signaled
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.fence.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.recorder
+            .record(CaptureCommand::ResetFence { fence: self.id });
+        self.fence.reset();
+    }
+}
+
+impl<TApi: IApi> Drop for FenceCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
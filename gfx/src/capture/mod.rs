@@ -0,0 +1,77 @@
+mod buffer_capture;
+mod capture_command;
+mod capture_file;
+mod capture_replayer;
+mod color_target_view_capture;
+mod command_buffer_capture;
+mod depth_stencil_view_capture;
+mod device_capture;
+mod fence_capture;
+mod queue_capture;
+mod sampler_capture;
+mod semaphore_capture;
+mod shader_capture;
+mod swap_chain_capture;
+mod texture_capture;
+mod texture_view_capture;
+mod vertex_state_capture;
+
+pub use buffer_capture::BufferCapture;
+pub use capture_command::{
+    CaptureCommand, CaptureData, CaptureTextureRegion, CaptureVertexAttribute, CaptureVertexBuffer,
+    ObjectId,
+};
+pub use capture_file::{Capture, CAPTURE_VERSION};
+pub use capture_replayer::{ReplayError, TCaptureReplayer};
+pub use color_target_view_capture::ColorTargetViewCapture;
+pub use command_buffer_capture::CommandBufferCapture;
+pub use depth_stencil_view_capture::DepthStencilViewCapture;
+pub use device_capture::DeviceCapture;
+pub use fence_capture::FenceCapture;
+pub use queue_capture::QueueCapture;
+pub use sampler_capture::SamplerCapture;
+pub use semaphore_capture::SemaphoreCapture;
+pub use shader_capture::ShaderCapture;
+pub use swap_chain_capture::SwapChainCapture;
+pub use texture_capture::TextureCapture;
+pub use texture_view_capture::TextureViewCapture;
+pub use vertex_state_capture::VertexStateCapture;
+
+use std::sync::{Arc, Mutex};
+
+// デバイスから作ったすべてのオブジェクトで共有して、呼び出された順にコマンドをためる
+#[derive(Clone)]
+pub(crate) struct Recorder {
+    state: Arc<Mutex<RecorderState>>,
+}
+
+struct RecorderState {
+    commands: Vec<CaptureCommand>,
+    next_id: u32,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(RecorderState {
+                commands: Vec::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    pub fn create_id(&self) -> ObjectId {
+        let mut state = self.state.lock().unwrap();
+        let id = ObjectId::new(state.next_id);
+        state.next_id += 1;
+        id
+    }
+
+    pub fn record(&self, command: CaptureCommand) {
+        self.state.lock().unwrap().commands.push(command);
+    }
+
+    pub fn create_capture(&self) -> Capture {
+        Capture::new(self.state.lock().unwrap().commands.clone())
+    }
+}
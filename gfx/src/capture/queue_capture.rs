@@ -0,0 +1,153 @@
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+
+use crate::{
+    api::IApi,
+    capture::{
+        CaptureCommand, CommandBufferCapture, DeviceCapture, FenceCapture, ObjectId, Recorder,
+        SemaphoreCapture, SwapChainCapture,
+    },
+};
+
+pub struct QueueCapture<TApi: IApi> {
+    queue: TApi::Queue,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> QueueCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Queue {
+        &self.queue
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> IQueue for QueueCapture<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type DeviceType = DeviceCapture<TApi>;
+    type CommandBufferType = CommandBufferCapture<TApi>;
+    type FenceType = FenceCapture<TApi>;
+    type SemaphoreType = SemaphoreCapture<TApi>;
+    type SwapChainType = SwapChainCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateQueue {
+            id,
+            queue_type: info.get_queue_type().clone(),
+        });
+
+        Self {
+            queue: TApi::Queue::new(device.as_inner_mut(), info),
+            id,
+            recorder,
+        }
+    }
+
+    fn execute(&mut self, command_buffer: &Self::CommandBufferType) {
+        self.recorder.record(CaptureCommand::Execute {
+            queue: self.id,
+            command_buffer: command_buffer.get_id(),
+        });
+        self.queue.execute(command_buffer.as_inner());
+    }
+
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        self.recorder.record(CaptureCommand::ExecuteMany {
+            queue: self.id,
+            command_buffers: command_buffers
+                .iter()
+                .map(|command_buffer| command_buffer.get_id())
+                .collect(),
+        });
+        let command_buffers = command_buffers
+            .iter()
+            .map(|command_buffer| command_buffer.as_inner())
+            .collect::<Vec<_>>();
+        self.queue.execute_many(&command_buffers);
+    }
+
+    fn execute_with_fence(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
+    ) {
+        self.recorder.record(CaptureCommand::ExecuteWithFence {
+            queue: self.id,
+            command_buffer: command_buffer.get_id(),
+            fence: fence.get_id(),
+        });
+        self.queue
+            .execute_with_fence(command_buffer.as_inner(), fence.as_inner_mut());
+    }
+
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        let convert_ids = |semaphores: &[(&Self::SemaphoreType, u64)]| {
+            semaphores
+                .iter()
+                .map(|(semaphore, value)| (semaphore.get_id(), *value))
+                .collect()
+        };
+        self.recorder.record(CaptureCommand::ExecuteWithSemaphores {
+            queue: self.id,
+            command_buffer: command_buffer.get_id(),
+            wait_semaphores: convert_ids(wait_semaphores),
+            signal_semaphores: convert_ids(signal_semaphores),
+        });
+
+        let wait_semaphores = wait_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.as_inner(), *value))
+            .collect::<Vec<_>>();
+        let signal_semaphores = signal_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.as_inner(), *value))
+            .collect::<Vec<_>>();
+        self.queue.execute_with_semaphores(
+            command_buffer.as_inner(),
+            &wait_semaphores,
+            &signal_semaphores,
+        );
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.queue.get_queue_type()
+    }
+
+    fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
+        self.recorder.record(CaptureCommand::Present {
+            queue: self.id,
+            swap_chain: swap_chain.get_id(),
+        });
+        self.queue.present(swap_chain.as_inner_mut());
+    }
+
+    fn flush(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Flush { queue: self.id });
+        self.queue.flush();
+    }
+
+    fn sync(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Sync { queue: self.id });
+        self.queue.sync();
+    }
+}
+
+impl<TApi: IApi> Drop for QueueCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,48 @@
+use sjgfx_interface::{ISampler, SamplerInfo};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder},
+};
+
+pub struct SamplerCapture<TApi: IApi> {
+    sampler: TApi::Sampler,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> SamplerCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Sampler {
+        &self.sampler
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> ISampler for SamplerCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &SamplerInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateSampler {
+            id,
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            sampler: TApi::Sampler::new(device.as_inner_mut(), info),
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> Drop for SamplerCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
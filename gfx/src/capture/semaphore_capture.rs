@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder},
+};
+
+pub struct SemaphoreCapture<TApi: IApi> {
+    semaphore: TApi::Semaphore,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> SemaphoreCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Semaphore {
+        &self.semaphore
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Semaphore {
+        &mut self.semaphore
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> ISemaphore for SemaphoreCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateSemaphore {
+            id,
+            initial_value: info.get_initial_value(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            semaphore: TApi::Semaphore::new(device.as_inner(), info),
+            id,
+            recorder,
+        }
+    }
+
+    fn get_value(&self) -> u64 {
+        self.semaphore.get_value()
+    }
+
+    // 別のスレッドのシグナルを待つこともあるので、待ち終わってから記録する
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        let is_reached = self.semaphore.wait(value, timeout);
+        self.recorder.record(CaptureCommand::WaitSemaphore {
+            semaphore: self.id,
+            value,
+            timeout: timeout.map(|timeout| timeout.as_nanos() as u64),
+        });
+        is_reached
+    }
+
+    fn signal(&self, value: u64) {
+        self.recorder.record(CaptureCommand::SignalSemaphore {
+            semaphore: self.id,
+            value,
+        });
+        self.semaphore.signal(value);
+    }
+}
+
+impl<TApi: IApi> Drop for SemaphoreCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,54 @@
+use sjgfx_interface::{IShader, ShaderInfo};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, CaptureData, DeviceCapture, ObjectId, Recorder},
+};
+
+pub struct ShaderCapture<TApi: IApi> {
+    shader: TApi::Shader,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> ShaderCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Shader {
+        &self.shader
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> IShader for ShaderCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &ShaderInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        let convert_binary = |binary: &Option<&[u8]>| binary.map(|x| CaptureData::new(x.to_vec()));
+        recorder.record(CaptureCommand::CreateShader {
+            id,
+            compute_shader_binary: convert_binary(info.get_compute_shader_binary()),
+            vertex_shader_binary: convert_binary(info.get_vertex_shader_binary()),
+            pixel_shader_binary: convert_binary(info.get_pixel_shader_binary()),
+            vertex_shader_source: info.get_vertex_shader_source().map(str::to_string),
+            pixel_shader_source: info.get_pixel_shader_source().map(str::to_string),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            shader: TApi::Shader::new(device.as_inner_mut(), info),
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> Drop for ShaderCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
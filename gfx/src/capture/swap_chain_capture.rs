@@ -0,0 +1,192 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    CompositeAlphaMode, IDisplayEventListener, ISwapChain, ImageData, ImageFormat, OutputTransform,
+    PresentMode, SwapChainInfo, SwapChainStatus,
+};
+
+use crate::{
+    api::IApi,
+    capture::{
+        CaptureCommand, ColorTargetViewCapture, DeviceCapture, FenceCapture, ObjectId, Recorder,
+        SemaphoreCapture,
+    },
+};
+
+pub struct SwapChainCapture<TApi: IApi> {
+    swap_chain: TApi::SwapChain,
+    id: ObjectId,
+    recorder: Recorder,
+
+    // バックエンドが返すビューは参照なので、複製して包んだものを返す
+    scan_buffer_view: Option<ColorTargetViewCapture<TApi>>,
+}
+
+impl<TApi: IApi> SwapChainCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::SwapChain {
+        &self.swap_chain
+    }
+
+    // 表示したスキャンバッファのビューを持ち続けないように、表示の前に手放す
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::SwapChain {
+        self.scan_buffer_view = None;
+        &mut self.swap_chain
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+
+    fn new_impl(swap_chain: TApi::SwapChain, recorder: Recorder, info: &SwapChainInfo) -> Self {
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateSwapChain {
+            id,
+            width: info.get_width(),
+            height: info.get_height(),
+            present_mode: info.get_present_mode(),
+            preferred_formats: info.get_preferred_formats().to_vec(),
+            alpha_mode: info.get_alpha_mode(),
+            frame_latency: info.get_frame_latency(),
+            is_direct_rendering_enabled: info.is_direct_rendering_enabled(),
+            render_scale: info.get_render_scale(),
+            render_size: info.get_render_size(),
+            scaling_mode: info.get_scaling_mode(),
+            upscale_filter: info.get_upscale_filter(),
+            render_format: info.get_render_format().clone(),
+            output_transform: info.get_output_transform().clone(),
+        });
+
+        Self {
+            swap_chain,
+            id,
+            recorder,
+            scan_buffer_view: None,
+        }
+    }
+}
+
+impl<TApi: IApi> ISwapChain for SwapChainCapture<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type ColorTargetViewType = ColorTargetViewCapture<TApi>;
+    type DeviceType = DeviceCapture<TApi>;
+    type SemaphoreType = SemaphoreCapture<TApi>;
+    type FenceType = FenceCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self {
+        let swap_chain = TApi::SwapChain::new(device.as_inner_mut(), info);
+        Self::new_impl(swap_chain, device.clone_recorder(), info)
+    }
+
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        let swap_chain = TApi::SwapChain::new_with_handle(device.as_inner_mut(), info, raw_handle);
+        Self::new_impl(swap_chain, device.clone_recorder(), info)
+    }
+
+    fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
+    ) -> &mut Self::ColorTargetViewType {
+        let id = self.recorder.create_id();
+        self.recorder
+            .record(CaptureCommand::AcquireNextScanBufferView {
+                swap_chain: self.id,
+                color_target_view: id,
+                semaphore: semaphore.as_ref().map(|semaphore| semaphore.get_id()),
+                fence: fence.as_ref().map(|fence| fence.get_id()),
+            });
+
+        let scan_buffer_view = self
+            .swap_chain
+            .acquire_next_scan_buffer_view(
+                semaphore.map(SemaphoreCapture::as_inner_mut),
+                fence.map(FenceCapture::as_inner_mut),
+            )
+            .clone();
+        self.scan_buffer_view
+            .insert(ColorTargetViewCapture::new_scan_buffer(
+                scan_buffer_view,
+                id,
+                self.recorder.clone(),
+            ))
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.swap_chain.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.swap_chain.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.swap_chain.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.swap_chain.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.swap_chain.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.swap_chain.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.swap_chain.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.swap_chain.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.recorder.record(CaptureCommand::SetRenderScale {
+            swap_chain: self.id,
+            render_scale,
+        });
+        self.swap_chain.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.recorder.record(CaptureCommand::SetOutputTransform {
+            swap_chain: self.id,
+            output_transform: output_transform.clone(),
+        });
+        self.swap_chain.set_output_transform(output_transform);
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        self.swap_chain.read_presented_image()
+    }
+}
+
+impl<TApi: IApi> IDisplayEventListener for SwapChainCapture<TApi> {
+    fn on_resized(&mut self, width: u32, height: u32) {
+        self.recorder.record(CaptureCommand::Resize {
+            swap_chain: self.id,
+            width,
+            height,
+        });
+        self.scan_buffer_view = None;
+        self.swap_chain.on_resized(width, height);
+    }
+}
+
+impl<TApi: IApi> Drop for SwapChainCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,93 @@
+use sjgfx_interface::{ITexture, ImageData, TextureCopyRegion, TextureInfo};
+
+use crate::{
+    api::IApi,
+    capture::{
+        CaptureCommand, CaptureData, CaptureTextureRegion, DeviceCapture, ObjectId, Recorder,
+    },
+};
+
+pub struct TextureCapture<TApi: IApi> {
+    texture: TApi::Texture,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> TextureCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::Texture {
+        &self.texture
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+
+    fn record_create(recorder: &Recorder, info: &TextureInfo, data: Option<&[u8]>) -> ObjectId {
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateTexture {
+            id,
+            width: info.get_width(),
+            height: info.get_height(),
+            depth: info.get_depth(),
+            mip_count: info.get_mip_count(),
+            gpu_access: *info.get_gpu_access_flags(),
+            image_format: info.get_image_format().clone(),
+            debug_label: info.get_debug_label().map(str::to_string),
+            data: data.map(|data| CaptureData::new(data.to_vec())),
+        });
+        id
+    }
+}
+
+impl<TApi: IApi> ITexture for TextureCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = Self::record_create(&recorder, info, None);
+        Self {
+            texture: TApi::Texture::new(device.as_inner_mut(), info),
+            id,
+            recorder,
+        }
+    }
+
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        let recorder = device.clone_recorder();
+        let id = Self::record_create(&recorder, info, Some(data));
+        Self {
+            texture: TApi::Texture::new_with_data(device.as_inner(), info, data),
+            id,
+            recorder,
+        }
+    }
+
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.recorder.record(CaptureCommand::WriteTexture {
+            texture: self.id,
+            region: CaptureTextureRegion::from_region(region),
+            data: CaptureData::new(data.to_vec()),
+            bytes_per_row: bytes_per_row as u64,
+            rows_per_image: rows_per_image as u64,
+        });
+        self.texture
+            .write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.texture.read_image()
+    }
+}
+
+impl<TApi: IApi> Drop for TextureCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,51 @@
+use sjgfx_interface::{ITextureView, TextureViewInfo};
+
+use crate::{
+    api::IApi,
+    capture::{CaptureCommand, DeviceCapture, ObjectId, Recorder, TextureCapture},
+};
+
+pub struct TextureViewCapture<TApi: IApi> {
+    texture_view: TApi::TextureView,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> TextureViewCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::TextureView {
+        &self.texture_view
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> ITextureView for TextureViewCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+    type TextureType = TextureCapture<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &TextureViewInfo, texture: &Self::TextureType) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateTextureView {
+            id,
+            texture: texture.get_id(),
+            image_format: info.get_format(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            texture_view: TApi::TextureView::new(device.as_inner(), info, texture.as_inner()),
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> Drop for TextureViewCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
@@ -0,0 +1,61 @@
+use sjgfx_interface::{IVertexState, VertexStateInfo};
+
+use crate::{
+    api::IApi,
+    capture::{
+        CaptureCommand, CaptureVertexAttribute, CaptureVertexBuffer, DeviceCapture, ObjectId,
+        Recorder,
+    },
+};
+
+pub struct VertexStateCapture<TApi: IApi> {
+    vertex_state: TApi::VertexState,
+    id: ObjectId,
+    recorder: Recorder,
+}
+
+impl<TApi: IApi> VertexStateCapture<TApi> {
+    pub fn as_inner(&self) -> &TApi::VertexState {
+        &self.vertex_state
+    }
+
+    pub fn get_id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl<TApi: IApi> IVertexState for VertexStateCapture<TApi> {
+    type DeviceType = DeviceCapture<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &VertexStateInfo) -> Self {
+        let recorder = device.clone_recorder();
+        let id = recorder.create_id();
+        recorder.record(CaptureCommand::CreateVertexState {
+            id,
+            attributes: info
+                .get_attribute_state_info_array()
+                .iter()
+                .map(CaptureVertexAttribute::from_info)
+                .collect(),
+            buffers: info
+                .get_buffer_state_info_array()
+                .iter()
+                .map(CaptureVertexBuffer::from_info)
+                .collect(),
+            debug_label: info.get_debug_label().map(str::to_string),
+        });
+
+        Self {
+            vertex_state: TApi::VertexState::new(device.as_inner(), info),
+            id,
+            recorder,
+        }
+    }
+}
+
+impl<TApi: IApi> Drop for VertexStateCapture<TApi> {
+    fn drop(&mut self) {
+        self.recorder
+            .record(CaptureCommand::Destroy { id: self.id });
+    }
+}
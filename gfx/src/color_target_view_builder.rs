@@ -16,6 +16,7 @@ impl<TApi: IApi> TColorTargetViewBuilder<TApi> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &TApi::Device, texture: &TApi::Texture) -> TApi::ColorTargetView {
         TApi::ColorTargetView::new(device, &self.info, texture)
     }
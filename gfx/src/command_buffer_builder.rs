@@ -3,17 +3,27 @@ use sjgfx_interface::{CommandBufferInfo, ICommandBuffer};
 use crate::api::IApi;
 
 pub struct TCommandBufferBuilder<T: IApi> {
+    info: CommandBufferInfo,
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T: IApi> TCommandBufferBuilder<T> {
     pub fn new() -> Self {
         Self {
+            info: CommandBufferInfo::new(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &T::Device) -> T::CommandBuffer {
-        T::CommandBuffer::new(device, &CommandBufferInfo::new())
+        T::CommandBuffer::new(device, &self.info)
+    }
+
+    pub fn with_debug_label(self, debug_label: &str) -> Self {
+        Self {
+            info: self.info.set_debug_label(debug_label),
+            _marker: std::marker::PhantomData,
+        }
     }
 }
@@ -1,5 +1,8 @@
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
-use sjgfx_interface::{DebugMode, DeviceInfo, IDevice};
+use sjgfx_interface::{
+    AdapterInfo, ApiType, BackendMask, DebugMode, DeviceFeatures, DeviceInfo, IDevice,
+    PowerPreference,
+};
 
 use crate::api::IApi;
 
@@ -16,15 +19,17 @@ impl<T: IApi> TDeviceBuilder<T> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self) -> T::Device {
-        T::Device::new(&DeviceInfo::new())
+        T::Device::new(&self.info)
     }
 
+    #[track_caller]
     pub fn build_with_surface<TRawHandle>(&self, raw_handle: &TRawHandle) -> T::Device
     where
         TRawHandle: HasRawWindowHandle + HasRawDisplayHandle,
     {
-        T::Device::new_with_handle(&DeviceInfo::new(), raw_handle)
+        T::Device::new_with_handle(&self.info, raw_handle)
     }
 
     pub fn enable_debug_assertion(self) -> Self {
@@ -33,6 +38,53 @@ impl<T: IApi> TDeviceBuilder<T> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    pub fn enumerate_adapters(&self) -> Vec<AdapterInfo> {
+        T::Device::enumerate_adapters(&self.info)
+    }
+
+    pub fn with_adapter_index(self, adapter_index: usize) -> Self {
+        Self {
+            info: self.info.set_adapter_index(adapter_index),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_power_preference(self, power_preference: PowerPreference) -> Self {
+        Self {
+            info: self.info.set_power_preference(power_preference),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_backends(self, backends: BackendMask) -> Self {
+        Self {
+            info: self.info.set_backends(backends),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_fallback_adapter_only(self) -> Self {
+        Self {
+            info: self.info.set_fallback_adapter_only(true),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_optional_features(self, optional_features: DeviceFeatures) -> Self {
+        Self {
+            info: self.info.set_optional_features(optional_features),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    // api::AnyApi で優先して使うバックエンド
+    pub fn with_api_type(self, api_type: ApiType) -> Self {
+        Self {
+            info: self.info.set_api_type(api_type),
+            _marker: std::marker::PhantomData,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
+pub mod any;
 pub mod api;
 mod buffer_builder;
+pub mod capture;
 mod color_target_view_builder;
 mod command_buffer_builder;
 mod device_builder;
@@ -10,6 +12,8 @@ mod shader_builder;
 mod swap_chain_builder;
 mod texture_builder;
 mod texture_view_builder;
+mod upload_ring_buffer;
+pub mod validation;
 mod vertex_state_builder;
 
 pub use buffer_builder::TBufferBuilder;
@@ -23,15 +27,23 @@ pub use shader_builder::TShaderBuilder;
 pub use swap_chain_builder::TSwapChainBuilder;
 pub use texture_builder::TTextureBuilder;
 pub use texture_view_builder::TTextureViewBuilder;
+pub use upload_ring_buffer::{TUploadRingBuffer, TUploadRingBufferBuilder, UploadAllocation};
 pub use vertex_state_builder::TVertexStateBuilder;
 
 pub mod vulkano;
 pub mod wgpu;
 
-#[cfg(feature = "backend-ash")]
+#[cfg(feature = "backend-any")]
+type BackendApi = api::AnyApi;
+
+#[cfg(all(not(feature = "backend-any"), feature = "backend-ash"))]
 type BackendApi = api::Ash;
 
-#[cfg(all(not(feature = "backend-ash"), feature = "backend-wgpu"))]
+#[cfg(all(
+    not(feature = "backend-any"),
+    not(feature = "backend-ash"),
+    feature = "backend-wgpu"
+))]
 type BackendApi = api::Wgpu;
 
 pub type BufferBuilder = TBufferBuilder<BackendApi>;
@@ -40,6 +52,7 @@ pub type DeviceBuilder = TDeviceBuilder<BackendApi>;
 pub type QueueBuilder = TQueueBuilder<BackendApi>;
 pub type ShaderBuilder = TShaderBuilder<BackendApi>;
 pub type SwapChainBuilder = TSwapChainBuilder<BackendApi>;
+pub type UploadRingBufferBuilder = TUploadRingBufferBuilder<BackendApi>;
 pub type VertexStateBuilder = TVertexStateBuilder<BackendApi>;
 
 pub struct FenceBuilder;
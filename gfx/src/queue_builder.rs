@@ -1,19 +1,29 @@
-use sjgfx_interface::{IQueue, QueueInfo};
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
 
 use crate::api::IApi;
 
 pub struct TQueueBuilder<T: IApi> {
+    info: QueueInfo,
     _marker: std::marker::PhantomData<T>,
 }
 
 impl<T: IApi> TQueueBuilder<T> {
     pub fn new() -> Self {
         Self {
+            info: QueueInfo::new(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut T::Device) -> T::Queue {
-        T::Queue::new(device, &QueueInfo::new())
+        T::Queue::new(device, &self.info)
+    }
+
+    pub fn with_queue_type(self, queue_type: QueueType) -> Self {
+        Self {
+            info: self.info.set_queue_type(queue_type),
+            _marker: std::marker::PhantomData,
+        }
     }
 }
@@ -15,6 +15,7 @@ impl<TApi: IApi> TSamplerBuilder<TApi> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut TApi::Device) -> TApi::Sampler {
         TApi::Sampler::new(device, &self.info)
     }
@@ -3,18 +3,28 @@ use sjgfx_interface::{ISemaphore, SemaphoreInfo};
 use crate::api::IApi;
 
 pub struct TSemaphoreBuilder<TApi: IApi> {
+    info: SemaphoreInfo,
     _marker: std::marker::PhantomData<TApi>,
 }
 
 impl<TApi: IApi> TSemaphoreBuilder<TApi> {
     pub fn new() -> Self {
         Self {
+            info: SemaphoreInfo::new(),
             _marker: std::marker::PhantomData,
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &TApi::Device) -> TApi::Semaphore {
-        TApi::Semaphore::new(device, &SemaphoreInfo::new())
+        TApi::Semaphore::new(device, &self.info)
+    }
+
+    pub fn with_initial_value(self, initial_value: u64) -> Self {
+        Self {
+            info: self.info.set_initial_value(initial_value),
+            _marker: std::marker::PhantomData,
+        }
     }
 }
 
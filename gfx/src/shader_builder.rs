@@ -19,6 +19,7 @@ impl<T: IApi> TShaderBuilder<T> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut T::Device) -> T::Shader {
         let shader_info = self.create_info();
         T::Shader::new(device, &shader_info)
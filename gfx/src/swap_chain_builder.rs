@@ -1,4 +1,8 @@
-use sjgfx_interface::{ISwapChain, SwapChainInfo};
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    CompositeAlphaMode, ISwapChain, ImageFormat, OutputTransform, PresentMode, ScalingMode,
+    SwapChainInfo, UpscaleFilter,
+};
 
 use crate::api::IApi;
 
@@ -15,10 +19,24 @@ impl<T: IApi> TSwapChainBuilder<T> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut T::Device) -> T::SwapChain {
         T::SwapChain::new(device, &self.info)
     }
 
+    // 既存のデバイスで別のウィンドウ用のスワップチェーンを作る
+    #[track_caller]
+    pub fn build_with_surface<TRawHandle>(
+        &self,
+        device: &mut T::Device,
+        raw_handle: &TRawHandle,
+    ) -> T::SwapChain
+    where
+        TRawHandle: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        T::SwapChain::new_with_handle(device, &self.info, raw_handle)
+    }
+
     pub fn with_width(self, width: u32) -> Self {
         Self {
             info: self.info.with_width(width),
@@ -32,4 +50,82 @@ impl<T: IApi> TSwapChainBuilder<T> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    pub fn with_present_mode(self, present_mode: PresentMode) -> Self {
+        Self {
+            info: self.info.with_present_mode(present_mode),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_preferred_formats(self, preferred_formats: &[ImageFormat]) -> Self {
+        Self {
+            info: self.info.with_preferred_formats(preferred_formats),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_alpha_mode(self, alpha_mode: CompositeAlphaMode) -> Self {
+        Self {
+            info: self.info.with_alpha_mode(alpha_mode),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_frame_latency(self, frame_latency: u32) -> Self {
+        Self {
+            info: self.info.with_frame_latency(frame_latency),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    // 内部のカラーターゲットを経由せずにスキャンバッファに直接描画する
+    pub fn enable_direct_rendering(self) -> Self {
+        Self {
+            info: self.info.with_direct_rendering_enabled(true),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_render_scale(self, render_scale: f32) -> Self {
+        Self {
+            info: self.info.with_render_scale(render_scale),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_render_size(self, width: u32, height: u32) -> Self {
+        Self {
+            info: self.info.with_render_size(width, height),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_scaling_mode(self, scaling_mode: ScalingMode) -> Self {
+        Self {
+            info: self.info.with_scaling_mode(scaling_mode),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_upscale_filter(self, upscale_filter: UpscaleFilter) -> Self {
+        Self {
+            info: self.info.with_upscale_filter(upscale_filter),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_render_format(self, render_format: ImageFormat) -> Self {
+        Self {
+            info: self.info.with_render_format(render_format),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn with_output_transform(self, output_transform: OutputTransform) -> Self {
+        Self {
+            info: self.info.with_output_transform(output_transform),
+            _marker: std::marker::PhantomData,
+        }
+    }
 }
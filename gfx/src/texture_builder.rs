@@ -17,6 +17,7 @@ impl<'a, TApi: IApi> TTextureBuilder<'a, TApi> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &mut TApi::Device) -> TApi::Texture {
         if let Some(data) = self.data {
             TApi::Texture::new_with_data(device, &self.info, data)
@@ -41,6 +42,11 @@ impl<'a, TApi: IApi> TTextureBuilder<'a, TApi> {
         self.enable_bit(GpuAccess::DEPTH_STENCIL)
     }
 
+    // ITexture::write で書き込めるようにする
+    pub fn enable_write(self) -> Self {
+        self.enable_bit(GpuAccess::WRITE)
+    }
+
     pub fn with_size(self, width: i32, height: i32) -> Self {
         Self {
             info: self.info.set_width(width).set_height(height),
@@ -57,6 +63,14 @@ impl<'a, TApi: IApi> TTextureBuilder<'a, TApi> {
         }
     }
 
+    pub fn with_mip_count(self, mip_count: i32) -> Self {
+        Self {
+            info: self.info.set_mip_count(mip_count),
+            data: self.data,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
     pub fn with_data(self, data: &'a [u8]) -> Self {
         Self {
             info: self.info,
@@ -65,6 +79,14 @@ impl<'a, TApi: IApi> TTextureBuilder<'a, TApi> {
         }
     }
 
+    pub fn with_debug_label(self, debug_label: &str) -> Self {
+        Self {
+            info: self.info.set_debug_label(debug_label),
+            data: self.data,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
     fn enable_bit(self, gpu_access: GpuAccess) -> Self {
         let gpu_access = *self.info.get_gpu_access_flags() | gpu_access;
 
@@ -15,6 +15,7 @@ impl<TApi: IApi> TTextureViewBuilder<TApi> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &TApi::Device, texture: &TApi::Texture) -> TApi::TextureView {
         TApi::TextureView::new(device, &self.info, texture)
     }
@@ -0,0 +1,380 @@
+use std::collections::VecDeque;
+
+use sjgfx_interface::{
+    BufferInfo, CpuAccessMode, FenceInfo, GpuAccess, IBuffer, ICommandBuffer, IDevice, IFence,
+};
+
+use crate::api::IApi;
+
+// リングバッファーから切り出した領域。offset と size はバイト単位
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadAllocation {
+    offset: usize,
+    size: usize,
+}
+
+impl UploadAllocation {
+    pub fn get_offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+}
+
+pub struct TUploadRingBufferBuilder<TApi: IApi> {
+    size: usize,
+    frame_count: usize,
+    gpu_access: GpuAccess,
+    _marker: std::marker::PhantomData<TApi>,
+}
+
+impl<TApi: IApi> TUploadRingBufferBuilder<TApi> {
+    pub fn new() -> Self {
+        Self {
+            size: 1024 * 1024,
+            frame_count: 3,
+            gpu_access: GpuAccess::CONSTANT_BUFFER
+                | GpuAccess::VERTEX_BUFFER
+                | GpuAccess::INDEX_BUFFER,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    pub fn build(&self, device: &mut TApi::Device) -> TUploadRingBuffer<TApi> {
+        TUploadRingBuffer::new(device, self.size, self.frame_count, self.gpu_access)
+    }
+
+    pub fn with_size(self, size: usize) -> Self {
+        Self { size, ..self }
+    }
+
+    // GPU が同時に処理しているフレームの最大数
+    pub fn with_frame_count(self, frame_count: usize) -> Self {
+        assert!(frame_count > 0);
+        Self {
+            frame_count,
+            ..self
+        }
+    }
+
+    pub fn with_gpu_access_flags(self, gpu_access: GpuAccess) -> Self {
+        Self { gpu_access, ..self }
+    }
+}
+
+struct FrameInfo {
+    fence_index: usize,
+
+    // このフレームで切り出した領域の終端
+    end: usize,
+}
+
+// 毎フレーム更新する定数や動的なジオメトリを 1 つの大きなバッファーから切り出して渡す
+// バッファーは CpuAccessMode::Persistent で作ってマップしたままにし、切り出すたびにそこへ直接書き込む
+// 書き込んだ範囲は flush (end_frame) で flush_mapped_range して GPU から見えるようにする
+// 切り出した領域はフレームごとのフェンスがシグナルするまで上書きしない
+pub struct TUploadRingBuffer<TApi: IApi> {
+    buffer: TApi::Buffer,
+    size: usize,
+    alignment: usize,
+
+    // 単調増加させて size で割った余りをオフセットとして使う
+    head: usize,
+    tail: usize,
+    flushed_head: usize,
+
+    // フレームごとのフェンス
+    fences: Vec<TApi::Fence>,
+    in_flight_frames: VecDeque<FrameInfo>,
+    frame_number: usize,
+}
+
+impl<TApi: IApi> TUploadRingBuffer<TApi> {
+    fn new(
+        device: &mut TApi::Device,
+        size: usize,
+        frame_count: usize,
+        gpu_access: GpuAccess,
+    ) -> Self {
+        // 切り出した領域はどの用途でもバインドできるように一番厳しいアライメントに揃える
+        let capabilities = device.get_capabilities();
+        let alignment = [
+            capabilities.get_constant_buffer_offset_alignment() as usize,
+            capabilities.get_unordered_access_buffer_offset_alignment() as usize,
+            16,
+        ]
+        .into_iter()
+        .max()
+        .unwrap();
+        let size = size.next_multiple_of(alignment);
+
+        let buffer = TApi::Buffer::new(
+            device,
+            &BufferInfo::new()
+                .set_size(size)
+                .set_gpu_access_flags(gpu_access)
+                .set_cpu_access_mode(CpuAccessMode::Persistent)
+                .set_debug_label("UploadRingBuffer"),
+        );
+        let fences = (0..frame_count)
+            .map(|_| TApi::Fence::new(device, &FenceInfo::new()))
+            .collect();
+
+        Self {
+            buffer,
+            size,
+            alignment,
+            head: 0,
+            tail: 0,
+            flushed_head: 0,
+            fences,
+            in_flight_frames: VecDeque::new(),
+            frame_number: 0,
+        }
+    }
+
+    pub fn get_buffer(&self) -> &TApi::Buffer {
+        &self.buffer
+    }
+
+    pub fn get_alignment(&self) -> usize {
+        self.alignment
+    }
+
+    // GPU で使い終わったフレームの領域を回収する
+    pub fn begin_frame(&mut self) {
+        self.reclaim();
+    }
+
+    // 書き込んだ範囲をフラッシュし、このフレームの最後にシグナルさせるフェンスを返す
+    // 返したフェンスは必ずこのフレームの最後のコマンドと一緒にサブミットすること
+    pub fn end_frame(&mut self) -> &mut TApi::Fence {
+        self.flush();
+
+        if self.in_flight_frames.len() == self.fences.len() {
+            self.wait_oldest_frame();
+        }
+
+        let fence_index = self.frame_number % self.fences.len();
+        self.frame_number += 1;
+        self.in_flight_frames.push_back(FrameInfo {
+            fence_index,
+            end: self.head,
+        });
+
+        let fence = &mut self.fences[fence_index];
+        fence.reset();
+        fence
+    }
+
+    pub fn allocate<T: bytemuck::Pod>(&mut self, data: &[T]) -> UploadAllocation {
+        let bytes: &[u8] = bytemuck::cast_slice(data);
+        let allocation = self.allocate_bytes(bytes.len());
+
+        // アライメントを揃えた領域ごと書き込むので、バックエンドのマップの範囲の制約にかからない
+        let range = allocation.offset..(allocation.offset + self.get_aligned_size(bytes.len()));
+        self.buffer.map_range_mut(range, |x: &mut [u8]| {
+            x[..bytes.len()].copy_from_slice(bytes)
+        });
+        allocation
+    }
+
+    pub fn allocate_value<T: bytemuck::Pod>(&mut self, value: &T) -> UploadAllocation {
+        self.allocate(std::slice::from_ref(value))
+    }
+
+    // まだフラッシュしていない領域をまとめてフラッシュする
+    pub fn flush(&mut self) {
+        if self.flushed_head == self.head {
+            return;
+        }
+
+        let start = self.flushed_head % self.size;
+        let end = start + (self.head - self.flushed_head);
+        if end <= self.size {
+            self.flush_range(start..end);
+        } else {
+            // 末尾から先頭に回り込んでいる
+            self.flush_range(start..self.size);
+            self.flush_range(0..(end - self.size));
+        }
+        self.flushed_head = self.head;
+    }
+
+    pub fn set_constant_buffer(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        index: i32,
+        allocation: &UploadAllocation,
+    ) {
+        command_buffer.set_constant_buffer_range(
+            index,
+            &self.buffer,
+            allocation.offset,
+            allocation.size,
+        );
+    }
+
+    pub fn set_vertex_buffer(
+        &self,
+        command_buffer: &mut TApi::CommandBuffer,
+        index: i32,
+        allocation: &UploadAllocation,
+    ) {
+        command_buffer.set_vertex_buffer_range(
+            index,
+            &self.buffer,
+            allocation.offset,
+            allocation.size,
+        );
+    }
+
+    fn allocate_bytes(&mut self, size: usize) -> UploadAllocation {
+        let aligned_size = self.get_aligned_size(size);
+        assert!(
+            aligned_size <= self.size,
+            "allocation size {} exceeds upload ring buffer size {}",
+            size,
+            self.size
+        );
+
+        // 末尾に収まらなければ先頭に戻る
+        let offset = self.head % self.size;
+        let padding = if offset + aligned_size > self.size {
+            self.size - offset
+        } else {
+            0
+        };
+
+        let required_size = padding + aligned_size;
+        if self.head + required_size - self.tail > self.size {
+            self.reclaim();
+        }
+        while self.head + required_size - self.tail > self.size {
+            // 空きがないので GPU で使い終わるのを待つ
+            assert!(
+                !self.in_flight_frames.is_empty(),
+                "upload ring buffer is full within a frame"
+            );
+            self.wait_oldest_frame();
+        }
+
+        self.head += padding;
+        let offset = self.head % self.size;
+        self.head += aligned_size;
+        UploadAllocation { offset, size }
+    }
+
+    fn reclaim(&mut self) {
+        while let Some(frame) = self.in_flight_frames.front() {
+            if !self.fences[frame.fence_index].is_signaled() {
+                break;
+            }
+            self.tail = frame.end;
+            self.in_flight_frames.pop_front();
+        }
+    }
+
+    fn wait_oldest_frame(&mut self) {
+        let frame = self.in_flight_frames.pop_front().unwrap();
+        let is_signaled = self.fences[frame.fence_index].wait(None);
+        assert!(
+            is_signaled,
+            "fence returned by end_frame must be submitted with the frame"
+        );
+        self.tail = frame.end;
+    }
+
+    fn get_aligned_size(&self, size: usize) -> usize {
+        size.max(1).next_multiple_of(self.alignment)
+    }
+
+    fn flush_range(&self, range: std::ops::Range<usize>) {
+        self.buffer
+            .flush_mapped_range(range.start as isize, range.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sjgfx_interface::{IBuffer, ICommandBuffer, IQueue};
+
+    use crate::{
+        api::{self, IApi},
+        TCommandBufferBuilder, TDeviceBuilder, TQueueBuilder, TUploadRingBufferBuilder,
+    };
+
+    #[test]
+    fn allocate() {
+        allocate_impl::<api::Vulkano>();
+        allocate_impl::<api::Wgpu>();
+    }
+
+    fn allocate_impl<TApi: IApi>() {
+        let mut device = TDeviceBuilder::<TApi>::new().build();
+        let mut ring_buffer = TUploadRingBufferBuilder::<TApi>::new()
+            .with_size(4096)
+            .build(&mut device);
+        let alignment = ring_buffer.get_alignment();
+
+        let allocation_0 = ring_buffer.allocate(&[1u32, 2, 3]);
+        let allocation_1 = ring_buffer.allocate_value(&4.0f32);
+        assert_eq!(allocation_0.get_offset(), 0);
+        assert_eq!(allocation_0.get_size(), 12);
+        assert_eq!(allocation_1.get_offset(), alignment);
+        assert_eq!(allocation_1.get_size(), 4);
+
+        // 4 バイトの倍数でない大きさも切り出せる
+        let allocation_2 = ring_buffer.allocate(&[5u8, 6, 7]);
+        assert_eq!(allocation_2.get_offset(), alignment * 2);
+        assert_eq!(allocation_2.get_size(), 3);
+
+        ring_buffer.flush();
+        ring_buffer.get_buffer().map_range(0..12, |x: &[u32]| {
+            assert_eq!(x, [1, 2, 3]);
+        });
+        ring_buffer
+            .get_buffer()
+            .map_range(alignment..(alignment + 4), |x: &[f32]| {
+                assert_eq!(x, [4.0]);
+            });
+        ring_buffer
+            .get_buffer()
+            .map_range((alignment * 2)..(alignment * 2 + 3), |x: &[u8]| {
+                assert_eq!(x, [5, 6, 7]);
+            });
+    }
+
+    #[test]
+    fn reclaim() {
+        reclaim_impl::<api::Vulkano>();
+        reclaim_impl::<api::Wgpu>();
+    }
+
+    fn reclaim_impl<TApi: IApi>() {
+        let mut device = TDeviceBuilder::<TApi>::new().build();
+        let mut queue = TQueueBuilder::<TApi>::new().build(&mut device);
+        let mut command_buffer = TCommandBufferBuilder::<TApi>::new().build(&device);
+        let mut ring_buffer = TUploadRingBufferBuilder::<TApi>::new()
+            .with_size(1024)
+            .with_frame_count(2)
+            .build(&mut device);
+
+        // リングバッファーを何周もさせる
+        for frame in 0..32u32 {
+            ring_buffer.begin_frame();
+            let allocation = ring_buffer.allocate(&[frame; 64]);
+
+            command_buffer.begin();
+            command_buffer.end();
+            queue.execute_with_fence(&command_buffer, ring_buffer.end_frame());
+
+            let range = allocation.get_offset()..(allocation.get_offset() + 256);
+            ring_buffer.get_buffer().map_range(range, |x: &[u32]| {
+                assert!(x.iter().all(|value| *value == frame));
+            });
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::ops::Range;
+
+use sjgfx_interface::{BufferInfo, GpuAccess, IBuffer};
+
+use crate::{
+    api::IApi,
+    validation::{self, is_range_in_buffer, DeviceValidation, Validator},
+};
+
+pub struct BufferValidation<TApi: IApi> {
+    buffer: TApi::Buffer,
+    size: usize,
+    gpu_access: GpuAccess,
+    validator: Validator,
+}
+
+impl<TApi: IApi> BufferValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Buffer {
+        &self.buffer
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Buffer {
+        &mut self.buffer
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get_gpu_access_flags(&self) -> GpuAccess {
+        self.gpu_access
+    }
+
+    #[track_caller]
+    pub(crate) fn validate_gpu_access(&self, required: GpuAccess, usage: &str) {
+        validation::validate_gpu_access(
+            &self.validator,
+            "buffer",
+            self.gpu_access,
+            required,
+            usage,
+        );
+    }
+
+    #[track_caller]
+    pub(crate) fn validate_range(&self, offset: usize, size: usize) {
+        self.validator
+            .check(is_range_in_buffer(self.size, offset, size), || {
+                format!(
+                    "range {}..{} is out of the buffer of size {}",
+                    offset,
+                    offset.saturating_add(size),
+                    self.size
+                )
+            });
+    }
+
+    #[track_caller]
+    fn validate_map_range<T: bytemuck::Pod>(&self, range: &Range<usize>) {
+        self.validator.check(range.start <= range.end, || {
+            format!("map range {:?} is reversed", range)
+        });
+        self.validate_range(range.start, range.len());
+        let element_size = std::mem::size_of::<T>();
+        self.validator.check(range.len() % element_size == 0, || {
+            format!(
+                "map range {:?} is not a multiple of the element size {}",
+                range, element_size
+            )
+        });
+    }
+}
+
+impl<TApi: IApi> IBuffer for BufferValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    #[track_caller]
+    fn new(device: &mut Self::DeviceType, info: &BufferInfo) -> Self {
+        let validator = device.clone_validator();
+        validator.check(info.get_size() > 0, || {
+            "buffer size must be positive".to_string()
+        });
+        Self {
+            buffer: TApi::Buffer::new(device.as_inner_mut(), info),
+            size: info.get_size(),
+            gpu_access: info.get_gpu_access_flags(),
+            validator,
+        }
+    }
+
+    #[track_caller]
+    fn map<T: bytemuck::Pod, F: FnOnce(&T)>(&self, func: F) {
+        self.validate_map_range::<T>(&(0..std::mem::size_of::<T>()));
+        self.buffer.map(func);
+    }
+
+    fn map_as_slice<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, func: F) {
+        self.buffer.map_as_slice(func);
+    }
+
+    #[track_caller]
+    fn map_range<T: bytemuck::Pod, F: FnOnce(&[T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        self.buffer.map_range(range, func);
+    }
+
+    #[track_caller]
+    fn map_mut<T: bytemuck::Pod, F: FnOnce(&mut T)>(&self, func: F) {
+        self.validate_map_range::<T>(&(0..std::mem::size_of::<T>()));
+        self.buffer.map_mut(func);
+    }
+
+    fn map_as_slice_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, func: F) {
+        self.buffer.map_as_slice_mut(func);
+    }
+
+    #[track_caller]
+    fn map_range_mut<T: bytemuck::Pod, F: FnOnce(&mut [T])>(&self, range: Range<usize>, func: F) {
+        self.validate_map_range::<T>(&range);
+        self.buffer.map_range_mut(range, func);
+    }
+
+    #[track_caller]
+    fn flush_mapped_range(&self, offset: isize, size: usize) {
+        self.validator
+            .check(offset >= 0, || format!("offset {} is negative", offset));
+        self.validate_range(offset.max(0) as usize, size);
+        self.buffer.flush_mapped_range(offset, size);
+    }
+
+    #[track_caller]
+    fn invalidate_mapped_range(&self, offset: isize, size: usize) {
+        self.validator
+            .check(offset >= 0, || format!("offset {} is negative", offset));
+        self.validate_range(offset.max(0) as usize, size);
+        self.buffer.invalidate_mapped_range(offset, size);
+    }
+}
@@ -0,0 +1,92 @@
+use sjgfx_interface::{ColorTargetViewInfo, GpuAccess, IColorTargetView, ImageData};
+
+use crate::{
+    api::IApi,
+    validation::{self, DeviceValidation, TextureValidation},
+};
+
+pub struct ColorTargetViewValidation<TApi: IApi> {
+    color_target_view: TApi::ColorTargetView,
+
+    // 塗りつぶす配列要素の範囲の確認用
+    array_length: i32,
+}
+
+impl<TApi: IApi> ColorTargetViewValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::ColorTargetView {
+        &self.color_target_view
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::ColorTargetView {
+        &mut self.color_target_view
+    }
+
+    // スワップチェーンが返すスキャンバッファは配列ではない
+    pub(crate) fn new_scan_buffer(color_target_view: TApi::ColorTargetView) -> Self {
+        Self {
+            color_target_view,
+            array_length: 1,
+        }
+    }
+
+    pub(crate) fn get_array_length(&self) -> i32 {
+        self.array_length
+    }
+}
+
+// スワップチェーンが返すビューを包めるように複製できる
+impl<TApi: IApi> Clone for ColorTargetViewValidation<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            color_target_view: self.color_target_view.clone(),
+            array_length: self.array_length,
+        }
+    }
+}
+
+impl<TApi: IApi> IColorTargetView for ColorTargetViewValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+    type TextureType = TextureValidation<TApi>;
+
+    #[track_caller]
+    fn new(
+        device: &Self::DeviceType,
+        info: &ColorTargetViewInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        let validator = device.clone_validator();
+        validation::validate_gpu_access(
+            &validator,
+            "texture",
+            texture.get_gpu_access_flags(),
+            GpuAccess::COLOR_BUFFER,
+            "color target",
+        );
+        validator.check(
+            &info.get_image_format() == texture.get_image_format(),
+            || {
+                format!(
+                    "color target view format {:?} does not match the texture format {:?}",
+                    info.get_image_format(),
+                    texture.get_image_format()
+                )
+            },
+        );
+
+        Self {
+            color_target_view: TApi::ColorTargetView::new(
+                device.as_inner(),
+                info,
+                texture.as_inner(),
+            ),
+            array_length: texture.get_array_length(),
+        }
+    }
+
+    fn read_image(&self) -> ImageData {
+        self.color_target_view.read_image()
+    }
+}
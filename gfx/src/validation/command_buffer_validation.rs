@@ -0,0 +1,544 @@
+use std::collections::BTreeMap;
+
+use sjgfx_interface::{
+    BufferCopyRegion, BufferTextureCopyRegion, CommandBufferInfo, GpuAccess, ICommandBuffer,
+    IDevice, IndexFormat, PrimitiveTopology, ScissorStateInfo, TextureArrayRange,
+};
+
+use crate::{
+    api::{IApi, Null, Wgpu},
+    validation::{
+        self, BindingKind, BufferValidation, ColorTargetViewValidation, DepthStencilViewValidation,
+        DeviceValidation, SamplerValidation, ShaderValidation, TextureValidation,
+        TextureViewValidation, Validator, VertexStateValidation,
+    },
+};
+
+// 描画とディスパッチのときに確認するためにシェーダーの情報を覚えておく
+struct ShaderState {
+    is_compute: bool,
+    bindings: Option<BTreeMap<i32, BindingKind>>,
+}
+
+pub struct CommandBufferValidation<TApi: IApi> {
+    command_buffer: TApi::CommandBuffer,
+    validator: Validator,
+    constant_buffer_offset_alignment: usize,
+    is_recording: bool,
+    shader: Option<ShaderState>,
+
+    // インデックスごとに設定したリソースの種類
+    bound_resources: BTreeMap<i32, BindingKind>,
+    has_render_target: bool,
+}
+
+impl<TApi: IApi> CommandBufferValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::CommandBuffer {
+        &self.command_buffer
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        self.is_recording
+    }
+
+    #[track_caller]
+    fn bind(&mut self, index: i32, binding_kind: BindingKind) {
+        self.validator.check(index >= 0, || {
+            format!("binding index {} is negative", index)
+        });
+        self.bound_resources.insert(index, binding_kind);
+    }
+
+    #[track_caller]
+    fn validate_buffer_range(&self, buffer: &BufferValidation<TApi>, offset: usize, size: usize) {
+        buffer.validate_range(offset, size);
+    }
+
+    #[track_caller]
+    fn validate_copy_buffer_to_buffer(
+        &self,
+        dst_buffer: &BufferValidation<TApi>,
+        src_buffer: &BufferValidation<TApi>,
+        region: &BufferCopyRegion,
+    ) {
+        let src_offset = region.get_src_offset();
+        let dst_offset = region.get_dst_offset();
+        self.validator
+            .check(src_offset >= 0 && dst_offset >= 0, || {
+                format!(
+                    "offsets {} and {} must not be negative",
+                    src_offset, dst_offset
+                )
+            });
+        let copy_size = region.get_copy_size();
+        src_buffer.validate_range(src_offset.max(0) as usize, copy_size);
+        dst_buffer.validate_range(dst_offset.max(0) as usize, copy_size);
+    }
+
+    #[track_caller]
+    fn validate_copy_image_to_buffer(
+        &self,
+        buffer: &BufferValidation<TApi>,
+        texture: &TextureValidation<TApi>,
+        copy_region: &BufferTextureCopyRegion,
+    ) {
+        texture.validate_gpu_access(GpuAccess::READ, "copy source");
+        texture.validate_buffer_copy_region(copy_region);
+
+        // バッファーには行を詰めて書き込む
+        let offset = copy_region.get_offset();
+        self.validator
+            .check(offset >= 0, || format!("offset {} is negative", offset));
+        let size = copy_region.get_image_width().max(0) as usize
+            * copy_region.get_image_height().max(0) as usize
+            * texture.get_image_format().get_bytes_per_pixel();
+        buffer.validate_range(offset.max(0) as usize, size);
+    }
+
+    #[track_caller]
+    fn validate_draw(&self) {
+        let Some(shader) = &self.shader else {
+            self.validator.check(false, || {
+                "draw needs a graphics shader, but no shader is set".to_string()
+            });
+            return;
+        };
+        self.validator.check(!shader.is_compute, || {
+            "draw needs a graphics shader, but a compute shader is set".to_string()
+        });
+        self.validator.check(self.has_render_target, || {
+            "draw needs a render target, but none is set".to_string()
+        });
+        self.validate_bindings(shader);
+    }
+
+    #[track_caller]
+    fn validate_dispatch(&self, count_x: i32, count_y: i32, count_z: i32) {
+        let Some(shader) = &self.shader else {
+            self.validator.check(false, || {
+                "dispatch needs a compute shader, but no shader is set".to_string()
+            });
+            return;
+        };
+        self.validator.check(shader.is_compute, || {
+            "dispatch needs a compute shader, but a graphics shader is set".to_string()
+        });
+        self.validator
+            .check(count_x > 0 && count_y > 0 && count_z > 0, || {
+                format!(
+                    "dispatch count ({}, {}, {}) must be positive",
+                    count_x, count_y, count_z
+                )
+            });
+        self.validate_bindings(shader);
+    }
+
+    // シェーダーが使うバインディングにすべて同じ種類のリソースが設定されているか
+    #[track_caller]
+    fn validate_bindings(&self, shader: &ShaderState) {
+        let Some(bindings) = &shader.bindings else {
+            return;
+        };
+
+        for (index, binding_kind) in bindings {
+            match self.bound_resources.get(index) {
+                None => self.validator.check(false, || {
+                    format!(
+                        "shader binding {} expects a {}, but nothing is bound",
+                        index,
+                        binding_kind.get_name()
+                    )
+                }),
+                Some(bound_kind) => self.validator.check(bound_kind == binding_kind, || {
+                    format!(
+                        "shader binding {} expects a {}, but a {} is bound",
+                        index,
+                        binding_kind.get_name(),
+                        bound_kind.get_name()
+                    )
+                }),
+            }
+        }
+    }
+
+    #[track_caller]
+    fn validate_counts(&self, count: i32, instance_count: i32, base_instance: i32) {
+        self.validator.check(
+            count >= 0 && instance_count >= 0 && base_instance >= 0,
+            || {
+                format!(
+                    "draw count {}, instance count {} and base instance {} must not be negative",
+                    count, instance_count, base_instance
+                )
+            },
+        );
+    }
+}
+
+impl<TApi: IApi> ICommandBuffer for CommandBufferValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+    type BufferType = BufferValidation<TApi>;
+    type ColorTargetViewType = ColorTargetViewValidation<TApi>;
+    type DepthStencilViewType = DepthStencilViewValidation<TApi>;
+    type SamplerType = SamplerValidation<TApi>;
+    type ShaderType = ShaderValidation<TApi>;
+    type TextureType = TextureValidation<TApi>;
+    type TextureViewType = TextureViewValidation<TApi>;
+    type VertexStateType = VertexStateValidation<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &CommandBufferInfo) -> Self {
+        let capabilities = device.get_capabilities();
+        Self {
+            command_buffer: TApi::CommandBuffer::new(device.as_inner(), info),
+            validator: device.clone_validator(),
+            constant_buffer_offset_alignment: capabilities.get_constant_buffer_offset_alignment()
+                as usize,
+            is_recording: false,
+            shader: None,
+            bound_resources: BTreeMap::new(),
+            has_render_target: false,
+        }
+    }
+
+    // 記録しなおすので設定した状態は引き継がない
+    fn begin(&mut self) {
+        self.is_recording = true;
+        self.shader = None;
+        self.bound_resources.clear();
+        self.has_render_target = false;
+        self.command_buffer.begin();
+    }
+
+    #[track_caller]
+    fn end(&mut self) {
+        self.validator.check(self.is_recording, || {
+            "end is called without begin".to_string()
+        });
+        self.is_recording = false;
+        self.command_buffer.end();
+    }
+
+    fn push_debug_group(&mut self, label: &str) {
+        self.command_buffer.push_debug_group(label);
+    }
+
+    fn pop_debug_group(&mut self) {
+        self.command_buffer.pop_debug_group();
+    }
+
+    fn insert_debug_marker(&mut self, label: &str) {
+        self.command_buffer.insert_debug_marker(label);
+    }
+
+    #[track_caller]
+    fn clear_color(
+        &mut self,
+        color_target_view: &mut Self::ColorTargetViewType,
+        red: f32,
+        green: f32,
+        blue: f32,
+        alpha: f32,
+        texture_array_range: TextureArrayRange,
+    ) {
+        let base_index = texture_array_range.get_base_index();
+        let length = texture_array_range.get_length();
+        let array_length = color_target_view.get_array_length();
+        self.validator.check(
+            base_index >= 0 && length > 0 && base_index + length <= array_length,
+            || {
+                format!(
+                    "clear range {}..{} is out of the color target with {} array layers",
+                    base_index,
+                    base_index + length,
+                    array_length
+                )
+            },
+        );
+        self.command_buffer.clear_color(
+            color_target_view.as_inner_mut(),
+            red,
+            green,
+            blue,
+            alpha,
+            texture_array_range,
+        );
+    }
+
+    fn set_render_targets(
+        &mut self,
+        color_target_views: &[&Self::ColorTargetViewType],
+        depth_stencil_view: Option<&Self::DepthStencilViewType>,
+    ) {
+        self.has_render_target = !color_target_views.is_empty() || depth_stencil_view.is_some();
+        let color_target_views = color_target_views
+            .iter()
+            .map(|view| view.as_inner())
+            .collect::<Vec<_>>();
+        self.command_buffer.set_render_targets(
+            &color_target_views,
+            depth_stencil_view.map(DepthStencilViewValidation::as_inner),
+        );
+    }
+
+    fn set_shader(&mut self, shader: &Self::ShaderType) {
+        self.shader = Some(ShaderState {
+            is_compute: shader.is_compute(),
+            bindings: shader.get_bindings().cloned(),
+        });
+        self.command_buffer.set_shader(shader.as_inner());
+    }
+
+    #[track_caller]
+    fn set_sampler(&mut self, index: i32, sampler: &Self::SamplerType) {
+        self.bind(index, BindingKind::Sampler);
+        self.command_buffer.set_sampler(index, sampler.as_inner());
+    }
+
+    #[track_caller]
+    fn set_texture(&mut self, index: i32, texture_view: &Self::TextureViewType) {
+        validation::validate_gpu_access(
+            &self.validator,
+            "texture",
+            texture_view.get_gpu_access_flags(),
+            GpuAccess::TEXTURE,
+            "texture",
+        );
+        self.bind(index, BindingKind::Texture);
+        self.command_buffer
+            .set_texture(index, texture_view.as_inner());
+    }
+
+    #[track_caller]
+    fn set_image(&mut self, index: i32, texture: &Self::TextureViewType) {
+        validation::validate_gpu_access(
+            &self.validator,
+            "texture",
+            texture.get_gpu_access_flags(),
+            GpuAccess::IMAGE,
+            "image",
+        );
+        self.bind(index, BindingKind::Image);
+        self.command_buffer.set_image(index, texture.as_inner());
+    }
+
+    #[track_caller]
+    fn set_constant_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        buffer.validate_gpu_access(GpuAccess::CONSTANT_BUFFER, "constant buffer");
+        self.bind(index, BindingKind::ConstantBuffer);
+        self.command_buffer
+            .set_constant_buffer(index, buffer.as_inner());
+    }
+
+    #[track_caller]
+    fn set_constant_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        buffer.validate_gpu_access(GpuAccess::CONSTANT_BUFFER, "constant buffer");
+        self.validate_buffer_range(buffer, offset, size);
+        let alignment = self.constant_buffer_offset_alignment;
+        self.validator.check(offset % alignment == 0, || {
+            format!(
+                "constant buffer offset {} is not aligned to {}",
+                offset, alignment
+            )
+        });
+        self.bind(index, BindingKind::ConstantBuffer);
+        self.command_buffer
+            .set_constant_buffer_range(index, buffer.as_inner(), offset, size);
+    }
+
+    #[track_caller]
+    fn set_unordered_access_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        buffer.validate_gpu_access(
+            GpuAccess::UNORDERED_ACCESS_BUFFER,
+            "unordered access buffer",
+        );
+        self.bind(index, BindingKind::UnorderedAccessBuffer);
+        self.command_buffer
+            .set_unordered_access_buffer(index, buffer.as_inner());
+    }
+
+    #[track_caller]
+    fn set_vertex_buffer(&mut self, index: i32, buffer: &Self::BufferType) {
+        buffer.validate_gpu_access(GpuAccess::VERTEX_BUFFER, "vertex buffer");
+        self.command_buffer
+            .set_vertex_buffer(index, buffer.as_inner());
+    }
+
+    #[track_caller]
+    fn set_vertex_buffer_range(
+        &mut self,
+        index: i32,
+        buffer: &Self::BufferType,
+        offset: usize,
+        size: usize,
+    ) {
+        buffer.validate_gpu_access(GpuAccess::VERTEX_BUFFER, "vertex buffer");
+        self.validate_buffer_range(buffer, offset, size);
+        self.command_buffer
+            .set_vertex_buffer_range(index, buffer.as_inner(), offset, size);
+    }
+
+    fn set_vertex_state(&mut self, vertex_state: &Self::VertexStateType) {
+        self.command_buffer
+            .set_vertex_state(vertex_state.as_inner());
+    }
+
+    fn set_scissor(&mut self, scissor_state_info: &ScissorStateInfo) {
+        self.command_buffer.set_scissor(scissor_state_info);
+    }
+
+    #[track_caller]
+    fn dispatch(&mut self, count_x: i32, count_y: i32, count_z: i32) {
+        self.validate_dispatch(count_x, count_y, count_z);
+        self.command_buffer.dispatch(count_x, count_y, count_z);
+    }
+
+    #[track_caller]
+    fn draw(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+    ) {
+        self.validate_draw();
+        self.validate_counts(vertex_count, 1, 0);
+        self.command_buffer
+            .draw(primitive_topology, vertex_count, vertex_offset);
+    }
+
+    #[track_caller]
+    fn draw_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        vertex_count: i32,
+        vertex_offset: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.validate_draw();
+        self.validate_counts(vertex_count, instance_count, base_instance);
+        self.command_buffer.draw_instanced(
+            primitive_topology,
+            vertex_count,
+            vertex_offset,
+            instance_count,
+            base_instance,
+        );
+    }
+
+    #[track_caller]
+    fn draw_indexed(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+    ) {
+        self.validate_draw();
+        self.validate_counts(index_count, 1, 0);
+        index_buffer.validate_gpu_access(GpuAccess::INDEX_BUFFER, "index buffer");
+        self.command_buffer.draw_indexed(
+            primitive_topology,
+            index_format,
+            index_buffer.as_inner(),
+            index_count,
+            base_vertex,
+        );
+    }
+
+    #[track_caller]
+    fn draw_indexed_instanced(
+        &mut self,
+        primitive_topology: PrimitiveTopology,
+        index_format: IndexFormat,
+        index_buffer: &Self::BufferType,
+        index_count: i32,
+        base_vertex: i32,
+        instance_count: i32,
+        base_instance: i32,
+    ) {
+        self.validate_draw();
+        self.validate_counts(index_count, instance_count, base_instance);
+        index_buffer.validate_gpu_access(GpuAccess::INDEX_BUFFER, "index buffer");
+        self.command_buffer.draw_indexed_instanced(
+            primitive_topology,
+            index_format,
+            index_buffer.as_inner(),
+            index_count,
+            base_vertex,
+            instance_count,
+            base_instance,
+        );
+    }
+}
+
+// コピーは ICommandBuffer にないので、コピーを持つバックエンドにだけ用意する
+impl CommandBufferValidation<Wgpu> {
+    #[track_caller]
+    pub fn copy_buffer_to_buffer(
+        &mut self,
+        dst_buffer: &mut BufferValidation<Wgpu>,
+        src_buffer: &BufferValidation<Wgpu>,
+        region: &BufferCopyRegion,
+    ) {
+        self.validate_copy_buffer_to_buffer(dst_buffer, src_buffer, region);
+        self.command_buffer.copy_buffer_to_buffer(
+            dst_buffer.as_inner_mut(),
+            src_buffer.as_inner(),
+            region,
+        );
+    }
+
+    #[track_caller]
+    pub fn copy_image_to_buffer(
+        &mut self,
+        buffer: &BufferValidation<Wgpu>,
+        texture: &TextureValidation<Wgpu>,
+        copy_region: BufferTextureCopyRegion,
+    ) {
+        self.validate_copy_image_to_buffer(buffer, texture, &copy_region);
+        self.command_buffer.copy_image_to_buffer(
+            buffer.as_inner(),
+            texture.as_inner(),
+            copy_region,
+        );
+    }
+}
+
+impl CommandBufferValidation<Null> {
+    #[track_caller]
+    pub fn copy_buffer_to_buffer(
+        &mut self,
+        dst_buffer: &mut BufferValidation<Null>,
+        src_buffer: &BufferValidation<Null>,
+        region: &BufferCopyRegion,
+    ) {
+        self.validate_copy_buffer_to_buffer(dst_buffer, src_buffer, region);
+        self.command_buffer.copy_buffer_to_buffer(
+            dst_buffer.as_inner_mut(),
+            src_buffer.as_inner(),
+            region,
+        );
+    }
+
+    #[track_caller]
+    pub fn copy_image_to_buffer(
+        &mut self,
+        buffer: &BufferValidation<Null>,
+        texture: &TextureValidation<Null>,
+        copy_region: BufferTextureCopyRegion,
+    ) {
+        self.validate_copy_image_to_buffer(buffer, texture, &copy_region);
+        self.command_buffer.copy_image_to_buffer(
+            buffer.as_inner(),
+            texture.as_inner(),
+            copy_region,
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use sjgfx_interface::{DepthStencilStateInfo, GpuAccess, IDepthStencilView};
+
+use crate::{
+    api::IApi,
+    validation::{self, DeviceValidation, TextureValidation},
+};
+
+pub struct DepthStencilViewValidation<TApi: IApi> {
+    depth_stencil_view: TApi::DepthStencilView,
+}
+
+impl<TApi: IApi> DepthStencilViewValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::DepthStencilView {
+        &self.depth_stencil_view
+    }
+}
+
+impl<TApi: IApi> IDepthStencilView for DepthStencilViewValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+    type TextureType = TextureValidation<TApi>;
+
+    #[track_caller]
+    fn new(
+        device: &Self::DeviceType,
+        info: &DepthStencilStateInfo,
+        texture: &Self::TextureType,
+    ) -> Self {
+        validation::validate_gpu_access(
+            &device.clone_validator(),
+            "texture",
+            texture.get_gpu_access_flags(),
+            GpuAccess::DEPTH_STENCIL,
+            "depth stencil",
+        );
+
+        Self {
+            depth_stencil_view: TApi::DepthStencilView::new(
+                device.as_inner(),
+                info,
+                texture.as_inner(),
+            ),
+        }
+    }
+}
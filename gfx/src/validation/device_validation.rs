@@ -0,0 +1,54 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{AdapterInfo, DeviceCapabilities, DeviceInfo, IDevice};
+
+use crate::{api::IApi, validation::Validator};
+
+pub struct DeviceValidation<TApi: IApi> {
+    device: TApi::Device,
+    validator: Validator,
+}
+
+impl<TApi: IApi> DeviceValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Device {
+        &self.device
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Device {
+        &mut self.device
+    }
+
+    pub(crate) fn clone_validator(&self) -> Validator {
+        self.validator.clone()
+    }
+}
+
+impl<TApi: IApi> IDevice for DeviceValidation<TApi> {
+    fn new(info: &DeviceInfo) -> Self {
+        Self {
+            device: TApi::Device::new(info),
+            validator: Validator::new(info),
+        }
+    }
+
+    fn new_with_handle<T>(info: &DeviceInfo, raw_handle: &T) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self {
+            device: TApi::Device::new_with_handle(info, raw_handle),
+            validator: Validator::new(info),
+        }
+    }
+
+    fn get_capabilities(&self) -> DeviceCapabilities {
+        self.device.get_capabilities()
+    }
+
+    fn enumerate_adapters(info: &DeviceInfo) -> Vec<AdapterInfo> {
+        TApi::Device::enumerate_adapters(info)
+    }
+
+    fn get_adapter_info(&self) -> AdapterInfo {
+        self.device.get_adapter_info()
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use sjgfx_interface::{FenceInfo, IFence};
+
+use crate::{api::IApi, validation::DeviceValidation};
+
+pub struct FenceValidation<TApi: IApi> {
+    fence: TApi::Fence,
+}
+
+impl<TApi: IApi> FenceValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Fence {
+        &self.fence
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Fence {
+        &mut self.fence
+    }
+}
+
+impl<TApi: IApi> IFence for FenceValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &FenceInfo) -> Self {
+        Self {
+            fence: TApi::Fence::new(device.as_inner(), info),
+        }
+    }
+
+    fn wait(&mut self, timeout: Option<Duration>) -> bool {
+        self.fence.wait(timeout)
+    }
+
+    fn is_signaled(&self) -> bool {
+        self.fence.is_signaled()
+    }
+
+    fn reset(&mut self) {
+        self.fence.reset();
+    }
+}
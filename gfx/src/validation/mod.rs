@@ -0,0 +1,110 @@
+mod buffer_validation;
+mod color_target_view_validation;
+mod command_buffer_validation;
+mod depth_stencil_view_validation;
+mod device_validation;
+mod fence_validation;
+mod queue_validation;
+mod sampler_validation;
+mod semaphore_validation;
+mod shader_validation;
+mod swap_chain_validation;
+mod texture_validation;
+mod texture_view_validation;
+mod vertex_state_validation;
+
+pub use buffer_validation::BufferValidation;
+pub use color_target_view_validation::ColorTargetViewValidation;
+pub use command_buffer_validation::CommandBufferValidation;
+pub use depth_stencil_view_validation::DepthStencilViewValidation;
+pub use device_validation::DeviceValidation;
+pub use fence_validation::FenceValidation;
+pub use queue_validation::QueueValidation;
+pub use sampler_validation::SamplerValidation;
+pub use semaphore_validation::SemaphoreValidation;
+pub use shader_validation::ShaderValidation;
+pub use swap_chain_validation::SwapChainValidation;
+pub use texture_validation::TextureValidation;
+pub use texture_view_validation::TextureViewValidation;
+pub use vertex_state_validation::VertexStateValidation;
+
+use std::panic::Location;
+
+use sjgfx_interface::{DebugMode, DeviceInfo, GpuAccess};
+
+// 使い方の誤りを報告する。FullAssertion のときは呼び出し元で panic し、Full のときは標準エラーに出して続ける
+// 報告する場所が呼び出し元になるように、検証する関数にはすべて #[track_caller] をつけておく
+#[derive(Clone)]
+pub(crate) struct Validator {
+    debug_mode: DebugMode,
+}
+
+impl Validator {
+    pub fn new(info: &DeviceInfo) -> Self {
+        Self {
+            debug_mode: info.get_debug_mode(),
+        }
+    }
+
+    #[track_caller]
+    pub fn check<F: FnOnce() -> String>(&self, condition: bool, message: F) {
+        if condition {
+            return;
+        }
+
+        match self.debug_mode {
+            DebugMode::Full => {
+                eprintln!("validation error: {} at {}", message(), Location::caller())
+            }
+            DebugMode::FullAssertion => {
+                panic!("validation error: {} at {}", message(), Location::caller())
+            }
+        }
+    }
+}
+
+// シェーダーのバインディングに設定するリソースの種類
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum BindingKind {
+    ConstantBuffer,
+    UnorderedAccessBuffer,
+    Texture,
+    Image,
+    Sampler,
+}
+
+impl BindingKind {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::ConstantBuffer => "constant buffer",
+            Self::UnorderedAccessBuffer => "unordered access buffer",
+            Self::Texture => "texture",
+            Self::Image => "image",
+            Self::Sampler => "sampler",
+        }
+    }
+}
+
+// バッファーの範囲 offset..(offset + size) が収まっているか
+pub(crate) fn is_range_in_buffer(buffer_size: usize, offset: usize, size: usize) -> bool {
+    offset
+        .checked_add(size)
+        .is_some_and(|end| end <= buffer_size)
+}
+
+// リソースが usage の用途に必要な GpuAccess をつけて作られているか
+#[track_caller]
+pub(crate) fn validate_gpu_access(
+    validator: &Validator,
+    resource: &str,
+    gpu_access: GpuAccess,
+    required: GpuAccess,
+    usage: &str,
+) {
+    validator.check(gpu_access.contains(required), || {
+        format!(
+            "{} used as {} must be created with GpuAccess::{:?}, but it has {:?}",
+            resource, usage, required, gpu_access
+        )
+    });
+}
@@ -0,0 +1,113 @@
+use sjgfx_interface::{IQueue, QueueInfo, QueueType};
+
+use crate::{
+    api::IApi,
+    validation::{
+        CommandBufferValidation, DeviceValidation, FenceValidation, SemaphoreValidation,
+        SwapChainValidation, Validator,
+    },
+};
+
+pub struct QueueValidation<TApi: IApi> {
+    queue: TApi::Queue,
+    validator: Validator,
+}
+
+impl<TApi: IApi> QueueValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Queue {
+        &self.queue
+    }
+
+    #[track_caller]
+    fn validate_command_buffer(&self, command_buffer: &CommandBufferValidation<TApi>) {
+        self.validator.check(!command_buffer.is_recording(), || {
+            "command buffer is executed before end is called".to_string()
+        });
+    }
+}
+
+impl<TApi: IApi> IQueue for QueueValidation<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type DeviceType = DeviceValidation<TApi>;
+    type CommandBufferType = CommandBufferValidation<TApi>;
+    type FenceType = FenceValidation<TApi>;
+    type SemaphoreType = SemaphoreValidation<TApi>;
+    type SwapChainType = SwapChainValidation<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &QueueInfo) -> Self {
+        Self {
+            queue: TApi::Queue::new(device.as_inner_mut(), info),
+            validator: device.clone_validator(),
+        }
+    }
+
+    #[track_caller]
+    fn execute(&mut self, command_buffer: &Self::CommandBufferType) {
+        self.validate_command_buffer(command_buffer);
+        self.queue.execute(command_buffer.as_inner());
+    }
+
+    #[track_caller]
+    fn execute_many(&mut self, command_buffers: &[&Self::CommandBufferType]) {
+        for command_buffer in command_buffers {
+            self.validate_command_buffer(command_buffer);
+        }
+        let command_buffers = command_buffers
+            .iter()
+            .map(|command_buffer| command_buffer.as_inner())
+            .collect::<Vec<_>>();
+        self.queue.execute_many(&command_buffers);
+    }
+
+    #[track_caller]
+    fn execute_with_fence(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        fence: &mut Self::FenceType,
+    ) {
+        self.validate_command_buffer(command_buffer);
+        self.queue
+            .execute_with_fence(command_buffer.as_inner(), fence.as_inner_mut());
+    }
+
+    #[track_caller]
+    fn execute_with_semaphores(
+        &mut self,
+        command_buffer: &Self::CommandBufferType,
+        wait_semaphores: &[(&Self::SemaphoreType, u64)],
+        signal_semaphores: &[(&Self::SemaphoreType, u64)],
+    ) {
+        self.validate_command_buffer(command_buffer);
+        let wait_semaphores = wait_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.as_inner(), *value))
+            .collect::<Vec<_>>();
+        let signal_semaphores = signal_semaphores
+            .iter()
+            .map(|(semaphore, value)| (semaphore.as_inner(), *value))
+            .collect::<Vec<_>>();
+        self.queue.execute_with_semaphores(
+            command_buffer.as_inner(),
+            &wait_semaphores,
+            &signal_semaphores,
+        );
+    }
+
+    fn get_queue_type(&self) -> &QueueType {
+        self.queue.get_queue_type()
+    }
+
+    fn present(&mut self, swap_chain: &mut Self::SwapChainType) {
+        self.queue.present(swap_chain.as_inner_mut());
+    }
+
+    fn flush(&mut self) {
+        self.queue.flush();
+    }
+
+    fn sync(&mut self) {
+        self.queue.sync();
+    }
+}
@@ -0,0 +1,23 @@
+use sjgfx_interface::{ISampler, SamplerInfo};
+
+use crate::{api::IApi, validation::DeviceValidation};
+
+pub struct SamplerValidation<TApi: IApi> {
+    sampler: TApi::Sampler,
+}
+
+impl<TApi: IApi> SamplerValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Sampler {
+        &self.sampler
+    }
+}
+
+impl<TApi: IApi> ISampler for SamplerValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &SamplerInfo) -> Self {
+        Self {
+            sampler: TApi::Sampler::new(device.as_inner_mut(), info),
+        }
+    }
+}
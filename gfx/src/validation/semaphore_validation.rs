@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use sjgfx_interface::{ISemaphore, SemaphoreInfo};
+
+use crate::{api::IApi, validation::DeviceValidation};
+
+pub struct SemaphoreValidation<TApi: IApi> {
+    semaphore: TApi::Semaphore,
+}
+
+impl<TApi: IApi> SemaphoreValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Semaphore {
+        &self.semaphore
+    }
+
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::Semaphore {
+        &mut self.semaphore
+    }
+}
+
+impl<TApi: IApi> ISemaphore for SemaphoreValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &SemaphoreInfo) -> Self {
+        Self {
+            semaphore: TApi::Semaphore::new(device.as_inner(), info),
+        }
+    }
+
+    fn get_value(&self) -> u64 {
+        self.semaphore.get_value()
+    }
+
+    fn wait(&self, value: u64, timeout: Option<Duration>) -> bool {
+        self.semaphore.wait(value, timeout)
+    }
+
+    fn signal(&self, value: u64) {
+        self.semaphore.signal(value);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use sjgfx_interface::{IShader, ShaderInfo};
+
+use crate::{
+    api::IApi,
+    validation::{BindingKind, DeviceValidation},
+};
+
+pub struct ShaderValidation<TApi: IApi> {
+    shader: TApi::Shader,
+    is_compute: bool,
+
+    // SPIR-V から読み取ったバインディング。ソースから作ったときは確認しない
+    bindings: Option<BTreeMap<i32, BindingKind>>,
+}
+
+impl<TApi: IApi> ShaderValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Shader {
+        &self.shader
+    }
+
+    pub fn is_compute(&self) -> bool {
+        self.is_compute
+    }
+
+    pub(crate) fn get_bindings(&self) -> Option<&BTreeMap<i32, BindingKind>> {
+        self.bindings.as_ref()
+    }
+
+    // 各ステージのバインディングをまとめる。SPIR-V として読めなければ None
+    fn reflect_bindings(shader_binaries: &[&[u8]]) -> Option<BTreeMap<i32, BindingKind>> {
+        let mut bindings = BTreeMap::new();
+        for shader_binary in shader_binaries {
+            let module =
+                naga::front::spv::parse_u8_slice(shader_binary, &Default::default()).ok()?;
+            for (_, global_variable) in module.global_variables.iter() {
+                let Some(binding) = &global_variable.binding else {
+                    continue;
+                };
+                if let Some(binding_kind) = Self::convert_global_variable(&module, global_variable)
+                {
+                    bindings.insert(binding.binding as i32, binding_kind);
+                }
+            }
+        }
+        Some(bindings)
+    }
+
+    fn convert_global_variable(
+        module: &naga::Module,
+        global_variable: &naga::GlobalVariable,
+    ) -> Option<BindingKind> {
+        match global_variable.space {
+            naga::AddressSpace::Uniform => Some(BindingKind::ConstantBuffer),
+            naga::AddressSpace::Storage { .. } => Some(BindingKind::UnorderedAccessBuffer),
+            naga::AddressSpace::Handle => match module.types[global_variable.ty].inner {
+                naga::TypeInner::Image {
+                    class: naga::ImageClass::Storage { .. },
+                    ..
+                } => Some(BindingKind::Image),
+                naga::TypeInner::Image { .. } => Some(BindingKind::Texture),
+                naga::TypeInner::Sampler { .. } => Some(BindingKind::Sampler),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<TApi: IApi> IShader for ShaderValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    #[track_caller]
+    fn new(device: &mut Self::DeviceType, info: &ShaderInfo) -> Self {
+        let is_compute = info.get_compute_shader_binary().is_some();
+        let has_vertex_shader =
+            info.get_vertex_shader_binary().is_some() || info.get_vertex_shader_source().is_some();
+        let has_pixel_shader =
+            info.get_pixel_shader_binary().is_some() || info.get_pixel_shader_source().is_some();
+        let validator = device.clone_validator();
+        validator.check(
+            is_compute || (has_vertex_shader && has_pixel_shader),
+            || "shader needs a compute shader binary or both vertex and pixel shaders".to_string(),
+        );
+
+        let shader_binaries = if let Some(compute_shader_binary) = info.get_compute_shader_binary()
+        {
+            vec![*compute_shader_binary]
+        } else {
+            info.get_vertex_shader_binary()
+                .iter()
+                .chain(info.get_pixel_shader_binary().iter())
+                .copied()
+                .collect()
+        };
+        let bindings = if shader_binaries.is_empty() {
+            None
+        } else {
+            let bindings = Self::reflect_bindings(&shader_binaries);
+            validator.check(bindings.is_some(), || {
+                "shader binary is not valid SPIR-V".to_string()
+            });
+            bindings
+        };
+
+        Self {
+            shader: TApi::Shader::new(device.as_inner_mut(), info),
+            is_compute,
+            bindings,
+        }
+    }
+}
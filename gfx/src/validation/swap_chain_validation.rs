@@ -0,0 +1,129 @@
+use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
+use sjgfx_interface::{
+    CompositeAlphaMode, IDisplayEventListener, ISwapChain, ImageData, ImageFormat, OutputTransform,
+    PresentMode, SwapChainInfo, SwapChainStatus,
+};
+
+use crate::{
+    api::IApi,
+    validation::{
+        ColorTargetViewValidation, DeviceValidation, FenceValidation, SemaphoreValidation,
+    },
+};
+
+pub struct SwapChainValidation<TApi: IApi> {
+    swap_chain: TApi::SwapChain,
+
+    // バックエンドが返すビューは参照なので、複製して包んだものを返す
+    scan_buffer_view: Option<ColorTargetViewValidation<TApi>>,
+}
+
+impl<TApi: IApi> SwapChainValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::SwapChain {
+        &self.swap_chain
+    }
+
+    // 表示したスキャンバッファのビューを持ち続けないように、表示の前に手放す
+    pub(crate) fn as_inner_mut(&mut self) -> &mut TApi::SwapChain {
+        self.scan_buffer_view = None;
+        &mut self.swap_chain
+    }
+}
+
+impl<TApi: IApi> ISwapChain for SwapChainValidation<TApi>
+where
+    TApi::ColorTargetView: Clone,
+{
+    type ColorTargetViewType = ColorTargetViewValidation<TApi>;
+    type DeviceType = DeviceValidation<TApi>;
+    type SemaphoreType = SemaphoreValidation<TApi>;
+    type FenceType = FenceValidation<TApi>;
+
+    fn new(device: &mut Self::DeviceType, info: &SwapChainInfo) -> Self {
+        Self {
+            swap_chain: TApi::SwapChain::new(device.as_inner_mut(), info),
+            scan_buffer_view: None,
+        }
+    }
+
+    fn new_with_handle<T>(
+        device: &mut Self::DeviceType,
+        info: &SwapChainInfo,
+        raw_handle: &T,
+    ) -> Self
+    where
+        T: HasRawWindowHandle + HasRawDisplayHandle,
+    {
+        Self {
+            swap_chain: TApi::SwapChain::new_with_handle(device.as_inner_mut(), info, raw_handle),
+            scan_buffer_view: None,
+        }
+    }
+
+    fn acquire_next_scan_buffer_view(
+        &mut self,
+        semaphore: Option<&mut Self::SemaphoreType>,
+        fence: Option<&mut Self::FenceType>,
+    ) -> &mut Self::ColorTargetViewType {
+        let scan_buffer_view = self
+            .swap_chain
+            .acquire_next_scan_buffer_view(
+                semaphore.map(SemaphoreValidation::as_inner_mut),
+                fence.map(FenceValidation::as_inner_mut),
+            )
+            .clone();
+        self.scan_buffer_view
+            .insert(ColorTargetViewValidation::new_scan_buffer(scan_buffer_view))
+    }
+
+    fn get_present_mode(&self) -> PresentMode {
+        self.swap_chain.get_present_mode()
+    }
+
+    fn get_format(&self) -> ImageFormat {
+        self.swap_chain.get_format()
+    }
+
+    fn get_alpha_mode(&self) -> CompositeAlphaMode {
+        self.swap_chain.get_alpha_mode()
+    }
+
+    fn get_frame_latency(&self) -> u32 {
+        self.swap_chain.get_frame_latency()
+    }
+
+    fn is_direct_rendering_enabled(&self) -> bool {
+        self.swap_chain.is_direct_rendering_enabled()
+    }
+
+    fn get_status(&self) -> SwapChainStatus {
+        self.swap_chain.get_status()
+    }
+
+    fn get_render_width(&self) -> u32 {
+        self.swap_chain.get_render_width()
+    }
+
+    fn get_render_height(&self) -> u32 {
+        self.swap_chain.get_render_height()
+    }
+
+    fn set_render_scale(&mut self, render_scale: f32) {
+        self.swap_chain.set_render_scale(render_scale);
+    }
+
+    fn set_output_transform(&mut self, output_transform: &OutputTransform) {
+        self.swap_chain.set_output_transform(output_transform);
+    }
+
+    fn read_presented_image(&self) -> Option<ImageData> {
+        self.swap_chain.read_presented_image()
+    }
+}
+
+impl<TApi: IApi> IDisplayEventListener for SwapChainValidation<TApi> {
+    fn on_resized(&mut self, width: u32, height: u32) {
+        self.scan_buffer_view = None;
+        self.swap_chain.on_resized(width, height);
+    }
+}
@@ -0,0 +1,210 @@
+use sjgfx_interface::{
+    BufferTextureCopyRegion, GpuAccess, ITexture, ImageData, ImageFormat, TextureCopyRegion,
+    TextureInfo, TextureSubresource,
+};
+
+use crate::{
+    api::IApi,
+    validation::{self, DeviceValidation, Validator},
+};
+
+pub struct TextureValidation<TApi: IApi> {
+    texture: TApi::Texture,
+    width: i32,
+    height: i32,
+    array_length: i32,
+    mip_count: i32,
+    image_format: ImageFormat,
+    gpu_access: GpuAccess,
+    validator: Validator,
+}
+
+impl<TApi: IApi> TextureValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::Texture {
+        &self.texture
+    }
+
+    pub fn get_image_format(&self) -> &ImageFormat {
+        &self.image_format
+    }
+
+    pub fn get_gpu_access_flags(&self) -> GpuAccess {
+        self.gpu_access
+    }
+
+    pub(crate) fn get_array_length(&self) -> i32 {
+        self.array_length
+    }
+
+    #[track_caller]
+    pub(crate) fn validate_gpu_access(&self, required: GpuAccess, usage: &str) {
+        validation::validate_gpu_access(
+            &self.validator,
+            "texture",
+            self.gpu_access,
+            required,
+            usage,
+        );
+    }
+
+    #[track_caller]
+    fn validate_info(validator: &Validator, info: &TextureInfo) {
+        let width = info.get_width();
+        let height = info.get_height();
+        validator.check(width > 0 && height > 0 && info.get_depth() > 0, || {
+            format!(
+                "texture size {}x{}x{} must be positive",
+                width,
+                height,
+                info.get_depth()
+            )
+        });
+
+        // 1x1 までのミップレベルしか作れない
+        let max_mip_count = 32 - (width.max(height).max(1) as u32).leading_zeros() as i32;
+        validator.check(
+            0 < info.get_mip_count() && info.get_mip_count() <= max_mip_count,
+            || {
+                format!(
+                    "mip count {} must be in 1..={} for a {}x{} texture",
+                    info.get_mip_count(),
+                    max_mip_count,
+                    width,
+                    height
+                )
+            },
+        );
+    }
+
+    #[track_caller]
+    fn validate_region(&self, region: &TextureCopyRegion) {
+        let subresource = region.get_texture_subresource();
+        let mip_level = subresource.get_mip_level();
+        self.validator
+            .check(0 <= mip_level && mip_level < self.mip_count, || {
+                format!(
+                    "mip level {} is out of the texture with {} mip levels",
+                    mip_level, self.mip_count
+                )
+            });
+
+        let mip_width = (self.width >> mip_level.clamp(0, 31)).max(1);
+        let mip_height = (self.height >> mip_level.clamp(0, 31)).max(1);
+        let is_in_mip = region.get_offset_u() >= 0
+            && region.get_offset_v() >= 0
+            && region.get_offset_u() + region.get_width() <= mip_width
+            && region.get_offset_v() + region.get_height() <= mip_height;
+        self.validator.check(is_in_mip, || {
+            format!(
+                "region ({}, {}) {}x{} is out of mip level {} of size {}x{}",
+                region.get_offset_u(),
+                region.get_offset_v(),
+                region.get_width(),
+                region.get_height(),
+                mip_level,
+                mip_width,
+                mip_height
+            )
+        });
+
+        let array_index = subresource.get_array_index();
+        let is_in_array = array_index >= 0
+            && region.get_array_length() > 0
+            && array_index + region.get_array_length() <= self.array_length;
+        self.validator.check(is_in_array, || {
+            format!(
+                "array range {}..{} is out of the texture with {} array layers",
+                array_index,
+                array_index + region.get_array_length(),
+                self.array_length
+            )
+        });
+    }
+
+    // バッファーへのコピーはテクスチャーの 1 配列要素から画像の大きさの分を読む
+    #[track_caller]
+    pub(crate) fn validate_buffer_copy_region(&self, region: &BufferTextureCopyRegion) {
+        let texture_copy_region = region.get_texture_copy_region();
+        let subresource = texture_copy_region.get_texture_subresource();
+        self.validate_region(
+            &TextureCopyRegion::new()
+                .set_offset_u(texture_copy_region.get_offset_u())
+                .set_offset_v(texture_copy_region.get_offset_v())
+                .set_width(region.get_image_width())
+                .set_height(region.get_image_height())
+                .set_array_length(1)
+                .set_texture_subresource(
+                    TextureSubresource::new()
+                        .set_mip_level(subresource.get_mip_level())
+                        .set_array_index(subresource.get_array_index()),
+                ),
+        );
+    }
+
+    fn new_impl(texture: TApi::Texture, info: &TextureInfo, validator: Validator) -> Self {
+        Self {
+            texture,
+            width: info.get_width(),
+            height: info.get_height(),
+            array_length: info.get_depth(),
+            mip_count: info.get_mip_count(),
+            image_format: info.get_image_format().clone(),
+            gpu_access: *info.get_gpu_access_flags(),
+            validator,
+        }
+    }
+}
+
+impl<TApi: IApi> ITexture for TextureValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    #[track_caller]
+    fn new(device: &mut Self::DeviceType, info: &TextureInfo) -> Self {
+        let validator = device.clone_validator();
+        Self::validate_info(&validator, info);
+        let texture = TApi::Texture::new(device.as_inner_mut(), info);
+        Self::new_impl(texture, info, validator)
+    }
+
+    #[track_caller]
+    fn new_with_data(device: &Self::DeviceType, info: &TextureInfo, data: &[u8]) -> Self {
+        let validator = device.clone_validator();
+        Self::validate_info(&validator, info);
+
+        // ミップレベル 0 のすべての配列要素の分が必要
+        let required_size = info.get_width().max(0) as usize
+            * info.get_height().max(0) as usize
+            * info.get_depth().max(0) as usize
+            * info.get_image_format().get_bytes_per_pixel();
+        validator.check(data.len() >= required_size, || {
+            format!(
+                "initial data size {} is less than the required size {}",
+                data.len(),
+                required_size
+            )
+        });
+
+        let texture = TApi::Texture::new_with_data(device.as_inner(), info, data);
+        Self::new_impl(texture, info, validator)
+    }
+
+    #[track_caller]
+    fn write(
+        &self,
+        region: &TextureCopyRegion,
+        data: &[u8],
+        bytes_per_row: usize,
+        rows_per_image: usize,
+    ) {
+        self.validate_gpu_access(GpuAccess::WRITE, "copy destination");
+        self.validate_region(region);
+        self.texture
+            .write(region, data, bytes_per_row, rows_per_image);
+    }
+
+    #[track_caller]
+    fn read_image(&self) -> ImageData {
+        self.validate_gpu_access(GpuAccess::READ, "copy source");
+        self.texture.read_image()
+    }
+}
@@ -0,0 +1,46 @@
+use sjgfx_interface::{GpuAccess, ITextureView, TextureViewInfo};
+
+use crate::{
+    api::IApi,
+    validation::{DeviceValidation, TextureValidation},
+};
+
+pub struct TextureViewValidation<TApi: IApi> {
+    texture_view: TApi::TextureView,
+
+    // バインドするときにテクスチャーの用途を確認する
+    gpu_access: GpuAccess,
+}
+
+impl<TApi: IApi> TextureViewValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::TextureView {
+        &self.texture_view
+    }
+
+    pub(crate) fn get_gpu_access_flags(&self) -> GpuAccess {
+        self.gpu_access
+    }
+}
+
+impl<TApi: IApi> ITextureView for TextureViewValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+    type TextureType = TextureValidation<TApi>;
+
+    #[track_caller]
+    fn new(device: &Self::DeviceType, info: &TextureViewInfo, texture: &Self::TextureType) -> Self {
+        let gpu_access = texture.get_gpu_access_flags();
+        device
+            .clone_validator()
+            .check(gpu_access.intersects(GpuAccess::TEXTURE | GpuAccess::IMAGE), || {
+                format!(
+                    "texture for a texture view must be created with GpuAccess::TEXTURE or GpuAccess::IMAGE, but it has {:?}",
+                    gpu_access
+                )
+            });
+
+        Self {
+            texture_view: TApi::TextureView::new(device.as_inner(), info, texture.as_inner()),
+            gpu_access,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use sjgfx_interface::{IVertexState, VertexStateInfo};
+
+use crate::{api::IApi, validation::DeviceValidation};
+
+pub struct VertexStateValidation<TApi: IApi> {
+    vertex_state: TApi::VertexState,
+}
+
+impl<TApi: IApi> VertexStateValidation<TApi> {
+    pub fn as_inner(&self) -> &TApi::VertexState {
+        &self.vertex_state
+    }
+}
+
+impl<TApi: IApi> IVertexState for VertexStateValidation<TApi> {
+    type DeviceType = DeviceValidation<TApi>;
+
+    fn new(device: &Self::DeviceType, info: &VertexStateInfo) -> Self {
+        Self {
+            vertex_state: TApi::VertexState::new(device.as_inner(), info),
+        }
+    }
+}
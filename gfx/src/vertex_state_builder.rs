@@ -17,6 +17,7 @@ impl<T: IApi> TVertexStateBuilder<T> {
         }
     }
 
+    #[track_caller]
     pub fn build(&self, device: &T::Device) -> T::VertexState {
         T::VertexState::new(device, &self.info)
     }
@@ -0,0 +1,55 @@
+use sjgfx::{
+    any::{DeviceAny, API_TYPE_ENV},
+    api::AnyApi,
+    TCommandBufferBuilder, TDeviceBuilder, TQueueBuilder, TSwapChainBuilder,
+};
+use sjgfx_interface::{ApiType, DeviceInfo, ICommandBuffer, IQueue, ISwapChain, TextureArrayRange};
+
+#[test]
+fn new_with_api_type() {
+    // 環境変数が設定されているとそちらが優先される
+    if std::env::var(API_TYPE_ENV).is_ok() {
+        return;
+    }
+
+    let device = TDeviceBuilder::<AnyApi>::new()
+        .with_api_type(ApiType::Wgpu)
+        .build();
+    assert_eq!(device.get_api_type(), ApiType::Wgpu);
+}
+
+#[test]
+fn enumerate_adapters() {
+    assert!(!DeviceAny::enumerate_adapters(&DeviceInfo::new()).is_empty());
+}
+
+#[test]
+fn present_headless() {
+    let mut device = TDeviceBuilder::<AnyApi>::new()
+        .with_api_type(ApiType::Wgpu)
+        .build();
+    let mut queue = TQueueBuilder::<AnyApi>::new().build(&mut device);
+    let mut command_buffer = TCommandBufferBuilder::<AnyApi>::new().build(&device);
+    let mut swap_chain = TSwapChainBuilder::<AnyApi>::new()
+        .with_width(4)
+        .with_height(4)
+        .enable_direct_rendering()
+        .build(&mut device);
+
+    let scan_buffer_view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.begin();
+    command_buffer.clear_color(
+        scan_buffer_view,
+        1.0,
+        0.0,
+        0.0,
+        1.0,
+        TextureArrayRange::new(),
+    );
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.present(&mut swap_chain);
+
+    let image = swap_chain.read_presented_image().unwrap();
+    assert!(image.get_data().chunks(4).all(|p| p == [255, 0, 0, 255]));
+}
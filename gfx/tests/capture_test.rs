@@ -0,0 +1,129 @@
+use sjgfx::{
+    api::{CaptureApi, Null},
+    capture::{Capture, CaptureCommand, ObjectId, ReplayError, TCaptureReplayer},
+    TBufferBuilder, TCommandBufferBuilder, TDeviceBuilder, TQueueBuilder, TSwapChainBuilder,
+};
+use sjgfx_interface::{
+    DeviceInfo, IBuffer, ICommandBuffer, IQueue, ISwapChain, ImageData, TextureArrayRange,
+};
+
+type TestApi = CaptureApi<Null>;
+
+fn record_clear_color() -> Capture {
+    let mut device = TDeviceBuilder::<TestApi>::new().build();
+    let mut queue = TQueueBuilder::<TestApi>::new().build(&mut device);
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let mut swap_chain = TSwapChainBuilder::<TestApi>::new()
+        .with_width(4)
+        .with_height(4)
+        .build(&mut device);
+
+    for red in [1.0, 0.0] {
+        let scan_buffer_view = swap_chain.acquire_next_scan_buffer_view(None, None);
+        command_buffer.begin();
+        command_buffer.clear_color(
+            scan_buffer_view,
+            red,
+            0.0,
+            0.0,
+            1.0,
+            TextureArrayRange::new(),
+        );
+        command_buffer.end();
+        queue.execute(&command_buffer);
+        queue.present(&mut swap_chain);
+    }
+    queue.sync();
+
+    device.get_capture()
+}
+
+#[test]
+fn save_and_load() {
+    let capture = record_clear_color();
+    let path = std::env::temp_dir().join("sjgfx_capture_test_save_and_load.sjcap");
+    capture.save(&path).unwrap();
+    let loaded_capture = Capture::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded_capture, capture);
+    assert_eq!(
+        capture
+            .get_commands()
+            .iter()
+            .filter(|x| matches!(x, CaptureCommand::Present { .. }))
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn replay_presented_images() {
+    let capture = record_clear_color();
+
+    let mut images: Vec<ImageData> = Vec::new();
+    TCaptureReplayer::<Null>::new(&DeviceInfo::new())
+        .replay(&capture, |image| images.push(image))
+        .unwrap();
+
+    assert_eq!(images.len(), 2);
+    assert_eq!(images[0].get_width(), 4);
+    assert_eq!(images[0].get_height(), 4);
+    assert!(images[0]
+        .get_data()
+        .chunks(4)
+        .all(|p| p == [255, 0, 0, 255]));
+    assert!(images[1].get_data().chunks(4).all(|p| p == [0, 0, 0, 255]));
+}
+
+#[test]
+fn record_buffer_write() {
+    let mut device = TDeviceBuilder::<TestApi>::new().build();
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_constant_buffer()
+        .with_size(16)
+        .build(&mut device);
+    buffer.map_as_slice_mut(|x: &mut [u32]| x.copy_from_slice(&[1, 2, 3, 4]));
+
+    let capture = device.get_capture();
+    let data = capture
+        .get_commands()
+        .iter()
+        .find_map(|x| match x {
+            CaptureCommand::WriteBuffer { data, .. } => Some(data.get_data().to_vec()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(data, bytemuck::cast_slice::<u32, u8>(&[1, 2, 3, 4]));
+}
+
+#[test]
+fn record_buffer_destroy() {
+    let mut device = TDeviceBuilder::<TestApi>::new().build();
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_constant_buffer()
+        .with_size(16)
+        .build(&mut device);
+    let id = buffer.get_id();
+    std::mem::drop(buffer);
+
+    let capture = device.get_capture();
+    assert!(matches!(
+        capture.get_commands().last(),
+        Some(CaptureCommand::Destroy { id: x }) if *x == id
+    ));
+    TCaptureReplayer::<Null>::new(&DeviceInfo::new())
+        .replay(&capture, |_| {})
+        .unwrap();
+}
+
+#[test]
+fn replay_unknown_object() {
+    let mut replayer = TCaptureReplayer::<Null>::new(&DeviceInfo::new());
+    let id = ObjectId::new(100);
+    let result = replayer.replay_command(&CaptureCommand::SignalSemaphore {
+        semaphore: id,
+        value: 1,
+    });
+    assert_eq!(result, Err(ReplayError::ObjectNotFound(id)));
+}
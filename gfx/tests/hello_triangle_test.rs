@@ -29,6 +29,8 @@ fn make_commnd_impl<TApi: IApi>() {
     let mut queue = TQueueBuilder::<TApi>::new().build(&device);
     let mut command_buffer = TCommandBufferBuilder::<TApi>::new().build(&device);
 
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
     struct Vertex {
         position_x: f32,
         position_y: f32,
@@ -0,0 +1,309 @@
+use sjgfx::{
+    api::{Null, ValidationApi},
+    validation::TextureValidation,
+    TBufferBuilder, TColorTargetViewBuilder, TCommandBufferBuilder, TDeviceBuilder, TQueueBuilder,
+    TShaderBuilder, TSwapChainBuilder, TTextureBuilder,
+};
+use sjgfx_interface::{
+    BufferCopyRegion, BufferTextureCopyRegion, GpuAccess, ICommandBuffer, IQueue, ISwapChain,
+    ITexture, ImageFormat, PrimitiveTopology, TextureArrayRange, TextureCopyRegion, TextureInfo,
+};
+
+type TestApi = ValidationApi<Null>;
+
+fn create_graphics_shader_binary() -> (Vec<u8>, Vec<u8>) {
+    let vertex_shader_source = "
+            #version 450
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }";
+    let pixel_shader_source = "
+            #version 450
+
+            layout(location = 0) out vec4 o_Color;
+            layout(binding = 0) uniform Block {
+                vec4 u_Color;
+            };
+
+            void main() {
+                o_Color = u_Color;
+            }";
+    let mut compiler = shaderc::Compiler::new().unwrap();
+    let vertex_shader_binary = compiler
+        .compile_into_spirv(
+            vertex_shader_source,
+            shaderc::ShaderKind::Vertex,
+            "test.vs",
+            "main",
+            None,
+        )
+        .unwrap();
+    let pixel_shader_binary = compiler
+        .compile_into_spirv(
+            pixel_shader_source,
+            shaderc::ShaderKind::Fragment,
+            "test.fs",
+            "main",
+            None,
+        )
+        .unwrap();
+    (
+        vertex_shader_binary.as_binary_u8().to_vec(),
+        pixel_shader_binary.as_binary_u8().to_vec(),
+    )
+}
+
+#[test]
+fn draw_headless() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut queue = TQueueBuilder::<TestApi>::new().build(&mut device);
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let mut swap_chain = TSwapChainBuilder::<TestApi>::new()
+        .with_width(4)
+        .with_height(4)
+        .enable_direct_rendering()
+        .build(&mut device);
+    let (vertex_shader_binary, pixel_shader_binary) = create_graphics_shader_binary();
+    let shader = TShaderBuilder::<TestApi>::new()
+        .set_vertex_shader_binary(&vertex_shader_binary)
+        .set_pixel_shader_binary(&pixel_shader_binary)
+        .build(&mut device);
+    let constant_buffer = TBufferBuilder::<TestApi>::new()
+        .enable_constant_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    let scan_buffer_view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.begin();
+    command_buffer.set_render_targets(&[scan_buffer_view], None);
+    command_buffer.set_shader(&shader);
+    command_buffer.set_constant_buffer(0, &constant_buffer);
+    command_buffer.draw(PrimitiveTopology::TriangleList, 3, 0);
+    command_buffer.end();
+    queue.execute(&command_buffer);
+    queue.present(&mut swap_chain);
+    queue.sync();
+}
+
+#[test]
+#[should_panic(expected = "shader binding 0 expects a constant buffer, but nothing is bound")]
+fn draw_without_constant_buffer() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let mut swap_chain = TSwapChainBuilder::<TestApi>::new()
+        .with_width(4)
+        .with_height(4)
+        .enable_direct_rendering()
+        .build(&mut device);
+    let (vertex_shader_binary, pixel_shader_binary) = create_graphics_shader_binary();
+    let shader = TShaderBuilder::<TestApi>::new()
+        .set_vertex_shader_binary(&vertex_shader_binary)
+        .set_pixel_shader_binary(&pixel_shader_binary)
+        .build(&mut device);
+
+    let scan_buffer_view = swap_chain.acquire_next_scan_buffer_view(None, None);
+    command_buffer.begin();
+    command_buffer.set_render_targets(&[scan_buffer_view], None);
+    command_buffer.set_shader(&shader);
+    command_buffer.draw(PrimitiveTopology::TriangleList, 3, 0);
+}
+
+#[test]
+#[should_panic(expected = "must be created with GpuAccess::CONSTANT_BUFFER")]
+fn set_constant_buffer_without_gpu_access() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_vertex_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.set_constant_buffer(0, &buffer);
+}
+
+#[test]
+#[should_panic(expected = "range 8..24 is out of the buffer of size 16")]
+fn set_vertex_buffer_range_out_of_buffer() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_vertex_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.set_vertex_buffer_range(0, &buffer, 8, 16);
+}
+
+#[test]
+#[should_panic(expected = "draw needs a graphics shader, but no shader is set")]
+fn draw_without_shader() {
+    let device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+
+    command_buffer.begin();
+    command_buffer.draw(PrimitiveTopology::TriangleList, 3, 0);
+}
+
+#[test]
+#[should_panic(expected = "draw needs a render target, but none is set")]
+fn draw_without_render_target() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let (vertex_shader_binary, pixel_shader_binary) = create_graphics_shader_binary();
+    let shader = TShaderBuilder::<TestApi>::new()
+        .set_vertex_shader_binary(&vertex_shader_binary)
+        .set_pixel_shader_binary(&pixel_shader_binary)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.set_shader(&shader);
+    command_buffer.draw(PrimitiveTopology::TriangleList, 3, 0);
+}
+
+#[test]
+#[should_panic(expected = "clear range 1..3 is out of the color target with 1 array layers")]
+fn clear_color_out_of_array() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let texture = TTextureBuilder::<TestApi>::new()
+        .with_size(4, 4)
+        .with_format(ImageFormat::R8G8B8A8Unorm)
+        .enable_color_buffer()
+        .build(&mut device);
+    let mut color_target_view = TColorTargetViewBuilder::<TestApi>::new().build(&device, &texture);
+
+    command_buffer.begin();
+    command_buffer.clear_color(
+        &mut color_target_view,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        TextureArrayRange::new().set_base_index(1).set_length(2),
+    );
+}
+
+#[test]
+#[should_panic(expected = "region (2, 0) 4x4 is out of mip level 0 of size 4x4")]
+fn write_texture_out_of_region() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let texture = TTextureBuilder::<TestApi>::new()
+        .with_size(4, 4)
+        .with_format(ImageFormat::R8Unorm)
+        .enable_write()
+        .build(&mut device);
+
+    texture.write(
+        &TextureCopyRegion::new()
+            .set_offset_u(2)
+            .set_width(4)
+            .set_height(4),
+        &[0; 16],
+        4,
+        4,
+    );
+}
+
+#[test]
+#[should_panic(expected = "range 8..24 is out of the buffer of size 16")]
+fn copy_buffer_to_buffer_out_of_buffer() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let src_buffer = TBufferBuilder::<TestApi>::new()
+        .enable_unordered_access_buffer()
+        .with_size(32)
+        .build(&mut device);
+    let mut dst_buffer = TBufferBuilder::<TestApi>::new()
+        .enable_unordered_access_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.copy_buffer_to_buffer(
+        &mut dst_buffer,
+        &src_buffer,
+        &BufferCopyRegion::default()
+            .set_dst_offset(8)
+            .set_copy_size(16),
+    );
+}
+
+#[test]
+#[should_panic(expected = "range 0..64 is out of the buffer of size 16")]
+fn copy_image_to_buffer_out_of_buffer() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let texture = TextureValidation::<Null>::new(
+        &mut device,
+        &TextureInfo::new()
+            .set_width(4)
+            .set_height(4)
+            .set_image_format(ImageFormat::R8G8B8A8Unorm)
+            .set_gpu_access_flags(GpuAccess::READ),
+    );
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_unordered_access_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.copy_image_to_buffer(
+        &buffer,
+        &texture,
+        BufferTextureCopyRegion::new()
+            .set_image_width(4)
+            .set_image_height(4),
+    );
+}
+
+#[test]
+#[should_panic(expected = "command buffer is executed before end is called")]
+fn execute_while_recording() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut queue = TQueueBuilder::<TestApi>::new().build(&mut device);
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+
+    command_buffer.begin();
+    queue.execute(&command_buffer);
+}
+
+#[test]
+#[should_panic(expected = "validation_test.rs")]
+fn report_call_site() {
+    let mut device = TDeviceBuilder::<TestApi>::new()
+        .enable_debug_assertion()
+        .build();
+    let mut command_buffer = TCommandBufferBuilder::<TestApi>::new().build(&device);
+    let buffer = TBufferBuilder::<TestApi>::new()
+        .enable_vertex_buffer()
+        .with_size(16)
+        .build(&mut device);
+
+    command_buffer.begin();
+    command_buffer.set_constant_buffer(0, &buffer);
+}